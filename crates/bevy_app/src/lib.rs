@@ -6,6 +6,7 @@ mod app;
 mod plugin;
 mod plugin_group;
 mod schedule_runner;
+mod testing;
 
 #[cfg(feature = "bevy_ci_testing")]
 mod ci_testing;
@@ -15,6 +16,7 @@ pub use bevy_derive::DynamicPlugin;
 pub use plugin::*;
 pub use plugin_group::*;
 pub use schedule_runner::*;
+pub use testing::*;
 
 #[allow(missing_docs)]
 pub mod prelude {
@@ -0,0 +1,420 @@
+use crate::App;
+use bevy_ecs::{
+    entity::Entity,
+    event::Event,
+    query::{ROQueryItem, ReadOnlyWorldQuery},
+    system::Resource,
+    world::{Mut, World},
+};
+use bevy_utils::Duration;
+use std::fmt::{Debug, Write};
+
+/// Advances the clock of an [`App`] by an exact amount before a frame is run.
+///
+/// Plugins that own a clock insert this resource so that an [`AppTester`] can control the
+/// frame delta without knowing about the concrete time resource.
+/// `bevy_time`'s `TimePlugin` inserts one that drives `Time` through `TimeUpdateStrategy`.
+#[derive(Resource)]
+pub struct FrameDeltaDriver(Box<FrameDeltaFn>);
+
+type FrameDeltaFn = dyn Fn(&mut World, Duration) + Send + Sync;
+
+impl FrameDeltaDriver {
+    /// Creates a new driver from a function that prepares `world` so that the next frame
+    /// observes `delta` as its elapsed time.
+    pub fn new(driver: impl Fn(&mut World, Duration) + Send + Sync + 'static) -> Self {
+        Self(Box::new(driver))
+    }
+
+    /// Prepares `world` so that the next frame observes `delta` as its elapsed time.
+    pub fn drive(&self, world: &mut World, delta: Duration) {
+        (self.0)(world, delta);
+    }
+}
+
+/// The error returned by [`AppTester::run_until`] when the predicate did not become true
+/// within the allotted number of frames.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RunUntilTimeout {
+    /// The number of frames that were run before giving up.
+    pub frames: u32,
+}
+
+impl std::fmt::Display for RunUntilTimeout {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "predicate was still false after {} frames", self.frames)
+    }
+}
+
+impl std::error::Error for RunUntilTimeout {}
+
+/// A headless harness for driving an [`App`] frame by frame in tests.
+///
+/// The harness does not add any plugins on its own: build the [`App`] as usual (typically with
+/// `MinimalPlugins`) and hand it over. Frames are then run on demand, optionally with a fixed
+/// frame delta (see [`set_frame_delta`](Self::set_frame_delta)).
+///
+/// Assertion failures panic with a dump of the relevant entities and their components. When the
+/// `bevy_reflect` feature is enabled, components registered in the [`AppTypeRegistry`](crate::AppTypeRegistry)
+/// are printed with their values.
+///
+/// # Examples
+///
+/// ```
+/// # use bevy_app::{prelude::*, AppTester};
+/// # use bevy_ecs::prelude::*;
+/// #
+/// #[derive(Component)]
+/// struct Counter(u32);
+///
+/// fn count(mut counters: Query<&mut Counter>) {
+///     for mut counter in &mut counters {
+///         counter.0 += 1;
+///     }
+/// }
+///
+/// let mut app = App::new();
+/// app.add_system(count);
+/// app.world.spawn(Counter(0));
+///
+/// let mut tester = AppTester::new(app);
+/// tester.step(3);
+/// tester.assert_all::<&Counter>(|counter| counter.0 == 3);
+///
+/// let frames = tester
+///     .run_until(|world| world.query::<&Counter>().iter(world).all(|c| c.0 >= 10), 100)
+///     .unwrap();
+/// assert_eq!(frames, 7);
+/// ```
+pub struct AppTester {
+    app: App,
+    frame_delta: Option<Duration>,
+    frame_count: u32,
+}
+
+impl From<App> for AppTester {
+    fn from(app: App) -> Self {
+        Self::new(app)
+    }
+}
+
+impl AppTester {
+    /// Creates a new harness around `app`.
+    pub fn new(app: App) -> Self {
+        Self {
+            app,
+            frame_delta: None,
+            frame_count: 0,
+        }
+    }
+
+    /// Returns a reference to the wrapped [`App`].
+    pub fn app(&self) -> &App {
+        &self.app
+    }
+
+    /// Returns a mutable reference to the wrapped [`App`].
+    pub fn app_mut(&mut self) -> &mut App {
+        &mut self.app
+    }
+
+    /// Consumes the harness and returns the wrapped [`App`].
+    pub fn into_app(self) -> App {
+        self.app
+    }
+
+    /// Returns a reference to the main [`World`] of the wrapped [`App`].
+    pub fn world(&self) -> &World {
+        &self.app.world
+    }
+
+    /// Returns a mutable reference to the main [`World`] of the wrapped [`App`].
+    pub fn world_mut(&mut self) -> &mut World {
+        &mut self.app.world
+    }
+
+    /// Returns the number of frames run through this harness.
+    pub fn frame_count(&self) -> u32 {
+        self.frame_count
+    }
+
+    /// Sets the delta every following frame observes.
+    ///
+    /// Requires a [`FrameDeltaDriver`] resource, which `TimePlugin` inserts. Passing [`None`]
+    /// stops driving the clock; the clock then keeps whatever state the driver left it in.
+    ///
+    /// Note that `Time` reports a zero delta for its very first update.
+    pub fn set_frame_delta(&mut self, delta: impl Into<Option<Duration>>) -> &mut Self {
+        self.frame_delta = delta.into();
+        self
+    }
+
+    /// Runs a single frame.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a frame delta is set and no [`FrameDeltaDriver`] resource exists.
+    pub fn update(&mut self) -> &mut Self {
+        if let Some(delta) = self.frame_delta {
+            self.app
+                .world
+                .resource_scope(|world, driver: Mut<FrameDeltaDriver>| {
+                    driver.drive(world, delta);
+                });
+        }
+        self.app.update();
+        self.frame_count += 1;
+        self
+    }
+
+    /// Runs `frames` frames.
+    pub fn step(&mut self, frames: u32) -> &mut Self {
+        for _ in 0..frames {
+            self.update();
+        }
+        self
+    }
+
+    /// Runs frames until `predicate` returns `true`, checking it after every frame.
+    ///
+    /// Returns the number of frames that were run, or [`RunUntilTimeout`] if `predicate` was
+    /// still `false` after `max_frames` frames.
+    pub fn run_until(
+        &mut self,
+        mut predicate: impl FnMut(&mut World) -> bool,
+        max_frames: u32,
+    ) -> Result<u32, RunUntilTimeout> {
+        for frame in 1..=max_frames {
+            self.update();
+            if predicate(&mut self.app.world) {
+                return Ok(frame);
+            }
+        }
+        Err(RunUntilTimeout { frames: max_frames })
+    }
+
+    /// Sends `event` so that it is visible to the systems of the next frame.
+    ///
+    /// The event type must have been added with [`App::add_event`].
+    pub fn send_event<E: Event>(&mut self, event: E) -> &mut Self {
+        self.app.world.send_event(event);
+        self
+    }
+
+    /// Asserts that the resource `R` exists and satisfies `predicate`.
+    #[track_caller]
+    pub fn assert_resource<R: Resource + Debug>(&self, predicate: impl FnOnce(&R) -> bool) {
+        let name = std::any::type_name::<R>();
+        let resource = self
+            .app
+            .world
+            .get_resource::<R>()
+            .unwrap_or_else(|| panic!("resource `{name}` does not exist"));
+        assert!(
+            predicate(resource),
+            "resource `{name}` did not satisfy the predicate: {resource:?}"
+        );
+    }
+
+    /// Asserts that exactly `expected` entities match the query filter `F`.
+    #[track_caller]
+    pub fn assert_count<F: ReadOnlyWorldQuery>(&mut self, expected: usize) {
+        let entities = self.matching_entities::<(), F>();
+        if entities.len() != expected {
+            panic!(
+                "expected {expected} entities matching `{}`, found {}:\n{}",
+                std::any::type_name::<F>(),
+                entities.len(),
+                dump_entities(&self.app.world, entities)
+            );
+        }
+    }
+
+    /// Asserts that at least one entity matches `Q` and satisfies `predicate`.
+    #[track_caller]
+    pub fn assert_any<Q: ReadOnlyWorldQuery>(
+        &mut self,
+        mut predicate: impl FnMut(ROQueryItem<'_, Q>) -> bool,
+    ) {
+        let mut state = self.app.world.query::<Q>();
+        if !state.iter(&self.app.world).any(&mut predicate) {
+            let entities = self.matching_entities::<Q, ()>();
+            panic!(
+                "no entity matching `{}` satisfied the predicate, candidates were:\n{}",
+                std::any::type_name::<Q>(),
+                dump_entities(&self.app.world, entities)
+            );
+        }
+    }
+
+    /// Asserts that every entity matching `Q` satisfies `predicate`.
+    ///
+    /// This trivially succeeds if no entity matches `Q`.
+    #[track_caller]
+    pub fn assert_all<Q: ReadOnlyWorldQuery>(
+        &mut self,
+        mut predicate: impl FnMut(ROQueryItem<'_, Q>) -> bool,
+    ) {
+        let mut state = self.app.world.query::<(Entity, Q)>();
+        let failed: Vec<Entity> = state
+            .iter(&self.app.world)
+            .filter_map(|(entity, item)| (!predicate(item)).then_some(entity))
+            .collect();
+        if !failed.is_empty() {
+            panic!(
+                "{} entities matching `{}` did not satisfy the predicate:\n{}",
+                failed.len(),
+                std::any::type_name::<Q>(),
+                dump_entities(&self.app.world, failed)
+            );
+        }
+    }
+
+    /// Returns a human readable dump of `entity` and its components.
+    ///
+    /// See [`dump_entities`] for the format.
+    pub fn dump_entity(&self, entity: Entity) -> String {
+        dump_entities(&self.app.world, [entity])
+    }
+
+    fn matching_entities<Q: ReadOnlyWorldQuery, F: ReadOnlyWorldQuery>(&mut self) -> Vec<Entity> {
+        let mut state = self.app.world.query_filtered::<(Entity, Q), F>();
+        state
+            .iter(&self.app.world)
+            .map(|(entity, _)| entity)
+            .collect()
+    }
+}
+
+/// Returns a human readable dump of `entities` and their components, one entity per line
+/// followed by one component per line.
+///
+/// With the `bevy_reflect` feature, components that are registered in the
+/// [`AppTypeRegistry`](crate::AppTypeRegistry) with `ReflectComponent` are printed with their
+/// values. All other components are printed by name only.
+pub fn dump_entities(world: &World, entities: impl IntoIterator<Item = Entity>) -> String {
+    let mut output = String::new();
+    for entity in entities {
+        if world.get_entity(entity).is_none() {
+            let _ = writeln!(output, "{entity:?} (despawned)");
+            continue;
+        }
+        let _ = writeln!(output, "{entity:?}");
+        for info in world.inspect_entity(entity) {
+            match reflect_component_debug(world, entity, info) {
+                Some(value) => {
+                    let _ = writeln!(output, "  {}: {value}", info.name());
+                }
+                None => {
+                    let _ = writeln!(output, "  {}", info.name());
+                }
+            }
+        }
+    }
+    output
+}
+
+#[cfg(feature = "bevy_reflect")]
+fn reflect_component_debug(
+    world: &World,
+    entity: Entity,
+    info: &bevy_ecs::component::ComponentInfo,
+) -> Option<String> {
+    use bevy_ecs::reflect::ReflectComponent;
+
+    let registry = world.get_resource::<crate::AppTypeRegistry>()?.read();
+    let reflect_component = registry.get(info.type_id()?)?.data::<ReflectComponent>()?;
+    let value = reflect_component.reflect(world, entity)?;
+    Some(format!("{value:?}"))
+}
+
+#[cfg(not(feature = "bevy_reflect"))]
+fn reflect_component_debug(
+    _world: &World,
+    _entity: Entity,
+    _info: &bevy_ecs::component::ComponentInfo,
+) -> Option<String> {
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{AppTester, FrameDeltaDriver, RunUntilTimeout};
+    use crate::App;
+    use bevy_ecs::prelude::*;
+    #[cfg(feature = "bevy_reflect")]
+    use bevy_reflect::Reflect;
+    use bevy_utils::Duration;
+
+    #[derive(Component, Debug)]
+    struct Health(u32);
+
+    #[derive(Resource, Default, Debug)]
+    struct Clock(Duration);
+
+    struct Damage(u32);
+
+    fn apply_damage(mut events: EventReader<Damage>, mut query: Query<&mut Health>) {
+        for Damage(amount) in events.iter() {
+            for mut health in &mut query {
+                health.0 = health.0.saturating_sub(*amount);
+            }
+        }
+    }
+
+    #[test]
+    fn steps_frames_and_sends_events() {
+        let mut app = App::new();
+        app.add_event::<Damage>().add_system(apply_damage);
+        app.world.spawn(Health(10));
+
+        let mut tester = AppTester::new(app);
+        tester.send_event(Damage(3)).update();
+        tester.assert_all::<&Health>(|health| health.0 == 7);
+
+        tester.step(2);
+        tester.assert_any::<&Health>(|health| health.0 == 7);
+        tester.assert_count::<With<Health>>(1);
+        assert_eq!(tester.frame_count(), 3);
+    }
+
+    #[test]
+    fn drives_frame_delta() {
+        let mut app = App::new();
+        app.init_resource::<Clock>()
+            .insert_resource(FrameDeltaDriver::new(|world, delta| {
+                world.resource_mut::<Clock>().0 += delta;
+            }));
+
+        let mut tester = AppTester::new(app);
+        tester.set_frame_delta(Duration::from_millis(250)).step(4);
+        tester.assert_resource::<Clock>(|clock| clock.0 == Duration::from_secs(1));
+    }
+
+    #[test]
+    fn run_until_times_out() {
+        let mut tester = AppTester::new(App::new());
+        assert_eq!(
+            tester.run_until(|_| false, 5),
+            Err(RunUntilTimeout { frames: 5 })
+        );
+        assert_eq!(tester.run_until(|_| true, 5), Ok(1));
+    }
+
+    #[cfg(feature = "bevy_reflect")]
+    #[test]
+    #[should_panic(expected = "TestHealth(5)")]
+    fn failed_assertion_dumps_components() {
+        let mut app = App::new();
+        app.register_type::<TestHealth>();
+        app.world.spawn(TestHealth(5));
+
+        let mut tester = AppTester::new(app);
+        tester.assert_all::<&TestHealth>(|health| health.0 == 0);
+    }
+
+    #[cfg(feature = "bevy_reflect")]
+    #[derive(Component, Reflect, Default)]
+    #[reflect(Component)]
+    struct TestHealth(u32);
+}
@@ -1,17 +1,33 @@
 use crate::{
+    deserialize_meta_loader,
+    load_queue::LoadQueue,
+    meta_path,
     path::{AssetPath, AssetPathId, SourcePathId},
     Asset, AssetIo, AssetIoError, AssetLifecycle, AssetLifecycleChannel, AssetLifecycleEvent,
-    AssetLoader, Assets, Handle, HandleId, HandleUntyped, LabelId, LoadContext, LoadState,
-    RefChange, RefChangeChannel, SourceInfo, SourceMeta,
+    AssetLoader, AssetMetaError, AssetSaver, Assets, ErasedAssetSaver, Handle, HandleId,
+    HandleUntyped, LabelId, LoadContext, LoadPriority, LoadState, LoaderSettings,
+    RecursiveDependencyLoadState, RefChange, RefChangeChannel, SettingsOverride, SourceInfo,
+    SourceMeta, UntypedAssetLoadFailedEvent,
 };
 use anyhow::Result;
-use bevy_ecs::system::{Res, ResMut, Resource};
+use bevy_ecs::{
+    event::EventWriter,
+    system::{Res, ResMut, Resource},
+};
 use bevy_log::warn;
+use bevy_reflect::TypeUuid;
 use bevy_tasks::IoTaskPool;
-use bevy_utils::{Entry, HashMap, Uuid};
+use bevy_utils::{Entry, HashMap, HashSet, Uuid};
 use crossbeam_channel::TryRecvError;
 use parking_lot::{Mutex, RwLock};
-use std::{path::Path, sync::Arc};
+use std::{
+    path::{Path, PathBuf},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    time::SystemTime,
+};
 use thiserror::Error;
 
 /// Errors that occur while loading assets with an `AssetServer`.
@@ -21,6 +37,17 @@ pub enum AssetServerError {
     #[error("asset folder path is not a directory: {0}")]
     AssetFolderNotADirectory(String),
 
+    /// No asset source was registered with the name of an asset path.
+    #[error("no asset source named `{0}`")]
+    MissingAssetSource(String),
+
+    /// No asset saver was found for the type of the asset and the specified extensions.
+    #[error("no `AssetSaver` found{}", format_missing_asset_ext(.extensions))]
+    MissingAssetSaver {
+        /// The list of extensions detected on the path the asset was saved to.
+        extensions: Vec<String>,
+    },
+
     /// No asset loader was found for the specified extensions.
     #[error("no `AssetLoader` found{}", format_missing_asset_ext(.extensions))]
     MissingAssetLoader {
@@ -41,6 +68,63 @@ pub enum AssetServerError {
     /// Encountered an error while reading an asset from disk.
     #[error("encountered an error while reading an asset: {0}")]
     AssetIoError(#[from] AssetIoError),
+
+    /// Encountered an error while reading the settings of an asset from its `.meta` file.
+    #[error("encountered an error while reading the settings of an asset: {0}")]
+    AssetMetaError(#[from] AssetMetaError),
+}
+
+/// Why an asset failed to load, see [`AssetServer::get_load_error`] and
+/// [`UntypedAssetLoadFailedEvent`](crate::UntypedAssetLoadFailedEvent).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AssetLoadError {
+    /// The path of the asset that failed to load.
+    pub path: AssetPath<'static>,
+    /// The [type name](AssetLoader::type_name) of the loader selected for the asset, or `None` if
+    /// it failed before a loader was found.
+    pub loader: Option<&'static str>,
+    /// The error and its causes, outermost first.
+    pub chain: Vec<String>,
+}
+
+impl AssetLoadError {
+    fn new(
+        path: AssetPath<'static>,
+        loader: Option<&'static str>,
+        error: &AssetServerError,
+    ) -> Self {
+        let mut chain = vec![error.to_string()];
+        match error {
+            AssetServerError::AssetLoaderError(error) => {
+                chain.extend(error.chain().map(ToString::to_string));
+            }
+            _ => {
+                let mut source = std::error::Error::source(error);
+                while let Some(error) = source {
+                    chain.push(error.to_string());
+                    source = error.source();
+                }
+            }
+        }
+        Self {
+            path,
+            loader,
+            chain,
+        }
+    }
+}
+
+impl std::fmt::Display for AssetLoadError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "failed to load `{}`", self.path)?;
+        if let Some(loader) = self.loader {
+            write!(f, " with `{}`", loader)?;
+        }
+        for error in &self.chain {
+            write!(f, ": {}", error)?;
+        }
+        Ok(())
+    }
 }
 
 fn format_missing_asset_ext(exts: &[String]) -> String {
@@ -59,6 +143,8 @@ fn format_missing_asset_ext(exts: &[String]) -> String {
 pub(crate) struct AssetRefCounter {
     pub(crate) channel: Arc<RefChangeChannel>,
     pub(crate) ref_counts: Arc<RwLock<HashMap<HandleId, usize>>>,
+    /// The number of handle ids of each source that have strong handles.
+    pub(crate) source_ref_counts: Arc<RwLock<HashMap<SourcePathId, usize>>>,
     pub(crate) mark_unused_assets: Arc<Mutex<Vec<HandleId>>>,
 }
 
@@ -66,13 +152,39 @@ pub(crate) struct AssetRefCounter {
 ///
 /// [`AssetServer`] is the public API for interacting with the asset server.
 pub struct AssetServerInternal {
-    pub(crate) asset_io: Box<dyn AssetIo>,
+    pub(crate) asset_io: Arc<dyn AssetIo>,
+    pub(crate) named_asset_io: RwLock<HashMap<String, Arc<dyn AssetIo>>>,
     pub(crate) asset_ref_counter: AssetRefCounter,
     pub(crate) asset_sources: Arc<RwLock<HashMap<SourcePathId, SourceInfo>>>,
     pub(crate) asset_lifecycles: Arc<RwLock<HashMap<Uuid, Box<dyn AssetLifecycle>>>>,
     loaders: RwLock<Vec<Arc<dyn AssetLoader>>>,
     extension_to_loader_index: RwLock<HashMap<String, usize>>,
     handle_to_path: Arc<RwLock<HashMap<HandleId, AssetPath<'static>>>>,
+    read_all_meta_files: AtomicBool,
+    handle_types: RwLock<HashMap<HandleId, Uuid>>,
+    load_errors: RwLock<HashMap<SourcePathId, AssetLoadError>>,
+    failed_loads: Mutex<Vec<(HandleId, AssetLoadError)>>,
+    savers: RwLock<HashMap<(Uuid, String), Arc<dyn ErasedAssetSaver>>>,
+    save_requests: Mutex<Vec<SaveRequest>>,
+    save_echoes: Mutex<HashMap<SourcePathId, Option<SystemTime>>>,
+    load_queue: Mutex<LoadQueue>,
+    content_paths: RwLock<HashMap<ContentKey, AssetPath<'static>>>,
+}
+
+/// Identifies the assets loaded the same way from the same contents.
+#[derive(Clone, PartialEq, Eq, Hash)]
+struct ContentKey {
+    hash: u64,
+    meta_hash: Option<u64>,
+    extension: String,
+}
+
+/// An asset queued with [`AssetServer::save`].
+struct SaveRequest {
+    handle: HandleId,
+    type_uuid: Uuid,
+    path: AssetPath<'static>,
+    saver: Arc<dyn ErasedAssetSaver>,
 }
 
 /// Loads assets from the filesystem in the background.
@@ -132,16 +244,80 @@ impl AssetServer {
                 asset_ref_counter: Default::default(),
                 handle_to_path: Default::default(),
                 asset_lifecycles: Default::default(),
-                asset_io,
+                read_all_meta_files: AtomicBool::new(false),
+                handle_types: Default::default(),
+                load_errors: Default::default(),
+                failed_loads: Default::default(),
+                savers: Default::default(),
+                save_requests: Default::default(),
+                save_echoes: Default::default(),
+                load_queue: Default::default(),
+                content_paths: Default::default(),
+                asset_io: asset_io.into(),
+                named_asset_io: Default::default(),
             }),
         }
     }
 
-    /// Returns the associated asset I/O.
+    /// Returns the asset I/O of the default asset source.
     pub fn asset_io(&self) -> &dyn AssetIo {
         &*self.server.asset_io
     }
 
+    /// Registers an asset I/O as the asset source named `name`.
+    ///
+    /// Assets of the source are loaded with paths like `name://path/to/asset.png`. If the asset
+    /// I/O watches for changes, its assets are hot reloaded like those of the default source.
+    /// Registering a source again replaces it.
+    pub fn add_source<T: AssetIo>(&self, name: impl Into<String>, asset_io: T) {
+        self.add_boxed_source(name, Box::new(asset_io));
+    }
+
+    /// Registers a boxed asset I/O as the asset source named `name`.
+    ///
+    /// See [`add_source`](AssetServer::add_source).
+    pub fn add_boxed_source(&self, name: impl Into<String>, asset_io: Box<dyn AssetIo>) {
+        self.server
+            .named_asset_io
+            .write()
+            .insert(name.into(), asset_io.into());
+    }
+
+    /// Returns the asset I/O of the named asset source, or of the default source if `source` is
+    /// `None`.
+    pub fn source_io(&self, source: Option<&str>) -> Result<Arc<dyn AssetIo>, AssetServerError> {
+        match source {
+            Some(source) => self
+                .server
+                .named_asset_io
+                .read()
+                .get(source)
+                .cloned()
+                .ok_or_else(|| AssetServerError::MissingAssetSource(source.to_string())),
+            None => Ok(self.server.asset_io.clone()),
+        }
+    }
+
+    /// Returns the names of the registered asset sources, without the default one.
+    pub fn source_names(&self) -> Vec<String> {
+        self.server.named_asset_io.read().keys().cloned().collect()
+    }
+
+    /// Sets whether the `.meta` file of every asset is read before loading it, instead of only
+    /// those of assets whose loader has settings.
+    ///
+    /// Processed assets name the loader they need in their `.meta` file, see
+    /// [`ProcessedAsset::with_loader`](crate::ProcessedAsset::with_loader). The [`AssetPlugin`]
+    /// enables this outside of [`AssetMode::Unprocessed`].
+    ///
+    /// [`AssetPlugin`]: crate::AssetPlugin
+    /// [`AssetMode::Unprocessed`]: crate::AssetMode::Unprocessed
+    pub fn set_read_all_meta_files(&self, read_all_meta_files: bool) {
+        self.server
+            .read_all_meta_files
+            .store(read_all_meta_files, Ordering::Relaxed);
+    }
+
     pub(crate) fn register_asset_type<T: Asset>(&self) -> Assets<T> {
         if self
             .server
@@ -175,6 +351,50 @@ impl AssetServer {
         loaders.push(Arc::new(loader));
     }
 
+    /// Adds the provided asset saver to the server.
+    ///
+    /// If a saver for the same asset type was already registered for an extension, it is replaced
+    /// for that extension.
+    pub fn add_saver<T>(&self, saver: T)
+    where
+        T: AssetSaver,
+    {
+        let saver = Arc::new(saver);
+        let mut savers = self.server.savers.write();
+        for extension in saver.extensions() {
+            savers.insert(
+                (<T::Asset as TypeUuid>::TYPE_UUID, extension.to_string()),
+                saver.clone(),
+            );
+        }
+    }
+
+    fn get_path_asset_saver(
+        &self,
+        type_uuid: Uuid,
+        path: &Path,
+    ) -> Result<Arc<dyn ErasedAssetSaver>, AssetServerError> {
+        let file_name = path
+            .file_name()
+            .and_then(|file_name| file_name.to_str())
+            .map(|file_name| file_name.to_lowercase())
+            .unwrap_or_default();
+
+        let savers = self.server.savers.read();
+        let mut exts = Vec::new();
+        let mut ext = file_name.as_str();
+        while let Some(idx) = ext.find('.') {
+            ext = &ext[idx + 1..];
+            exts.push(ext);
+            if let Some(saver) = savers.get(&(type_uuid, ext.to_string())) {
+                return Ok(saver.clone());
+            }
+        }
+        Err(AssetServerError::MissingAssetSaver {
+            extensions: exts.into_iter().map(String::from).collect(),
+        })
+    }
+
     /// Gets a strong handle for an asset with the provided id.
     pub fn get_handle<T: Asset, I: Into<HandleId>>(&self, id: I) -> Handle<T> {
         let sender = self.server.asset_ref_counter.channel.sender.clone();
@@ -252,6 +472,38 @@ impl AssetServer {
         }
     }
 
+    /// Gets the reason the asset of the provided handle failed to load.
+    ///
+    /// Returns `None` if the asset did not fail, or if it is being loaded again.
+    pub fn get_load_error<H: Into<HandleId>>(&self, handle: H) -> Option<AssetLoadError> {
+        match handle.into() {
+            HandleId::AssetPathId(id) => self
+                .server
+                .load_errors
+                .read()
+                .get(&id.source_path_id())
+                .cloned(),
+            HandleId::Id(_, _) => None,
+        }
+    }
+
+    /// Returns the type of the asset of the provided handle, as requested by
+    /// [`load`](AssetServer::load) or as found by its last successful load.
+    pub(crate) fn get_handle_type(&self, handle: HandleId) -> Option<Uuid> {
+        if let Some(type_uuid) = self.server.handle_types.read().get(&handle) {
+            return Some(*type_uuid);
+        }
+        match handle {
+            HandleId::AssetPathId(id) => self
+                .server
+                .asset_sources
+                .read()
+                .get(&id.source_path_id())
+                .and_then(|info| info.get_asset_type(id.label_id())),
+            HandleId::Id(_, _) => None,
+        }
+    }
+
     /// Gets the overall load state of a group of assets from the provided handles.
     ///
     /// This method will only return [`LoadState::Loaded`] if all assets in the
@@ -276,6 +528,81 @@ impl AssetServer {
         load_state
     }
 
+    /// Gets the load state of an asset together with all of its transitive dependencies, as
+    /// declared with [`LoadedAsset::with_dependency`](crate::LoadedAsset::with_dependency).
+    ///
+    /// All labeled assets of a file are loaded together, so the dependencies of every asset of the
+    /// file are taken into account. If any of them failed to load, the path of the first failure
+    /// found is returned.
+    pub fn get_recursive_dependency_load_state<H: Into<HandleId>>(
+        &self,
+        handle: H,
+    ) -> RecursiveDependencyLoadState {
+        let id = match handle.into() {
+            HandleId::AssetPathId(id) => id,
+            HandleId::Id(_, _) => return RecursiveDependencyLoadState::NotLoaded,
+        };
+        let asset_sources = self.server.asset_sources.read();
+        let root_path = || {
+            self.server
+                .handle_to_path
+                .read()
+                .get(&id.into())
+                .cloned()
+                .or_else(|| {
+                    asset_sources
+                        .get(&id.source_path_id())
+                        .map(|info| AssetPath::new(info.path.clone(), None))
+                })
+                .unwrap_or_else(|| AssetPath::new(PathBuf::new(), None))
+        };
+        match asset_sources
+            .get(&id.source_path_id())
+            .map(|info| info.load_state)
+        {
+            Some(LoadState::Loaded) => {}
+            Some(LoadState::Loading) => return RecursiveDependencyLoadState::Loading,
+            Some(LoadState::Failed) => {
+                return RecursiveDependencyLoadState::Failed { path: root_path() }
+            }
+            None | Some(LoadState::NotLoaded | LoadState::Unloaded) => {
+                return RecursiveDependencyLoadState::NotLoaded
+            }
+        }
+
+        let mut state = RecursiveDependencyLoadState::Loaded;
+        let mut visited = HashSet::default();
+        visited.insert(id.source_path_id());
+        let mut queue = vec![id.source_path_id()];
+        while let Some(source_path_id) = queue.pop() {
+            let dependencies = asset_sources
+                .get(&source_path_id)
+                .and_then(|info| info.meta.as_ref())
+                .into_iter()
+                .flat_map(|meta| meta.assets.iter())
+                .flat_map(|asset| asset.dependencies.iter());
+            for dependency in dependencies {
+                let dependency_id = dependency.get_id().source_path_id();
+                if !visited.insert(dependency_id) {
+                    continue;
+                }
+                match asset_sources
+                    .get(&dependency_id)
+                    .map(|info| info.load_state)
+                {
+                    Some(LoadState::Loaded) => queue.push(dependency_id),
+                    Some(LoadState::Failed) => {
+                        return RecursiveDependencyLoadState::Failed {
+                            path: dependency.clone(),
+                        }
+                    }
+                    _ => state = RecursiveDependencyLoadState::Loading,
+                }
+            }
+        }
+        state
+    }
+
     /// Queues an [`Asset`] at the provided relative path for asynchronous loading.
     ///
     /// The absolute path to the asset is `"ROOT/ASSET_FOLDER_NAME/path"`. Its extension is then
@@ -298,92 +625,214 @@ impl AssetServer {
     /// effectively loaded and available in the [`Assets`] collection. The asset will always fail to
     /// load if the provided path doesn't contain an extension.
     ///
+    /// If the [`AssetIo`] knows the [hash](AssetIo::content_hash) of the contents of the asset,
+    /// and another path with the same contents is loading or loaded, the handle of that asset is
+    /// returned instead of loading the contents again.
+    ///
     /// [asset loader]: AssetLoader
     #[must_use = "not using the returned strong handle may result in the unexpected release of the asset"]
     pub fn load<'a, T: Asset, P: Into<AssetPath<'a>>>(&self, path: P) -> Handle<T> {
-        self.load_untyped(path).typed()
+        let handle = self.load_untyped(path);
+        self.server
+            .handle_types
+            .write()
+            .insert(handle.id, T::TYPE_UUID);
+        handle.typed()
     }
 
+    /// Loads an asset on the current task and returns the outcome, bypassing the load queue.
+    ///
+    /// Only used by tests, which need the error of a specific load.
+    #[cfg(test)]
     async fn load_async(
         &self,
         asset_path: AssetPath<'_>,
         force: bool,
     ) -> Result<AssetPathId, AssetServerError> {
+        match self.begin_load(&asset_path, force) {
+            Some(version) => {
+                self.load_version(asset_path, version, LoadPriority::default())
+                    .await
+            }
+            None => Ok(asset_path.get_id()),
+        }
+    }
+
+    /// Marks the source of `asset_path` as loading, and returns the version of the new load.
+    ///
+    /// Returns `None` if the asset is already loaded or loading and `force` is `false`.
+    fn begin_load(&self, asset_path: &AssetPath, force: bool) -> Option<usize> {
         let asset_path_id: AssetPathId = asset_path.get_id();
+        let mut asset_sources = self.server.asset_sources.write();
+        let source_info = match asset_sources.entry(asset_path_id.source_path_id()) {
+            Entry::Occupied(entry) => entry.into_mut(),
+            Entry::Vacant(entry) => entry.insert(SourceInfo {
+                asset_types: Default::default(),
+                committed_assets: Default::default(),
+                load_state: LoadState::NotLoaded,
+                meta: None,
+                path: asset_path.path().to_owned(),
+                version: 0,
+                settings_override: None,
+            }),
+        };
 
-        // load metadata and update source info. this is done in a scope to ensure we release the
-        // locks before loading
-        let version = {
-            let mut asset_sources = self.server.asset_sources.write();
-            let source_info = match asset_sources.entry(asset_path_id.source_path_id()) {
-                Entry::Occupied(entry) => entry.into_mut(),
-                Entry::Vacant(entry) => entry.insert(SourceInfo {
-                    asset_types: Default::default(),
-                    committed_assets: Default::default(),
-                    load_state: LoadState::NotLoaded,
-                    meta: None,
-                    path: asset_path.path().to_owned(),
-                    version: 0,
-                }),
-            };
+        // if asset is already loaded or is loading, don't load again
+        if !force
+            && (source_info
+                .committed_assets
+                .contains(&asset_path_id.label_id())
+                || source_info.load_state == LoadState::Loading)
+        {
+            return None;
+        }
 
-            // if asset is already loaded or is loading, don't load again
-            if !force
-                && (source_info
-                    .committed_assets
-                    .contains(&asset_path_id.label_id())
-                    || source_info.load_state == LoadState::Loading)
-            {
-                return Ok(asset_path_id);
-            }
+        self.server
+            .load_errors
+            .write()
+            .remove(&asset_path_id.source_path_id());
+        source_info.load_state = LoadState::Loading;
+        source_info.committed_assets.clear();
+        source_info.version += 1;
+        source_info.meta = None;
+        Some(source_info.version)
+    }
 
-            source_info.load_state = LoadState::Loading;
-            source_info.committed_assets.clear();
-            source_info.version += 1;
-            source_info.meta = None;
-            source_info.version
-        };
+    /// Returns `true` if `version` is the latest load of the source, meaning it was neither
+    /// cancelled nor superseded by a reload.
+    fn is_current_load(&self, source_path_id: SourcePathId, version: usize) -> bool {
+        self.server
+            .asset_sources
+            .read()
+            .get(&source_path_id)
+            .map(|source_info| source_info.version)
+            == Some(version)
+    }
+
+    /// Loads the asset for the `version` returned by [`begin_load`](AssetServer::begin_load).
+    ///
+    /// Dependencies of the asset are queued with `priority`.
+    async fn load_version(
+        &self,
+        asset_path: AssetPath<'_>,
+        version: usize,
+        priority: LoadPriority,
+    ) -> Result<AssetPathId, AssetServerError> {
+        let asset_path_id: AssetPathId = asset_path.get_id();
 
-        let set_asset_failed = || {
+        // the load was cancelled or superseded while it was queued
+        if !self.is_current_load(asset_path_id.source_path_id(), version) {
+            return Ok(asset_path_id);
+        }
+
+        let set_asset_failed = |err: &AssetServerError, loader: Option<&'static str>| {
             let mut asset_sources = self.server.asset_sources.write();
             let source_info = asset_sources
                 .get_mut(&asset_path_id.source_path_id())
                 .expect("`AssetSource` should exist at this point.");
+            if source_info.version != version {
+                return;
+            }
             source_info.load_state = LoadState::Failed;
+
+            let error = AssetLoadError::new(asset_path.to_owned(), loader, err);
+            self.server
+                .load_errors
+                .write()
+                .insert(asset_path_id.source_path_id(), error.clone());
+            self.server
+                .failed_loads
+                .lock()
+                .push((asset_path_id.into(), error));
         };
 
-        // get the according asset loader
-        let asset_loader = match self.get_path_asset_loader(asset_path.path()) {
+        // get the asset source and the according asset loader
+        let asset_io = match self.source_io(asset_path.source()) {
+            Ok(asset_io) => asset_io,
+            Err(err) => {
+                set_asset_failed(&err, None);
+                return Err(err);
+            }
+        };
+        let mut asset_loader = match self.get_path_asset_loader(asset_path.path()) {
             Ok(loader) => loader,
             Err(err) => {
-                set_asset_failed();
+                set_asset_failed(&err, None);
                 return Err(err);
             }
         };
 
-        // load the asset bytes
-        let bytes = match self.asset_io().load_path(asset_path.path()).await {
-            Ok(bytes) => bytes,
+        // open the asset file
+        let mut reader = match asset_io.open_path(asset_path.path()).await {
+            Ok(reader) => reader,
+            Err(err) => {
+                let err = AssetServerError::AssetIoError(err);
+                set_asset_failed(&err, Some(asset_loader.type_name()));
+                return Err(err);
+            }
+        };
+
+        // load the `.meta` file, if it can name another loader or the loader has settings
+        let read_all_meta_files = self.server.read_all_meta_files.load(Ordering::Relaxed);
+        let meta = if read_all_meta_files || asset_loader.settings_deserializer().is_some() {
+            match self.load_meta(&*asset_io, asset_path.path()).await {
+                Ok(meta) => meta,
+                Err(err) => {
+                    set_asset_failed(&err, Some(asset_loader.type_name()));
+                    return Err(err);
+                }
+            }
+        } else {
+            None
+        };
+        if let (true, Some(meta)) = (read_all_meta_files, &meta) {
+            let loader = deserialize_meta_loader(meta)
+                .map_err(AssetServerError::from)
+                .and_then(|loader| loader.map(|ext| self.get_asset_loader(&ext)).transpose());
+            match loader {
+                Ok(Some(loader)) => asset_loader = loader,
+                Ok(None) => {}
+                Err(err) => {
+                    set_asset_failed(&err, Some(asset_loader.type_name()));
+                    return Err(err);
+                }
+            }
+        }
+
+        // load the asset settings, if the loader has any
+        let settings = match self.load_settings(
+            &*asset_loader,
+            asset_path.get_id().source_path_id(),
+            meta.as_deref(),
+        ) {
+            Ok(settings) => settings,
             Err(err) => {
-                set_asset_failed();
-                return Err(AssetServerError::AssetIoError(err));
+                set_asset_failed(&err, Some(asset_loader.type_name()));
+                return Err(err);
             }
         };
 
+        // the load was cancelled while the file was being opened
+        if !self.is_current_load(asset_path_id.source_path_id(), version) {
+            return Ok(asset_path_id);
+        }
+
         // load the asset source using the corresponding AssetLoader
         let mut load_context = LoadContext::new(
+            asset_path.source(),
             asset_path.path(),
             &self.server.asset_ref_counter.channel,
-            self.asset_io(),
+            &*asset_io,
             version,
+            settings.as_deref(),
         );
 
         if let Err(err) = asset_loader
-            .load(&bytes, &mut load_context)
+            .load_reader(&mut *reader, &mut load_context)
             .await
             .map_err(AssetServerError::AssetLoaderError)
         {
-            set_asset_failed();
+            set_asset_failed(&err, Some(asset_loader.type_name()));
             return Err(err);
         }
 
@@ -411,129 +860,579 @@ impl AssetServer {
             assets: load_context.get_asset_metas(),
         });
 
-        // load asset dependencies and prepare asset type hashmap
+        // prepare asset type hashmap
         for (label, loaded_asset) in &mut load_context.labeled_assets {
             let label_id = LabelId::from(label.as_ref().map(|label| label.as_str()));
             let type_uuid = loaded_asset.value.as_ref().unwrap().type_uuid();
             source_info.asset_types.insert(label_id, type_uuid);
+        }
+        drop(asset_sources);
+
+        // load asset dependencies, once the lock is released as queuing them updates their sources
+        for loaded_asset in load_context.labeled_assets.values() {
             for dependency in &loaded_asset.dependencies {
-                self.load_untracked(dependency.clone(), false);
+                self.load_untracked_with_priority(dependency.clone(), false, priority);
             }
         }
 
-        self.asset_io()
-            .watch_path_for_changes(asset_path.path())
-            .unwrap();
+        asset_io.watch_path_for_changes(asset_path.path()).unwrap();
+        if meta.is_some() {
+            asset_io
+                .watch_path_for_changes(&meta_path(asset_path.path()))
+                .unwrap();
+        }
         self.create_assets_in_load_context(&mut load_context);
         Ok(asset_path_id)
     }
 
-    /// Queues the [`Asset`] at the provided path for loading and returns an untyped handle.
-    ///
-    /// See [`load`](AssetServer::load).
-    #[must_use = "not using the returned strong handle may result in the unexpected release of the asset"]
-    pub fn load_untyped<'a, P: Into<AssetPath<'a>>>(&self, path: P) -> HandleUntyped {
-        let handle_id = self.load_untracked(path.into(), false);
-        self.get_handle_untyped(handle_id)
+    /// Reads the `.meta` file of the asset at `path`, if there is one.
+    async fn load_meta(
+        &self,
+        asset_io: &dyn AssetIo,
+        path: &Path,
+    ) -> Result<Option<Vec<u8>>, AssetServerError> {
+        match asset_io.load_path(&meta_path(path)).await {
+            Ok(meta) => Ok(Some(meta)),
+            Err(AssetIoError::NotFound(_)) => Ok(None),
+            Err(err) => Err(err.into()),
+        }
     }
 
-    /// Force an [`Asset`] to be reloaded.
-    ///
-    /// This is useful for custom hot-reloading or for supporting `watch_for_changes`
-    /// in custom [`AssetIo`] implementations.
-    pub fn reload_asset<'a, P: Into<AssetPath<'a>>>(&self, path: P) {
-        self.load_untracked(path.into(), true);
-    }
+    /// Deserializes the settings of an asset from its `.meta` file and applies the override
+    /// registered with [`AssetServer::load_with_settings`], if any.
+    fn load_settings(
+        &self,
+        asset_loader: &dyn AssetLoader,
+        source_path_id: SourcePathId,
+        meta: Option<&[u8]>,
+    ) -> Result<Option<Box<dyn LoaderSettings>>, AssetServerError> {
+        let mut settings = match (asset_loader.settings_deserializer(), meta) {
+            (Some(deserialize), Some(meta)) => Some(deserialize(meta)?),
+            _ => None,
+        };
 
-    pub(crate) fn load_untracked(&self, asset_path: AssetPath<'_>, force: bool) -> HandleId {
-        let server = self.clone();
-        let owned_path = asset_path.to_owned();
-        IoTaskPool::get()
-            .spawn(async move {
-                if let Err(err) = server.load_async(owned_path, force).await {
-                    warn!("{}", err);
-                }
-            })
-            .detach();
+        let settings_override = self
+            .server
+            .asset_sources
+            .read()
+            .get(&source_path_id)
+            .and_then(|source_info| source_info.settings_override.clone());
+        if let Some(settings_override) = settings_override {
+            settings = Some(settings_override.apply(settings));
+        }
+        Ok(settings)
+    }
 
-        let handle_id = asset_path.get_id().into();
+    /// Queues an [`Asset`] for loading like [`load`](AssetServer::load), with settings of type
+    /// `S` modified by `settings`.
+    ///
+    /// `settings` receives the settings read from the `.meta` file of the asset, or `S::default()`
+    /// if there is none, and its changes are passed to the loader through
+    /// [`LoadContext::settings`]. The override is kept for later reloads of the asset, including
+    /// hot reloads, until all strong handles to it are dropped. The asset is always reloaded, even
+    /// if it was already loaded with other settings.
+    #[must_use = "not using the returned strong handle may result in the unexpected release of the asset"]
+    pub fn load_with_settings<'a, T, S, P>(
+        &self,
+        path: P,
+        settings: impl Fn(&mut S) + Send + Sync + 'static,
+    ) -> Handle<T>
+    where
+        T: Asset,
+        S: LoaderSettings + Default,
+        P: Into<AssetPath<'a>>,
+    {
+        let handle_id = self.queue_load(
+            path.into(),
+            true,
+            LoadPriority::default(),
+            Some(SettingsOverride::new(settings)),
+        );
         self.server
-            .handle_to_path
+            .handle_types
             .write()
-            .entry(handle_id)
-            .or_insert_with(|| asset_path.to_owned());
-
-        asset_path.into()
+            .insert(handle_id, T::TYPE_UUID);
+        self.get_handle(handle_id)
     }
 
-    /// Loads assets from the specified folder recursively.
-    ///
-    /// # Errors
+    /// Queues an [`Asset`] for loading like [`load`](AssetServer::load), with the given priority.
     ///
-    /// - If the provided path is not a directory, it will fail with
-    /// [`AssetServerError::AssetFolderNotADirectory`].
-    /// - If something unexpected happened while loading an asset, other
-    /// [`AssetServerError`]s may be returned.
-    #[must_use = "not using the returned strong handles may result in the unexpected release of the assets"]
-    pub fn load_folder<P: AsRef<Path>>(
+    /// At most [`max_concurrent_loads`](AssetServer::set_max_concurrent_loads) loads run at the
+    /// same time, the others wait in a queue ordered by priority. Dependencies of the asset are
+    /// queued with the same priority.
+    #[must_use = "not using the returned strong handle may result in the unexpected release of the asset"]
+    pub fn load_with_priority<'a, T: Asset, P: Into<AssetPath<'a>>>(
         &self,
         path: P,
-    ) -> Result<Vec<HandleUntyped>, AssetServerError> {
-        let path = path.as_ref();
-        if !self.asset_io().is_dir(path) {
-            return Err(AssetServerError::AssetFolderNotADirectory(
-                path.to_str().unwrap().to_string(),
-            ));
-        }
+        priority: LoadPriority,
+    ) -> Handle<T> {
+        let path = self.shared_content_path(path.into());
+        let handle_id = self.load_untracked_with_priority(path, false, priority);
+        self.server
+            .handle_types
+            .write()
+            .insert(handle_id, T::TYPE_UUID);
+        self.get_handle(handle_id)
+    }
 
-        let mut handles = Vec::new();
-        for child_path in self.asset_io().read_directory(path.as_ref())? {
-            if self.asset_io().is_dir(&child_path) {
-                handles.extend(self.load_folder(&child_path)?);
-            } else {
-                if self.get_path_asset_loader(&child_path).is_err() {
-                    continue;
-                }
-                let handle =
-                    self.load_untyped(child_path.to_str().expect("Path should be a valid string."));
-                handles.push(handle);
-            }
+    /// Changes the priority of the load of an asset that is still queued.
+    ///
+    /// Returns `false` if the load already started or was never queued.
+    pub fn set_load_priority<H: Into<HandleId>>(&self, handle: H, priority: LoadPriority) -> bool {
+        match handle.into() {
+            HandleId::AssetPathId(id) => self
+                .server
+                .load_queue
+                .lock()
+                .set_priority(id.source_path_id(), priority),
+            HandleId::Id(_, _) => false,
         }
+    }
 
-        Ok(handles)
+    /// Sets the number of loads that run at the same time, [`DEFAULT_MAX_CONCURRENT_LOADS`] by
+    /// default.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `max_concurrent_loads` is zero.
+    pub fn set_max_concurrent_loads(&self, max_concurrent_loads: usize) {
+        assert!(
+            max_concurrent_loads > 0,
+            "at least one load must be able to run"
+        );
+        self.server.load_queue.lock().max_concurrent_loads = max_concurrent_loads;
+        self.start_queued_loads();
     }
 
-    /// Frees unused assets, unloading them from memory.
-    pub fn free_unused_assets(&self) {
-        let mut potential_frees = self.server.asset_ref_counter.mark_unused_assets.lock();
+    /// Holds the queued loads while `paused` is `true`, and starts them once it is `false` again.
+    ///
+    /// Used by the [`AssetPipeline`](crate::AssetPipeline) to keep loads from reading processed
+    /// assets before they are written.
+    pub(crate) fn set_loads_paused(&self, paused: bool) {
+        let mut load_queue = self.server.load_queue.lock();
+        if load_queue.paused != paused {
+            load_queue.paused = paused;
+            drop(load_queue);
+            self.start_queued_loads();
+        }
+    }
 
-        if !potential_frees.is_empty() {
-            let ref_counts = self.server.asset_ref_counter.ref_counts.read();
-            let asset_sources = self.server.asset_sources.read();
-            let asset_lifecycles = self.server.asset_lifecycles.read();
-            for potential_free in potential_frees.drain(..) {
-                if let Some(&0) = ref_counts.get(&potential_free) {
-                    let type_uuid = match potential_free {
-                        HandleId::Id(type_uuid, _) => Some(type_uuid),
-                        HandleId::AssetPathId(id) => asset_sources
-                            .get(&id.source_path_id())
-                            .and_then(|source_info| source_info.get_asset_type(id.label_id())),
-                    };
+    /// Returns the number of loads waiting in the queue.
+    pub fn queued_load_count(&self) -> usize {
+        self.server.load_queue.lock().len()
+    }
 
-                    if let Some(type_uuid) = type_uuid {
-                        if let Some(asset_lifecycle) = asset_lifecycles.get(&type_uuid) {
-                            asset_lifecycle.free_asset(potential_free);
-                        }
+    /// Returns the number of loads that are running.
+    pub fn in_flight_load_count(&self) -> usize {
+        self.server.load_queue.lock().in_flight
+    }
+
+    /// Starts queued loads until the maximum number of concurrent loads is reached.
+    fn start_queued_loads(&self) {
+        let mut load_queue = self.server.load_queue.lock();
+        while let Some(load) = load_queue.start_next() {
+            let in_flight = InFlightLoad(self.clone());
+            IoTaskPool::get()
+                .spawn(async move {
+                    if let Err(err) = in_flight
+                        .0
+                        .load_version(load.path, load.version, load.priority)
+                        .await
+                    {
+                        warn!("{}", err);
                     }
-                }
-            }
+                })
+                .detach();
         }
     }
 
-    /// Iterates through asset references and marks assets with no active handles as unused.
-    pub fn mark_unused_assets(&self) {
+    /// Cancels the load of the source of `id` if it is queued or running, unless other assets of
+    /// the source still have strong handles.
+    ///
+    /// A running load is not interrupted: its loader runs to completion, but its assets are
+    /// discarded instead of being committed.
+    fn cancel_unused_load(
+        &self,
+        id: AssetPathId,
+        source_ref_counts: &HashMap<SourcePathId, usize>,
+    ) {
+        let source_path_id = id.source_path_id();
+        if source_ref_counts.contains_key(&source_path_id) {
+            return;
+        }
+
+        let mut asset_sources = self.server.asset_sources.write();
+        if let Some(source_info) = asset_sources.get_mut(&source_path_id) {
+            if source_info.load_state == LoadState::Loading {
+                source_info.load_state = LoadState::NotLoaded;
+                source_info.version += 1;
+                self.server.load_queue.lock().remove(source_path_id);
+            }
+        }
+    }
+
+    /// Queues the [`Asset`] at the provided path for loading and returns an untyped handle.
+    ///
+    /// See [`load`](AssetServer::load).
+    #[must_use = "not using the returned strong handle may result in the unexpected release of the asset"]
+    pub fn load_untyped<'a, P: Into<AssetPath<'a>>>(&self, path: P) -> HandleUntyped {
+        let handle_id = self.load_untracked(self.shared_content_path(path.into()), false);
+        self.get_handle_untyped(handle_id)
+    }
+
+    /// Saves the asset of `handle` to `path`, with the [`AssetSaver`] registered for its type and
+    /// the extension of `path`.
+    ///
+    /// The asset is serialized at the end of the frame, in
+    /// [`AssetStage::AssetEvents`](crate::AssetStage::AssetEvents), and written in the background
+    /// through the [`AssetIo`] of the source of `path`. Failures past this call are logged.
+    ///
+    /// [`get_handle_path`](AssetServer::get_handle_path) returns `path` for the handle from now
+    /// on, and writing the file does not hot-reload the asset it came from.
+    pub fn save<'a, T: Asset, P: Into<AssetPath<'a>>>(
+        &self,
+        handle: &Handle<T>,
+        path: P,
+    ) -> Result<(), AssetServerError> {
+        let path = path.into().to_owned();
+        self.source_io(path.source())?;
+        let saver = self.get_path_asset_saver(T::TYPE_UUID, path.path())?;
+
+        self.server
+            .handle_to_path
+            .write()
+            .insert(handle.id(), path.clone());
+        self.server.save_requests.lock().push(SaveRequest {
+            handle: handle.id(),
+            type_uuid: T::TYPE_UUID,
+            path,
+            saver,
+        });
+        Ok(())
+    }
+
+    /// Serializes the assets of type `T` queued with [`AssetServer::save`] and writes them in the
+    /// background.
+    pub(crate) fn save_assets<T: Asset>(&self, assets: &Assets<T>) {
+        let requests: Vec<_> = {
+            let mut save_requests = self.server.save_requests.lock();
+            if save_requests.is_empty() {
+                return;
+            }
+            let (requests, others) = std::mem::take(&mut *save_requests)
+                .into_iter()
+                .partition(|request| request.type_uuid == T::TYPE_UUID);
+            *save_requests = others;
+            requests
+        };
+
+        for request in requests {
+            let asset = match assets.get(&Handle::weak(request.handle)) {
+                Some(asset) => asset,
+                None => {
+                    warn!(
+                        "failed to save `{}`: the asset does not exist",
+                        request.path
+                    );
+                    continue;
+                }
+            };
+            let bytes = match request.saver.save(asset) {
+                Ok(bytes) => bytes,
+                Err(err) => {
+                    warn!("failed to save `{}`: {:#}", request.path, err);
+                    continue;
+                }
+            };
+            let asset_io = match self.source_io(request.path.source()) {
+                Ok(asset_io) => asset_io,
+                Err(err) => {
+                    warn!("failed to save `{}`: {}", request.path, err);
+                    continue;
+                }
+            };
+
+            // the modification time of the file is only known once it has been written
+            let source_path_id = request.path.get_id().source_path_id();
+            self.server.save_echoes.lock().insert(source_path_id, None);
+            let asset_server = self.clone();
+            let path = request.path;
+            IoTaskPool::get()
+                .spawn(async move {
+                    let modified = match asset_io.write_path(path.path(), &bytes).await {
+                        Ok(()) => asset_io
+                            .get_metadata(path.path())
+                            .ok()
+                            .and_then(|metadata| metadata.modified()),
+                        Err(err) => {
+                            warn!("failed to save `{}`: {}", path, err);
+                            None
+                        }
+                    };
+                    let mut save_echoes = asset_server.server.save_echoes.lock();
+                    match modified {
+                        Some(modified) => {
+                            save_echoes.insert(source_path_id, Some(modified));
+                        }
+                        None => {
+                            save_echoes.remove(&source_path_id);
+                        }
+                    }
+                })
+                .detach();
+        }
+    }
+
+    /// Returns `true` if the change of the file of `asset_path` comes from its last write by
+    /// [`AssetServer::save`], in which case it should not reload the asset.
+    ///
+    /// The file is recognized by the modification time the write left on it, and only the first
+    /// change after the write completed is treated as its echo.
+    #[cfg(all(
+        feature = "filesystem_watcher",
+        all(not(target_arch = "wasm32"), not(target_os = "android"))
+    ))]
+    pub(crate) fn is_save_echo(&self, asset_path: &AssetPath, asset_io: &dyn AssetIo) -> bool {
+        let source_path_id = asset_path.get_id().source_path_id();
+        let saved = {
+            let mut save_echoes = self.server.save_echoes.lock();
+            match save_echoes.get(&source_path_id) {
+                // the write is still in progress
+                Some(None) => return true,
+                Some(Some(_)) => save_echoes.remove(&source_path_id).flatten(),
+                None => return false,
+            }
+        };
+        let modified = asset_io
+            .get_metadata(asset_path.path())
+            .ok()
+            .and_then(|metadata| metadata.modified());
+        modified.is_some() && modified == saved
+    }
+
+    /// Force an [`Asset`] to be reloaded.
+    ///
+    /// This is useful for custom hot-reloading or for supporting `watch_for_changes`
+    /// in custom [`AssetIo`] implementations.
+    pub fn reload_asset<'a, P: Into<AssetPath<'a>>>(&self, path: P) {
+        self.load_untracked(path.into(), true);
+    }
+
+    /// Returns the path of the asset which is loading or loaded from the same contents as
+    /// `asset_path`, so that both paths share its handle, or `asset_path` if there is none.
+    ///
+    /// Contents are only compared when the asset I/O knows their
+    /// [hash](AssetIo::content_hash), and the paths must also have the same extension and
+    /// `.meta` file.
+    fn shared_content_path<'a>(&self, asset_path: AssetPath<'a>) -> AssetPath<'a> {
+        let Some(key) = self.content_key(&asset_path) else {
+            return asset_path;
+        };
+        let Some(shared_path) = self.server.content_paths.read().get(&key).cloned() else {
+            return asset_path;
+        };
+
+        let source_path_id = shared_path.get_id().source_path_id();
+        let load_state = self
+            .server
+            .asset_sources
+            .read()
+            .get(&source_path_id)
+            .map(|source_info| source_info.load_state);
+        if source_path_id == asset_path.get_id().source_path_id()
+            || !matches!(load_state, Some(LoadState::Loading | LoadState::Loaded))
+        {
+            return asset_path;
+        }
+        with_label(&shared_path, asset_path.label())
+    }
+
+    fn content_key(&self, asset_path: &AssetPath) -> Option<ContentKey> {
+        let asset_io = self.source_io(asset_path.source()).ok()?;
+        let hash = asset_io.content_hash(asset_path.path())?;
+        let file_name = asset_path.path().file_name()?.to_str()?.to_lowercase();
+        let (_, extension) = file_name.split_once('.')?;
+        Some(ContentKey {
+            hash,
+            meta_hash: asset_io.content_hash(&meta_path(asset_path.path())),
+            extension: extension.to_string(),
+        })
+    }
+
+    pub(crate) fn load_untracked(&self, asset_path: AssetPath<'_>, force: bool) -> HandleId {
+        self.load_untracked_with_priority(asset_path, force, LoadPriority::default())
+    }
+
+    fn load_untracked_with_priority(
+        &self,
+        asset_path: AssetPath<'_>,
+        force: bool,
+        priority: LoadPriority,
+    ) -> HandleId {
+        self.queue_load(asset_path, force, priority, None)
+    }
+
+    /// Queues the load of `asset_path`, whose settings are modified by `settings_override`.
+    fn queue_load(
+        &self,
+        asset_path: AssetPath<'_>,
+        force: bool,
+        priority: LoadPriority,
+        settings_override: Option<SettingsOverride>,
+    ) -> HandleId {
+        if let Some(version) = self.begin_load(&asset_path, force) {
+            if let Some(key) = self.content_key(&asset_path) {
+                let source_path = with_label(&asset_path, None);
+                self.server.content_paths.write().insert(key, source_path);
+            }
+            if let Some(settings_override) = settings_override {
+                if let Some(source_info) = self
+                    .server
+                    .asset_sources
+                    .write()
+                    .get_mut(&asset_path.get_id().source_path_id())
+                {
+                    source_info.settings_override = Some(settings_override);
+                }
+            }
+            self.server
+                .load_queue
+                .lock()
+                .push(asset_path.to_owned(), version, priority);
+            self.start_queued_loads();
+        }
+
+        let handle_id = asset_path.get_id().into();
+        self.server
+            .handle_to_path
+            .write()
+            .entry(handle_id)
+            .or_insert_with(|| asset_path.to_owned());
+
+        asset_path.into()
+    }
+
+    /// Loads assets from the specified folder recursively.
+    ///
+    /// The folder may be in a named asset source, like `mods://weapons`.
+    ///
+    /// # Errors
+    ///
+    /// - If the provided path is not a directory, it will fail with
+    /// [`AssetServerError::AssetFolderNotADirectory`].
+    /// - If something unexpected happened while loading an asset, other
+    /// [`AssetServerError`]s may be returned.
+    #[must_use = "not using the returned strong handles may result in the unexpected release of the assets"]
+    pub fn load_folder<'a, P: Into<AssetPath<'a>>>(
+        &self,
+        path: P,
+    ) -> Result<Vec<HandleUntyped>, AssetServerError> {
+        let path = path.into();
+        let asset_io = self.source_io(path.source())?;
+        self.load_folder_in_source(&*asset_io, path.source(), path.path())
+    }
+
+    fn load_folder_in_source(
+        &self,
+        asset_io: &dyn AssetIo,
+        source: Option<&str>,
+        path: &Path,
+    ) -> Result<Vec<HandleUntyped>, AssetServerError> {
+        if !asset_io.is_dir(path) {
+            return Err(AssetServerError::AssetFolderNotADirectory(
+                path.to_str().unwrap().to_string(),
+            ));
+        }
+
+        let mut handles = Vec::new();
+        for child_path in asset_io.read_directory(path)? {
+            if asset_io.is_dir(&child_path) {
+                handles.extend(self.load_folder_in_source(asset_io, source, &child_path)?);
+            } else {
+                if self.get_path_asset_loader(&child_path).is_err() {
+                    continue;
+                }
+                let handle =
+                    self.load_untyped(AssetPath::new_ref_in_source(source, &child_path, None));
+                handles.push(handle);
+            }
+        }
+
+        Ok(handles)
+    }
+
+    /// Frees unused assets, unloading them from memory.
+    pub fn free_unused_assets(&self) {
+        let mut potential_frees = self.server.asset_ref_counter.mark_unused_assets.lock();
+
+        if !potential_frees.is_empty() {
+            let ref_counts = self.server.asset_ref_counter.ref_counts.read();
+            let source_ref_counts = self.server.asset_ref_counter.source_ref_counts.read();
+            let mut unused_loads = Vec::new();
+            let mut freed = Vec::new();
+            let asset_sources = self.server.asset_sources.read();
+            let asset_lifecycles = self.server.asset_lifecycles.read();
+            for potential_free in potential_frees.drain(..) {
+                if let Some(&0) = ref_counts.get(&potential_free) {
+                    if let HandleId::AssetPathId(id) = potential_free {
+                        if let Some(LoadState::Loading) = asset_sources
+                            .get(&id.source_path_id())
+                            .map(|source_info| source_info.load_state)
+                        {
+                            unused_loads.push(id);
+                        }
+                    }
+
+                    let type_uuid = match potential_free {
+                        HandleId::Id(type_uuid, _) => Some(type_uuid),
+                        HandleId::AssetPathId(id) => asset_sources
+                            .get(&id.source_path_id())
+                            .and_then(|source_info| source_info.get_asset_type(id.label_id())),
+                    };
+
+                    if let Some(type_uuid) = type_uuid {
+                        if let Some(asset_lifecycle) = asset_lifecycles.get(&type_uuid) {
+                            asset_lifecycle.free_asset(potential_free);
+                        }
+                    }
+                    freed.push(potential_free);
+                }
+            }
+
+            drop(asset_sources);
+            if !freed.is_empty() {
+                let mut handle_types = self.server.handle_types.write();
+                let mut load_errors = self.server.load_errors.write();
+                for handle_id in &freed {
+                    handle_types.remove(handle_id);
+                    if let HandleId::AssetPathId(id) = handle_id {
+                        load_errors.remove(&id.source_path_id());
+                    }
+                }
+            }
+            // settings overrides only live as long as the strong handles of their source
+            let mut asset_sources = self.server.asset_sources.write();
+            for handle_id in freed {
+                if let HandleId::AssetPathId(id) = handle_id {
+                    if !source_ref_counts.contains_key(&id.source_path_id()) {
+                        if let Some(source_info) = asset_sources.get_mut(&id.source_path_id()) {
+                            source_info.settings_override = None;
+                        }
+                    }
+                }
+            }
+            drop(asset_sources);
+            for id in unused_loads {
+                self.cancel_unused_load(id, &source_ref_counts);
+            }
+        }
+    }
+
+    /// Iterates through asset references and marks assets with no active handles as unused.
+    pub fn mark_unused_assets(&self) {
         let receiver = &self.server.asset_ref_counter.channel.receiver;
         let mut ref_counts = self.server.asset_ref_counter.ref_counts.write();
+        let mut source_ref_counts = self.server.asset_ref_counter.source_ref_counts.write();
         let mut potential_frees = None;
         loop {
             let ref_change = match receiver.try_recv() {
@@ -542,11 +1441,27 @@ impl AssetServer {
                 Err(TryRecvError::Disconnected) => panic!("RefChange channel disconnected."),
             };
             match ref_change {
-                RefChange::Increment(handle_id) => *ref_counts.entry(handle_id).or_insert(0) += 1,
+                RefChange::Increment(handle_id) => {
+                    let entry = ref_counts.entry(handle_id).or_insert(0);
+                    *entry += 1;
+                    if let (1, HandleId::AssetPathId(id)) = (*entry, handle_id) {
+                        *source_ref_counts.entry(id.source_path_id()).or_insert(0) += 1;
+                    }
+                }
                 RefChange::Decrement(handle_id) => {
                     let entry = ref_counts.entry(handle_id).or_insert(0);
                     *entry -= 1;
                     if *entry == 0 {
+                        if let HandleId::AssetPathId(id) = handle_id {
+                            if let Entry::Occupied(mut count) =
+                                source_ref_counts.entry(id.source_path_id())
+                            {
+                                *count.get_mut() -= 1;
+                                if *count.get() == 0 {
+                                    count.remove();
+                                }
+                            }
+                        }
                         potential_frees
                             .get_or_insert_with(|| {
                                 self.server.asset_ref_counter.mark_unused_assets.lock()
@@ -566,8 +1481,11 @@ impl AssetServer {
                 .take()
                 .expect("Asset should exist at this point.");
             if let Some(asset_lifecycle) = asset_lifecycles.get(&asset_value.type_uuid()) {
-                let asset_path =
-                    AssetPath::new_ref(load_context.path, label.as_ref().map(|l| l.as_str()));
+                let asset_path = AssetPath::new_ref_in_source(
+                    load_context.source,
+                    load_context.path,
+                    label.as_ref().map(|l| l.as_str()),
+                );
                 asset_lifecycle.create_asset(asset_path.into(), asset_value, load_context.version);
             } else {
                 panic!(
@@ -608,6 +1526,9 @@ impl AssetServer {
                         }
                     }
 
+                    if let HandleId::AssetPathId(_) = result.id {
+                        assets.pending_dependency_loads.insert(result.id);
+                    }
                     assets.set_untracked(result.id, *result.asset);
                 }
                 Ok(AssetLifecycleEvent::Free(handle_id)) => {
@@ -627,6 +1548,31 @@ impl AssetServer {
                 Err(TryRecvError::Disconnected) => panic!("AssetChannel disconnected."),
             }
         }
+
+        drop(asset_sources_guard);
+        if !assets.pending_dependency_loads.is_empty() {
+            assets.send_loaded_with_dependencies_events(self);
+        }
+    }
+}
+
+/// Returns `asset_path` in the same source, with the given label.
+fn with_label(asset_path: &AssetPath, label: Option<&str>) -> AssetPath<'static> {
+    let path = AssetPath::new(asset_path.path().to_owned(), label.map(str::to_string));
+    match asset_path.source() {
+        Some(source) => path.with_source(source.to_string()),
+        None => path,
+    }
+}
+
+/// Counts a started load as in flight until it is dropped, even if its loader panics, then
+/// starts the next queued loads.
+struct InFlightLoad(AssetServer);
+
+impl Drop for InFlightLoad {
+    fn drop(&mut self) {
+        self.0.server.load_queue.lock().in_flight -= 1;
+        self.0.start_queued_loads();
     }
 }
 
@@ -635,6 +1581,23 @@ fn free_unused_assets_system_impl(asset_server: &AssetServer) {
     asset_server.mark_unused_assets();
 }
 
+/// A system that sends an [`UntypedAssetLoadFailedEvent`](crate::UntypedAssetLoadFailedEvent)
+/// for every asset that failed to load since it last ran.
+pub fn asset_load_failed_event_system(
+    asset_server: Res<AssetServer>,
+    mut events: EventWriter<UntypedAssetLoadFailedEvent>,
+) {
+    let mut failed_loads = asset_server.server.failed_loads.lock();
+    events.send_batch(
+        failed_loads
+            .drain(..)
+            .map(|(id, error)| UntypedAssetLoadFailedEvent {
+                handle: HandleUntyped::weak(id),
+                error,
+            }),
+    );
+}
+
 /// A system for freeing assets that have no active handles.
 pub fn free_unused_assets_system(asset_server: Res<AssetServer>) {
     free_unused_assets_system_impl(&asset_server);
@@ -643,7 +1606,9 @@ pub fn free_unused_assets_system(asset_server: Res<AssetServer>) {
 #[cfg(test)]
 mod test {
     use super::*;
-    use crate::{loader::LoadedAsset, update_asset_storage_system};
+    use crate::{
+        loader::LoadedAsset, update_asset_storage_system, AssetEvent, AssetLoadFailedEvent,
+    };
     use bevy_app::App;
     use bevy_ecs::prelude::*;
     use bevy_reflect::TypeUuid;
@@ -684,6 +1649,21 @@ mod test {
         }
     }
 
+    struct PanickingLoader;
+    impl AssetLoader for PanickingLoader {
+        fn load<'a>(
+            &'a self,
+            _: &'a [u8],
+            _: &'a mut LoadContext,
+        ) -> BoxedFuture<'a, Result<(), anyhow::Error>> {
+            Box::pin(async { panic!("loader panicked") })
+        }
+
+        fn extensions(&self) -> &[&str] {
+            &["panic"]
+        }
+    }
+
     struct FakeMultipleDotLoader;
     impl AssetLoader for FakeMultipleDotLoader {
         fn load<'a>(
@@ -699,6 +1679,132 @@ mod test {
         }
     }
 
+    /// Loads a [`PngAsset`] that depends on the paths listed in the file, one per line.
+    struct DependenciesLoader;
+    impl AssetLoader for DependenciesLoader {
+        fn load<'a>(
+            &'a self,
+            bytes: &'a [u8],
+            ctx: &'a mut LoadContext,
+        ) -> BoxedFuture<'a, Result<(), anyhow::Error>> {
+            let dependencies = std::str::from_utf8(bytes)
+                .unwrap()
+                .lines()
+                .map(|path| AssetPath::from(path).to_owned())
+                .collect();
+            ctx.set_default_asset(LoadedAsset::new(PngAsset).with_dependencies(dependencies));
+            Box::pin(async move { Ok(()) })
+        }
+
+        fn extensions(&self) -> &[&str] {
+            &["deps"]
+        }
+    }
+
+    struct FakePngSaver;
+    impl AssetSaver for FakePngSaver {
+        type Asset = PngAsset;
+
+        fn save(&self, _: &PngAsset) -> Result<Vec<u8>, anyhow::Error> {
+            Ok(b"png".to_vec())
+        }
+
+        fn extensions(&self) -> &[&str] {
+            &["png"]
+        }
+    }
+
+    #[derive(Debug, TypeUuid)]
+    #[uuid = "3e3ca1a4-6f39-4d5b-9a3c-35d0c1b3c0f2"]
+    struct ScaledAsset(u32);
+
+    #[derive(Clone, Debug, Default, serde::Deserialize)]
+    struct ScaleSettings {
+        scale: u32,
+    }
+
+    struct ScaledLoader;
+    impl AssetLoader for ScaledLoader {
+        fn load<'a>(
+            &'a self,
+            bytes: &'a [u8],
+            ctx: &'a mut LoadContext,
+        ) -> BoxedFuture<'a, Result<(), anyhow::Error>> {
+            let scale = ctx.settings::<ScaleSettings>().map_or(1, |s| s.scale);
+            ctx.set_default_asset(LoadedAsset::new(ScaledAsset(bytes.len() as u32 * scale)));
+            Box::pin(async move { Ok(()) })
+        }
+
+        fn extensions(&self) -> &[&str] {
+            &["scaled"]
+        }
+
+        fn settings_deserializer(&self) -> Option<crate::SettingsDeserializer> {
+            Some(crate::deserialize_settings::<ScaleSettings>)
+        }
+    }
+
+    /// Reads only the last byte of the file, as a [`ScaledAsset`].
+    struct LastByteLoader;
+    impl AssetLoader for LastByteLoader {
+        fn load<'a>(
+            &'a self,
+            bytes: &'a [u8],
+            ctx: &'a mut LoadContext,
+        ) -> BoxedFuture<'a, Result<(), anyhow::Error>> {
+            Box::pin(async move {
+                self.load_reader(&mut futures_lite::io::Cursor::new(bytes), ctx)
+                    .await
+            })
+        }
+
+        fn load_reader<'a>(
+            &'a self,
+            reader: &'a mut dyn crate::AssetReader,
+            ctx: &'a mut LoadContext,
+        ) -> BoxedFuture<'a, Result<(), anyhow::Error>> {
+            use futures_lite::{AsyncReadExt, AsyncSeekExt};
+            Box::pin(async move {
+                reader.seek(std::io::SeekFrom::End(-1)).await?;
+                let mut last = [0];
+                reader.read_exact(&mut last).await?;
+                ctx.set_default_asset(LoadedAsset::new(ScaledAsset(last[0] as u32)));
+                Ok(())
+            })
+        }
+
+        fn extensions(&self) -> &[&str] {
+            &["last"]
+        }
+    }
+
+    /// Records the order in which loads start, and holds loads of `block*` files until released.
+    #[derive(Default, Clone)]
+    struct RecordingLoader {
+        started: Arc<Mutex<Vec<PathBuf>>>,
+        released: Arc<AtomicBool>,
+    }
+    impl AssetLoader for RecordingLoader {
+        fn load<'a>(
+            &'a self,
+            _: &'a [u8],
+            ctx: &'a mut LoadContext,
+        ) -> BoxedFuture<'a, Result<(), anyhow::Error>> {
+            self.started.lock().push(ctx.path().to_owned());
+            if ctx.path().starts_with("block") {
+                while !self.released.load(Ordering::Acquire) {
+                    std::thread::sleep(std::time::Duration::from_millis(1));
+                }
+            }
+            ctx.set_default_asset(LoadedAsset::new(PngAsset));
+            Box::pin(async move { Ok(()) })
+        }
+
+        fn extensions(&self) -> &[&str] {
+            &["rec"]
+        }
+    }
+
     fn setup(asset_path: impl AsRef<Path>) -> AssetServer {
         use crate::FileAssetIo;
         IoTaskPool::init(Default::default);
@@ -809,6 +1915,47 @@ mod test {
         assert_eq!(asset_server.get_load_state(handle), LoadState::Failed);
     }
 
+    #[test]
+    fn test_named_sources() {
+        let base = create_dir_and_file("sword.png");
+        let mods = tempfile::tempdir().unwrap();
+        std::fs::create_dir(mods.path().join("weapons")).unwrap();
+        std::fs::write(mods.path().join("weapons/sword.png"), []).unwrap();
+        std::fs::write(mods.path().join("weapons/axe.png"), []).unwrap();
+
+        let asset_server = setup(base.path());
+        asset_server.add_loader(FakePngLoader);
+        let _assets = asset_server.register_asset_type::<PngAsset>();
+        asset_server.add_source("mods", crate::FileAssetIo::new(mods.path(), false));
+        let load =
+            |path: &str| futures_lite::future::block_on(asset_server.load_async(path.into(), true));
+
+        let base_id = load("sword.png").unwrap();
+        assert_eq!(
+            load("sword.png#label").unwrap(),
+            AssetPathId::from("sword.png#label")
+        );
+        let mod_id = load("mods://weapons/sword.png").unwrap();
+        assert_ne!(base_id, mod_id);
+        assert!(matches!(
+            load("mods://sword.png"),
+            Err(AssetServerError::AssetIoError(AssetIoError::NotFound(_)))
+        ));
+        assert!(matches!(
+            load("dlc://sword.png"),
+            Err(AssetServerError::MissingAssetSource(source)) if source == "dlc"
+        ));
+
+        let handles = asset_server.load_folder("mods://weapons").unwrap();
+        assert_eq!(handles.len(), 2);
+        assert!(handles.iter().all(|handle| asset_server
+            .get_handle_path(handle)
+            .unwrap()
+            .source()
+            == Some("mods")));
+        assert_eq!(asset_server.source_names(), ["mods"]);
+    }
+
     #[test]
     fn test_invalid_asset_path() {
         let asset_server = setup(".");
@@ -840,6 +1987,451 @@ mod test {
         assert_eq!(asset_server.get_load_state(handle), LoadState::Failed);
     }
 
+    #[test]
+    fn test_load_errors() {
+        let dir = create_dir_and_file("fake.fail");
+        std::fs::write(dir.path().join("unknown.ext"), []).unwrap();
+        let asset_server = setup(dir.path());
+        asset_server.add_loader(FailingLoader);
+
+        let mut app = App::new();
+        app.insert_resource(asset_server.register_asset_type::<PngAsset>());
+        app.insert_resource(asset_server.clone());
+        app.add_event::<UntypedAssetLoadFailedEvent>();
+        app.add_event::<AssetLoadFailedEvent<PngAsset>>();
+        app.add_system(asset_load_failed_event_system);
+        app.add_system(
+            Assets::<PngAsset>::asset_load_failed_event_system
+                .after(asset_load_failed_event_system),
+        );
+
+        let failing: Handle<PngAsset> = asset_server.load("fake.fail");
+        let unknown = asset_server.load_untyped("unknown.ext");
+        for _ in 0..1000 {
+            if asset_server.get_load_state(&failing) == LoadState::Failed
+                && asset_server.get_load_state(&unknown) == LoadState::Failed
+            {
+                break;
+            }
+            std::thread::sleep(std::time::Duration::from_millis(1));
+        }
+        app.update();
+
+        let error = asset_server.get_load_error(&failing).unwrap();
+        assert_eq!(error.path, AssetPath::from("fake.fail"));
+        assert_eq!(error.loader, Some(std::any::type_name::<FailingLoader>()));
+        assert_eq!(
+            error.chain,
+            [
+                "encountered an error while loading an asset: failed",
+                "failed"
+            ]
+        );
+        let error = asset_server.get_load_error(&unknown).unwrap();
+        assert_eq!(error.loader, None);
+        assert!(matches!(
+            futures_lite::future::block_on(asset_server.load_async("unknown.ext".into(), true)),
+            Err(AssetServerError::MissingAssetLoader { .. })
+        ));
+
+        let untyped_events = app.world.resource::<Events<UntypedAssetLoadFailedEvent>>();
+        let mut failed: Vec<_> = untyped_events
+            .get_reader()
+            .iter(untyped_events)
+            .map(|event| event.error.path.clone())
+            .collect();
+        failed.sort_by_key(|path| path.to_string());
+        assert_eq!(
+            failed,
+            [AssetPath::from("fake.fail"), AssetPath::from("unknown.ext")]
+        );
+        let events = app
+            .world
+            .resource::<Events<AssetLoadFailedEvent<PngAsset>>>();
+        let mut reader = events.get_reader();
+        let typed: Vec<_> = reader.iter(events).collect();
+        assert_eq!(typed.len(), 1);
+        assert_eq!(typed[0].handle, failing);
+        assert_eq!(typed[0].error.path, AssetPath::from("fake.fail"));
+    }
+
+    #[test]
+    fn test_free_load_errors() {
+        let dir = create_dir_and_file("fake.fail");
+        let asset_server = setup(dir.path());
+        asset_server.add_loader(FailingLoader);
+
+        let failing: Handle<PngAsset> = asset_server.load("fake.fail");
+        for _ in 0..1000 {
+            if asset_server.get_load_state(&failing) == LoadState::Failed {
+                break;
+            }
+            std::thread::sleep(std::time::Duration::from_millis(1));
+        }
+        assert!(asset_server.get_load_error(&failing).is_some());
+        assert!(!asset_server.server.handle_types.read().is_empty());
+
+        drop(failing);
+        asset_server.mark_unused_assets();
+        asset_server.free_unused_assets();
+
+        assert!(asset_server.server.handle_types.read().is_empty());
+        assert!(asset_server.server.load_errors.read().is_empty());
+    }
+
+    #[test]
+    fn test_source_ref_counts() {
+        let dir = create_dir_and_file("file.png");
+        let asset_server = setup(dir.path());
+        let source_path_id = AssetPathId::from(AssetPath::from("file.png")).source_path_id();
+        let source_ref_count = || {
+            let source_ref_counts = asset_server
+                .server
+                .asset_ref_counter
+                .source_ref_counts
+                .read();
+            source_ref_counts.get(&source_path_id).copied()
+        };
+
+        let handle = asset_server.get_handle_untyped("file.png");
+        let labeled = asset_server.get_handle_untyped("file.png#label");
+        let other = asset_server.get_handle_untyped("file.png#label");
+        asset_server.mark_unused_assets();
+        assert_eq!(source_ref_count(), Some(2));
+
+        drop(handle);
+        drop(other);
+        asset_server.mark_unused_assets();
+        assert_eq!(source_ref_count(), Some(1));
+
+        drop(labeled);
+        asset_server.mark_unused_assets();
+        assert_eq!(source_ref_count(), None);
+    }
+
+    #[test]
+    fn test_save() {
+        let dir = tempfile::tempdir().unwrap();
+        let asset_server = setup(dir.path());
+        asset_server.add_saver(FakePngSaver);
+        let mut assets = asset_server.register_asset_type::<PngAsset>();
+        let handle = assets.add(PngAsset);
+
+        assert!(matches!(
+            asset_server.save(&handle, "saved.jpg"),
+            Err(AssetServerError::MissingAssetSaver { .. })
+        ));
+        asset_server.save(&handle, "images/saved.png").unwrap();
+        assert_eq!(
+            asset_server.get_handle_path(&handle),
+            Some(AssetPath::from("images/saved.png"))
+        );
+
+        let mut app = App::new();
+        app.insert_resource(assets);
+        app.insert_resource(asset_server.clone());
+        app.add_system(crate::save_assets_system::<PngAsset>);
+        app.update();
+
+        let saved_path = dir.path().join("images/saved.png");
+        for _ in 0..1000 {
+            if matches!(std::fs::read(&saved_path), Ok(bytes) if bytes == b"png") {
+                break;
+            }
+            std::thread::sleep(std::time::Duration::from_millis(1));
+        }
+        assert_eq!(std::fs::read(&saved_path).unwrap(), b"png");
+
+        let asset_io = asset_server.source_io(None).unwrap();
+        #[cfg(feature = "filesystem_watcher")]
+        {
+            let path = AssetPath::from("images/saved.png");
+            let source_path_id = path.get_id().source_path_id();
+            let written = || {
+                matches!(
+                    asset_server.server.save_echoes.lock().get(&source_path_id),
+                    Some(Some(_))
+                )
+            };
+            for _ in 0..1000 {
+                if written() {
+                    break;
+                }
+                std::thread::sleep(std::time::Duration::from_millis(1));
+            }
+            assert!(written());
+            assert!(asset_server.is_save_echo(&path, &*asset_io));
+            // the echo of a write is only recognized once
+            assert!(asset_server.server.save_echoes.lock().is_empty());
+            assert!(!asset_server.is_save_echo(&path, &*asset_io));
+        }
+
+        let (saved, moved) = (Path::new("images/saved.png"), Path::new("moved/saved.png"));
+        futures_lite::future::block_on(asset_io.rename_path(saved, moved)).unwrap();
+        assert!(!asset_io.is_file(saved));
+        assert!(asset_io.is_file(moved));
+        futures_lite::future::block_on(asset_io.delete_path(moved)).unwrap();
+        assert!(matches!(
+            futures_lite::future::block_on(asset_io.delete_path(moved)),
+            Err(AssetIoError::NotFound(_))
+        ));
+    }
+
+    #[test]
+    fn test_loader_settings() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("a.scaled"), [0; 2]).unwrap();
+        std::fs::write(dir.path().join("b.scaled"), [0; 2]).unwrap();
+        std::fs::write(dir.path().join("b.scaled.meta"), "(settings: (scale: 3))").unwrap();
+        std::fs::write(dir.path().join("c.scaled"), [0; 2]).unwrap();
+        std::fs::write(dir.path().join("c.scaled.meta"), "(settings: (scale: x))").unwrap();
+
+        let asset_server = setup(dir.path());
+        asset_server.add_loader(ScaledLoader);
+        let mut app = App::new();
+        app.insert_resource(asset_server.register_asset_type::<ScaledAsset>())
+            .insert_resource(asset_server.clone())
+            .add_system(update_asset_storage_system::<ScaledAsset>);
+
+        let load =
+            |path: &str| futures_lite::future::block_on(asset_server.load_async(path.into(), true));
+        let a = load("a.scaled").unwrap();
+        let b = load("b.scaled").unwrap();
+        let err = load("c.scaled").unwrap_err();
+        assert!(matches!(err, AssetServerError::AssetMetaError(_)));
+
+        // an override is applied on top of the `.meta` settings
+        let b_override: Handle<ScaledAsset> =
+            asset_server.load_with_settings("b.scaled", |s: &mut ScaleSettings| s.scale += 2);
+        assert_eq!(b_override.id(), HandleId::from(b));
+        // supersedes the queued load, with the same override
+        load("b.scaled").unwrap();
+        app.update();
+
+        let assets = app.world.resource::<Assets<ScaledAsset>>();
+        assert_eq!(assets.get(&asset_server.get_handle(a)).unwrap().0, 2);
+        assert_eq!(assets.get(&b_override).unwrap().0, 10);
+
+        // reloads keep the override, on top of the new `.meta` settings
+        std::fs::write(dir.path().join("b.scaled.meta"), "(settings: (scale: 5))").unwrap();
+        load("b.scaled").unwrap();
+        app.update();
+        let assets = app.world.resource::<Assets<ScaledAsset>>();
+        assert_eq!(assets.get(&b_override).unwrap().0, 14);
+
+        // the override is dropped with the last strong handle
+        drop(b_override);
+        asset_server.mark_unused_assets();
+        asset_server.free_unused_assets();
+        assert!(
+            asset_server.server.asset_sources.read()[&b.source_path_id()]
+                .settings_override
+                .is_none()
+        );
+        load("b.scaled").unwrap();
+        app.update();
+        let assets = app.world.resource::<Assets<ScaledAsset>>();
+        assert_eq!(
+            assets
+                .get(&asset_server.get_handle::<ScaledAsset, _>(b))
+                .unwrap()
+                .0,
+            10
+        );
+    }
+
+    #[test]
+    fn test_reader_loader() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("bank.last"), [1, 2, 3, 42]).unwrap();
+        std::fs::write(dir.path().join("a.scaled"), [0; 3]).unwrap();
+
+        let asset_server = setup(dir.path());
+        asset_server.add_loader(LastByteLoader);
+        asset_server.add_loader(ScaledLoader);
+        let mut app = App::new();
+        app.insert_resource(asset_server.register_asset_type::<ScaledAsset>())
+            .insert_resource(asset_server.clone())
+            .add_system(update_asset_storage_system::<ScaledAsset>);
+
+        let load =
+            |path: &str| futures_lite::future::block_on(asset_server.load_async(path.into(), true));
+        // streamed through `load_reader`
+        let bank = load("bank.last").unwrap();
+        // `load` still receives the whole file
+        let a = load("a.scaled").unwrap();
+        app.update();
+
+        let assets = app.world.resource::<Assets<ScaledAsset>>();
+        assert_eq!(assets.get(&asset_server.get_handle(bank)).unwrap().0, 42);
+        assert_eq!(assets.get(&asset_server.get_handle(a)).unwrap().0, 3);
+
+        let missing = futures_lite::future::block_on(
+            asset_server
+                .source_io(None)
+                .unwrap()
+                .open_path(Path::new("missing.last")),
+        );
+        assert!(matches!(missing, Err(AssetIoError::NotFound(_))));
+    }
+
+    #[test]
+    fn test_load_priority_and_cancellation() {
+        let dir = tempfile::tempdir().unwrap();
+        for file in [
+            "block.rec",
+            "low.rec",
+            "high.rec",
+            "bumped.rec",
+            "dropped.rec",
+        ] {
+            std::fs::write(dir.path().join(file), []).unwrap();
+        }
+        let asset_server = setup(dir.path());
+        let loader = RecordingLoader::default();
+        asset_server.add_loader(loader.clone());
+        let _assets = asset_server.register_asset_type::<PngAsset>();
+        asset_server.set_max_concurrent_loads(1);
+
+        let wait_until = |condition: &dyn Fn() -> bool| {
+            for _ in 0..1000 {
+                if condition() {
+                    return;
+                }
+                std::thread::sleep(std::time::Duration::from_millis(1));
+            }
+            panic!("timed out");
+        };
+
+        let _block: Handle<PngAsset> = asset_server.load("block.rec");
+        wait_until(&|| loader.started.lock().len() == 1);
+        let _low: Handle<PngAsset> = asset_server.load_with_priority("low.rec", LoadPriority::LOW);
+        let _high: Handle<PngAsset> =
+            asset_server.load_with_priority("high.rec", LoadPriority::HIGH);
+        let bumped: Handle<PngAsset> =
+            asset_server.load_with_priority("bumped.rec", LoadPriority::LOW);
+        let dropped: Handle<PngAsset> = asset_server.load("dropped.rec");
+        let dropped_id = dropped.id();
+        assert_eq!(asset_server.in_flight_load_count(), 1);
+        assert_eq!(asset_server.queued_load_count(), 4);
+        assert_eq!(asset_server.get_load_state(&bumped), LoadState::Loading);
+
+        assert!(asset_server.set_load_priority(&bumped, LoadPriority::HIGH));
+        drop(dropped);
+        asset_server.mark_unused_assets();
+        asset_server.free_unused_assets();
+        assert_eq!(asset_server.queued_load_count(), 3);
+        assert_eq!(
+            asset_server.get_load_state(dropped_id),
+            LoadState::NotLoaded
+        );
+
+        loader.released.store(true, Ordering::Release);
+        wait_until(&|| {
+            asset_server.queued_load_count() == 0 && asset_server.in_flight_load_count() == 0
+        });
+        let started: Vec<_> = loader.started.lock().clone();
+        assert_eq!(
+            started,
+            ["block.rec", "high.rec", "bumped.rec", "low.rec"].map(PathBuf::from)
+        );
+    }
+
+    #[test]
+    fn test_panicking_loader_frees_load_slot() {
+        let dir = create_dir_and_file("fake.panic");
+        let asset_server = setup(dir.path());
+        asset_server.add_loader(PanickingLoader);
+
+        let _handle = asset_server.load_untyped("fake.panic");
+        for _ in 0..1000 {
+            if asset_server.in_flight_load_count() == 0 {
+                break;
+            }
+            std::thread::sleep(std::time::Duration::from_millis(1));
+        }
+        assert_eq!(asset_server.in_flight_load_count(), 0);
+    }
+
+    #[test]
+    fn test_paused_loads() {
+        let dir = create_dir_and_file("fake.png");
+        let asset_server = setup(dir.path());
+        asset_server.add_loader(FakePngLoader);
+
+        asset_server.set_loads_paused(true);
+        let _handle: Handle<PngAsset> = asset_server.load("fake.png");
+        assert_eq!(asset_server.queued_load_count(), 1);
+        assert_eq!(asset_server.in_flight_load_count(), 0);
+
+        asset_server.set_loads_paused(false);
+        assert_eq!(asset_server.queued_load_count(), 0);
+    }
+
+    #[test]
+    fn test_shared_content_handles() {
+        use crate::{ContentAddressedAssetIo, FileAssetIo};
+
+        let dir = tempfile::tempdir().unwrap();
+        let store = ContentAddressedAssetIo::new(FileAssetIo::new(dir.path(), false)).unwrap();
+        for (path, bytes) in [("a.png", "pixels"), ("b.png", "pixels"), ("c.png", "other")] {
+            futures_lite::future::block_on(store.write_path(Path::new(path), bytes.as_bytes()))
+                .unwrap();
+        }
+        IoTaskPool::init(Default::default);
+        let asset_server = AssetServer::new(store);
+        asset_server.add_loader(FakePngLoader);
+
+        let a: Handle<PngAsset> = asset_server.load("a.png");
+        let b: Handle<PngAsset> = asset_server.load("b.png");
+        let c: Handle<PngAsset> = asset_server.load("c.png");
+        assert_eq!(a.id(), b.id());
+        assert_ne!(a.id(), c.id());
+        assert_eq!(
+            asset_server.get_handle_path(&b),
+            Some(AssetPath::from("a.png"))
+        );
+    }
+
+    #[test]
+    fn test_meta_loader() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("processed.png"), [0; 3]).unwrap();
+        std::fs::write(
+            dir.path().join("processed.png.meta"),
+            "(loader: Some(\"scaled\"), settings: (scale: 2))",
+        )
+        .unwrap();
+
+        let asset_server = setup(dir.path());
+        asset_server.add_loader(FakePngLoader);
+        asset_server.add_loader(ScaledLoader);
+        let mut app = App::new();
+        app.insert_resource(asset_server.register_asset_type::<ScaledAsset>())
+            .insert_resource(asset_server.register_asset_type::<PngAsset>())
+            .insert_resource(asset_server.clone())
+            .add_system(update_asset_storage_system::<ScaledAsset>)
+            .add_system(update_asset_storage_system::<PngAsset>);
+
+        // the `loader` field is ignored unless all `.meta` files are read
+        let load = |path: &str| {
+            futures_lite::future::block_on(asset_server.load_async(path.into(), true)).unwrap()
+        };
+        let id = load("processed.png");
+        app.update();
+        let handle = asset_server.get_handle_untyped(id);
+        assert!(app
+            .world
+            .resource::<Assets<PngAsset>>()
+            .contains(&handle.clone().typed()));
+
+        asset_server.set_read_all_meta_files(true);
+        load("processed.png");
+        app.update();
+        let assets = app.world.resource::<Assets<ScaledAsset>>();
+        assert_eq!(assets.get(&handle.typed()).unwrap().0, 6);
+    }
+
     #[test]
     fn test_asset_lifecycle() {
         let dir = create_dir_and_file("fake.png");
@@ -917,6 +2509,77 @@ mod test {
         assert!(get_asset(&handle, &app.world).is_some());
     }
 
+    #[test]
+    fn test_recursive_dependency_load_state() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("a.deps"), "b.deps").unwrap();
+        std::fs::write(dir.path().join("b.deps"), "c.png").unwrap();
+        std::fs::write(dir.path().join("c.png"), []).unwrap();
+        std::fs::write(dir.path().join("broken.deps"), "b.deps\nd.fail").unwrap();
+        std::fs::write(dir.path().join("d.fail"), []).unwrap();
+        let asset_server = setup(dir.path());
+        asset_server.add_loader(FakePngLoader);
+        asset_server.add_loader(FailingLoader);
+        asset_server.add_loader(DependenciesLoader);
+
+        let mut app = App::new();
+        app.insert_resource(asset_server.register_asset_type::<PngAsset>());
+        app.insert_resource(asset_server.clone());
+        app.add_event::<AssetEvent<PngAsset>>();
+        app.add_system(update_asset_storage_system::<PngAsset>);
+        app.add_system(
+            Assets::<PngAsset>::asset_event_system.after(update_asset_storage_system::<PngAsset>),
+        );
+
+        let a: Handle<PngAsset> = asset_server.load("a.deps");
+        let broken: Handle<PngAsset> = asset_server.load("broken.deps");
+        assert_eq!(
+            asset_server.get_recursive_dependency_load_state(&a),
+            RecursiveDependencyLoadState::Loading
+        );
+
+        let mut loaded_with_dependencies = Vec::new();
+        let mut reader = app
+            .world
+            .resource::<Events<AssetEvent<PngAsset>>>()
+            .get_reader();
+        for _ in 0..1000 {
+            app.update();
+            let events = app.world.resource::<Events<AssetEvent<PngAsset>>>();
+            loaded_with_dependencies.extend(reader.iter(events).filter_map(|event| match event {
+                AssetEvent::LoadedWithDependencies { handle } => Some(handle.clone_weak()),
+                _ => None,
+            }));
+            if asset_server.get_recursive_dependency_load_state(&a)
+                != RecursiveDependencyLoadState::Loading
+                && asset_server.get_recursive_dependency_load_state(&broken)
+                    != RecursiveDependencyLoadState::Loading
+            {
+                break;
+            }
+            std::thread::sleep(std::time::Duration::from_millis(1));
+        }
+
+        assert_eq!(
+            asset_server.get_recursive_dependency_load_state(&a),
+            RecursiveDependencyLoadState::Loaded
+        );
+        assert_eq!(
+            asset_server.get_recursive_dependency_load_state(&broken),
+            RecursiveDependencyLoadState::Failed {
+                path: "d.fail".into()
+            }
+        );
+        assert!(loaded_with_dependencies.contains(&a));
+        assert!(!loaded_with_dependencies.contains(&broken));
+        assert_eq!(
+            asset_server.get_recursive_dependency_load_state(HandleId::from(AssetPath::from(
+                "unknown.png"
+            ))),
+            RecursiveDependencyLoadState::NotLoaded
+        );
+    }
+
     #[test]
     fn test_get_handle_path() {
         const PATH: &str = "path/file.png";
@@ -1,15 +1,16 @@
 use crate::{
-    update_asset_storage_system, Asset, AssetLoader, AssetServer, AssetStage, Handle, HandleId,
-    RefChange, ReflectAsset, ReflectHandle,
+    save_assets_system, update_asset_storage_system, Asset, AssetLoadError, AssetLoader,
+    AssetProcessor, AssetSaver, AssetServer, AssetStage, Handle, HandleId, HandleUntyped,
+    RecursiveDependencyLoadState, RefChange, ReflectAsset, ReflectHandle,
 };
 use bevy_app::{App, AppTypeRegistry};
 use bevy_ecs::{
-    event::{EventWriter, Events},
-    system::{ResMut, Resource},
+    event::{EventReader, EventWriter, Events},
+    system::{Res, ResMut, Resource},
     world::FromWorld,
 };
 use bevy_reflect::{FromReflect, GetTypeRegistration, Reflect};
-use bevy_utils::HashMap;
+use bevy_utils::{HashMap, HashSet};
 use crossbeam_channel::Sender;
 use std::fmt::Debug;
 
@@ -24,6 +25,47 @@ pub enum AssetEvent<T: Asset> {
     Modified { handle: Handle<T> },
     #[allow(missing_docs)]
     Removed { handle: Handle<T> },
+    /// Sent once an asset loaded by the [`AssetServer`] and all of its transitive dependencies
+    /// are loaded, see [`AssetServer::get_recursive_dependency_load_state`].
+    ///
+    /// This is sent again after the asset is reloaded.
+    LoadedWithDependencies {
+        /// The handle of the asset.
+        handle: Handle<T>,
+    },
+}
+
+/// Sent by the [`AssetServer`] when an asset it loads as `T` fails to load.
+///
+/// The asset is of type `T` if it was loaded with [`AssetServer::load`] or previously loaded
+/// successfully. Failures of every asset, including untyped loads, are also sent as
+/// [`UntypedAssetLoadFailedEvent`]s.
+pub struct AssetLoadFailedEvent<T: Asset> {
+    /// A weak handle of the asset.
+    pub handle: Handle<T>,
+    /// Why the asset failed to load.
+    pub error: AssetLoadError,
+}
+
+impl<T: Asset> Debug for AssetLoadFailedEvent<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct(&format!(
+            "AssetLoadFailedEvent<{}>",
+            std::any::type_name::<T>()
+        ))
+        .field("handle", &self.handle.id())
+        .field("error", &self.error)
+        .finish()
+    }
+}
+
+/// Sent by the [`AssetServer`] when an asset fails to load, whatever its type.
+#[derive(Debug)]
+pub struct UntypedAssetLoadFailedEvent {
+    /// A weak handle of the asset.
+    pub handle: HandleUntyped,
+    /// Why the asset failed to load.
+    pub error: AssetLoadError,
 }
 
 impl<T: Asset> Debug for AssetEvent<T> {
@@ -50,6 +92,13 @@ impl<T: Asset> Debug for AssetEvent<T> {
                 ))
                 .field("handle", &handle.id())
                 .finish(),
+            AssetEvent::LoadedWithDependencies { handle } => f
+                .debug_struct(&format!(
+                    "AssetEvent<{}>::LoadedWithDependencies",
+                    std::any::type_name::<T>()
+                ))
+                .field("handle", &handle.id())
+                .finish(),
         }
     }
 }
@@ -72,6 +121,8 @@ pub struct Assets<T: Asset> {
     assets: HashMap<HandleId, T>,
     events: Events<AssetEvent<T>>,
     pub(crate) ref_change_sender: Sender<RefChange>,
+    /// Assets loaded by the [`AssetServer`] whose dependencies are still loading.
+    pub(crate) pending_dependency_loads: HashSet<HandleId>,
 }
 
 impl<T: Asset> Assets<T> {
@@ -80,6 +131,7 @@ impl<T: Asset> Assets<T> {
             assets: HashMap::default(),
             events: Events::default(),
             ref_change_sender,
+            pending_dependency_loads: HashSet::default(),
         }
     }
 
@@ -225,6 +277,25 @@ impl<T: Asset> Assets<T> {
         asset
     }
 
+    /// Sends [`AssetEvent::LoadedWithDependencies`] for the pending assets whose dependencies are
+    /// all loaded, and stops tracking those that failed or were unloaded.
+    pub(crate) fn send_loaded_with_dependencies_events(&mut self, asset_server: &AssetServer) {
+        let events = &mut self.events;
+        self.pending_dependency_loads.retain(|id| {
+            match asset_server.get_recursive_dependency_load_state(*id) {
+                RecursiveDependencyLoadState::Loading => true,
+                RecursiveDependencyLoadState::Loaded => {
+                    events.send(AssetEvent::LoadedWithDependencies {
+                        handle: Handle::weak(*id),
+                    });
+                    false
+                }
+                RecursiveDependencyLoadState::NotLoaded
+                | RecursiveDependencyLoadState::Failed { .. } => false,
+            }
+        });
+    }
+
     /// Clears the inner asset map, removing all key-value pairs.
     ///
     /// Keeps the allocated memory for reuse.
@@ -260,6 +331,23 @@ impl<T: Asset> Assets<T> {
         }
     }
 
+    /// A system that sends [`AssetLoadFailedEvent`]s for the [`UntypedAssetLoadFailedEvent`]s of
+    /// assets of type `T`.
+    pub fn asset_load_failed_event_system(
+        asset_server: Res<AssetServer>,
+        mut untyped_events: EventReader<UntypedAssetLoadFailedEvent>,
+        mut events: EventWriter<AssetLoadFailedEvent<T>>,
+    ) {
+        for event in untyped_events.iter() {
+            if asset_server.get_handle_type(event.handle.id) == Some(T::TYPE_UUID) {
+                events.send(AssetLoadFailedEvent {
+                    handle: Handle::weak(event.handle.id),
+                    error: event.error.clone(),
+                });
+            }
+        }
+    }
+
     /// Gets the number of assets in the collection.
     pub fn len(&self) -> usize {
         self.assets.len()
@@ -319,6 +407,33 @@ pub trait AddAsset {
     fn add_asset_loader<T>(&mut self, loader: T) -> &mut Self
     where
         T: AssetLoader;
+
+    /// Adds an asset saver `T` using default values.
+    ///
+    /// The default values may come from the `World` or from `T::default()`.
+    fn init_asset_saver<T>(&mut self) -> &mut Self
+    where
+        T: AssetSaver + FromWorld;
+
+    /// Adds the provided asset saver to the application.
+    fn add_asset_saver<T>(&mut self, saver: T) -> &mut Self
+    where
+        T: AssetSaver;
+
+    /// Adds an asset processor `T` using default values.
+    ///
+    /// The default values may come from the `World` or from `T::default()`.
+    fn init_asset_processor<T>(&mut self) -> &mut Self
+    where
+        T: AssetProcessor + FromWorld;
+
+    /// Adds the provided asset processor to the application.
+    ///
+    /// Processors only run in [`AssetMode::Processor`](crate::AssetMode::Processor), this does
+    /// nothing in other modes.
+    fn add_asset_processor<T>(&mut self, processor: T) -> &mut Self
+    where
+        T: AssetProcessor;
 }
 
 impl AddAsset for App {
@@ -336,9 +451,16 @@ impl AddAsset for App {
 
         self.insert_resource(assets)
             .add_system_to_stage(AssetStage::AssetEvents, Assets::<T>::asset_event_system)
+            .add_system_to_stage(
+                AssetStage::AssetEvents,
+                Assets::<T>::asset_load_failed_event_system,
+            )
+            .add_system_to_stage(AssetStage::AssetEvents, save_assets_system::<T>)
             .add_system_to_stage(AssetStage::LoadAssets, update_asset_storage_system::<T>)
             .register_type::<Handle<T>>()
             .add_event::<AssetEvent<T>>()
+            .add_event::<AssetLoadFailedEvent<T>>()
+            .add_event::<UntypedAssetLoadFailedEvent>()
     }
 
     fn register_asset_reflect<T>(&mut self) -> &mut Self
@@ -403,6 +525,41 @@ impl AddAsset for App {
         self.world.resource_mut::<AssetServer>().add_loader(loader);
         self
     }
+
+    fn init_asset_saver<T>(&mut self) -> &mut Self
+    where
+        T: AssetSaver + FromWorld,
+    {
+        let result = T::from_world(&mut self.world);
+        self.add_asset_saver(result)
+    }
+
+    fn add_asset_saver<T>(&mut self, saver: T) -> &mut Self
+    where
+        T: AssetSaver,
+    {
+        self.world.resource_mut::<AssetServer>().add_saver(saver);
+        self
+    }
+
+    fn init_asset_processor<T>(&mut self) -> &mut Self
+    where
+        T: AssetProcessor + FromWorld,
+    {
+        let result = T::from_world(&mut self.world);
+        self.add_asset_processor(result)
+    }
+
+    fn add_asset_processor<T>(&mut self, _processor: T) -> &mut Self
+    where
+        T: AssetProcessor,
+    {
+        #[cfg(all(not(target_arch = "wasm32"), not(target_os = "android")))]
+        if let Some(mut pipeline) = self.world.get_resource_mut::<crate::AssetPipeline>() {
+            pipeline.add_processor(_processor);
+        }
+        self
+    }
 }
 
 /// Loads an internal asset.
@@ -0,0 +1,51 @@
+use crate::AssetServer;
+use bevy_app::prelude::*;
+use bevy_diagnostic::{Diagnostic, DiagnosticId, Diagnostics};
+use bevy_ecs::system::{Res, ResMut};
+
+/// Adds diagnostics of the load queue of the [`AssetServer`] to an [`App`]: the number of queued
+/// loads and the number of loads in flight.
+///
+/// See [`AssetServer::load_with_priority`].
+#[derive(Default)]
+pub struct AssetLoadQueueDiagnosticsPlugin;
+
+impl Plugin for AssetLoadQueueDiagnosticsPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_startup_system(Self::setup_system)
+            .add_system(Self::diagnostic_system);
+    }
+}
+
+impl AssetLoadQueueDiagnosticsPlugin {
+    /// The number of loads waiting for a free slot.
+    pub const QUEUED_LOADS: DiagnosticId =
+        DiagnosticId::from_u128(200418453427410938729466591418113540306);
+    /// The number of loads that are running.
+    pub const IN_FLIGHT_LOADS: DiagnosticId =
+        DiagnosticId::from_u128(92375208157453816090384734466021377045);
+
+    /// Registers the load queue diagnostics for the current application.
+    pub fn setup_system(mut diagnostics: ResMut<Diagnostics>) {
+        diagnostics.add(Diagnostic::new(
+            Self::QUEUED_LOADS,
+            "asset_queued_loads",
+            20,
+        ));
+        diagnostics.add(Diagnostic::new(
+            Self::IN_FLIGHT_LOADS,
+            "asset_in_flight_loads",
+            20,
+        ));
+    }
+
+    /// Updates the load queue diagnostics.
+    pub fn diagnostic_system(mut diagnostics: ResMut<Diagnostics>, asset_server: Res<AssetServer>) {
+        diagnostics.add_measurement(Self::QUEUED_LOADS, || {
+            asset_server.queued_load_count() as f64
+        });
+        diagnostics.add_measurement(Self::IN_FLIGHT_LOADS, || {
+            asset_server.in_flight_load_count() as f64
+        });
+    }
+}
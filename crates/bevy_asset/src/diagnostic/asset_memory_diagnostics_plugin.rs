@@ -0,0 +1,133 @@
+use crate::{Asset, Assets};
+use bevy_app::prelude::*;
+use bevy_diagnostic::{
+    Diagnostic, DiagnosticId, Diagnostics, MemoryUsage, MemoryUsageEntry, MAX_DIAGNOSTIC_NAME_WIDTH,
+};
+use bevy_ecs::system::{Res, ResMut};
+use bevy_utils::Uuid;
+
+/// Reports the memory used by an asset, for the [`AssetMemoryDiagnosticsPlugin`].
+pub trait AssetMemoryUsage {
+    /// Returns the number of bytes this asset owns on the heap.
+    ///
+    /// The inline size of the asset, `std::mem::size_of::<Self>()`, is accounted for separately.
+    fn heap_size_bytes(&self) -> usize;
+}
+
+/// Adds an asset memory diagnostic to an [`App`] for assets of type `T`.
+///
+/// The total bytes used by all `T` assets are reported as a [`Diagnostic`] and as an entry of the
+/// [`MemoryUsage::ASSETS`] category of the [`MemoryUsage`] resource.
+pub struct AssetMemoryDiagnosticsPlugin<T: Asset + AssetMemoryUsage> {
+    marker: std::marker::PhantomData<T>,
+}
+
+impl<T: Asset + AssetMemoryUsage> Default for AssetMemoryDiagnosticsPlugin<T> {
+    fn default() -> Self {
+        Self {
+            marker: std::marker::PhantomData,
+        }
+    }
+}
+
+impl<T: Asset + AssetMemoryUsage> Plugin for AssetMemoryDiagnosticsPlugin<T> {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<MemoryUsage>()
+            .add_startup_system(Self::setup_system)
+            .add_system(Self::diagnostic_system);
+    }
+}
+
+impl<T: Asset + AssetMemoryUsage> AssetMemoryDiagnosticsPlugin<T> {
+    /// Gets unique id of this diagnostic.
+    ///
+    /// The diagnostic id is derived from the type uuid of `T`.
+    pub fn diagnostic_id() -> DiagnosticId {
+        // Flip a few bits so that the id differs from `AssetCountDiagnosticsPlugin`'s.
+        DiagnosticId(Uuid::from_u128(
+            T::TYPE_UUID.as_u128() ^ 0x6d65_6d6f_7279_0000_0000_0000_0000_0000,
+        ))
+    }
+
+    /// Registers the asset memory diagnostic for the current application.
+    pub fn setup_system(mut diagnostics: ResMut<Diagnostics>) {
+        let asset_type_name = std::any::type_name::<T>();
+        let max_length = MAX_DIAGNOSTIC_NAME_WIDTH - "asset_bytes ".len();
+        diagnostics.add(Diagnostic::new(
+            Self::diagnostic_id(),
+            format!(
+                "asset_bytes {}",
+                if asset_type_name.len() > max_length {
+                    asset_type_name
+                        .split_at(asset_type_name.len() - max_length + 1)
+                        .1
+                } else {
+                    asset_type_name
+                }
+            ),
+            20,
+        ));
+    }
+
+    /// Updates the memory used by `T` assets.
+    pub fn diagnostic_system(
+        mut diagnostics: ResMut<Diagnostics>,
+        mut memory_usage: ResMut<MemoryUsage>,
+        assets: Res<Assets<T>>,
+    ) {
+        let bytes = assets.len() * std::mem::size_of::<T>()
+            + assets
+                .iter()
+                .map(|(_, asset)| asset.heap_size_bytes())
+                .sum::<usize>();
+        memory_usage.set_entry(
+            MemoryUsage::ASSETS,
+            MemoryUsageEntry {
+                name: std::any::type_name::<T>().to_string(),
+                bytes,
+                count: assets.len(),
+            },
+        );
+        diagnostics.add_measurement(Self::diagnostic_id(), || bytes as f64);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{AssetMemoryDiagnosticsPlugin, AssetMemoryUsage};
+    use crate::{AddAsset, Assets};
+    use bevy_app::App;
+    use bevy_diagnostic::{DiagnosticsPlugin, MemoryUsage};
+    use bevy_reflect::TypeUuid;
+
+    #[derive(TypeUuid)]
+    #[uuid = "0b4e2fbe-5b8a-4ac6-b6c5-2f8f0d2d9b1a"]
+    struct Blob(Vec<u8>);
+
+    impl AssetMemoryUsage for Blob {
+        fn heap_size_bytes(&self) -> usize {
+            self.0.len()
+        }
+    }
+
+    #[test]
+    fn reports_asset_memory() {
+        let mut app = App::new();
+        app.add_plugin(bevy_core::CorePlugin::default())
+            .add_plugin(crate::AssetPlugin::default())
+            .add_plugin(DiagnosticsPlugin)
+            .add_plugin(AssetMemoryDiagnosticsPlugin::<Blob>::default())
+            .add_asset::<Blob>();
+        {
+            let mut assets = app.world.resource_mut::<Assets<Blob>>();
+            assets.add(Blob(vec![0; 100]));
+            assets.add(Blob(vec![0; 28]));
+        }
+        app.update();
+
+        let memory_usage = app.world.resource::<MemoryUsage>();
+        let entry = &memory_usage.category(MemoryUsage::ASSETS)[0];
+        assert_eq!(entry.count, 2);
+        assert_eq!(entry.bytes, 128 + 2 * std::mem::size_of::<Blob>());
+    }
+}
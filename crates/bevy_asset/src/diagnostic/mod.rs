@@ -2,3 +2,9 @@
 
 mod asset_count_diagnostics_plugin;
 pub use asset_count_diagnostics_plugin::AssetCountDiagnosticsPlugin;
+
+mod asset_memory_diagnostics_plugin;
+pub use asset_memory_diagnostics_plugin::{AssetMemoryDiagnosticsPlugin, AssetMemoryUsage};
+
+mod asset_load_queue_diagnostics_plugin;
+pub use asset_load_queue_diagnostics_plugin::AssetLoadQueueDiagnosticsPlugin;
@@ -1,4 +1,4 @@
-use crate::{path::AssetPath, LabelId};
+use crate::{path::AssetPath, LabelId, SettingsOverride};
 use bevy_utils::{HashMap, HashSet, Uuid};
 use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
@@ -36,6 +36,9 @@ pub struct SourceInfo {
     pub committed_assets: HashSet<LabelId>,
     /// Current version of the source.
     pub version: usize,
+    /// The override of [`AssetServer::load_with_settings`](crate::AssetServer::load_with_settings),
+    /// applied to every load of the source until it has no strong handles left.
+    pub(crate) settings_override: Option<SettingsOverride>,
 }
 
 impl SourceInfo {
@@ -52,6 +55,24 @@ impl SourceInfo {
     }
 }
 
+/// The load state of an asset together with all of its transitive dependencies.
+///
+/// See [`AssetServer::get_recursive_dependency_load_state`](crate::AssetServer::get_recursive_dependency_load_state).
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum RecursiveDependencyLoadState {
+    /// The asset itself is not loaded, or was unloaded.
+    NotLoaded,
+    /// The asset or at least one of its dependencies is still loading.
+    Loading,
+    /// The asset and all of its dependencies are loaded.
+    Loaded,
+    /// The asset or one of its dependencies failed to load.
+    Failed {
+        /// The path of the asset that failed to load.
+        path: AssetPath<'static>,
+    },
+}
+
 /// The load state of an asset.
 #[derive(Copy, Clone, Debug, Eq, PartialEq, Ord, PartialOrd)]
 pub enum LoadState {
@@ -0,0 +1,359 @@
+use crate::{content_hash, AssetIo, AssetIoError, AssetReader, FileType, Metadata};
+use anyhow::Result;
+use bevy_utils::{BoxedFuture, HashMap};
+use futures_lite::{future::block_on, io::Cursor};
+use parking_lot::{Mutex, RwLock};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::{BTreeMap, VecDeque},
+    io,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+/// The name of the file mapping asset paths to content hashes in the store of a
+/// [`ContentAddressedAssetIo`].
+pub const CONTENT_MANIFEST_FILE: &str = "content_manifest.ron";
+
+/// The folder of a [`ContentAddressedAssetIo`] store holding the contents of the assets, in files
+/// named after their hash.
+pub const CONTENT_OBJECTS_FOLDER: &str = "objects";
+
+/// The number of bytes of verified contents a [`ContentAddressedAssetIo`] keeps in memory by
+/// default.
+pub const DEFAULT_CONTENT_CACHE_CAPACITY: usize = 64 * 1024 * 1024;
+
+/// Maps the paths of the assets of a [`ContentAddressedAssetIo`] to the [`content_hash`] of their
+/// contents.
+///
+/// It is stored as RON in the [`CONTENT_MANIFEST_FILE`] of the store.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ContentManifest {
+    /// The hash of the contents of every asset, by path.
+    pub assets: BTreeMap<PathBuf, u64>,
+}
+
+impl ContentManifest {
+    /// Records `bytes` as the contents of the asset at `path`, and returns their hash.
+    pub fn insert(&mut self, path: impl Into<PathBuf>, bytes: &[u8]) -> u64 {
+        let hash = content_hash(bytes);
+        self.assets.insert(path.into(), hash);
+        hash
+    }
+
+    /// Gets the hash of the contents of the asset at `path`.
+    pub fn get(&self, path: &Path) -> Option<u64> {
+        self.assets.get(path).copied()
+    }
+}
+
+/// Returns the path of the file holding the contents with the given hash, relative to the store.
+pub fn content_object_path(hash: u64) -> PathBuf {
+    Path::new(CONTENT_OBJECTS_FOLDER).join(format!("{:016x}", hash))
+}
+
+/// Recently loaded contents, by hash, evicted oldest first once over capacity.
+#[derive(Default)]
+struct ContentCache {
+    contents: HashMap<u64, Arc<[u8]>>,
+    order: VecDeque<u64>,
+    size: usize,
+    capacity: usize,
+}
+
+impl ContentCache {
+    fn get(&self, hash: u64) -> Option<Arc<[u8]>> {
+        self.contents.get(&hash).cloned()
+    }
+
+    fn insert(&mut self, hash: u64, bytes: Arc<[u8]>) {
+        if bytes.len() > self.capacity || self.contents.contains_key(&hash) {
+            return;
+        }
+        self.size += bytes.len();
+        self.contents.insert(hash, bytes);
+        self.order.push_back(hash);
+        while self.size > self.capacity {
+            let oldest = self.order.pop_front().unwrap();
+            if let Some(evicted) = self.contents.remove(&oldest) {
+                self.size -= evicted.len();
+            }
+        }
+    }
+
+    fn clear(&mut self) {
+        self.contents.clear();
+        self.order.clear();
+        self.size = 0;
+    }
+}
+
+/// I/O implementation loading assets from a content-addressed store, for example a downloaded
+/// patch.
+///
+/// The store is read through another [`AssetIo`]. Its [`CONTENT_MANIFEST_FILE`] maps the path of
+/// every asset to the [`content_hash`] of its contents, which are stored once per hash at the
+/// [`content_object_path`]. Contents are verified against their hash when loaded: corrupted files
+/// fail to load with [`AssetIoError::ContentHashMismatch`].
+///
+/// Verified contents are kept in memory up to a [capacity](Self::set_cache_capacity), so assets
+/// whose paths share a hash are read from the store and verified only once. The
+/// [`AssetServer`](crate::AssetServer) also returns the handle of the asset already loaded from
+/// the same contents when another path shares its hash.
+///
+/// Writing an asset stores its contents under their hash and updates the manifest, so that
+/// [`AssetServer::save`](crate::AssetServer::save) can build a store.
+pub struct ContentAddressedAssetIo {
+    store: Box<dyn AssetIo>,
+    manifest: RwLock<ContentManifest>,
+    cache: Mutex<ContentCache>,
+}
+
+impl ContentAddressedAssetIo {
+    /// Opens the store read through `store`, reading its [`CONTENT_MANIFEST_FILE`].
+    ///
+    /// A store without a manifest is empty.
+    pub fn new(store: impl AssetIo) -> Result<Self, AssetIoError> {
+        let manifest = match block_on(store.load_path(Path::new(CONTENT_MANIFEST_FILE))) {
+            Ok(manifest) => ron::de::from_bytes(&manifest)
+                .map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error))?,
+            Err(AssetIoError::NotFound(_)) => ContentManifest::default(),
+            Err(error) => return Err(error),
+        };
+        Ok(Self::with_manifest(store, manifest))
+    }
+
+    /// Creates an asset I/O reading the contents of the assets in `manifest` through `store`.
+    pub fn with_manifest(store: impl AssetIo, manifest: ContentManifest) -> Self {
+        Self {
+            store: Box::new(store),
+            manifest: RwLock::new(manifest),
+            cache: Mutex::new(ContentCache {
+                capacity: DEFAULT_CONTENT_CACHE_CAPACITY,
+                ..Default::default()
+            }),
+        }
+    }
+
+    /// Gets a copy of the manifest of the store.
+    pub fn manifest(&self) -> ContentManifest {
+        self.manifest.read().clone()
+    }
+
+    /// Sets the number of bytes of verified contents kept in memory, and evicts contents over it.
+    pub fn set_cache_capacity(&self, capacity: usize) {
+        let mut cache = self.cache.lock();
+        cache.capacity = capacity;
+        if cache.size > capacity {
+            cache.clear();
+        }
+    }
+
+    /// Forgets the contents kept in memory.
+    pub fn clear_cache(&self) {
+        self.cache.lock().clear();
+    }
+
+    async fn load_contents(&self, path: &Path) -> Result<Arc<[u8]>, AssetIoError> {
+        let expected = self
+            .content_hash(path)
+            .ok_or_else(|| AssetIoError::NotFound(path.to_owned()))?;
+        if let Some(bytes) = self.cache.lock().get(expected) {
+            return Ok(bytes);
+        }
+        let bytes = self.store.load_path(&content_object_path(expected)).await?;
+        let actual = content_hash(&bytes);
+        if actual != expected {
+            return Err(AssetIoError::ContentHashMismatch {
+                path: path.to_owned(),
+                expected,
+                actual,
+            });
+        }
+        let bytes: Arc<[u8]> = bytes.into();
+        self.cache.lock().insert(expected, bytes.clone());
+        Ok(bytes)
+    }
+
+    async fn write_manifest(&self) -> Result<(), AssetIoError> {
+        let manifest = ron::ser::to_string_pretty(&*self.manifest.read(), Default::default())
+            .map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error))?;
+        self.store
+            .write_path(Path::new(CONTENT_MANIFEST_FILE), manifest.as_bytes())
+            .await
+    }
+}
+
+impl AssetIo for ContentAddressedAssetIo {
+    fn load_path<'a>(&'a self, path: &'a Path) -> BoxedFuture<'a, Result<Vec<u8>, AssetIoError>> {
+        Box::pin(async move { Ok(self.load_contents(path).await?.to_vec()) })
+    }
+
+    fn open_path<'a>(
+        &'a self,
+        path: &'a Path,
+    ) -> BoxedFuture<'a, Result<Box<dyn AssetReader>, AssetIoError>> {
+        // contents are verified as a whole before anything is read from them
+        Box::pin(async move {
+            let bytes = self.load_contents(path).await?;
+            Ok(Box::new(Cursor::new(bytes)) as Box<dyn AssetReader>)
+        })
+    }
+
+    fn read_directory(
+        &self,
+        path: &Path,
+    ) -> Result<Box<dyn Iterator<Item = PathBuf>>, AssetIoError> {
+        let mut children = Vec::new();
+        for asset_path in self.manifest.read().assets.keys() {
+            let child = asset_path
+                .strip_prefix(path)
+                .ok()
+                .and_then(|relative_path| relative_path.components().next())
+                .map(|component| path.join(component));
+            if let Some(child) = child {
+                if !children.contains(&child) {
+                    children.push(child);
+                }
+            }
+        }
+        Ok(Box::new(children.into_iter()))
+    }
+
+    fn get_metadata(&self, path: &Path) -> Result<Metadata, AssetIoError> {
+        let manifest = self.manifest.read();
+        if manifest.assets.contains_key(path) {
+            Ok(Metadata::new(FileType::File))
+        } else if manifest
+            .assets
+            .keys()
+            .any(|asset_path| asset_path.starts_with(path))
+        {
+            Ok(Metadata::new(FileType::Directory))
+        } else {
+            Err(AssetIoError::NotFound(path.to_owned()))
+        }
+    }
+
+    fn content_hash(&self, path: &Path) -> Option<u64> {
+        self.manifest.read().get(path)
+    }
+
+    fn watch_path_for_changes(&self, _path: &Path) -> Result<(), AssetIoError> {
+        // the contents of a hash never change
+        Ok(())
+    }
+
+    fn watch_for_changes(&self) -> Result<(), AssetIoError> {
+        Ok(())
+    }
+
+    fn write_path<'a>(
+        &'a self,
+        path: &'a Path,
+        bytes: &'a [u8],
+    ) -> BoxedFuture<'a, Result<(), AssetIoError>> {
+        Box::pin(async move {
+            let hash = content_hash(bytes);
+            let stored = self.manifest.read().assets.values().any(|h| *h == hash);
+            if !stored {
+                self.store
+                    .write_path(&content_object_path(hash), bytes)
+                    .await?;
+            }
+            self.manifest.write().assets.insert(path.to_owned(), hash);
+            self.write_manifest().await
+        })
+    }
+
+    fn rename_path<'a>(
+        &'a self,
+        from: &'a Path,
+        to: &'a Path,
+    ) -> BoxedFuture<'a, Result<(), AssetIoError>> {
+        Box::pin(async move {
+            {
+                let mut manifest = self.manifest.write();
+                let hash = manifest
+                    .assets
+                    .remove(from)
+                    .ok_or_else(|| AssetIoError::NotFound(from.to_owned()))?;
+                manifest.assets.insert(to.to_owned(), hash);
+            }
+            self.write_manifest().await
+        })
+    }
+
+    /// Removes the asset from the manifest. Its contents stay in the store, other paths may share
+    /// them.
+    fn delete_path<'a>(&'a self, path: &'a Path) -> BoxedFuture<'a, Result<(), AssetIoError>> {
+        Box::pin(async move {
+            if self.manifest.write().assets.remove(path).is_none() {
+                return Err(AssetIoError::NotFound(path.to_owned()));
+            }
+            self.write_manifest().await
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::FileAssetIo;
+
+    #[test]
+    fn verifies_and_shares_contents() {
+        let dir = tempfile::tempdir().unwrap();
+        let io = ContentAddressedAssetIo::new(FileAssetIo::new(dir.path(), false)).unwrap();
+        block_on(io.write_path(Path::new("textures/a.png"), b"pixels")).unwrap();
+        block_on(io.write_path(Path::new("textures/b.png"), b"pixels")).unwrap();
+        block_on(io.write_path(Path::new("c.png"), b"other pixels")).unwrap();
+
+        // reopen the store from its manifest
+        let io = ContentAddressedAssetIo::new(FileAssetIo::new(dir.path(), false)).unwrap();
+        let hash = content_hash(b"pixels");
+        assert_eq!(io.content_hash(Path::new("textures/b.png")), Some(hash));
+        assert!(io.is_file(Path::new("textures/a.png")));
+        assert!(io.is_dir(Path::new("textures")));
+        let mut children: Vec<_> = io.read_directory(Path::new("")).unwrap().collect();
+        children.sort();
+        assert_eq!(
+            children,
+            [PathBuf::from("c.png"), PathBuf::from("textures")]
+        );
+        assert!(matches!(
+            block_on(io.load_path(Path::new("missing.png"))),
+            Err(AssetIoError::NotFound(_))
+        ));
+
+        assert_eq!(
+            block_on(io.load_path(Path::new("textures/a.png"))).unwrap(),
+            b"pixels"
+        );
+        // the contents shared by both paths are only read from the store once
+        std::fs::remove_file(dir.path().join(content_object_path(hash))).unwrap();
+        assert_eq!(
+            block_on(io.load_path(Path::new("textures/b.png"))).unwrap(),
+            b"pixels"
+        );
+
+        std::fs::write(
+            dir.path()
+                .join(content_object_path(content_hash(b"other pixels"))),
+            b"corrupted",
+        )
+        .unwrap();
+        match block_on(io.load_path(Path::new("c.png"))) {
+            Err(AssetIoError::ContentHashMismatch {
+                path,
+                expected,
+                actual,
+            }) => {
+                assert_eq!(path, Path::new("c.png"));
+                assert_eq!(expected, content_hash(b"other pixels"));
+                assert_eq!(actual, content_hash(b"corrupted"));
+            }
+            other => panic!("expected a hash mismatch, got {:?}", other.map(|_| ())),
+        }
+    }
+}
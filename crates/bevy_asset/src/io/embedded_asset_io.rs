@@ -0,0 +1,358 @@
+#[cfg(all(
+    feature = "filesystem_watcher",
+    all(not(target_arch = "wasm32"), not(target_os = "android"))
+))]
+use crate::{filesystem_watcher::FilesystemWatcher, AssetPath, AssetServer};
+use crate::{AssetIo, AssetIoError, FileType, Metadata};
+use anyhow::Result;
+use bevy_ecs::system::Resource;
+use bevy_utils::{BoxedFuture, HashMap};
+use parking_lot::RwLock;
+use std::{
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+/// The name of the asset source of the [`EmbeddedAssetIo`] added by the
+/// [`AssetPlugin`](crate::AssetPlugin).
+///
+/// Embedded assets are loaded with paths like `embedded://icon.png`.
+pub const EMBEDDED_ASSET_SOURCE: &str = "embedded";
+
+struct EmbeddedAsset {
+    bytes: &'static [u8],
+    original_path: Option<PathBuf>,
+}
+
+/// I/O implementation for assets compiled into the binary.
+///
+/// Assets are usually embedded with the [`embedded_asset!`](crate::embedded_asset) macro, into
+/// the instance the [`AssetPlugin`](crate::AssetPlugin) registers as the
+/// [`EMBEDDED_ASSET_SOURCE`]. They then go through [`AssetServer::load`](crate::AssetServer::load)
+/// and the registered loaders like any other asset.
+///
+/// When watching for changes, assets embedded with the path of their original file are read from
+/// and hot reloaded from that file as long as it exists. The macro only records it in debug
+/// builds.
+#[derive(Resource, Clone, Default)]
+pub struct EmbeddedAssetIo {
+    assets: Arc<RwLock<HashMap<PathBuf, EmbeddedAsset>>>,
+    #[cfg(all(
+        feature = "filesystem_watcher",
+        all(not(target_arch = "wasm32"), not(target_os = "android"))
+    ))]
+    filesystem_watcher: Arc<RwLock<Option<FilesystemWatcher>>>,
+}
+
+impl EmbeddedAssetIo {
+    /// Embeds `bytes` as the asset at `path`.
+    ///
+    /// Embedding an asset again at the same path replaces it.
+    pub fn insert(&self, path: impl Into<PathBuf>, bytes: &'static [u8]) {
+        self.assets.write().insert(
+            path.into(),
+            EmbeddedAsset {
+                bytes,
+                original_path: None,
+            },
+        );
+    }
+
+    /// Embeds `bytes` as the asset at `path`, which were read from the file at `original_path`.
+    ///
+    /// When watching for changes, the asset is read from the original file instead.
+    pub fn insert_with_original(
+        &self,
+        path: impl Into<PathBuf>,
+        bytes: &'static [u8],
+        original_path: impl Into<PathBuf>,
+    ) {
+        self.assets.write().insert(
+            path.into(),
+            EmbeddedAsset {
+                bytes,
+                original_path: Some(original_path.into()),
+            },
+        );
+    }
+
+    /// Returns `true` if an asset is embedded at `path`.
+    pub fn contains(&self, path: &Path) -> bool {
+        self.assets.read().contains_key(path)
+    }
+
+    fn is_watching(&self) -> bool {
+        #[cfg(all(
+            feature = "filesystem_watcher",
+            all(not(target_arch = "wasm32"), not(target_os = "android"))
+        ))]
+        return self.filesystem_watcher.read().is_some();
+        #[cfg(not(all(
+            feature = "filesystem_watcher",
+            all(not(target_arch = "wasm32"), not(target_os = "android"))
+        )))]
+        false
+    }
+
+    fn read(&self, path: &Path) -> Result<Vec<u8>, AssetIoError> {
+        let assets = self.assets.read();
+        let asset = assets
+            .get(path)
+            .ok_or_else(|| AssetIoError::NotFound(path.to_owned()))?;
+        if let (true, Some(original_path)) = (self.is_watching(), &asset.original_path) {
+            if let Ok(bytes) = std::fs::read(original_path) {
+                return Ok(bytes);
+            }
+        }
+        Ok(asset.bytes.to_vec())
+    }
+}
+
+impl AssetIo for EmbeddedAssetIo {
+    fn load_path<'a>(&'a self, path: &'a Path) -> BoxedFuture<'a, Result<Vec<u8>, AssetIoError>> {
+        let bytes = self.read(path);
+        Box::pin(async move { bytes })
+    }
+
+    fn read_directory(
+        &self,
+        path: &Path,
+    ) -> Result<Box<dyn Iterator<Item = PathBuf>>, AssetIoError> {
+        let mut children = Vec::new();
+        for asset_path in self.assets.read().keys() {
+            let child = asset_path
+                .strip_prefix(path)
+                .ok()
+                .and_then(|relative_path| relative_path.components().next())
+                .map(|component| path.join(component));
+            if let Some(child) = child {
+                if !children.contains(&child) {
+                    children.push(child);
+                }
+            }
+        }
+        Ok(Box::new(children.into_iter()))
+    }
+
+    fn get_metadata(&self, path: &Path) -> Result<Metadata, AssetIoError> {
+        let assets = self.assets.read();
+        if assets.contains_key(path) {
+            Ok(Metadata::new(FileType::File))
+        } else if assets.keys().any(|asset_path| asset_path.starts_with(path)) {
+            Ok(Metadata::new(FileType::Directory))
+        } else {
+            Err(AssetIoError::NotFound(path.to_owned()))
+        }
+    }
+
+    fn watch_path_for_changes(&self, _path: &Path) -> Result<(), AssetIoError> {
+        #[cfg(all(
+            feature = "filesystem_watcher",
+            all(not(target_arch = "wasm32"), not(target_os = "android"))
+        ))]
+        {
+            let assets = self.assets.read();
+            let original_path = assets
+                .get(_path)
+                .and_then(|asset| asset.original_path.as_ref());
+            let mut watcher = self.filesystem_watcher.write();
+            if let (Some(watcher), Some(original_path)) = (&mut *watcher, original_path) {
+                if original_path.is_file() {
+                    watcher
+                        .watch(original_path)
+                        .map_err(|_error| AssetIoError::PathWatchError(original_path.clone()))?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    fn watch_for_changes(&self) -> Result<(), AssetIoError> {
+        #[cfg(all(
+            feature = "filesystem_watcher",
+            all(not(target_arch = "wasm32"), not(target_os = "android"))
+        ))]
+        {
+            *self.filesystem_watcher.write() = Some(FilesystemWatcher::default());
+        }
+        #[cfg(not(all(
+            feature = "filesystem_watcher",
+            all(not(target_arch = "wasm32"), not(target_os = "android"))
+        )))]
+        bevy_log::warn!("Watching for changes of embedded assets requires the `filesystem_watcher` feature, and is not supported on wasm32 / android targets");
+
+        Ok(())
+    }
+}
+
+/// Reloads the embedded assets of `asset_io` whose original file changed.
+#[cfg(all(
+    feature = "filesystem_watcher",
+    all(not(target_arch = "wasm32"), not(target_os = "android"))
+))]
+pub(crate) fn reload_changed_embedded_assets(
+    asset_server: &AssetServer,
+    asset_io: &EmbeddedAssetIo,
+    source: Option<&str>,
+) {
+    use crossbeam_channel::TryRecvError;
+
+    let watcher = asset_io.filesystem_watcher.read();
+    let watcher = match &*watcher {
+        Some(watcher) => watcher,
+        None => return,
+    };
+    let mut changed = Vec::new();
+    loop {
+        let event = match watcher.receiver.try_recv() {
+            Ok(result) => result.unwrap(),
+            Err(TryRecvError::Empty) => break,
+            Err(TryRecvError::Disconnected) => panic!("FilesystemWatcher disconnected."),
+        };
+        if let notify::event::Event {
+            kind: notify::event::EventKind::Modify(_),
+            paths,
+            ..
+        } = event
+        {
+            for path in paths {
+                if !changed.contains(&path) {
+                    changed.push(path);
+                }
+            }
+        }
+    }
+
+    let assets = asset_io.assets.read();
+    for (asset_path, asset) in assets.iter() {
+        if let Some(original_path) = &asset.original_path {
+            if changed.contains(original_path) {
+                let _ = asset_server
+                    .load_untracked(AssetPath::new_ref_in_source(source, asset_path, None), true);
+            }
+        }
+    }
+}
+
+/// Returns the path of the file embedded by [`embedded_asset!`](crate::embedded_asset) from the
+/// source file `source_file`, as given by `file!()`, in the crate at `manifest_dir`.
+///
+/// `file!()` is relative to the root of the workspace when the crate is part of one, so the file
+/// is searched relative to every ancestor of the crate.
+#[doc(hidden)]
+pub fn embedded_original_path(manifest_dir: &str, source_file: &str, path: &str) -> PathBuf {
+    let source_file = Path::new(source_file);
+    let source_file = Path::new(manifest_dir)
+        .ancestors()
+        .map(|root| root.join(source_file))
+        .find(|source_file| source_file.is_file())
+        .unwrap_or_else(|| Path::new(manifest_dir).join(source_file));
+    source_file
+        .parent()
+        .map_or_else(|| PathBuf::from(path), |parent| parent.join(path))
+}
+
+/// Embeds a file into the binary as an asset of the [`EMBEDDED_ASSET_SOURCE`].
+///
+/// The file path is relative to the current source file, like with `include_bytes!`. The asset is
+/// embedded at the same path, or at the given asset path, and can be loaded like any other asset:
+///
+/// ```ignore
+/// # use bevy_app::App;
+/// # use bevy_asset::{embedded_asset, AssetServer, HandleUntyped};
+/// # let mut app = App::new();
+/// embedded_asset!(app, "icon.png");
+/// embedded_asset!(app, "ui/font.ttf", "../assets/fonts/FiraSans-Bold.ttf");
+///
+/// let asset_server = app.world.resource::<AssetServer>();
+/// let icon: HandleUntyped = asset_server.load_untyped("embedded://icon.png");
+/// ```
+///
+/// In debug builds, the path of the original file is recorded, so that the asset is hot reloaded
+/// from it when watching for changes.
+#[macro_export]
+macro_rules! embedded_asset {
+    ($app: ident, $path_str: expr) => {
+        $crate::embedded_asset!($app, $path_str, $path_str)
+    };
+    ($app: ident, $asset_path: expr, $path_str: expr) => {{
+        let embedded = $app.world.resource::<$crate::EmbeddedAssetIo>();
+        #[cfg(debug_assertions)]
+        embedded.insert_with_original(
+            $asset_path,
+            include_bytes!($path_str),
+            $crate::embedded_original_path(env!("CARGO_MANIFEST_DIR"), file!(), $path_str),
+        );
+        #[cfg(not(debug_assertions))]
+        embedded.insert($asset_path, include_bytes!($path_str));
+    }};
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::AssetServer;
+
+    #[test]
+    fn reads_embedded_assets() {
+        let asset_io = EmbeddedAssetIo::default();
+        asset_io.insert("icon.png", b"icon");
+        asset_io.insert("ui/font.ttf", b"font");
+        asset_io.insert("ui/buttons/ok.png", b"ok");
+
+        let load = |path: &str| futures_lite::future::block_on(asset_io.load_path(Path::new(path)));
+        assert_eq!(load("icon.png").unwrap(), b"icon");
+        assert_eq!(load("ui/font.ttf").unwrap(), b"font");
+        assert!(matches!(
+            load("missing.png"),
+            Err(AssetIoError::NotFound(_))
+        ));
+
+        assert!(asset_io.is_file(Path::new("icon.png")));
+        assert!(asset_io.is_dir(Path::new("ui")));
+        assert!(asset_io.is_dir(Path::new("")));
+        assert!(!asset_io.is_dir(Path::new("icon")));
+
+        let mut root = asset_io
+            .read_directory(Path::new(""))
+            .unwrap()
+            .collect::<Vec<_>>();
+        root.sort();
+        assert_eq!(root, [PathBuf::from("icon.png"), PathBuf::from("ui")]);
+        let mut ui = asset_io
+            .read_directory(Path::new("ui"))
+            .unwrap()
+            .collect::<Vec<_>>();
+        ui.sort();
+        assert_eq!(
+            ui,
+            [PathBuf::from("ui/buttons"), PathBuf::from("ui/font.ttf")]
+        );
+    }
+
+    #[test]
+    fn embeds_assets_into_the_embedded_source() {
+        let mut app = bevy_app::App::new();
+        app.add_plugin(crate::AssetPlugin::default());
+        embedded_asset!(app, "mod.rs");
+        embedded_asset!(app, "io/metadata.rs", "metadata.rs");
+
+        let asset_server = app.world.resource::<AssetServer>();
+        let asset_io = asset_server.source_io(Some(EMBEDDED_ASSET_SOURCE)).unwrap();
+        let load = |path: &str| futures_lite::future::block_on(asset_io.load_path(Path::new(path)));
+        assert_eq!(load("mod.rs").unwrap(), include_bytes!("mod.rs"));
+        assert_eq!(
+            load("io/metadata.rs").unwrap(),
+            include_bytes!("metadata.rs")
+        );
+    }
+
+    #[test]
+    fn finds_original_path() {
+        let manifest_dir = env!("CARGO_MANIFEST_DIR");
+        let original_path = embedded_original_path(manifest_dir, file!(), "mod.rs");
+        assert!(original_path.is_file());
+        assert_eq!(original_path, Path::new(manifest_dir).join("src/io/mod.rs"));
+    }
+}
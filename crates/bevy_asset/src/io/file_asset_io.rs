@@ -1,6 +1,6 @@
 #[cfg(feature = "filesystem_watcher")]
-use crate::{filesystem_watcher::FilesystemWatcher, AssetServer};
-use crate::{AssetIo, AssetIoError, Metadata};
+use crate::{asset_path_of_meta, filesystem_watcher::FilesystemWatcher, AssetPath, AssetServer};
+use crate::{AssetIo, AssetIoError, AssetReader, Metadata};
 use anyhow::Result;
 #[cfg(feature = "filesystem_watcher")]
 use bevy_ecs::system::Res;
@@ -10,6 +10,7 @@ use bevy_utils::HashSet;
 #[cfg(feature = "filesystem_watcher")]
 use crossbeam_channel::TryRecvError;
 use fs::File;
+use futures_lite::io::AssertAsync;
 #[cfg(feature = "filesystem_watcher")]
 use parking_lot::RwLock;
 #[cfg(feature = "filesystem_watcher")]
@@ -27,7 +28,7 @@ use std::{
 pub struct FileAssetIo {
     root_path: PathBuf,
     #[cfg(feature = "filesystem_watcher")]
-    filesystem_watcher: Arc<RwLock<Option<FilesystemWatcher>>>,
+    pub(crate) filesystem_watcher: Arc<RwLock<Option<FilesystemWatcher>>>,
 }
 
 impl FileAssetIo {
@@ -105,6 +106,54 @@ impl AssetIo for FileAssetIo {
         })
     }
 
+    fn open_path<'a>(
+        &'a self,
+        path: &'a Path,
+    ) -> BoxedFuture<'a, Result<Box<dyn AssetReader>, AssetIoError>> {
+        Box::pin(async move {
+            let full_path = self.root_path.join(path);
+            let file = File::open(&full_path).map_err(|e| not_found_or_io(e, full_path))?;
+            Ok(Box::new(AssertAsync::new(file)) as Box<dyn AssetReader>)
+        })
+    }
+
+    fn write_path<'a>(
+        &'a self,
+        path: &'a Path,
+        bytes: &'a [u8],
+    ) -> BoxedFuture<'a, Result<(), AssetIoError>> {
+        Box::pin(async move {
+            let full_path = self.root_path.join(path);
+            if let Some(parent) = full_path.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            fs::write(full_path, bytes)?;
+            Ok(())
+        })
+    }
+
+    fn rename_path<'a>(
+        &'a self,
+        from: &'a Path,
+        to: &'a Path,
+    ) -> BoxedFuture<'a, Result<(), AssetIoError>> {
+        Box::pin(async move {
+            let full_from = self.root_path.join(from);
+            let full_to = self.root_path.join(to);
+            if let Some(parent) = full_to.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            fs::rename(&full_from, full_to).map_err(|e| not_found_or_io(e, full_from))
+        })
+    }
+
+    fn delete_path<'a>(&'a self, path: &'a Path) -> BoxedFuture<'a, Result<(), AssetIoError>> {
+        Box::pin(async move {
+            let full_path = self.root_path.join(path);
+            fs::remove_file(&full_path).map_err(|e| not_found_or_io(e, full_path))
+        })
+    }
+
     fn read_directory(
         &self,
         path: &Path,
@@ -149,29 +198,49 @@ impl AssetIo for FileAssetIo {
         full_path
             .metadata()
             .and_then(Metadata::try_from)
-            .map_err(|e| {
-                if e.kind() == std::io::ErrorKind::NotFound {
-                    AssetIoError::NotFound(full_path)
-                } else {
-                    e.into()
-                }
-            })
+            .map_err(|e| not_found_or_io(e, full_path))
+    }
+}
+
+fn not_found_or_io(error: std::io::Error, full_path: PathBuf) -> AssetIoError {
+    if error.kind() == std::io::ErrorKind::NotFound {
+        AssetIoError::NotFound(full_path)
+    } else {
+        error.into()
     }
 }
 
 /// Watches for file changes in the local file system.
+///
+/// Reloads the changed assets of the default asset source and of every named source that is a
+/// [`FileAssetIo`] or an [`EmbeddedAssetIo`](crate::EmbeddedAssetIo).
 #[cfg(all(
     feature = "filesystem_watcher",
     all(not(target_arch = "wasm32"), not(target_os = "android"))
 ))]
 pub fn filesystem_watcher_system(asset_server: Res<AssetServer>) {
-    let mut changed = HashSet::default();
-    let asset_io =
-        if let Some(asset_io) = asset_server.server.asset_io.downcast_ref::<FileAssetIo>() {
-            asset_io
+    reload_changed_assets(&asset_server, &*asset_server.server.asset_io, None);
+    let named_asset_io = asset_server.server.named_asset_io.read().clone();
+    for (source, asset_io) in &named_asset_io {
+        if let Some(asset_io) = asset_io.downcast_ref::<crate::EmbeddedAssetIo>() {
+            crate::reload_changed_embedded_assets(&asset_server, asset_io, Some(source));
         } else {
-            return;
-        };
+            reload_changed_assets(&asset_server, &**asset_io, Some(source));
+        }
+    }
+}
+
+#[cfg(all(
+    feature = "filesystem_watcher",
+    all(not(target_arch = "wasm32"), not(target_os = "android"))
+))]
+fn reload_changed_assets(asset_server: &AssetServer, asset_io: &dyn AssetIo, source: Option<&str>) {
+    let mut changed = HashSet::default();
+    let asset_io = if let Some(asset_io) = asset_io.downcast_ref::<FileAssetIo>() {
+        asset_io
+    } else {
+        return;
+    };
     let watcher = asset_io.filesystem_watcher.read();
     if let Some(ref watcher) = *watcher {
         loop {
@@ -189,7 +258,20 @@ pub fn filesystem_watcher_system(asset_server: Res<AssetServer>) {
                 for path in &paths {
                     if !changed.contains(path) {
                         let relative_path = path.strip_prefix(&asset_io.root_path).unwrap();
-                        let _ = asset_server.load_untracked(relative_path.into(), true);
+                        // a changed `.meta` file reloads the asset it configures
+                        let configured_path = asset_path_of_meta(relative_path);
+                        let asset_path = AssetPath::new_ref_in_source(
+                            source,
+                            configured_path.unwrap_or(relative_path),
+                            None,
+                        );
+                        // files written by `AssetServer::save` already match their assets
+                        if configured_path.is_none()
+                            && asset_server.is_save_echo(&asset_path, asset_io)
+                        {
+                            continue;
+                        }
+                        let _ = asset_server.load_untracked(asset_path, true);
                     }
                 }
                 changed.extend(paths);
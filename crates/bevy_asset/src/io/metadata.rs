@@ -1,4 +1,7 @@
-use std::convert::{TryFrom, TryInto};
+use std::{
+    convert::{TryFrom, TryInto},
+    time::SystemTime,
+};
 
 /// A enum representing a type of file.
 #[non_exhaustive]
@@ -47,12 +50,16 @@ impl TryFrom<std::fs::FileType> for FileType {
 #[derive(Debug, Clone)]
 pub struct Metadata {
     file_type: FileType,
+    modified: Option<SystemTime>,
 }
 
 impl Metadata {
     /// Creates new metadata information.
     pub fn new(file_type: FileType) -> Self {
-        Self { file_type }
+        Self {
+            file_type,
+            modified: None,
+        }
     }
 
     /// Returns the file type.
@@ -72,6 +79,12 @@ impl Metadata {
     pub const fn is_file(&self) -> bool {
         self.file_type.is_file()
     }
+
+    /// Returns the last modification time of the entry, if known.
+    #[inline]
+    pub const fn modified(&self) -> Option<SystemTime> {
+        self.modified
+    }
 }
 
 impl TryFrom<std::fs::Metadata> for Metadata {
@@ -80,6 +93,7 @@ impl TryFrom<std::fs::Metadata> for Metadata {
     fn try_from(metadata: std::fs::Metadata) -> Result<Self, Self::Error> {
         Ok(Self {
             file_type: metadata.file_type().try_into()?,
+            modified: metadata.modified().ok(),
         })
     }
 }
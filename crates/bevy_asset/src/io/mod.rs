@@ -5,6 +5,8 @@ mod file_asset_io;
 #[cfg(target_arch = "wasm32")]
 mod wasm_asset_io;
 
+mod content_addressed_asset_io;
+mod embedded_asset_io;
 mod metadata;
 
 #[cfg(target_os = "android")]
@@ -14,11 +16,14 @@ pub use file_asset_io::*;
 #[cfg(target_arch = "wasm32")]
 pub use wasm_asset_io::*;
 
+pub use content_addressed_asset_io::*;
+pub use embedded_asset_io::*;
 pub use metadata::*;
 
 use anyhow::Result;
 use bevy_utils::BoxedFuture;
 use downcast_rs::{impl_downcast, Downcast};
+use futures_lite::io::{AsyncRead, AsyncSeek, Cursor};
 use std::{
     io,
     path::{Path, PathBuf},
@@ -39,8 +44,31 @@ pub enum AssetIoError {
     /// Failed to watch path.
     #[error("failed to watch path: {0}")]
     PathWatchError(PathBuf),
+
+    /// The asset I/O does not support writing.
+    #[error("writing is not supported by this asset I/O: {0}")]
+    WriteNotSupported(PathBuf),
+
+    /// The contents of a file do not match the hash they are expected to have.
+    #[error("the contents of {path:?} do not match their hash: expected {expected:016x}, found {actual:016x}")]
+    ContentHashMismatch {
+        /// The path of the file.
+        path: PathBuf,
+        /// The hash the contents should have.
+        expected: u64,
+        /// The hash of the contents that were read.
+        actual: u64,
+    },
 }
 
+/// An asynchronous reader over the contents of a file, see [`AssetIo::open_path`].
+///
+/// Use [`AsyncReadExt`](futures_lite::AsyncReadExt) and [`AsyncSeekExt`](futures_lite::AsyncSeekExt)
+/// to read from it.
+pub trait AssetReader: AsyncRead + AsyncSeek + Send + Sync + Unpin {}
+
+impl<T: AsyncRead + AsyncSeek + Send + Sync + Unpin> AssetReader for T {}
+
 /// A storage provider for an [`AssetServer`].
 ///
 /// An asset I/O is the backend actually providing data for the asset loaders managed by the asset
@@ -56,6 +84,20 @@ pub trait AssetIo: Downcast + Send + Sync + 'static {
     /// Returns a future to load the full file data at the provided path.
     fn load_path<'a>(&'a self, path: &'a Path) -> BoxedFuture<'a, Result<Vec<u8>, AssetIoError>>;
 
+    /// Returns a future to open a reader over the file at the provided path.
+    ///
+    /// Unlike [`load_path`](AssetIo::load_path), this lets large files be read incrementally.
+    /// The default implementation loads the whole file with `load_path` and reads from memory.
+    fn open_path<'a>(
+        &'a self,
+        path: &'a Path,
+    ) -> BoxedFuture<'a, Result<Box<dyn AssetReader>, AssetIoError>> {
+        Box::pin(async move {
+            let bytes = self.load_path(path).await?;
+            Ok(Box::new(Cursor::new(bytes)) as Box<dyn AssetReader>)
+        })
+    }
+
     /// Returns an iterator of directory entry names at the provided path.
     fn read_directory(
         &self,
@@ -71,6 +113,45 @@ pub trait AssetIo: Downcast + Send + Sync + 'static {
     /// Enables change tracking in this asset I/O.
     fn watch_for_changes(&self) -> Result<(), AssetIoError>;
 
+    /// Returns a future to write `bytes` to the file at the provided path, replacing its contents.
+    ///
+    /// Missing parent directories are created. The default implementation returns
+    /// [`AssetIoError::WriteNotSupported`].
+    fn write_path<'a>(
+        &'a self,
+        path: &'a Path,
+        _bytes: &'a [u8],
+    ) -> BoxedFuture<'a, Result<(), AssetIoError>> {
+        Box::pin(async move { Err(AssetIoError::WriteNotSupported(path.to_owned())) })
+    }
+
+    /// Returns a future to move the file at `from` to `to`, replacing any file at `to`.
+    ///
+    /// The default implementation returns [`AssetIoError::WriteNotSupported`].
+    fn rename_path<'a>(
+        &'a self,
+        from: &'a Path,
+        _to: &'a Path,
+    ) -> BoxedFuture<'a, Result<(), AssetIoError>> {
+        Box::pin(async move { Err(AssetIoError::WriteNotSupported(from.to_owned())) })
+    }
+
+    /// Returns a future to delete the file at the provided path.
+    ///
+    /// The default implementation returns [`AssetIoError::WriteNotSupported`].
+    fn delete_path<'a>(&'a self, path: &'a Path) -> BoxedFuture<'a, Result<(), AssetIoError>> {
+        Box::pin(async move { Err(AssetIoError::WriteNotSupported(path.to_owned())) })
+    }
+
+    /// Returns the [`content_hash`](crate::content_hash) of the file at the provided path, if it
+    /// is known without reading the file.
+    ///
+    /// The [`AssetServer`](crate::AssetServer) shares the handle of an asset between the paths
+    /// with the same contents. The default implementation returns `None`.
+    fn content_hash(&self, _path: &Path) -> Option<u64> {
+        None
+    }
+
     /// Returns `true` if the path is a directory.
     fn is_dir(&self, path: &Path) -> bool {
         self.get_metadata(path)
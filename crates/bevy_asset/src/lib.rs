@@ -23,9 +23,13 @@ mod filesystem_watcher;
 mod handle;
 mod info;
 mod io;
+mod load_queue;
 mod loader;
 mod path;
+mod processor;
 mod reflect;
+mod saver;
+mod settings;
 
 /// The `bevy_asset` prelude.
 pub mod prelude {
@@ -42,9 +46,13 @@ pub use bevy_utils::BoxedFuture;
 pub use handle::*;
 pub use info::*;
 pub use io::*;
+pub use load_queue::{LoadPriority, DEFAULT_MAX_CONCURRENT_LOADS};
 pub use loader::*;
 pub use path::*;
+pub use processor::*;
 pub use reflect::*;
+pub use saver::*;
+pub use settings::*;
 
 use bevy_app::{prelude::Plugin, App};
 use bevy_ecs::schedule::{StageLabel, SystemStage};
@@ -58,6 +66,22 @@ pub enum AssetStage {
     AssetEvents,
 }
 
+/// Whether assets are loaded as they are, or processed by [`AssetProcessor`]s first.
+#[derive(Debug, Default, Hash, PartialEq, Eq, Clone, Copy)]
+pub enum AssetMode {
+    /// Assets are loaded from the asset folder.
+    #[default]
+    Unprocessed,
+    /// Assets are loaded from the imported asset folder, as written by an earlier run in
+    /// [`AssetMode::Processor`]. This is the mode of shipped apps.
+    Processed,
+    /// Assets of the asset folder are processed into the imported asset folder at startup, and
+    /// loaded from there. When watching for changes, changed assets are processed again.
+    ///
+    /// Only supported on desktop platforms; elsewhere this behaves like [`AssetMode::Processed`].
+    Processor,
+}
+
 /// Adds support for Assets to an App.
 ///
 /// Assets are typed collections with change tracking, which are added as App Resources. Examples of
@@ -65,16 +89,23 @@ pub enum AssetStage {
 pub struct AssetPlugin {
     /// The base folder where assets are loaded from, relative to the executable.
     pub asset_folder: String,
+    /// The folder where processed assets are written to and loaded from, relative to the
+    /// executable. Only used outside of [`AssetMode::Unprocessed`].
+    pub imported_asset_folder: String,
     /// Whether to watch for changes in asset files. Requires the `filesystem_watcher` feature,
     /// and cannot be supported on the wasm32 arch nor android os.
     pub watch_for_changes: bool,
+    /// Whether assets are processed before being loaded.
+    pub mode: AssetMode,
 }
 
 impl Default for AssetPlugin {
     fn default() -> Self {
         Self {
             asset_folder: "assets".to_string(),
+            imported_asset_folder: "imported_assets".to_string(),
             watch_for_changes: false,
+            mode: AssetMode::Unprocessed,
         }
     }
 }
@@ -84,16 +115,31 @@ impl AssetPlugin {
     ///
     /// This is useful when providing a custom `AssetIo` instance that needs to
     /// delegate to the default `AssetIo` for the platform.
+    ///
+    /// Reads from the imported asset folder outside of [`AssetMode::Unprocessed`].
     pub fn create_platform_default_asset_io(&self) -> Box<dyn AssetIo> {
+        let folder = match self.mode {
+            AssetMode::Unprocessed => &self.asset_folder,
+            AssetMode::Processed | AssetMode::Processor => &self.imported_asset_folder,
+        };
         #[cfg(all(not(target_arch = "wasm32"), not(target_os = "android")))]
-        let source = FileAssetIo::new(&self.asset_folder, self.watch_for_changes);
+        let source = FileAssetIo::new(folder, self.watch_for_changes);
         #[cfg(target_arch = "wasm32")]
-        let source = WasmAssetIo::new(&self.asset_folder);
+        let source = WasmAssetIo::new(folder);
         #[cfg(target_os = "android")]
-        let source = AndroidAssetIo::new(&self.asset_folder);
+        let source = AndroidAssetIo::new(folder);
 
         Box::new(source)
     }
+
+    /// Creates the [`AssetPipeline`] used in [`AssetMode::Processor`].
+    #[cfg(all(not(target_arch = "wasm32"), not(target_os = "android")))]
+    pub fn create_asset_pipeline(&self) -> AssetPipeline {
+        AssetPipeline::new(
+            FileAssetIo::new(&self.asset_folder, self.watch_for_changes),
+            FileAssetIo::get_base_path().join(&self.imported_asset_folder),
+        )
+    }
 }
 
 impl Plugin for AssetPlugin {
@@ -103,6 +149,20 @@ impl Plugin for AssetPlugin {
             let asset_server = AssetServer::with_boxed_io(source);
             app.insert_resource(asset_server);
         }
+        if self.mode != AssetMode::Unprocessed {
+            app.world
+                .resource::<AssetServer>()
+                .set_read_all_meta_files(true);
+        }
+
+        let embedded_asset_io = EmbeddedAssetIo::default();
+        if self.watch_for_changes {
+            embedded_asset_io.watch_for_changes().unwrap();
+        }
+        app.world
+            .resource::<AssetServer>()
+            .add_source(EMBEDDED_ASSET_SOURCE, embedded_asset_io.clone());
+        app.insert_resource(embedded_asset_io);
 
         app.add_stage_before(
             bevy_app::CoreStage::PreUpdate,
@@ -115,9 +175,14 @@ impl Plugin for AssetPlugin {
             SystemStage::parallel(),
         )
         .register_type::<HandleId>()
+        .add_event::<UntypedAssetLoadFailedEvent>()
         .add_system_to_stage(
             bevy_app::CoreStage::PreUpdate,
             asset_server::free_unused_assets_system,
+        )
+        .add_system_to_stage(
+            AssetStage::LoadAssets,
+            asset_server::asset_load_failed_event_system,
         );
 
         #[cfg(all(
@@ -125,5 +190,17 @@ impl Plugin for AssetPlugin {
             all(not(target_arch = "wasm32"), not(target_os = "android"))
         ))]
         app.add_system_to_stage(AssetStage::LoadAssets, io::filesystem_watcher_system);
+
+        #[cfg(all(not(target_arch = "wasm32"), not(target_os = "android")))]
+        if self.mode == AssetMode::Processor {
+            app.insert_resource(self.create_asset_pipeline())
+                .add_startup_system_to_stage(
+                    bevy_app::StartupStage::PreStartup,
+                    process_assets_system,
+                )
+                .add_system_to_stage(AssetStage::LoadAssets, update_asset_pipeline_system);
+            #[cfg(feature = "filesystem_watcher")]
+            app.add_system_to_stage(AssetStage::LoadAssets, reprocess_changed_assets_system);
+        }
     }
 }
@@ -0,0 +1,169 @@
+use crate::{path::SourcePathId, AssetPath};
+use std::{cmp::Ordering, collections::BinaryHeap};
+
+/// The number of loads the [`AssetServer`](crate::AssetServer) runs at the same time by default.
+pub const DEFAULT_MAX_CONCURRENT_LOADS: usize = 16;
+
+/// The priority of a load queued on the [`AssetServer`](crate::AssetServer).
+///
+/// Queued loads with a higher priority start first, loads of equal priority start in the order
+/// they were requested. See [`AssetServer::load_with_priority`](crate::AssetServer::load_with_priority).
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct LoadPriority(pub i32);
+
+impl LoadPriority {
+    /// For assets that can wait, like distant level chunks.
+    pub const LOW: Self = Self(-100);
+    /// The priority of [`AssetServer::load`](crate::AssetServer::load).
+    pub const NORMAL: Self = Self(0);
+    /// For assets that are needed right away.
+    pub const HIGH: Self = Self(100);
+}
+
+/// A load waiting for a free slot.
+pub(crate) struct QueuedLoad {
+    pub(crate) path: AssetPath<'static>,
+    pub(crate) version: usize,
+    pub(crate) priority: LoadPriority,
+    order: u64,
+}
+
+impl PartialEq for QueuedLoad {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for QueuedLoad {}
+
+impl PartialOrd for QueuedLoad {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for QueuedLoad {
+    fn cmp(&self, other: &Self) -> Ordering {
+        // the heap pops the greatest load first: the highest priority, then the oldest request
+        self.priority
+            .cmp(&other.priority)
+            .then_with(|| other.order.cmp(&self.order))
+    }
+}
+
+/// The loads of the [`AssetServer`](crate::AssetServer), started by priority while fewer than
+/// `max_concurrent_loads` are in flight.
+pub(crate) struct LoadQueue {
+    queued: BinaryHeap<QueuedLoad>,
+    next_order: u64,
+    pub(crate) in_flight: usize,
+    pub(crate) max_concurrent_loads: usize,
+    /// Holds all queued loads, see [`AssetServer::set_loads_paused`](crate::AssetServer::set_loads_paused).
+    pub(crate) paused: bool,
+}
+
+impl Default for LoadQueue {
+    fn default() -> Self {
+        Self {
+            queued: BinaryHeap::new(),
+            next_order: 0,
+            in_flight: 0,
+            max_concurrent_loads: DEFAULT_MAX_CONCURRENT_LOADS,
+            paused: false,
+        }
+    }
+}
+
+impl LoadQueue {
+    pub(crate) fn push(
+        &mut self,
+        path: AssetPath<'static>,
+        version: usize,
+        priority: LoadPriority,
+    ) {
+        self.queued.push(QueuedLoad {
+            path,
+            version,
+            priority,
+            order: self.next_order,
+        });
+        self.next_order += 1;
+    }
+
+    /// Takes the next load to start, if a slot is free.
+    pub(crate) fn start_next(&mut self) -> Option<QueuedLoad> {
+        if self.paused || self.in_flight >= self.max_concurrent_loads {
+            return None;
+        }
+        let load = self.queued.pop()?;
+        self.in_flight += 1;
+        Some(load)
+    }
+
+    pub(crate) fn len(&self) -> usize {
+        self.queued.len()
+    }
+
+    /// Changes the priority of the queued loads of a source. Returns `true` if any was queued.
+    pub(crate) fn set_priority(
+        &mut self,
+        source_path_id: SourcePathId,
+        priority: LoadPriority,
+    ) -> bool {
+        let mut found = false;
+        let queued = std::mem::take(&mut self.queued).into_vec();
+        self.queued = queued
+            .into_iter()
+            .map(|mut load| {
+                if load.path.get_id().source_path_id() == source_path_id {
+                    load.priority = priority;
+                    found = true;
+                }
+                load
+            })
+            .collect();
+        found
+    }
+
+    /// Removes the queued loads of a source.
+    pub(crate) fn remove(&mut self, source_path_id: SourcePathId) {
+        let queued = std::mem::take(&mut self.queued).into_vec();
+        self.queued = queued
+            .into_iter()
+            .filter(|load| load.path.get_id().source_path_id() != source_path_id)
+            .collect();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn starts_by_priority_then_order() {
+        let mut queue = LoadQueue {
+            max_concurrent_loads: 3,
+            ..Default::default()
+        };
+        queue.push("far.png".into(), 1, LoadPriority::LOW);
+        queue.push("a.png".into(), 1, LoadPriority::NORMAL);
+        queue.push("b.png".into(), 1, LoadPriority::NORMAL);
+        queue.push("near.png".into(), 1, LoadPriority::HIGH);
+        queue.push("c.png".into(), 1, LoadPriority::NORMAL);
+        assert!(queue.set_priority(
+            AssetPath::from("c.png").get_id().source_path_id(),
+            LoadPriority::HIGH
+        ));
+        queue.remove(AssetPath::from("b.png").get_id().source_path_id());
+
+        let mut started = Vec::new();
+        while let Some(load) = queue.start_next() {
+            started.push(load.path.path().to_str().unwrap().to_string());
+        }
+        assert_eq!(started, ["near.png", "c.png", "a.png"]);
+        assert_eq!(queue.len(), 1);
+
+        queue.in_flight -= 1;
+        assert_eq!(queue.start_next().unwrap().path, AssetPath::from("far.png"));
+    }
+}
@@ -1,6 +1,6 @@
 use crate::{
-    path::AssetPath, AssetIo, AssetIoError, AssetMeta, AssetServer, Assets, Handle, HandleId,
-    RefChangeChannel,
+    path::AssetPath, AssetIo, AssetIoError, AssetMeta, AssetReader, AssetServer, Assets, Handle,
+    HandleId, LoaderSettings, RefChangeChannel, SettingsDeserializer,
 };
 use anyhow::Error;
 use anyhow::Result;
@@ -9,6 +9,7 @@ use bevy_reflect::{TypeUuid, TypeUuidDynamic};
 use bevy_utils::{BoxedFuture, HashMap};
 use crossbeam_channel::{Receiver, Sender};
 use downcast_rs::{impl_downcast, Downcast};
+use futures_lite::AsyncReadExt;
 use std::path::Path;
 
 /// A loader for an asset source.
@@ -23,8 +24,44 @@ pub trait AssetLoader: Send + Sync + 'static {
         load_context: &'a mut LoadContext,
     ) -> BoxedFuture<'a, Result<(), Error>>;
 
+    /// Processes the asset from a reader over its file, in an asynchronous closure.
+    ///
+    /// This is what the [`AssetServer`] calls. Loaders of large files, like audio banks, override
+    /// it to read and seek through the file incrementally instead of receiving it whole. Such
+    /// loaders can implement [`load`](AssetLoader::load) by wrapping the bytes in a
+    /// [`Cursor`](futures_lite::io::Cursor) and calling this method.
+    ///
+    /// The default implementation reads the whole file and passes it to
+    /// [`load`](AssetLoader::load).
+    fn load_reader<'a>(
+        &'a self,
+        reader: &'a mut dyn AssetReader,
+        load_context: &'a mut LoadContext,
+    ) -> BoxedFuture<'a, Result<(), Error>> {
+        Box::pin(async move {
+            let mut bytes = Vec::new();
+            reader.read_to_end(&mut bytes).await?;
+            self.load(&bytes, load_context).await
+        })
+    }
+
     /// Returns a list of extensions supported by this asset loader, without the preceding dot.
     fn extensions(&self) -> &[&str];
+
+    /// Returns the function reading the settings of this loader from `.meta` files.
+    ///
+    /// Loaders that can be configured per asset return
+    /// [`deserialize_settings::<MySettings>`](crate::deserialize_settings) here, and read the
+    /// settings of each load with [`LoadContext::settings`]. The default implementation returns
+    /// `None`, in which case `.meta` files are not read for the assets of this loader.
+    fn settings_deserializer(&self) -> Option<SettingsDeserializer> {
+        None
+    }
+
+    /// Returns the name of this loader, as reported in [`AssetLoadError::loader`](crate::AssetLoadError::loader).
+    fn type_name(&self) -> &'static str {
+        std::any::type_name::<Self>()
+    }
 }
 
 /// An essential piece of data of an application.
@@ -123,23 +160,29 @@ pub struct LoadContext<'a> {
     pub(crate) ref_change_channel: &'a RefChangeChannel,
     pub(crate) asset_io: &'a dyn AssetIo,
     pub(crate) labeled_assets: HashMap<Option<String>, BoxedLoadedAsset>,
+    pub(crate) source: Option<&'a str>,
     pub(crate) path: &'a Path,
     pub(crate) version: usize,
+    pub(crate) settings: Option<&'a dyn LoaderSettings>,
 }
 
 impl<'a> LoadContext<'a> {
     pub(crate) fn new(
+        source: Option<&'a str>,
         path: &'a Path,
         ref_change_channel: &'a RefChangeChannel,
         asset_io: &'a dyn AssetIo,
         version: usize,
+        settings: Option<&'a dyn LoaderSettings>,
     ) -> Self {
         Self {
             ref_change_channel,
             asset_io,
             labeled_assets: Default::default(),
             version,
+            source,
             path,
+            settings,
         }
     }
 
@@ -148,6 +191,35 @@ impl<'a> LoadContext<'a> {
         self.path
     }
 
+    /// Gets the name of the asset source the asset is loaded from, or `None` for the default
+    /// source.
+    pub fn source(&self) -> Option<&str> {
+        self.source
+    }
+
+    /// Gets the full asset path of the loaded asset, including its source.
+    pub fn asset_path(&self) -> AssetPath<'_> {
+        AssetPath::new_ref_in_source(self.source, self.path, None)
+    }
+
+    /// Gets the asset path of the sub-asset with the given label.
+    ///
+    /// Use this rather than building an [`AssetPath`] from [`LoadContext::path`], which would
+    /// miss the asset source.
+    pub fn labeled_asset_path<'b>(&'b self, label: &'b str) -> AssetPath<'b> {
+        AssetPath::new_ref_in_source(self.source, self.path, Some(label))
+    }
+
+    /// Gets the settings of this load, if they are of type `S`.
+    ///
+    /// Settings are read from the `.meta` file next to the asset and may be overridden with
+    /// [`AssetServer::load_with_settings`]. Returns `None` if neither provided settings, in which
+    /// case loaders should fall back to `S::default()`.
+    pub fn settings<S: LoaderSettings>(&self) -> Option<&S> {
+        self.settings
+            .and_then(|settings| settings.downcast_ref::<S>())
+    }
+
     /// Returns `true` if the load context contains an asset with the specified label.
     pub fn has_labeled_asset(&self, label: &str) -> bool {
         self.labeled_assets.contains_key(&Some(label.to_string()))
@@ -163,7 +235,7 @@ impl<'a> LoadContext<'a> {
         assert!(!label.is_empty());
         self.labeled_assets
             .insert(Some(label.to_string()), asset.into());
-        self.get_handle(AssetPath::new_ref(self.path(), Some(label)))
+        self.get_handle(self.labeled_asset_path(label))
     }
 
     /// Gets a handle to an asset of type `T` from its id.
@@ -172,7 +244,7 @@ impl<'a> LoadContext<'a> {
     }
 
     /// Reads the contents of the file at the specified path through the [`AssetIo`] associated
-    /// with this context.
+    /// with this context, which is the one of the asset source the asset is loaded from.
     pub async fn read_asset_bytes<P: AsRef<Path>>(&self, path: P) -> Result<Vec<u8>, AssetIoError> {
         self.asset_io.load_path(path.as_ref()).await
     }
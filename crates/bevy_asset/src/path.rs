@@ -3,13 +3,21 @@ use bevy_utils::AHasher;
 use serde::{Deserialize, Serialize};
 use std::{
     borrow::Cow,
+    fmt,
     hash::{Hash, Hasher},
     path::{Path, PathBuf},
 };
 
 /// Represents a path to an asset in the file system.
+///
+/// The asset is read from the named source registered with
+/// [`AssetServer::add_source`](crate::AssetServer::add_source) if the path has one, or from the
+/// default source otherwise. As a string, an asset path is written `source://path#label`, where
+/// both the source and the label are optional.
 #[derive(Debug, Eq, PartialEq, Hash, Clone, Serialize, Deserialize)]
 pub struct AssetPath<'a> {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    source: Option<Cow<'a, str>>,
     path: Cow<'a, Path>,
     label: Option<Cow<'a, str>>,
 }
@@ -19,6 +27,22 @@ impl<'a> AssetPath<'a> {
     #[inline]
     pub fn new_ref(path: &'a Path, label: Option<&'a str>) -> AssetPath<'a> {
         AssetPath {
+            source: None,
+            path: Cow::Borrowed(path),
+            label: label.map(Cow::Borrowed),
+        }
+    }
+
+    /// Creates a new asset path in the named asset source using borrowed information, or in the
+    /// default source if `source` is `None`.
+    #[inline]
+    pub(crate) fn new_ref_in_source(
+        source: Option<&'a str>,
+        path: &'a Path,
+        label: Option<&'a str>,
+    ) -> AssetPath<'a> {
+        AssetPath {
+            source: source.map(Cow::Borrowed),
             path: Cow::Borrowed(path),
             label: label.map(Cow::Borrowed),
         }
@@ -28,11 +52,26 @@ impl<'a> AssetPath<'a> {
     #[inline]
     pub fn new(path: PathBuf, label: Option<String>) -> AssetPath<'a> {
         AssetPath {
+            source: None,
             path: Cow::Owned(path),
             label: label.map(Cow::Owned),
         }
     }
 
+    /// Returns this asset path in the named asset source.
+    #[inline]
+    #[must_use]
+    pub fn with_source(mut self, source: impl Into<Cow<'a, str>>) -> AssetPath<'a> {
+        self.source = Some(source.into());
+        self
+    }
+
+    /// Gets the name of the asset source, or `None` for the default source.
+    #[inline]
+    pub fn source(&self) -> Option<&str> {
+        self.source.as_deref()
+    }
+
     /// Constructs an identifier from this asset path.
     #[inline]
     pub fn get_id(&self) -> AssetPathId {
@@ -55,6 +94,10 @@ impl<'a> AssetPath<'a> {
     #[inline]
     pub fn to_owned(&self) -> AssetPath<'static> {
         AssetPath {
+            source: self
+                .source
+                .as_ref()
+                .map(|value| Cow::Owned(value.to_string())),
             path: Cow::Owned(self.path.to_path_buf()),
             label: self
                 .label
@@ -64,6 +107,19 @@ impl<'a> AssetPath<'a> {
     }
 }
 
+impl<'a> fmt::Display for AssetPath<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(source) = &self.source {
+            write!(f, "{}://", source)?;
+        }
+        write!(f, "{}", self.path.display())?;
+        if let Some(label) = &self.label {
+            write!(f, "#{}", label)?;
+        }
+        Ok(())
+    }
+}
+
 /// An unique identifier to an asset path.
 #[derive(
     Debug, Clone, Copy, Eq, PartialEq, Hash, Ord, PartialOrd, Serialize, Deserialize, Reflect,
@@ -85,14 +141,26 @@ pub struct SourcePathId(u64);
 #[reflect_value(PartialEq, Hash, Serialize, Deserialize)]
 pub struct LabelId(u64);
 
-impl<'a> From<&'a Path> for SourcePathId {
-    fn from(value: &'a Path) -> Self {
+impl SourcePathId {
+    /// Creates the identifier of the source path `path` in the named asset `source`, or in the
+    /// default source if `source` is `None`.
+    pub fn new(source: Option<&str>, path: &Path) -> Self {
         let mut hasher = get_hasher();
-        value.hash(&mut hasher);
+        path.hash(&mut hasher);
+        // paths of the default source keep the identifier they had before named sources existed
+        if let Some(source) = source {
+            source.hash(&mut hasher);
+        }
         SourcePathId(hasher.finish())
     }
 }
 
+impl<'a> From<&'a Path> for SourcePathId {
+    fn from(value: &'a Path) -> Self {
+        SourcePathId::new(None, value)
+    }
+}
+
 impl From<AssetPathId> for SourcePathId {
     fn from(id: AssetPathId) -> Self {
         id.source_path_id()
@@ -136,29 +204,36 @@ where
 {
     fn from(value: T) -> Self {
         let asset_path: AssetPath = value.into();
-        AssetPathId(
-            SourcePathId::from(asset_path.path()),
-            LabelId::from(asset_path.label()),
-        )
+        AssetPathId::from(&asset_path)
     }
 }
 
 impl<'a, 'b> From<&'a AssetPath<'b>> for AssetPathId {
     fn from(asset_path: &'a AssetPath<'b>) -> Self {
         AssetPathId(
-            SourcePathId::from(asset_path.path()),
+            SourcePathId::new(asset_path.source(), asset_path.path()),
             LabelId::from(asset_path.label()),
         )
     }
 }
 
+/// Splits `source://path#label` into its parts.
+fn parse_asset_path(asset_path: &str) -> (Option<&str>, &str, Option<&str>) {
+    let mut parts = asset_path.splitn(2, '#');
+    let path = parts.next().expect("Path must be set.");
+    let label = parts.next();
+    match path.split_once("://") {
+        Some((source, path)) => (Some(source), path, label),
+        None => (None, path, label),
+    }
+}
+
 impl<'a> From<&'a str> for AssetPath<'a> {
     fn from(asset_path: &'a str) -> Self {
-        let mut parts = asset_path.splitn(2, '#');
-        let path = Path::new(parts.next().expect("Path must be set."));
-        let label = parts.next();
+        let (source, path, label) = parse_asset_path(asset_path);
         AssetPath {
-            path: Cow::Borrowed(path),
+            source: source.map(Cow::Borrowed),
+            path: Cow::Borrowed(Path::new(path)),
             label: label.map(Cow::Borrowed),
         }
     }
@@ -173,6 +248,7 @@ impl<'a> From<&'a String> for AssetPath<'a> {
 impl<'a> From<&'a Path> for AssetPath<'a> {
     fn from(path: &'a Path) -> Self {
         AssetPath {
+            source: None,
             path: Cow::Borrowed(path),
             label: None,
         }
@@ -182,6 +258,7 @@ impl<'a> From<&'a Path> for AssetPath<'a> {
 impl<'a> From<PathBuf> for AssetPath<'a> {
     fn from(path: PathBuf) -> Self {
         AssetPath {
+            source: None,
             path: Cow::Owned(path),
             label: None,
         }
@@ -190,12 +267,43 @@ impl<'a> From<PathBuf> for AssetPath<'a> {
 
 impl<'a> From<String> for AssetPath<'a> {
     fn from(asset_path: String) -> Self {
-        let mut parts = asset_path.splitn(2, '#');
-        let path = PathBuf::from(parts.next().expect("Path must be set."));
-        let label = parts.next().map(String::from);
-        AssetPath {
-            path: Cow::Owned(path),
-            label: label.map(Cow::Owned),
-        }
+        AssetPath::from(asset_path.as_str()).to_owned()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_asset_paths() {
+        let path = AssetPath::from("mods://weapons/sword.gltf#Mesh0");
+        assert_eq!(path.source(), Some("mods"));
+        assert_eq!(path.path(), Path::new("weapons/sword.gltf"));
+        assert_eq!(path.label(), Some("Mesh0"));
+        assert_eq!(path.to_string(), "mods://weapons/sword.gltf#Mesh0");
+        assert_eq!(AssetPath::from(path.to_string()), path);
+
+        let path = AssetPath::from("weapons/sword.gltf");
+        assert_eq!(path.source(), None);
+        assert_eq!(path.label(), None);
+        assert_eq!(path.to_string(), "weapons/sword.gltf");
+    }
+
+    #[test]
+    fn sources_have_distinct_ids() {
+        let path = Path::new("weapons/sword.gltf");
+        assert_eq!(
+            AssetPathId::from("weapons/sword.gltf").source_path_id(),
+            SourcePathId::from(path)
+        );
+        assert_ne!(
+            AssetPathId::from("mods://weapons/sword.gltf"),
+            AssetPathId::from("weapons/sword.gltf")
+        );
+        assert_ne!(
+            AssetPathId::from("mods://weapons/sword.gltf"),
+            AssetPathId::from("dlc://weapons/sword.gltf")
+        );
     }
 }
@@ -0,0 +1,241 @@
+#[cfg(all(not(target_arch = "wasm32"), not(target_os = "android")))]
+mod pipeline;
+
+#[cfg(all(not(target_arch = "wasm32"), not(target_os = "android")))]
+pub use pipeline::*;
+
+use crate::{AssetIo, AssetIoError};
+use anyhow::Result;
+use bevy_utils::BoxedFuture;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+/// Turns the source bytes of an asset into a processed, usually platform-optimized, form.
+///
+/// Processors run in [`AssetMode::Processor`](crate::AssetMode::Processor), where the
+/// [`AssetPipeline`] writes their output into the imported asset folder that the
+/// [`AssetServer`](crate::AssetServer) then loads from. Like [`AssetLoader`](crate::AssetLoader)s,
+/// they are selected by the extension of the source file.
+///
+/// Examples are compressing PNG textures to KTX2, or splitting glTF scenes into meshes.
+pub trait AssetProcessor: Send + Sync + 'static {
+    /// Processes the source `bytes` of the asset at [`ProcessContext::path`].
+    fn process<'a>(
+        &'a self,
+        bytes: &'a [u8],
+        process_context: &'a mut ProcessContext,
+    ) -> BoxedFuture<'a, Result<ProcessedAsset, anyhow::Error>>;
+
+    /// Returns a list of extensions supported by this processor, without the preceding dot.
+    fn extensions(&self) -> &[&str];
+
+    /// Returns the version of the output of this processor.
+    ///
+    /// Bumping the version reprocesses every asset handled by the processor, even if its source
+    /// did not change.
+    fn version(&self) -> u32 {
+        0
+    }
+}
+
+/// The output of an [`AssetProcessor`].
+///
+/// It is written at the same path as its source in the imported asset folder, so handles stay the
+/// same whether or not assets are processed. If a loader or settings are set, a `.meta` file
+/// naming them is written next to it. Otherwise the `.meta` file of the source, if any, is written
+/// next to it, so the loader of the source extension still gets its settings.
+#[derive(Debug, Clone, Default)]
+pub struct ProcessedAsset {
+    /// The processed bytes of the asset.
+    pub bytes: Vec<u8>,
+    /// The extension of the loader that loads the processed bytes, if it differs from the one of
+    /// the source file.
+    pub loader: Option<String>,
+    /// The settings of the loader, serialized as RON.
+    pub settings: Option<String>,
+}
+
+impl ProcessedAsset {
+    /// Creates a [`ProcessedAsset`] from its bytes, loaded by the loader of the source extension.
+    pub fn new(bytes: Vec<u8>) -> Self {
+        Self {
+            bytes,
+            ..Default::default()
+        }
+    }
+
+    /// Loads the processed bytes with the loader of the given extension, without the preceding
+    /// dot.
+    #[must_use]
+    pub fn with_loader(mut self, extension: impl Into<String>) -> Self {
+        self.loader = Some(extension.into());
+        self
+    }
+
+    /// Passes `settings` to the loader, see [`LoadContext::settings`](crate::LoadContext::settings).
+    pub fn with_settings<S: Serialize>(mut self, settings: &S) -> Result<Self, ron::Error> {
+        self.settings = Some(ron::to_string(settings)?);
+        Ok(self)
+    }
+
+    /// Returns the contents of the `.meta` file of the processed asset, or `None` if it does not
+    /// need one.
+    pub fn meta(&self) -> Option<String> {
+        if self.loader.is_none() && self.settings.is_none() {
+            return None;
+        }
+        let mut meta = String::from("(\n");
+        if let Some(loader) = &self.loader {
+            meta.push_str(&format!("    loader: Some({:?}),\n", loader));
+        }
+        if let Some(settings) = &self.settings {
+            meta.push_str(&format!("    settings: {},\n", settings));
+        }
+        meta.push_str(")\n");
+        Some(meta)
+    }
+}
+
+/// A source file read by an [`AssetProcessor`] in addition to the asset it processes.
+///
+/// The asset is processed again when the file changes.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProcessDependency {
+    /// The path of the file, relative to the asset folder.
+    pub path: PathBuf,
+    /// The [`content_hash`] of the file when the asset was processed.
+    pub hash: u64,
+}
+
+/// The context of an [`AssetProcessor`] while it processes an asset.
+pub struct ProcessContext<'a> {
+    path: &'a Path,
+    meta: Option<&'a [u8]>,
+    asset_io: &'a dyn AssetIo,
+    dependencies: Vec<ProcessDependency>,
+}
+
+impl<'a> ProcessContext<'a> {
+    pub(crate) fn new(path: &'a Path, meta: Option<&'a [u8]>, asset_io: &'a dyn AssetIo) -> Self {
+        Self {
+            path,
+            meta,
+            asset_io,
+            dependencies: Vec::new(),
+        }
+    }
+
+    /// Gets the source path of the asset, relative to the asset folder.
+    pub fn path(&self) -> &Path {
+        self.path
+    }
+
+    /// Gets the contents of the `.meta` file of the source asset, if it has one.
+    pub fn meta(&self) -> Option<&[u8]> {
+        self.meta
+    }
+
+    /// Reads another source file, and records it as a dependency of the processed asset.
+    pub async fn read_source(&mut self, path: &Path) -> Result<Vec<u8>, AssetIoError> {
+        let bytes = self.asset_io.load_path(path).await?;
+        self.dependencies.push(ProcessDependency {
+            path: path.to_owned(),
+            hash: content_hash(&bytes),
+        });
+        Ok(bytes)
+    }
+
+    /// Gets the files read with [`ProcessContext::read_source`].
+    pub fn dependencies(&self) -> &[ProcessDependency] {
+        &self.dependencies
+    }
+
+    pub(crate) fn into_dependencies(self) -> Vec<ProcessDependency> {
+        self.dependencies
+    }
+}
+
+/// Hashes bytes with the 64 bit FNV-1a hash, in a way that is stable across runs, platforms and
+/// releases.
+///
+/// This is used to detect changed files, it is not a cryptographic hash.
+#[derive(Debug, Clone, Copy)]
+pub struct ContentHasher(u64);
+
+impl Default for ContentHasher {
+    fn default() -> Self {
+        Self(0xcbf2_9ce4_8422_2325)
+    }
+}
+
+impl ContentHasher {
+    /// Adds `bytes` to the hash.
+    pub fn write(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            self.0 ^= *byte as u64;
+            self.0 = self.0.wrapping_mul(0x0100_0000_01b3);
+        }
+    }
+
+    /// Adds `bytes` to the hash, prefixed with their length so that consecutive writes of
+    /// different parts cannot collide.
+    pub fn write_part(&mut self, bytes: &[u8]) {
+        self.write(&(bytes.len() as u64).to_le_bytes());
+        self.write(bytes);
+    }
+
+    /// Returns the hash of the bytes written so far.
+    pub fn finish(&self) -> u64 {
+        self.0
+    }
+}
+
+/// Returns the [`ContentHasher`] hash of `bytes`.
+pub fn content_hash(bytes: &[u8]) -> u64 {
+    let mut hasher = ContentHasher::default();
+    hasher.write(bytes);
+    hasher.finish()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn content_hash_is_stable() {
+        // reference values of the 64 bit FNV-1a hash
+        assert_eq!(content_hash(b""), 0xcbf29ce484222325);
+        assert_eq!(content_hash(b"a"), 0xaf63dc4c8601ec8c);
+        assert_eq!(content_hash(b"foobar"), 0x85944171f73967e8);
+
+        let mut ab = ContentHasher::default();
+        ab.write_part(b"a");
+        ab.write_part(b"b");
+        let mut a_b = ContentHasher::default();
+        a_b.write_part(b"ab");
+        a_b.write_part(b"");
+        assert_ne!(ab.finish(), a_b.finish());
+    }
+
+    #[test]
+    fn processed_asset_meta() {
+        assert_eq!(ProcessedAsset::new(vec![1]).meta(), None);
+
+        #[derive(Serialize)]
+        struct Settings {
+            nearest: bool,
+        }
+        let processed = ProcessedAsset::new(vec![1])
+            .with_loader("ktx2")
+            .with_settings(&Settings { nearest: true })
+            .unwrap();
+        let meta = processed.meta().unwrap();
+        assert_eq!(
+            crate::deserialize_meta_loader(meta.as_bytes())
+                .unwrap()
+                .as_deref(),
+            Some("ktx2")
+        );
+        assert!(meta.contains("settings: (nearest:true)"));
+    }
+}
@@ -0,0 +1,782 @@
+use crate::{
+    asset_path_of_meta, meta_path, AssetIo, AssetIoError, AssetProcessor, AssetServer,
+    ContentHasher, FileAssetIo, ProcessContext, ProcessDependency, META_FILE_EXTENSION,
+};
+use bevy_ecs::system::{Res, ResMut, Resource};
+use bevy_log::{info, warn};
+use bevy_tasks::{IoTaskPool, Task};
+use bevy_utils::HashMap;
+use futures_lite::future::block_on;
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::BTreeMap,
+    fs, io,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+use thiserror::Error;
+
+/// The name of the file in the imported asset folder that records how every asset was processed.
+pub const PROCESSOR_MANIFEST_FILE: &str = "processed_assets.ron";
+
+/// Errors that occur while processing assets with an [`AssetPipeline`].
+#[derive(Error, Debug)]
+pub enum ProcessError {
+    /// Encountered an error while reading a source file.
+    #[error("encountered an error while reading a source asset: {0}")]
+    AssetIoError(#[from] AssetIoError),
+
+    /// Encountered an error while writing to the imported asset folder.
+    #[error("encountered an error while writing a processed asset: {0}")]
+    Io(#[from] io::Error),
+
+    /// The processor of an asset failed.
+    #[error("failed to process {path:?}: {error}")]
+    ProcessorError {
+        /// The source path of the asset.
+        path: PathBuf,
+        /// The error returned by the processor.
+        error: anyhow::Error,
+    },
+
+    /// The manifest of the imported asset folder could not be written.
+    #[error("failed to write the processor manifest: {0}")]
+    ManifestError(#[from] ron::Error),
+}
+
+/// What the [`AssetPipeline`] did with a source file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProcessOutcome {
+    /// The file was run through its [`AssetProcessor`].
+    Processed,
+    /// The file has no processor and was copied as is.
+    Copied,
+    /// Neither the file, its `.meta` file, its dependencies nor its processor changed since it was
+    /// last processed.
+    UpToDate,
+    /// The file no longer exists, and its output was removed.
+    Removed,
+}
+
+/// How an asset was processed, used to skip it when nothing changed.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct ProcessedAssetInfo {
+    /// The hash of the source file, its `.meta` file and the processor.
+    hash: u64,
+    dependencies: Vec<ProcessDependency>,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct ProcessorManifest {
+    assets: BTreeMap<PathBuf, ProcessedAssetInfo>,
+}
+
+#[derive(Clone)]
+struct RegisteredProcessor {
+    processor: Arc<dyn AssetProcessor>,
+    type_name: &'static str,
+}
+
+/// The outcome of every asset of a batch.
+type ProcessOutcomes = Vec<(PathBuf, Result<ProcessOutcome, ProcessError>)>;
+
+/// The result of processing one asset of a batch.
+struct ProcessResult {
+    path: PathBuf,
+    outcome: Result<ProcessOutcome, ProcessError>,
+    /// The new manifest entry of a processed or copied asset.
+    info: Option<ProcessedAssetInfo>,
+}
+
+/// Processes the assets of a source folder into an imported asset folder.
+///
+/// Every file of the source folder is written at the same relative path in the imported folder:
+/// either run through the [`AssetProcessor`] registered for its extension, or copied as is along
+/// with its `.meta` file. The hash of the inputs of every asset is recorded in the
+/// [`PROCESSOR_MANIFEST_FILE`], so that assets are only processed again when their source, their
+/// `.meta` file, a [dependency](ProcessContext::read_source) or their processor changed.
+///
+/// Assets are processed in batches: the assets of a batch are processed in parallel on the
+/// [`IoTaskPool`], and the manifest is written once the whole batch is done. Assets
+/// [queued](AssetPipeline::queue) while a batch runs are processed in the next one.
+///
+/// In [`AssetMode::Processor`](crate::AssetMode::Processor), the [`AssetPlugin`](crate::AssetPlugin)
+/// adds this resource, processes all assets at startup, and reprocesses changed assets when
+/// watching for changes. The [`AssetServer`](crate::AssetServer) starts no load until the assets
+/// processed at startup are written.
+#[derive(Resource)]
+pub struct AssetPipeline {
+    source_io: Arc<FileAssetIo>,
+    destination: PathBuf,
+    processors: Vec<RegisteredProcessor>,
+    extension_to_processor_index: HashMap<String, usize>,
+    manifest: ProcessorManifest,
+    queued: Vec<PathBuf>,
+    batch: Option<Vec<Task<ProcessResult>>>,
+}
+
+impl AssetPipeline {
+    /// Creates a pipeline processing the assets of `source_io` into the `destination` folder.
+    ///
+    /// The manifest of a previous run is read from `destination`, if there is one. If
+    /// `source_io` watches for changes, the whole source folder is watched.
+    pub fn new(source_io: FileAssetIo, destination: impl Into<PathBuf>) -> Self {
+        source_io.watch_path_for_changes(Path::new("")).unwrap();
+        let destination = destination.into();
+        let manifest = fs::read(destination.join(PROCESSOR_MANIFEST_FILE))
+            .ok()
+            .and_then(|manifest| ron::de::from_bytes(&manifest).ok())
+            .unwrap_or_default();
+        Self {
+            source_io: Arc::new(source_io),
+            destination,
+            processors: Vec::new(),
+            extension_to_processor_index: Default::default(),
+            manifest,
+            queued: Vec::new(),
+            batch: None,
+        }
+    }
+
+    /// Returns the folder processed assets are written to.
+    pub fn destination(&self) -> &Path {
+        &self.destination
+    }
+
+    /// Adds the provided asset processor.
+    pub fn add_processor<T: AssetProcessor>(&mut self, processor: T) {
+        let index = self.processors.len();
+        for extension in processor.extensions() {
+            self.extension_to_processor_index
+                .insert(extension.to_string(), index);
+        }
+        self.processors.push(RegisteredProcessor {
+            processor: Arc::new(processor),
+            type_name: std::any::type_name::<T>(),
+        });
+    }
+
+    fn get_path_processor(&self, path: &Path) -> Option<&RegisteredProcessor> {
+        let file_name = path.file_name()?.to_str()?.to_lowercase();
+        let mut ext = file_name.as_str();
+        while let Some(idx) = ext.find('.') {
+            ext = &ext[idx + 1..];
+            if let Some(index) = self.extension_to_processor_index.get(ext) {
+                return Some(&self.processors[*index]);
+            }
+        }
+        None
+    }
+
+    /// Queues the asset at `path`, relative to the source folder, for the next batch.
+    ///
+    /// The path of a `.meta` file queues the asset it describes.
+    pub fn queue(&mut self, path: &Path) {
+        let path = asset_path_of_meta(path).unwrap_or(path);
+        if !self.queued.iter().any(|queued| queued == path) {
+            self.queued.push(path.to_owned());
+        }
+    }
+
+    /// Queues every asset of the source folder, and the outputs of source files that no longer
+    /// exist for removal.
+    pub fn queue_all(&mut self) -> Result<(), AssetIoError> {
+        let mut paths = Vec::new();
+        self.collect_source_paths(Path::new(""), &mut paths)?;
+        let removed = self
+            .manifest
+            .assets
+            .keys()
+            .filter(|path| !paths.contains(path))
+            .cloned()
+            .collect::<Vec<_>>();
+        for path in paths.iter().chain(&removed) {
+            self.queue(path);
+        }
+        Ok(())
+    }
+
+    fn collect_source_paths(
+        &self,
+        path: &Path,
+        paths: &mut Vec<PathBuf>,
+    ) -> Result<(), AssetIoError> {
+        for child_path in self.source_io.read_directory(path)? {
+            if self.source_io.is_dir(&child_path) {
+                self.collect_source_paths(&child_path, paths)?;
+            } else if child_path.extension() != Some(META_FILE_EXTENSION.as_ref()) {
+                paths.push(child_path);
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns `true` if a batch is running or assets are queued.
+    pub fn is_processing(&self) -> bool {
+        self.batch.is_some() || !self.queued.is_empty()
+    }
+
+    /// Finishes the running batch if all its assets are processed, and starts the next batch.
+    ///
+    /// Returns the outcome for every asset of the finished batch, or `None` if the running batch
+    /// is not done yet. Each asset is only processed if anything it depends on changed since it
+    /// was last processed.
+    pub fn update(&mut self) -> Option<Vec<(PathBuf, Result<ProcessOutcome, ProcessError>)>> {
+        let outcomes = match &self.batch {
+            Some(batch) if batch.iter().all(Task::is_finished) => {
+                let batch = self.batch.take().unwrap();
+                let (mut outcomes, manifest) = self.finish_batch(block_on(join_all(batch)));
+                if let Err(err) = manifest {
+                    outcomes.push((PathBuf::from(PROCESSOR_MANIFEST_FILE), Err(err)));
+                }
+                Some(outcomes)
+            }
+            Some(_) => None,
+            None => Some(Vec::new()),
+        };
+        if self.batch.is_none() && !self.queued.is_empty() {
+            self.start_batch();
+        }
+        outcomes
+    }
+
+    /// Processes the queued assets, blocking until they are all written.
+    ///
+    /// Returns the outcome for every asset processed since the last [`update`](Self::update).
+    pub fn process_queued(&mut self) -> Vec<(PathBuf, Result<ProcessOutcome, ProcessError>)> {
+        let (mut outcomes, manifest) = self.finish_all_batches();
+        if let Err(err) = manifest {
+            outcomes.push((PathBuf::from(PROCESSOR_MANIFEST_FILE), Err(err)));
+        }
+        outcomes
+    }
+
+    /// Runs batches until no asset is queued, and returns their outcomes along with the result of
+    /// the last failed manifest write, if any.
+    fn finish_all_batches(&mut self) -> (ProcessOutcomes, Result<(), ProcessError>) {
+        let mut outcomes = Vec::new();
+        let mut manifest = Ok(());
+        while self.is_processing() {
+            if let Some(batch) = self.batch.take() {
+                let (batch_outcomes, batch_manifest) = self.finish_batch(block_on(join_all(batch)));
+                outcomes.extend(batch_outcomes);
+                if batch_manifest.is_err() {
+                    manifest = batch_manifest;
+                }
+            }
+            if !self.queued.is_empty() {
+                self.start_batch();
+            }
+        }
+        (outcomes, manifest)
+    }
+
+    /// Processes every asset of the source folder, and removes the outputs of source files that
+    /// no longer exist, blocking until they are all written.
+    ///
+    /// Returns the outcome for every source file.
+    pub fn process_all(&mut self) -> Vec<(PathBuf, Result<ProcessOutcome, ProcessError>)> {
+        if let Err(err) = self.queue_all() {
+            return vec![(PathBuf::new(), Err(err.into()))];
+        }
+        self.process_queued()
+    }
+
+    /// Processes the asset at `path`, relative to the source folder, blocking until it is written.
+    ///
+    /// The path of a `.meta` file processes the asset it describes.
+    pub fn process(&mut self, path: &Path) -> Result<ProcessOutcome, ProcessError> {
+        self.queue(path);
+        let path = asset_path_of_meta(path).unwrap_or(path);
+        let (outcomes, manifest) = self.finish_all_batches();
+        manifest?;
+        outcomes
+            .into_iter()
+            .find(|(processed_path, _)| processed_path == path)
+            .map_or(Ok(ProcessOutcome::UpToDate), |(_, outcome)| outcome)
+    }
+
+    fn start_batch(&mut self) {
+        let task_pool = IoTaskPool::get();
+        let batch = std::mem::take(&mut self.queued)
+            .into_iter()
+            .map(|path| {
+                let job = ProcessJob {
+                    processor: self.get_path_processor(&path).cloned(),
+                    previous: self.manifest.assets.get(&path).cloned(),
+                    source_io: self.source_io.clone(),
+                    output_path: self.destination.join(&path),
+                    path,
+                };
+                task_pool.spawn(job.run())
+            })
+            .collect();
+        self.batch = Some(batch);
+    }
+
+    /// Records the results of a batch in the manifest and writes it.
+    fn finish_batch(
+        &mut self,
+        results: Vec<ProcessResult>,
+    ) -> (ProcessOutcomes, Result<(), ProcessError>) {
+        let mut changed = false;
+        for result in &results {
+            match (&result.outcome, &result.info) {
+                (Ok(ProcessOutcome::Removed), _) => {
+                    changed |= self.manifest.assets.remove(&result.path).is_some();
+                }
+                (Ok(_), Some(info)) => {
+                    self.manifest
+                        .assets
+                        .insert(result.path.clone(), info.clone());
+                    changed = true;
+                }
+                _ => {}
+            }
+        }
+
+        let outcomes = results
+            .into_iter()
+            .map(|result| (result.path, result.outcome))
+            .collect();
+        let manifest = if changed {
+            self.write_manifest()
+        } else {
+            Ok(())
+        };
+        (outcomes, manifest)
+    }
+
+    fn write_manifest(&self) -> Result<(), ProcessError> {
+        let manifest = ron::ser::to_string_pretty(&self.manifest, Default::default())?;
+        fs::create_dir_all(&self.destination)?;
+        fs::write(self.destination.join(PROCESSOR_MANIFEST_FILE), manifest)?;
+        Ok(())
+    }
+
+    /// Returns the assets that read the source file at `path` while they were processed.
+    pub fn dependents(&self, path: &Path) -> Vec<PathBuf> {
+        self.manifest
+            .assets
+            .iter()
+            .filter(|(_, info)| {
+                info.dependencies
+                    .iter()
+                    .any(|dependency| dependency.path == path)
+            })
+            .map(|(asset_path, _)| asset_path.clone())
+            .collect()
+    }
+}
+
+/// Everything needed to process one asset on the [`IoTaskPool`].
+struct ProcessJob {
+    path: PathBuf,
+    output_path: PathBuf,
+    source_io: Arc<FileAssetIo>,
+    processor: Option<RegisteredProcessor>,
+    previous: Option<ProcessedAssetInfo>,
+}
+
+impl ProcessJob {
+    async fn run(self) -> ProcessResult {
+        let (outcome, info) = match self.process().await {
+            Ok((outcome, info)) => (Ok(outcome), info),
+            Err(err) => (Err(err), None),
+        };
+        ProcessResult {
+            path: self.path,
+            outcome,
+            info,
+        }
+    }
+
+    async fn process(&self) -> Result<(ProcessOutcome, Option<ProcessedAssetInfo>), ProcessError> {
+        let path = self.path.as_path();
+        let bytes = match self.source_io.load_path(path).await {
+            Ok(bytes) => bytes,
+            Err(AssetIoError::NotFound(_)) => {
+                remove_file_if_exists(&self.output_path)?;
+                remove_file_if_exists(&meta_path(&self.output_path))?;
+                return Ok((ProcessOutcome::Removed, None));
+            }
+            Err(err) => return Err(err.into()),
+        };
+        let meta = match self.source_io.load_path(&meta_path(path)).await {
+            Ok(meta) => Some(meta),
+            Err(AssetIoError::NotFound(_)) => None,
+            Err(err) => return Err(err.into()),
+        };
+
+        let mut hasher = ContentHasher::default();
+        hasher.write_part(&bytes);
+        hasher.write_part(meta.as_deref().unwrap_or_default());
+        if let Some(processor) = &self.processor {
+            hasher.write_part(processor.type_name.as_bytes());
+            hasher.write_part(&processor.processor.version().to_le_bytes());
+        }
+        let hash = hasher.finish();
+        if self.is_up_to_date(hash).await {
+            return Ok((ProcessOutcome::UpToDate, None));
+        }
+
+        let (outcome, output, output_meta, dependencies) = match &self.processor {
+            Some(processor) => {
+                let mut context =
+                    ProcessContext::new(path, meta.as_deref(), self.source_io.as_ref());
+                let processed = processor
+                    .processor
+                    .process(&bytes, &mut context)
+                    .await
+                    .map_err(|error| ProcessError::ProcessorError {
+                        path: path.to_owned(),
+                        error,
+                    })?;
+                let dependencies = context.into_dependencies();
+                // without a loader or settings of its own, the output keeps the source settings
+                let output_meta = processed.meta().map(String::into_bytes).or(meta);
+                (
+                    ProcessOutcome::Processed,
+                    processed.bytes,
+                    output_meta,
+                    dependencies,
+                )
+            }
+            None => (ProcessOutcome::Copied, bytes, meta, Vec::new()),
+        };
+
+        if let Some(parent) = self.output_path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(&self.output_path, output)?;
+        let output_meta_path = meta_path(&self.output_path);
+        match output_meta {
+            Some(meta) => fs::write(output_meta_path, meta)?,
+            None => remove_file_if_exists(&output_meta_path)?,
+        }
+        Ok((outcome, Some(ProcessedAssetInfo { hash, dependencies })))
+    }
+
+    /// Returns `true` if the output of the asset exists, and was created from inputs with the
+    /// same `hash` and unchanged dependencies.
+    async fn is_up_to_date(&self, hash: u64) -> bool {
+        let info = match &self.previous {
+            Some(info) if info.hash == hash && self.output_path.is_file() => info,
+            _ => return false,
+        };
+        for dependency in &info.dependencies {
+            match self.source_io.load_path(&dependency.path).await {
+                Ok(bytes) if crate::content_hash(&bytes) == dependency.hash => {}
+                _ => return false,
+            }
+        }
+        true
+    }
+}
+
+async fn join_all<T>(tasks: Vec<Task<T>>) -> Vec<T> {
+    let mut results = Vec::with_capacity(tasks.len());
+    for task in tasks {
+        results.push(task.await);
+    }
+    results
+}
+
+fn remove_file_if_exists(path: &Path) -> io::Result<()> {
+    match fs::remove_file(path) {
+        Err(err) if err.kind() != io::ErrorKind::NotFound => Err(err),
+        _ => Ok(()),
+    }
+}
+
+fn log_outcomes(outcomes: &[(PathBuf, Result<ProcessOutcome, ProcessError>)]) {
+    for (path, outcome) in outcomes {
+        match outcome {
+            Ok(ProcessOutcome::UpToDate) => {}
+            Ok(outcome) => info!("{:?}: {:?}", path, outcome),
+            Err(err) => warn!("{:?}: {}", path, err),
+        }
+    }
+}
+
+/// Queues every asset of the [`AssetPipeline`] at startup, and holds the loads of the
+/// [`AssetServer`] until they are processed.
+pub fn process_assets_system(mut pipeline: ResMut<AssetPipeline>, asset_server: Res<AssetServer>) {
+    if let Err(err) = pipeline.queue_all() {
+        warn!("failed to read the source assets: {}", err);
+    }
+    asset_server.set_loads_paused(true);
+    pipeline.update();
+}
+
+/// Finishes the batches of the [`AssetPipeline`] and starts the next ones.
+///
+/// Once the assets processed at startup are written, the loads of the [`AssetServer`] start.
+pub fn update_asset_pipeline_system(
+    mut pipeline: ResMut<AssetPipeline>,
+    asset_server: Res<AssetServer>,
+) {
+    if let Some(outcomes) = pipeline.update() {
+        log_outcomes(&outcomes);
+        if pipeline.batch.is_none() {
+            asset_server.set_loads_paused(false);
+        }
+    }
+}
+
+/// Queues the source files changed since the last run, and the assets depending on them.
+#[cfg(feature = "filesystem_watcher")]
+pub fn reprocess_changed_assets_system(mut pipeline: ResMut<AssetPipeline>) {
+    use crossbeam_channel::TryRecvError;
+    use notify::event::{Event, EventKind};
+
+    let mut changed = Vec::new();
+    {
+        let watcher = pipeline.source_io.filesystem_watcher.read();
+        let watcher = match &*watcher {
+            Some(watcher) => watcher,
+            None => return,
+        };
+        loop {
+            let event = match watcher.receiver.try_recv() {
+                Ok(result) => result.unwrap(),
+                Err(TryRecvError::Empty) => break,
+                Err(TryRecvError::Disconnected) => panic!("FilesystemWatcher disconnected."),
+            };
+            if let Event {
+                kind: EventKind::Create(_) | EventKind::Modify(_) | EventKind::Remove(_),
+                paths,
+                ..
+            } = event
+            {
+                for path in paths {
+                    if let Ok(relative_path) = path.strip_prefix(pipeline.source_io.root_path()) {
+                        let relative_path = relative_path.to_owned();
+                        if !changed.contains(&relative_path) {
+                            changed.push(relative_path);
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    for path in changed {
+        if pipeline.source_io.is_dir(&path) {
+            continue;
+        }
+        let dependents = pipeline.dependents(&path);
+        pipeline.queue(&path);
+        for dependent in dependents {
+            pipeline.queue(&dependent);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{BoxedFuture, ProcessedAsset};
+
+    struct UppercaseProcessor;
+
+    impl AssetProcessor for UppercaseProcessor {
+        fn process<'a>(
+            &'a self,
+            bytes: &'a [u8],
+            process_context: &'a mut ProcessContext,
+        ) -> BoxedFuture<'a, Result<ProcessedAsset, anyhow::Error>> {
+            Box::pin(async move {
+                let mut text = String::from_utf8(bytes.to_vec())?.to_uppercase();
+                // `.up` files may include a footer from `footer.txt`
+                if process_context.meta().is_some() {
+                    let footer = process_context.read_source(Path::new("footer.txt")).await?;
+                    text.push_str(std::str::from_utf8(&footer)?);
+                }
+                Ok(ProcessedAsset::new(text.into_bytes()).with_loader("txt"))
+            })
+        }
+
+        fn extensions(&self) -> &[&str] {
+            &["up"]
+        }
+    }
+
+    struct ReverseProcessor;
+
+    impl AssetProcessor for ReverseProcessor {
+        fn process<'a>(
+            &'a self,
+            bytes: &'a [u8],
+            _process_context: &'a mut ProcessContext,
+        ) -> BoxedFuture<'a, Result<ProcessedAsset, anyhow::Error>> {
+            Box::pin(async move { Ok(ProcessedAsset::new(bytes.iter().rev().copied().collect())) })
+        }
+
+        fn extensions(&self) -> &[&str] {
+            &["rev"]
+        }
+    }
+
+    fn setup() -> (tempfile::TempDir, AssetPipeline) {
+        let dir = tempfile::tempdir().unwrap();
+        fs::create_dir_all(dir.path().join("source/nested")).unwrap();
+        fs::write(dir.path().join("source/nested/hello.up"), "hello").unwrap();
+        fs::write(dir.path().join("source/copied.bin"), [1, 2, 3]).unwrap();
+        fs::write(dir.path().join("source/copied.bin.meta"), "()").unwrap();
+        let pipeline = new_pipeline(&dir);
+        (dir, pipeline)
+    }
+
+    fn new_pipeline(dir: &tempfile::TempDir) -> AssetPipeline {
+        IoTaskPool::init(Default::default);
+        let mut pipeline = AssetPipeline::new(
+            FileAssetIo::new(dir.path().join("source"), false),
+            dir.path().join("imported"),
+        );
+        pipeline.add_processor(UppercaseProcessor);
+        pipeline.add_processor(ReverseProcessor);
+        pipeline
+    }
+
+    fn outcome(
+        outcomes: &[(PathBuf, Result<ProcessOutcome, ProcessError>)],
+        path: &str,
+    ) -> ProcessOutcome {
+        let (_, outcome) = outcomes
+            .iter()
+            .find(|(outcome_path, _)| outcome_path == Path::new(path))
+            .unwrap_or_else(|| panic!("{} was not processed", path));
+        *outcome.as_ref().unwrap()
+    }
+
+    #[test]
+    fn processes_and_copies_assets() {
+        let (dir, mut pipeline) = setup();
+        let outcomes = pipeline.process_all();
+        assert_eq!(outcomes.len(), 2);
+        assert_eq!(
+            outcome(&outcomes, "nested/hello.up"),
+            ProcessOutcome::Processed
+        );
+        assert_eq!(outcome(&outcomes, "copied.bin"), ProcessOutcome::Copied);
+
+        let imported = dir.path().join("imported");
+        assert_eq!(
+            fs::read_to_string(imported.join("nested/hello.up")).unwrap(),
+            "HELLO"
+        );
+        assert!(fs::read_to_string(imported.join("nested/hello.up.meta"))
+            .unwrap()
+            .contains("loader: Some(\"txt\")"));
+        assert_eq!(fs::read(imported.join("copied.bin")).unwrap(), [1, 2, 3]);
+        assert_eq!(
+            fs::read_to_string(imported.join("copied.bin.meta")).unwrap(),
+            "()"
+        );
+        assert!(imported.join(PROCESSOR_MANIFEST_FILE).is_file());
+    }
+
+    #[test]
+    fn reprocesses_incrementally() {
+        let (dir, mut pipeline) = setup();
+        pipeline.process_all();
+
+        // a new pipeline reads the manifest of the previous run
+        let mut pipeline = new_pipeline(&dir);
+        let outcomes = pipeline.process_all();
+        assert!(outcomes
+            .iter()
+            .all(|(_, outcome)| *outcome.as_ref().unwrap() == ProcessOutcome::UpToDate));
+
+        fs::write(dir.path().join("source/nested/hello.up"), "bye").unwrap();
+        let outcomes = pipeline.process_all();
+        assert_eq!(
+            outcome(&outcomes, "nested/hello.up"),
+            ProcessOutcome::Processed
+        );
+        assert_eq!(outcome(&outcomes, "copied.bin"), ProcessOutcome::UpToDate);
+
+        // changing the `.meta` file reprocesses the asset, which now reads a dependency
+        fs::write(dir.path().join("source/footer.txt"), "!").unwrap();
+        fs::write(dir.path().join("source/nested/hello.up.meta"), "()").unwrap();
+        assert_eq!(
+            pipeline.process(Path::new("nested/hello.up.meta")).unwrap(),
+            ProcessOutcome::Processed
+        );
+        assert_eq!(
+            pipeline.dependents(Path::new("footer.txt")),
+            vec![PathBuf::from("nested/hello.up")]
+        );
+        let imported = dir.path().join("imported/nested/hello.up");
+        assert_eq!(fs::read_to_string(&imported).unwrap(), "BYE!");
+
+        fs::write(dir.path().join("source/footer.txt"), "?").unwrap();
+        assert_eq!(
+            pipeline.process(Path::new("nested/hello.up")).unwrap(),
+            ProcessOutcome::Processed
+        );
+        assert_eq!(fs::read_to_string(&imported).unwrap(), "BYE?");
+    }
+
+    #[test]
+    fn keeps_source_settings_without_processed_meta() {
+        let (dir, mut pipeline) = setup();
+        fs::write(dir.path().join("source/bytes.rev"), [1, 2, 3]).unwrap();
+        fs::write(
+            dir.path().join("source/bytes.rev.meta"),
+            "(settings: (scale: 2))",
+        )
+        .unwrap();
+        pipeline.process_all();
+
+        let imported = dir.path().join("imported");
+        assert_eq!(fs::read(imported.join("bytes.rev")).unwrap(), [3, 2, 1]);
+        assert_eq!(
+            fs::read_to_string(imported.join("bytes.rev.meta")).unwrap(),
+            "(settings: (scale: 2))"
+        );
+    }
+
+    #[test]
+    fn processes_batches_in_the_background() {
+        let (dir, mut pipeline) = setup();
+        pipeline.queue_all().unwrap();
+        let mut outcomes = Vec::new();
+        for _ in 0..1000 {
+            match pipeline.update() {
+                Some(batch) if !pipeline.is_processing() => {
+                    outcomes.extend(batch);
+                    break;
+                }
+                Some(batch) => outcomes.extend(batch),
+                None => {}
+            }
+            std::thread::sleep(std::time::Duration::from_millis(1));
+        }
+        assert!(!pipeline.is_processing());
+        assert_eq!(outcomes.len(), 2);
+        assert_eq!(
+            outcome(&outcomes, "nested/hello.up"),
+            ProcessOutcome::Processed
+        );
+
+        // the manifest is written once the batch is done
+        let manifest =
+            fs::read_to_string(dir.path().join("imported").join(PROCESSOR_MANIFEST_FILE)).unwrap();
+        assert!(manifest.contains("nested/hello.up"));
+        assert!(manifest.contains("copied.bin"));
+    }
+
+    #[test]
+    fn removes_deleted_assets() {
+        let (dir, mut pipeline) = setup();
+        pipeline.process_all();
+
+        fs::remove_file(dir.path().join("source/copied.bin")).unwrap();
+        let outcomes = pipeline.process_all();
+        assert_eq!(outcome(&outcomes, "copied.bin"), ProcessOutcome::Removed);
+        assert!(!dir.path().join("imported/copied.bin").exists());
+        assert!(!dir.path().join("imported/copied.bin.meta").exists());
+    }
+}
@@ -0,0 +1,42 @@
+use crate::{Asset, AssetDynamic, AssetServer, Assets};
+use anyhow::Error;
+use bevy_ecs::system::Res;
+
+/// Serializes assets back into the bytes of a file, the inverse of an
+/// [`AssetLoader`](crate::AssetLoader).
+///
+/// Savers are selected by the type of the asset and the extension of the path passed to
+/// [`AssetServer::save`].
+pub trait AssetSaver: Send + Sync + 'static {
+    /// The type of the assets this saver serializes.
+    type Asset: Asset;
+
+    /// Serializes `asset` into the contents of a file.
+    ///
+    /// This runs in [`AssetStage::AssetEvents`](crate::AssetStage::AssetEvents), while the asset is
+    /// borrowed from its [`Assets`] storage. The returned bytes are then written in the
+    /// background.
+    fn save(&self, asset: &Self::Asset) -> Result<Vec<u8>, Error>;
+
+    /// Returns a list of extensions supported by this saver, without the preceding dot.
+    fn extensions(&self) -> &[&str];
+}
+
+/// An [`AssetSaver`] with its asset type erased, so savers of every type can be stored together.
+pub(crate) trait ErasedAssetSaver: Send + Sync + 'static {
+    fn save(&self, asset: &dyn AssetDynamic) -> Result<Vec<u8>, Error>;
+}
+
+impl<T: AssetSaver> ErasedAssetSaver for T {
+    fn save(&self, asset: &dyn AssetDynamic) -> Result<Vec<u8>, Error> {
+        let asset = asset
+            .downcast_ref::<T::Asset>()
+            .expect("the asset type of a save request should match its saver");
+        AssetSaver::save(self, asset)
+    }
+}
+
+/// A system that serializes and writes the assets of type `T` queued with [`AssetServer::save`].
+pub fn save_assets_system<T: Asset>(asset_server: Res<AssetServer>, assets: Res<Assets<T>>) {
+    asset_server.save_assets(&assets);
+}
@@ -0,0 +1,249 @@
+use downcast_rs::{impl_downcast, Downcast};
+use serde::{de::DeserializeOwned, Deserialize};
+use std::{
+    fmt::Debug,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+use thiserror::Error;
+
+/// The extension of the sidecar file holding the settings of an asset, without the preceding dot.
+///
+/// The settings of `textures/grass.png` are read from `textures/grass.png.meta`.
+pub const META_FILE_EXTENSION: &str = "meta";
+
+/// Settings that configure how an [`AssetLoader`](crate::AssetLoader) loads a single asset.
+///
+/// This is implemented for every `Clone + Debug` type, so loaders can use any serde-able struct
+/// as their settings. See [`AssetLoader::settings_deserializer`](crate::AssetLoader::settings_deserializer).
+pub trait LoaderSettings: Downcast + Debug + Send + Sync + 'static {
+    /// Clones the settings into a new box.
+    fn clone_settings(&self) -> Box<dyn LoaderSettings>;
+}
+impl_downcast!(LoaderSettings);
+
+impl<T: Clone + Debug + Send + Sync + 'static> LoaderSettings for T {
+    fn clone_settings(&self) -> Box<dyn LoaderSettings> {
+        Box::new(self.clone())
+    }
+}
+
+/// Deserializes the settings of a loader from the contents of a `.meta` file.
+pub type SettingsDeserializer = fn(&[u8]) -> Result<Box<dyn LoaderSettings>, AssetMetaError>;
+
+/// Errors that occur while reading the `.meta` file of an asset.
+#[derive(Error, Debug)]
+pub enum AssetMetaError {
+    /// The `.meta` file is not valid RON or does not match the settings of the loader.
+    #[error("invalid .meta file: {0}")]
+    Deserialize(#[from] ron::error::SpannedError),
+}
+
+/// The contents of a `.meta` file.
+///
+/// Fields unknown to a loader are ignored, so that other tools can store their own data in the
+/// same file.
+#[derive(Deserialize)]
+struct AssetMetaFile<S> {
+    #[serde(default)]
+    settings: S,
+}
+
+/// The loader named by a `.meta` file, see [`ProcessedAsset::with_loader`](crate::ProcessedAsset::with_loader).
+#[derive(Deserialize)]
+struct AssetMetaLoader {
+    #[serde(default)]
+    loader: Option<String>,
+}
+
+/// Returns the extension of the loader named by the `loader` field of a `.meta` file, if any.
+///
+/// Processed assets use this to be loaded by another loader than the one of their own extension.
+pub(crate) fn deserialize_meta_loader(meta: &[u8]) -> Result<Option<String>, AssetMetaError> {
+    let meta: AssetMetaLoader = ron::de::from_bytes(meta)?;
+    Ok(meta.loader)
+}
+
+/// Deserializes the loader settings of type `S` from the contents of a `.meta` file.
+///
+/// A `.meta` file is a RON struct with a `settings` field:
+///
+/// ```ron
+/// (
+///     settings: (
+///         sampler: Nearest,
+///     ),
+/// )
+/// ```
+///
+/// A missing `settings` field results in `S::default()`. Use this function as the
+/// [`SettingsDeserializer`] of a loader:
+///
+/// ```
+/// # use bevy_asset::{deserialize_settings, AssetLoader, LoadContext, SettingsDeserializer};
+/// # use bevy_utils::BoxedFuture;
+/// # use serde::Deserialize;
+/// #[derive(Clone, Debug, Default, Deserialize)]
+/// struct TextSettings {
+///     uppercase: bool,
+/// }
+///
+/// struct TextLoader;
+///
+/// impl AssetLoader for TextLoader {
+///     # fn load<'a>(
+///     #     &'a self,
+///     #     bytes: &'a [u8],
+///     #     load_context: &'a mut LoadContext,
+///     # ) -> BoxedFuture<'a, Result<(), bevy_asset::Error>> {
+///     #     Box::pin(async move { Ok(()) })
+///     # }
+///     # fn extensions(&self) -> &[&str] {
+///     #     &["txt"]
+///     # }
+///     // ...
+///     fn settings_deserializer(&self) -> Option<SettingsDeserializer> {
+///         Some(deserialize_settings::<TextSettings>)
+///     }
+/// }
+/// ```
+pub fn deserialize_settings<S: LoaderSettings + Default + DeserializeOwned>(
+    meta: &[u8],
+) -> Result<Box<dyn LoaderSettings>, AssetMetaError> {
+    let meta: AssetMetaFile<S> = ron::de::from_bytes(meta)?;
+    Ok(Box::new(meta.settings))
+}
+
+/// Returns the path of the `.meta` file of the asset at `path`.
+pub fn meta_path(path: &Path) -> PathBuf {
+    let mut meta_path = path.as_os_str().to_owned();
+    meta_path.push(".");
+    meta_path.push(META_FILE_EXTENSION);
+    PathBuf::from(meta_path)
+}
+
+/// Returns the path of the asset described by the `.meta` file at `path`, or `None` if `path` is
+/// not a `.meta` file.
+pub fn asset_path_of_meta(path: &Path) -> Option<&Path> {
+    let asset_path = path
+        .to_str()?
+        .strip_suffix(META_FILE_EXTENSION)?
+        .strip_suffix('.')?;
+    (!asset_path.is_empty()).then_some(Path::new(asset_path))
+}
+
+/// Applies a per-call override to the settings of a load, see
+/// [`AssetServer::load_with_settings`](crate::AssetServer::load_with_settings).
+#[derive(Clone)]
+pub(crate) struct SettingsOverride(Arc<ApplySettings>);
+
+type ApplySettings =
+    dyn Fn(Option<Box<dyn LoaderSettings>>) -> Box<dyn LoaderSettings> + Send + Sync;
+
+impl SettingsOverride {
+    /// Creates an override that runs `apply` on the settings of type `S` read from the `.meta`
+    /// file, or on `S::default()` if there are none.
+    pub(crate) fn new<S: LoaderSettings + Default>(
+        apply: impl Fn(&mut S) + Send + Sync + 'static,
+    ) -> Self {
+        Self(Arc::new(move |settings| {
+            let mut settings = settings
+                .and_then(|settings| settings.downcast::<S>().ok())
+                .map_or_else(S::default, |settings| *settings);
+            apply(&mut settings);
+            Box::new(settings)
+        }))
+    }
+
+    /// Applies the override to the settings read from the `.meta` file, if any.
+    pub(crate) fn apply(
+        &self,
+        settings: Option<Box<dyn LoaderSettings>>,
+    ) -> Box<dyn LoaderSettings> {
+        (self.0)(settings)
+    }
+}
+
+impl Debug for SettingsOverride {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SettingsOverride").finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Clone, Debug, Default, PartialEq, Deserialize)]
+    struct TestSettings {
+        nearest: bool,
+        #[serde(default)]
+        scale: f32,
+    }
+
+    #[test]
+    fn deserializes_settings() {
+        let settings =
+            deserialize_settings::<TestSettings>(b"(settings: (nearest: true), other_tool: 3)")
+                .unwrap();
+        assert_eq!(
+            settings.downcast_ref::<TestSettings>(),
+            Some(&TestSettings {
+                nearest: true,
+                scale: 0.0
+            })
+        );
+
+        let settings = deserialize_settings::<TestSettings>(b"()").unwrap();
+        assert_eq!(
+            settings.downcast_ref::<TestSettings>(),
+            Some(&TestSettings::default())
+        );
+
+        assert!(deserialize_settings::<TestSettings>(b"(settings: (nearest: 1))").is_err());
+    }
+
+    #[test]
+    fn deserializes_meta_loader() {
+        let meta = b"(loader: Some(\"ktx2\"), settings: (nearest: true))";
+        assert_eq!(
+            deserialize_meta_loader(meta).unwrap().as_deref(),
+            Some("ktx2")
+        );
+        assert!(deserialize_settings::<TestSettings>(meta).is_ok());
+        assert_eq!(deserialize_meta_loader(b"()").unwrap(), None);
+    }
+
+    #[test]
+    fn meta_paths() {
+        let path = Path::new("textures/grass.png");
+        let meta = meta_path(path);
+        assert_eq!(meta, Path::new("textures/grass.png.meta"));
+        assert_eq!(asset_path_of_meta(&meta), Some(path));
+        assert_eq!(
+            asset_path_of_meta(Path::new("grass.png.meta")),
+            Some(Path::new("grass.png"))
+        );
+        assert_eq!(asset_path_of_meta(path), None);
+    }
+
+    #[test]
+    fn overrides_settings() {
+        let settings_override =
+            SettingsOverride::new(|settings: &mut TestSettings| settings.scale = 2.0);
+        let settings = settings_override.apply(Some(Box::new(TestSettings {
+            nearest: true,
+            scale: 1.0,
+        })));
+        assert_eq!(
+            settings.downcast_ref::<TestSettings>(),
+            Some(&TestSettings {
+                nearest: true,
+                scale: 2.0
+            })
+        );
+
+        let settings = settings_override.apply(None);
+        assert_eq!(settings.downcast_ref::<TestSettings>().unwrap().scale, 2.0);
+    }
+}
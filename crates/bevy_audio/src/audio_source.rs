@@ -1,8 +1,13 @@
 use anyhow::Result;
-use bevy_asset::{AssetLoader, LoadContext, LoadedAsset};
+use bevy_asset::{
+    deserialize_settings, diagnostic::AssetMemoryUsage, AssetLoader, LoadContext, LoadedAsset,
+    SettingsDeserializer,
+};
 use bevy_reflect::TypeUuid;
 use bevy_utils::BoxedFuture;
-use std::{io::Cursor, sync::Arc};
+use rodio::{decoder::DecoderError, Source};
+use serde::{Deserialize, Serialize};
+use std::{io::Cursor, sync::Arc, time::Duration};
 
 /// A source of audio data
 #[derive(Debug, Clone, TypeUuid)]
@@ -10,6 +15,35 @@ use std::{io::Cursor, sync::Arc};
 pub struct AudioSource {
     /// Raw data of the audio source
     pub bytes: Arc<[u8]>,
+    /// Samples decoded when the audio source was loaded, played instead of decoding `bytes`.
+    ///
+    /// See [`AudioLoaderSettings::streaming`].
+    pub decoded: Option<DecodedAudio>,
+}
+
+/// Audio samples decoded ahead of playback.
+#[derive(Debug, Clone)]
+pub struct DecodedAudio {
+    /// The number of channels.
+    pub channels: u16,
+    /// The number of samples per second and channel.
+    pub sample_rate: u32,
+    /// The samples of all channels, interleaved.
+    pub samples: Arc<[i16]>,
+}
+
+impl DecodedAudio {
+    /// Decodes all samples of an audio file.
+    ///
+    /// The channel count and sample rate are those at the start of the file.
+    pub fn decode(bytes: Arc<[u8]>) -> Result<Self, DecoderError> {
+        let decoder = rodio::Decoder::new(Cursor::new(bytes))?;
+        Ok(Self {
+            channels: decoder.channels(),
+            sample_rate: decoder.sample_rate(),
+            samples: decoder.collect(),
+        })
+    }
 }
 
 impl AsRef<[u8]> for AudioSource {
@@ -18,6 +52,15 @@ impl AsRef<[u8]> for AudioSource {
     }
 }
 
+impl AssetMemoryUsage for AudioSource {
+    fn heap_size_bytes(&self) -> usize {
+        let decoded = self.decoded.as_ref().map_or(0, |decoded| {
+            decoded.samples.len() * std::mem::size_of::<i16>()
+        });
+        self.bytes.len() + decoded
+    }
+}
+
 /// Loads files as [`AudioSource`] [`Assets`](bevy_asset::Assets)
 ///
 /// This asset loader supports different audio formats based on the enable Bevy features.
@@ -29,11 +72,39 @@ impl AsRef<[u8]> for AudioSource {
 #[derive(Default)]
 pub struct AudioLoader;
 
+/// Settings of the [`AudioLoader`], read from the `.meta` file of an audio file.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AudioLoaderSettings {
+    /// Whether the audio is decoded from the data of the file while it plays.
+    ///
+    /// Otherwise it is decoded once when loaded into [`AudioSource::decoded`], which takes more
+    /// memory but saves the decoding work on each playback.
+    pub streaming: bool,
+}
+
+impl Default for AudioLoaderSettings {
+    fn default() -> Self {
+        Self { streaming: true }
+    }
+}
+
 impl AssetLoader for AudioLoader {
     fn load(&self, bytes: &[u8], load_context: &mut LoadContext) -> BoxedFuture<Result<()>> {
-        load_context.set_default_asset(LoadedAsset::new(AudioSource {
-            bytes: bytes.into(),
-        }));
+        let settings = load_context
+            .settings::<AudioLoaderSettings>()
+            .cloned()
+            .unwrap_or_default();
+        let bytes: Arc<[u8]> = bytes.into();
+        let decoded = if settings.streaming {
+            None
+        } else {
+            match DecodedAudio::decode(bytes.clone()) {
+                Ok(decoded) => Some(decoded),
+                Err(err) => return Box::pin(async move { Err(err.into()) }),
+            }
+        };
+        load_context.set_default_asset(LoadedAsset::new(AudioSource { bytes, decoded }));
         Box::pin(async move { Ok(()) })
     }
 
@@ -53,6 +124,10 @@ impl AssetLoader for AudioLoader {
             "spx",
         ]
     }
+
+    fn settings_deserializer(&self) -> Option<SettingsDeserializer> {
+        Some(deserialize_settings::<AudioLoaderSettings>)
+    }
 }
 
 /// A type implementing this trait can be decoded as a rodio source
@@ -66,11 +141,85 @@ pub trait Decodable: Send + Sync + 'static {
     fn decoder(&self) -> Self::Decoder;
 }
 
+/// The [`Decodable::Decoder`] of an [`AudioSource`].
+///
+/// Decodes the data of the source while it plays, or plays its [decoded](AudioSource::decoded)
+/// samples.
+pub struct AudioSourceDecoder(AudioSourceDecoderInner);
+
+enum AudioSourceDecoderInner {
+    Streaming(Box<rodio::Decoder<Cursor<AudioSource>>>),
+    Decoded {
+        audio: DecodedAudio,
+        position: usize,
+    },
+}
+
+impl Iterator for AudioSourceDecoder {
+    type Item = i16;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match &mut self.0 {
+            AudioSourceDecoderInner::Streaming(decoder) => decoder.next(),
+            AudioSourceDecoderInner::Decoded { audio, position } => {
+                let sample = audio.samples.get(*position).copied();
+                *position += 1;
+                sample
+            }
+        }
+    }
+}
+
+impl Source for AudioSourceDecoder {
+    fn current_frame_len(&self) -> Option<usize> {
+        match &self.0 {
+            AudioSourceDecoderInner::Streaming(decoder) => decoder.current_frame_len(),
+            AudioSourceDecoderInner::Decoded { audio, position } => {
+                Some(audio.samples.len().saturating_sub(*position))
+            }
+        }
+    }
+
+    fn channels(&self) -> u16 {
+        match &self.0 {
+            AudioSourceDecoderInner::Streaming(decoder) => decoder.channels(),
+            AudioSourceDecoderInner::Decoded { audio, .. } => audio.channels,
+        }
+    }
+
+    fn sample_rate(&self) -> u32 {
+        match &self.0 {
+            AudioSourceDecoderInner::Streaming(decoder) => decoder.sample_rate(),
+            AudioSourceDecoderInner::Decoded { audio, .. } => audio.sample_rate,
+        }
+    }
+
+    fn total_duration(&self) -> Option<Duration> {
+        match &self.0 {
+            AudioSourceDecoderInner::Streaming(decoder) => decoder.total_duration(),
+            AudioSourceDecoderInner::Decoded { audio, .. } => {
+                let frames = audio.samples.len() as u64 / u64::from(audio.channels.max(1));
+                Some(Duration::from_secs_f64(
+                    frames as f64 / f64::from(audio.sample_rate.max(1)),
+                ))
+            }
+        }
+    }
+}
+
 impl Decodable for AudioSource {
-    type Decoder = rodio::Decoder<Cursor<AudioSource>>;
-    type DecoderItem = <rodio::Decoder<Cursor<AudioSource>> as Iterator>::Item;
+    type Decoder = AudioSourceDecoder;
+    type DecoderItem = i16;
 
     fn decoder(&self) -> Self::Decoder {
-        rodio::Decoder::new(Cursor::new(self.clone())).unwrap()
+        AudioSourceDecoder(match &self.decoded {
+            Some(decoded) => AudioSourceDecoderInner::Decoded {
+                audio: decoded.clone(),
+                position: 0,
+            },
+            None => AudioSourceDecoderInner::Streaming(Box::new(
+                rodio::Decoder::new(Cursor::new(self.clone())).unwrap(),
+            )),
+        })
     }
 }
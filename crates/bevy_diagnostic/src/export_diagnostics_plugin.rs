@@ -0,0 +1,544 @@
+use super::{Diagnostic, DiagnosticId, Diagnostics};
+use bevy_app::prelude::*;
+use bevy_ecs::system::{Res, ResMut, Resource};
+use bevy_log::warn;
+use bevy_time::{Time, Timer, TimerMode};
+use bevy_utils::Duration;
+use std::{
+    fmt::Write as _,
+    fs::File,
+    io::{self, BufWriter, Write},
+    path::PathBuf,
+};
+
+#[cfg(not(target_arch = "wasm32"))]
+use std::{
+    net::{IpAddr, Ipv4Addr, SocketAddr, TcpListener, TcpStream},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Mutex,
+    },
+    thread::JoinHandle,
+};
+
+/// An App Plugin that periodically publishes every enabled [`Diagnostic`] to machine-readable sinks.
+///
+/// The built-in sinks are configured through the plugin fields. Custom sinks can be added to the
+/// [`DiagnosticSinks`] resource at any time.
+pub struct ExportDiagnosticsPlugin {
+    pub wait_duration: Duration,
+    pub filter: Option<Vec<DiagnosticId>>,
+    /// Appends one [`JsonLinesSink`] line per publish to this file.
+    pub json_lines_path: Option<PathBuf>,
+    /// Appends [`CsvSink`] rows to this file.
+    pub csv_path: Option<PathBuf>,
+    /// Serves the latest snapshot through an [`HttpSink`] bound to this port.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub http_port: Option<u16>,
+    /// The address the [`HttpSink`] is bound to, `127.0.0.1` by default.
+    ///
+    /// The endpoint has no authentication, so it should only be exposed on trusted networks.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub http_ip: IpAddr,
+}
+
+/// State used by the [`ExportDiagnosticsPlugin`]
+#[derive(Resource)]
+struct ExportDiagnosticsState {
+    timer: Timer,
+    filter: Option<Vec<DiagnosticId>>,
+}
+
+impl Default for ExportDiagnosticsPlugin {
+    fn default() -> Self {
+        ExportDiagnosticsPlugin {
+            wait_duration: Duration::from_secs(1),
+            filter: None,
+            json_lines_path: None,
+            csv_path: None,
+            #[cfg(not(target_arch = "wasm32"))]
+            http_port: None,
+            #[cfg(not(target_arch = "wasm32"))]
+            http_ip: IpAddr::V4(Ipv4Addr::LOCALHOST),
+        }
+    }
+}
+
+impl Plugin for ExportDiagnosticsPlugin {
+    fn build(&self, app: &mut App) {
+        let mut sinks = DiagnosticSinks::default();
+        if let Some(path) = &self.json_lines_path {
+            match JsonLinesSink::create(path) {
+                Ok(sink) => sinks.add(sink),
+                Err(err) => warn!("Failed to create diagnostic file {:?}: {}", path, err),
+            }
+        }
+        if let Some(path) = &self.csv_path {
+            match CsvSink::create(path) {
+                Ok(sink) => sinks.add(sink),
+                Err(err) => warn!("Failed to create diagnostic file {:?}: {}", path, err),
+            }
+        }
+        #[cfg(not(target_arch = "wasm32"))]
+        if let Some(port) = self.http_port {
+            let address = SocketAddr::new(self.http_ip, port);
+            match HttpSink::bind(address) {
+                Ok(sink) => sinks.add(sink),
+                Err(err) => warn!("Failed to serve diagnostics on {}: {}", address, err),
+            }
+        }
+
+        app.insert_resource(sinks)
+            .insert_resource(ExportDiagnosticsState {
+                timer: Timer::new(self.wait_duration, TimerMode::Repeating),
+                filter: self.filter.clone(),
+            })
+            .add_system_to_stage(CoreStage::PostUpdate, Self::export_diagnostics_system);
+    }
+}
+
+impl ExportDiagnosticsPlugin {
+    pub fn filtered(filter: Vec<DiagnosticId>) -> Self {
+        ExportDiagnosticsPlugin {
+            filter: Some(filter),
+            ..Default::default()
+        }
+    }
+
+    fn export_diagnostics_system(
+        mut state: ResMut<ExportDiagnosticsState>,
+        mut sinks: ResMut<DiagnosticSinks>,
+        time: Res<Time>,
+        diagnostics: Res<Diagnostics>,
+    ) {
+        if !state.timer.tick(time.raw_delta()).finished() {
+            return;
+        }
+
+        let snapshots: Vec<DiagnosticSnapshot> = if let Some(ref filter) = state.filter {
+            filter
+                .iter()
+                .filter_map(|id| diagnostics.get(*id))
+                .filter(|diagnostic| diagnostic.is_enabled)
+                .map(DiagnosticSnapshot::from)
+                .collect()
+        } else {
+            diagnostics
+                .iter()
+                .filter(|diagnostic| diagnostic.is_enabled)
+                .map(DiagnosticSnapshot::from)
+                .collect()
+        };
+        sinks.publish(time.raw_elapsed(), &snapshots);
+    }
+}
+
+/// The state of a single [`Diagnostic`] at the time it is published to a [`DiagnosticSink`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct DiagnosticSnapshot {
+    pub id: DiagnosticId,
+    pub name: String,
+    pub suffix: String,
+    pub value: Option<f64>,
+    pub average: Option<f64>,
+    pub smoothed: Option<f64>,
+}
+
+impl From<&Diagnostic> for DiagnosticSnapshot {
+    fn from(diagnostic: &Diagnostic) -> Self {
+        DiagnosticSnapshot {
+            id: diagnostic.id,
+            name: diagnostic.name.to_string(),
+            suffix: diagnostic.suffix.to_string(),
+            value: diagnostic.value(),
+            average: diagnostic.average(),
+            smoothed: diagnostic.smoothed(),
+        }
+    }
+}
+
+/// A destination that [`DiagnosticSnapshot`]s are published to by the [`ExportDiagnosticsPlugin`].
+pub trait DiagnosticSink: Send + Sync + 'static {
+    /// Publishes the snapshots of every exported diagnostic, taken `elapsed` after the app started.
+    fn publish(&mut self, elapsed: Duration, snapshots: &[DiagnosticSnapshot]) -> io::Result<()>;
+}
+
+/// The [`DiagnosticSink`]s the [`ExportDiagnosticsPlugin`] publishes to.
+#[derive(Resource, Default)]
+pub struct DiagnosticSinks {
+    sinks: Vec<Box<dyn DiagnosticSink>>,
+}
+
+impl DiagnosticSinks {
+    /// Adds a new [`DiagnosticSink`].
+    pub fn add(&mut self, sink: impl DiagnosticSink) {
+        self.sinks.push(Box::new(sink));
+    }
+
+    /// Publishes `snapshots` to every sink, logging sinks that fail.
+    pub fn publish(&mut self, elapsed: Duration, snapshots: &[DiagnosticSnapshot]) {
+        for sink in &mut self.sinks {
+            if let Err(err) = sink.publish(elapsed, snapshots) {
+                warn!("Failed to export diagnostics: {}", err);
+            }
+        }
+    }
+}
+
+/// Writes one JSON object per publish, followed by a newline.
+///
+/// Each line has the form
+/// `{"elapsed":12.5,"diagnostics":[{"id":"…","name":"fps","suffix":"","value":60.0,"average":59.8,"smoothed":59.9}]}`,
+/// where `elapsed` is in seconds since the app started and missing values are `null`.
+pub struct JsonLinesSink {
+    writer: Box<dyn Write + Send + Sync>,
+}
+
+impl JsonLinesSink {
+    /// Creates a sink writing to `writer`.
+    pub fn new(writer: impl Write + Send + Sync + 'static) -> Self {
+        JsonLinesSink {
+            writer: Box::new(writer),
+        }
+    }
+
+    /// Creates a sink appending to the file at `path`, creating it if needed.
+    pub fn create(path: impl Into<PathBuf>) -> io::Result<Self> {
+        let file = File::options()
+            .create(true)
+            .append(true)
+            .open(path.into())?;
+        Ok(Self::new(BufWriter::new(file)))
+    }
+}
+
+impl DiagnosticSink for JsonLinesSink {
+    fn publish(&mut self, elapsed: Duration, snapshots: &[DiagnosticSnapshot]) -> io::Result<()> {
+        let mut line = to_json(elapsed, snapshots);
+        line.push('\n');
+        self.writer.write_all(line.as_bytes())?;
+        self.writer.flush()
+    }
+}
+
+/// Writes one CSV row per diagnostic and publish.
+///
+/// The columns are `elapsed,id,name,suffix,value,average,smoothed`, where `elapsed` is in seconds
+/// since the app started and missing values are left empty. A header row is written when
+/// the sink is created.
+pub struct CsvSink {
+    writer: Box<dyn Write + Send + Sync>,
+}
+
+impl CsvSink {
+    /// Creates a sink writing to `writer`, starting with a header row.
+    pub fn new(writer: impl Write + Send + Sync + 'static) -> io::Result<Self> {
+        let mut writer: Box<dyn Write + Send + Sync> = Box::new(writer);
+        writeln!(writer, "elapsed,id,name,suffix,value,average,smoothed")?;
+        writer.flush()?;
+        Ok(CsvSink { writer })
+    }
+
+    /// Creates a sink truncating the file at `path`, creating it if needed.
+    pub fn create(path: impl Into<PathBuf>) -> io::Result<Self> {
+        Self::new(BufWriter::new(File::create(path.into())?))
+    }
+}
+
+impl DiagnosticSink for CsvSink {
+    fn publish(&mut self, elapsed: Duration, snapshots: &[DiagnosticSnapshot]) -> io::Result<()> {
+        let elapsed = elapsed.as_secs_f64();
+        for snapshot in snapshots {
+            writeln!(
+                self.writer,
+                "{},{},\"{}\",\"{}\",{},{},{}",
+                elapsed,
+                snapshot.id.0,
+                snapshot.name.replace('"', "\"\""),
+                snapshot.suffix.replace('"', "\"\""),
+                csv_value(snapshot.value),
+                csv_value(snapshot.average),
+                csv_value(snapshot.smoothed),
+            )?;
+        }
+        self.writer.flush()
+    }
+}
+
+/// Serves the latest published snapshot as JSON over HTTP.
+///
+/// A background thread answers `GET /` and `GET /diagnostics` with the same JSON object a
+/// [`JsonLinesSink`] would write, so that external tools can scrape a running app. Requests are
+/// answered one at a time, and clients which don't send or read within [`Self::TIMEOUT`] are
+/// dropped. The thread stops when the sink is dropped.
+#[cfg(not(target_arch = "wasm32"))]
+pub struct HttpSink {
+    latest: Arc<Mutex<String>>,
+    local_addr: SocketAddr,
+    shutdown: Arc<AtomicBool>,
+    thread: Option<JoinHandle<()>>,
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl HttpSink {
+    /// How long a client may take to send its request or read the response.
+    pub const TIMEOUT: Duration = Duration::from_secs(5);
+
+    /// How often the background thread checks for new connections and for shutdown.
+    const POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+    /// Binds the endpoint to `address` and starts serving it on a background thread.
+    pub fn bind(address: impl std::net::ToSocketAddrs) -> io::Result<Self> {
+        let listener = TcpListener::bind(address)?;
+        let local_addr = listener.local_addr()?;
+        // accepting without blocking lets the thread notice when the sink is dropped
+        listener.set_nonblocking(true)?;
+        let latest = Arc::new(Mutex::new(to_json(Duration::ZERO, &[])));
+        let shutdown = Arc::new(AtomicBool::new(false));
+
+        let served = latest.clone();
+        let stopped = shutdown.clone();
+        let thread = std::thread::Builder::new()
+            .name("diagnostics http".to_string())
+            .spawn(move || {
+                while !stopped.load(Ordering::Relaxed) {
+                    match listener.accept() {
+                        Ok((stream, _)) => {
+                            if let Err(err) = Self::respond(stream, &served) {
+                                warn!("Failed to answer diagnostics request: {}", err);
+                            }
+                        }
+                        Err(err) if err.kind() == io::ErrorKind::WouldBlock => {
+                            std::thread::sleep(Self::POLL_INTERVAL);
+                        }
+                        Err(err) => warn!("Failed to accept diagnostics request: {}", err),
+                    }
+                }
+            })?;
+
+        Ok(HttpSink {
+            latest,
+            local_addr,
+            shutdown,
+            thread: Some(thread),
+        })
+    }
+
+    /// Returns the address the endpoint is bound to.
+    pub fn local_addr(&self) -> SocketAddr {
+        self.local_addr
+    }
+
+    fn respond(mut stream: TcpStream, latest: &Mutex<String>) -> io::Result<()> {
+        stream.set_nonblocking(false)?;
+        stream.set_read_timeout(Some(Self::TIMEOUT))?;
+        stream.set_write_timeout(Some(Self::TIMEOUT))?;
+
+        let mut request_line = String::new();
+        io::BufRead::read_line(&mut io::BufReader::new(&mut stream), &mut request_line)?;
+        let mut parts = request_line.split_whitespace();
+        let (status, content_type, body) = match (parts.next(), parts.next()) {
+            (Some("GET"), Some("/" | "/diagnostics")) => {
+                let body = latest.lock().unwrap_or_else(|err| err.into_inner()).clone();
+                ("200 OK", "application/json", body)
+            }
+            (Some("GET"), _) => ("404 Not Found", "text/plain", "not found".to_string()),
+            _ => (
+                "405 Method Not Allowed",
+                "text/plain",
+                "method not allowed".to_string(),
+            ),
+        };
+        write!(
+            stream,
+            "HTTP/1.1 {status}\r\nContent-Type: {content_type}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+            body.len()
+        )?;
+        stream.flush()
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl Drop for HttpSink {
+    fn drop(&mut self) {
+        self.shutdown.store(true, Ordering::Relaxed);
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl DiagnosticSink for HttpSink {
+    fn publish(&mut self, elapsed: Duration, snapshots: &[DiagnosticSnapshot]) -> io::Result<()> {
+        let json = to_json(elapsed, snapshots);
+        *self.latest.lock().unwrap_or_else(|err| err.into_inner()) = json;
+        Ok(())
+    }
+}
+
+fn csv_value(value: Option<f64>) -> String {
+    value.map(|value| value.to_string()).unwrap_or_default()
+}
+
+fn to_json(elapsed: Duration, snapshots: &[DiagnosticSnapshot]) -> String {
+    let mut json = String::new();
+    let _ = write!(
+        json,
+        "{{\"elapsed\":{},\"diagnostics\":[",
+        elapsed.as_secs_f64()
+    );
+    for (index, snapshot) in snapshots.iter().enumerate() {
+        if index > 0 {
+            json.push(',');
+        }
+        let _ = write!(json, "{{\"id\":\"{}\",\"name\":", snapshot.id.0);
+        write_json_string(&mut json, &snapshot.name);
+        json.push_str(",\"suffix\":");
+        write_json_string(&mut json, &snapshot.suffix);
+        for (key, value) in [
+            ("value", snapshot.value),
+            ("average", snapshot.average),
+            ("smoothed", snapshot.smoothed),
+        ] {
+            let _ = write!(json, ",\"{key}\":");
+            match value {
+                Some(value) if value.is_finite() => {
+                    let _ = write!(json, "{value:?}");
+                }
+                _ => json.push_str("null"),
+            }
+        }
+        json.push('}');
+    }
+    json.push_str("]}");
+    json
+}
+
+fn write_json_string(json: &mut String, value: &str) {
+    json.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => json.push_str("\\\""),
+            '\\' => json.push_str("\\\\"),
+            '\n' => json.push_str("\\n"),
+            '\r' => json.push_str("\\r"),
+            '\t' => json.push_str("\\t"),
+            c if c.is_control() => {
+                let _ = write!(json, "\\u{:04x}", c as u32);
+            }
+            c => json.push(c),
+        }
+    }
+    json.push('"');
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::{Arc, Mutex};
+
+    #[derive(Clone, Default)]
+    struct SharedBuffer(Arc<Mutex<Vec<u8>>>);
+
+    impl Write for SharedBuffer {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.0.lock().unwrap().write(buf)
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    impl SharedBuffer {
+        fn contents(&self) -> String {
+            String::from_utf8(self.0.lock().unwrap().clone()).unwrap()
+        }
+    }
+
+    fn snapshots() -> Vec<DiagnosticSnapshot> {
+        vec![
+            DiagnosticSnapshot {
+                id: DiagnosticId::from_u128(1),
+                name: "frame_time".to_string(),
+                suffix: "ms".to_string(),
+                value: Some(16.5),
+                average: Some(16.0),
+                smoothed: Some(16.25),
+            },
+            DiagnosticSnapshot {
+                id: DiagnosticId::from_u128(2),
+                name: "a \"quoted\" name".to_string(),
+                suffix: String::new(),
+                value: None,
+                average: None,
+                smoothed: None,
+            },
+        ]
+    }
+
+    fn elapsed() -> Duration {
+        Duration::from_millis(1500)
+    }
+
+    #[test]
+    fn json_lines_sink() {
+        let buffer = SharedBuffer::default();
+        let mut sink = JsonLinesSink::new(buffer.clone());
+        sink.publish(elapsed(), &snapshots()).unwrap();
+
+        assert_eq!(
+            buffer.contents(),
+            "{\"elapsed\":1.5,\"diagnostics\":[\
+            {\"id\":\"00000000-0000-0000-0000-000000000001\",\"name\":\"frame_time\",\"suffix\":\"ms\",\"value\":16.5,\"average\":16.0,\"smoothed\":16.25},\
+            {\"id\":\"00000000-0000-0000-0000-000000000002\",\"name\":\"a \\\"quoted\\\" name\",\"suffix\":\"\",\"value\":null,\"average\":null,\"smoothed\":null}\
+            ]}\n"
+        );
+    }
+
+    #[test]
+    fn csv_sink() {
+        let buffer = SharedBuffer::default();
+        let mut sink = CsvSink::new(buffer.clone()).unwrap();
+        sink.publish(elapsed(), &snapshots()).unwrap();
+
+        assert_eq!(
+            buffer.contents(),
+            "elapsed,id,name,suffix,value,average,smoothed\n\
+            1.5,00000000-0000-0000-0000-000000000001,\"frame_time\",\"ms\",16.5,16,16.25\n\
+            1.5,00000000-0000-0000-0000-000000000002,\"a \"\"quoted\"\" name\",\"\",,,\n"
+        );
+    }
+
+    #[test]
+    fn http_sink() {
+        use std::{io::Read, net::TcpStream};
+
+        let mut sink = HttpSink::bind("127.0.0.1:0").unwrap();
+        sink.publish(elapsed(), &snapshots()).unwrap();
+
+        let mut stream = TcpStream::connect(sink.local_addr()).unwrap();
+        stream
+            .write_all(b"GET /diagnostics HTTP/1.1\r\nHost: localhost\r\n\r\n")
+            .unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).unwrap();
+
+        assert!(response.starts_with("HTTP/1.1 200 OK\r\n"));
+        assert!(response.ends_with(&to_json(elapsed(), &snapshots())));
+    }
+
+    #[test]
+    fn http_sink_stops_on_drop() {
+        use std::net::TcpStream;
+
+        let sink = HttpSink::bind("127.0.0.1:0").unwrap();
+        let address = sink.local_addr();
+        // an idle client is dropped once it times out, instead of keeping the thread alive
+        let _idle = TcpStream::connect(address).unwrap();
+        drop(sink);
+
+        assert!(TcpStream::connect(address).is_err());
+    }
+}
@@ -1,11 +1,25 @@
 mod diagnostic;
 mod entity_count_diagnostics_plugin;
+mod export_diagnostics_plugin;
 mod frame_time_diagnostics_plugin;
 mod log_diagnostics_plugin;
+mod memory_diagnostics_plugin;
+mod system_timing_diagnostics_plugin;
 pub use diagnostic::*;
 pub use entity_count_diagnostics_plugin::EntityCountDiagnosticsPlugin;
+#[cfg(not(target_arch = "wasm32"))]
+pub use export_diagnostics_plugin::HttpSink;
+pub use export_diagnostics_plugin::{
+    CsvSink, DiagnosticSink, DiagnosticSinks, DiagnosticSnapshot, ExportDiagnosticsPlugin,
+    JsonLinesSink,
+};
 pub use frame_time_diagnostics_plugin::FrameTimeDiagnosticsPlugin;
 pub use log_diagnostics_plugin::LogDiagnosticsPlugin;
+pub use memory_diagnostics_plugin::{MemoryDiagnosticsPlugin, MemoryUsage, MemoryUsageEntry};
+pub use system_timing_diagnostics_plugin::{
+    SystemTimingDiagnostics, SystemTimingDiagnosticsPlugin, SystemTimingReport,
+    SystemTimingReportEntry, TimingKind,
+};
 
 use bevy_app::prelude::*;
 
@@ -0,0 +1,246 @@
+use crate::{Diagnostic, DiagnosticId, Diagnostics};
+use bevy_app::prelude::*;
+use bevy_ecs::{
+    archetype::Archetype,
+    system::{ResMut, Resource},
+    world::World,
+};
+use bevy_utils::get_short_name;
+use std::{borrow::Cow, collections::BTreeMap};
+
+/// Adds memory usage diagnostics for the ECS storages of an App.
+///
+/// Reports the bytes allocated by every table and component sparse set, both as [`Diagnostic`]s
+/// and as a per-table breakdown in the [`MemoryUsage`] resource. Other crates contribute further
+/// categories to [`MemoryUsage`], such as `AssetMemoryDiagnosticsPlugin` in `bevy_asset` and
+/// `RenderMemoryDiagnosticsPlugin` in `bevy_render`.
+#[derive(Default)]
+pub struct MemoryDiagnosticsPlugin;
+
+impl Plugin for MemoryDiagnosticsPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<MemoryUsage>()
+            .add_startup_system(Self::setup_system)
+            .add_system_to_stage(CoreStage::Last, Self::diagnostic_system);
+    }
+}
+
+impl MemoryDiagnosticsPlugin {
+    pub const ECS_TABLE_BYTES: DiagnosticId =
+        DiagnosticId::from_u128(151893402738451932185437097282619585370);
+    pub const ECS_SPARSE_SET_BYTES: DiagnosticId =
+        DiagnosticId::from_u128(26409218364120356284218040862340771837);
+
+    pub fn setup_system(mut diagnostics: ResMut<Diagnostics>) {
+        diagnostics.add(Diagnostic::new(
+            Self::ECS_TABLE_BYTES,
+            "ecs_table_bytes",
+            20,
+        ));
+        diagnostics.add(Diagnostic::new(
+            Self::ECS_SPARSE_SET_BYTES,
+            "ecs_sparse_set_bytes",
+            20,
+        ));
+    }
+
+    /// Measures the ECS storages of `world` and records them in [`MemoryUsage`] and [`Diagnostics`].
+    pub fn diagnostic_system(world: &mut World) {
+        let tables = Self::table_usage(world);
+        let sparse_sets = Self::sparse_set_usage(world);
+        let table_bytes = MemoryUsage::sum_bytes(&tables);
+        let sparse_set_bytes = MemoryUsage::sum_bytes(&sparse_sets);
+
+        if let Some(mut memory_usage) = world.get_resource_mut::<MemoryUsage>() {
+            memory_usage.set_category(MemoryUsage::ECS_TABLES, tables);
+            memory_usage.set_category(MemoryUsage::ECS_SPARSE_SETS, sparse_sets);
+        }
+        if let Some(mut diagnostics) = world.get_resource_mut::<Diagnostics>() {
+            diagnostics.add_measurement(Self::ECS_TABLE_BYTES, || table_bytes as f64);
+            diagnostics.add_measurement(Self::ECS_SPARSE_SET_BYTES, || sparse_set_bytes as f64);
+        }
+    }
+
+    fn table_usage(world: &World) -> Vec<MemoryUsageEntry> {
+        // Tables are described by the table components of the first archetype stored in them.
+        let tables = &world.storages().tables;
+        let mut archetypes: Vec<Option<&Archetype>> = vec![None; tables.len()];
+        for archetype in world.archetypes().iter() {
+            archetypes[archetype.table_id().index()].get_or_insert(archetype);
+        }
+
+        tables
+            .iter()
+            .zip(archetypes)
+            .enumerate()
+            .map(|(index, (table, archetype))| {
+                let components = archetype
+                    .map(|archetype| {
+                        archetype
+                            .table_components()
+                            .iter()
+                            .filter_map(|id| world.components().get_info(*id))
+                            .map(|info| get_short_name(info.name()))
+                            .collect::<Vec<_>>()
+                            .join(", ")
+                    })
+                    .unwrap_or_default();
+                MemoryUsageEntry {
+                    name: format!("table {index} ({components})"),
+                    bytes: table.allocated_bytes(),
+                    count: table.entity_count(),
+                }
+            })
+            .collect()
+    }
+
+    fn sparse_set_usage(world: &World) -> Vec<MemoryUsageEntry> {
+        world
+            .storages()
+            .sparse_sets
+            .iter()
+            .map(|(id, set)| MemoryUsageEntry {
+                name: world
+                    .components()
+                    .get_info(id)
+                    .map(|info| get_short_name(info.name()))
+                    .unwrap_or_default(),
+                bytes: set.allocated_bytes(),
+                count: set.len(),
+            })
+            .collect()
+    }
+}
+
+/// A single item of a [`MemoryUsage`] category, such as a table or an asset type.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MemoryUsageEntry {
+    /// A human readable description of the item.
+    pub name: String,
+    /// The number of bytes the item uses.
+    pub bytes: usize,
+    /// The number of elements stored in the item, such as entities or assets.
+    pub count: usize,
+}
+
+/// A structured breakdown of the memory used by an App, grouped into named categories.
+///
+/// Each category is refreshed by the plugin that owns it; see [`MemoryDiagnosticsPlugin`].
+#[derive(Resource, Debug, Default)]
+pub struct MemoryUsage {
+    categories: BTreeMap<Cow<'static, str>, Vec<MemoryUsageEntry>>,
+}
+
+impl MemoryUsage {
+    /// The category holding one entry per ECS table.
+    pub const ECS_TABLES: &'static str = "ecs_tables";
+    /// The category holding one entry per component sparse set.
+    pub const ECS_SPARSE_SETS: &'static str = "ecs_sparse_sets";
+    /// The category holding one entry per asset type.
+    pub const ASSETS: &'static str = "assets";
+    /// The category holding one entry per kind of GPU resource.
+    pub const RENDER_RESOURCES: &'static str = "render_resources";
+
+    /// Replaces every entry of `category`.
+    pub fn set_category(
+        &mut self,
+        category: impl Into<Cow<'static, str>>,
+        entries: Vec<MemoryUsageEntry>,
+    ) {
+        self.categories.insert(category.into(), entries);
+    }
+
+    /// Inserts `entry` into `category`, replacing the entry with the same name if there is one.
+    pub fn set_entry(&mut self, category: impl Into<Cow<'static, str>>, entry: MemoryUsageEntry) {
+        let entries = self.categories.entry(category.into()).or_default();
+        match entries
+            .iter_mut()
+            .find(|existing| existing.name == entry.name)
+        {
+            Some(existing) => *existing = entry,
+            None => entries.push(entry),
+        }
+    }
+
+    /// Returns the entries of `category`, or an empty slice if nothing was recorded for it.
+    pub fn category(&self, category: &str) -> &[MemoryUsageEntry] {
+        self.categories
+            .get(category)
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+    }
+
+    /// Returns the total number of bytes recorded in `category`.
+    pub fn category_bytes(&self, category: &str) -> usize {
+        Self::sum_bytes(self.category(category))
+    }
+
+    /// Returns the total number of bytes recorded in all categories.
+    pub fn total_bytes(&self) -> usize {
+        self.categories
+            .values()
+            .map(|entries| Self::sum_bytes(entries))
+            .sum()
+    }
+
+    /// Returns an iterator over every category and its entries, ordered by category name.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &[MemoryUsageEntry])> {
+        self.categories
+            .iter()
+            .map(|(category, entries)| (&**category, entries.as_slice()))
+    }
+
+    fn sum_bytes(entries: &[MemoryUsageEntry]) -> usize {
+        entries.iter().map(|entry| entry.bytes).sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{MemoryDiagnosticsPlugin, MemoryUsage};
+    use crate::{Diagnostics, DiagnosticsPlugin};
+    use bevy_app::App;
+    use bevy_ecs::component::Component;
+
+    #[derive(Component)]
+    struct Position(#[allow(dead_code)] [f32; 3]);
+
+    #[derive(Component)]
+    #[component(storage = "SparseSet")]
+    struct Marker(#[allow(dead_code)] u64);
+
+    #[test]
+    fn reports_ecs_memory() {
+        let mut app = App::new();
+        app.add_plugin(DiagnosticsPlugin)
+            .add_plugin(MemoryDiagnosticsPlugin);
+        for _ in 0..10 {
+            app.world.spawn((Position([0.0; 3]), Marker(0)));
+        }
+        app.update();
+
+        let memory_usage = app.world.resource::<MemoryUsage>();
+        let table = memory_usage
+            .category(MemoryUsage::ECS_TABLES)
+            .iter()
+            .find(|entry| entry.name.contains("Position"))
+            .unwrap();
+        assert_eq!(table.count, 10);
+        assert!(table.bytes >= 10 * std::mem::size_of::<Position>());
+
+        let sparse_set = &memory_usage.category(MemoryUsage::ECS_SPARSE_SETS)[0];
+        assert_eq!(sparse_set.name, "Marker");
+        assert_eq!(sparse_set.count, 10);
+
+        let diagnostics = app.world.resource::<Diagnostics>();
+        let table_bytes = diagnostics
+            .get(MemoryDiagnosticsPlugin::ECS_TABLE_BYTES)
+            .unwrap()
+            .value()
+            .unwrap();
+        assert_eq!(
+            table_bytes as usize,
+            memory_usage.category_bytes(MemoryUsage::ECS_TABLES)
+        );
+    }
+}
@@ -0,0 +1,300 @@
+use crate::{Diagnostic, DiagnosticId, Diagnostics};
+use bevy_app::prelude::*;
+use bevy_ecs::{
+    schedule::ExecutionTimings,
+    system::{ResMut, Resource},
+};
+use bevy_log::info;
+use bevy_utils::{get_short_name, Duration, HashMap};
+use std::{borrow::Cow, fmt, io};
+
+/// Adds per-system and per-stage wall time diagnostics to an App.
+///
+/// Each system and stage gets its own [`Diagnostic`], measured in milliseconds and created the
+/// first time it runs. Use [`SystemTimingDiagnostics`] to find the diagnostic of a system or to
+/// build a [`SystemTimingReport`] of the slowest ones.
+pub struct SystemTimingDiagnosticsPlugin {
+    /// The history length of every created [`Diagnostic`].
+    pub max_history_length: usize,
+    /// Whether to also measure whole stages.
+    pub include_stages: bool,
+}
+
+impl Default for SystemTimingDiagnosticsPlugin {
+    fn default() -> Self {
+        SystemTimingDiagnosticsPlugin {
+            max_history_length: 20,
+            include_stages: true,
+        }
+    }
+}
+
+impl Plugin for SystemTimingDiagnosticsPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ExecutionTimings>()
+            .insert_resource(SystemTimingDiagnostics {
+                max_history_length: self.max_history_length,
+                include_stages: self.include_stages,
+                systems: Default::default(),
+                stages: Default::default(),
+            })
+            .add_system_to_stage(CoreStage::Last, Self::diagnostic_system);
+    }
+}
+
+impl SystemTimingDiagnosticsPlugin {
+    /// Turns the [`ExecutionTimings`] recorded since the last run into [`Diagnostic`] measurements.
+    ///
+    /// Systems and stages that ran several times since the last run are measured with their
+    /// total run time.
+    pub fn diagnostic_system(
+        mut timings: ResMut<ExecutionTimings>,
+        mut state: ResMut<SystemTimingDiagnostics>,
+        mut diagnostics: ResMut<Diagnostics>,
+    ) {
+        let state = &mut *state;
+        let history = state.max_history_length;
+
+        let systems = sum_by_name(timings.drain_systems());
+        for (name, run_time) in systems {
+            let id = state.systems.entry(name).or_insert_with_key(|name| {
+                add_timing_diagnostic(&mut diagnostics, TimingKind::System, name, history)
+            });
+            diagnostics.add_measurement(*id, || run_time.as_secs_f64() * 1000.0);
+        }
+
+        let stages = sum_by_name(timings.drain_stages());
+        if !state.include_stages {
+            return;
+        }
+        for (name, run_time) in stages {
+            let id = state.stages.entry(name).or_insert_with_key(|name| {
+                add_timing_diagnostic(&mut diagnostics, TimingKind::Stage, name, history)
+            });
+            diagnostics.add_measurement(*id, || run_time.as_secs_f64() * 1000.0);
+        }
+    }
+}
+
+fn sum_by_name(
+    timings: impl Iterator<Item = (Cow<'static, str>, Duration)>,
+) -> Vec<(Cow<'static, str>, Duration)> {
+    let mut sums: Vec<(Cow<'static, str>, Duration)> = Vec::new();
+    for (name, run_time) in timings {
+        match sums.iter_mut().find(|(existing, _)| *existing == name) {
+            Some((_, sum)) => *sum += run_time,
+            None => sums.push((name, run_time)),
+        }
+    }
+    sums
+}
+
+fn add_timing_diagnostic(
+    diagnostics: &mut Diagnostics,
+    kind: TimingKind,
+    name: &str,
+    max_history_length: usize,
+) -> DiagnosticId {
+    let id = DiagnosticId::default();
+    let mut diagnostic = Diagnostic::new(id, kind.prefix(), max_history_length).with_suffix("ms");
+    // Generated names are routinely longer than `MAX_DIAGNOSTIC_NAME_WIDTH`, so they are set
+    // after construction to avoid warning once per system.
+    diagnostic.name = format!("{}/{}", kind.prefix(), get_short_name(name)).into();
+    diagnostics.add(diagnostic);
+    id
+}
+
+/// Whether a timing entry describes a system or a stage.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TimingKind {
+    /// A system, identified by its name.
+    System,
+    /// A stage, identified by its label.
+    Stage,
+}
+
+impl TimingKind {
+    fn prefix(self) -> &'static str {
+        match self {
+            TimingKind::System => "system",
+            TimingKind::Stage => "stage",
+        }
+    }
+}
+
+/// State of the [`SystemTimingDiagnosticsPlugin`], mapping systems and stages to their
+/// [`Diagnostic`]s.
+#[derive(Resource, Debug)]
+pub struct SystemTimingDiagnostics {
+    max_history_length: usize,
+    include_stages: bool,
+    systems: HashMap<Cow<'static, str>, DiagnosticId>,
+    stages: HashMap<Cow<'static, str>, DiagnosticId>,
+}
+
+impl SystemTimingDiagnostics {
+    /// Returns the id of the [`Diagnostic`] measuring the system with the given full name.
+    pub fn system_diagnostic(&self, name: &str) -> Option<DiagnosticId> {
+        self.systems.get(name).copied()
+    }
+
+    /// Returns the id of the [`Diagnostic`] measuring the stage with the given label.
+    pub fn stage_diagnostic(&self, label: &str) -> Option<DiagnosticId> {
+        self.stages.get(label).copied()
+    }
+
+    /// Builds a report of the `count` systems with the highest average run time.
+    pub fn slowest_systems(&self, diagnostics: &Diagnostics, count: usize) -> SystemTimingReport {
+        Self::report(&self.systems, TimingKind::System, diagnostics, count)
+    }
+
+    /// Builds a report of the `count` stages with the highest average run time.
+    pub fn slowest_stages(&self, diagnostics: &Diagnostics, count: usize) -> SystemTimingReport {
+        Self::report(&self.stages, TimingKind::Stage, diagnostics, count)
+    }
+
+    fn report(
+        ids: &HashMap<Cow<'static, str>, DiagnosticId>,
+        kind: TimingKind,
+        diagnostics: &Diagnostics,
+        count: usize,
+    ) -> SystemTimingReport {
+        let mut entries: Vec<_> = ids
+            .iter()
+            .filter_map(|(name, id)| {
+                let diagnostic = diagnostics.get(*id)?;
+                Some(SystemTimingReportEntry {
+                    name: name.to_string(),
+                    kind,
+                    last_ms: diagnostic.value()?,
+                    average_ms: diagnostic.average()?,
+                    smoothed_ms: diagnostic.smoothed()?,
+                })
+            })
+            .collect();
+        entries.sort_by(|a, b| b.average_ms.total_cmp(&a.average_ms));
+        entries.truncate(count);
+        SystemTimingReport { entries }
+    }
+}
+
+/// A single row of a [`SystemTimingReport`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct SystemTimingReportEntry {
+    /// The full name of the system or the label of the stage.
+    pub name: String,
+    /// Whether this entry is a system or a stage.
+    pub kind: TimingKind,
+    /// The latest measured run time, in milliseconds.
+    pub last_ms: f64,
+    /// The average run time over the diagnostic's history, in milliseconds.
+    pub average_ms: f64,
+    /// The exponentially smoothed run time, in milliseconds.
+    pub smoothed_ms: f64,
+}
+
+/// A snapshot of the slowest systems or stages, sorted by descending average run time.
+///
+/// Created by [`SystemTimingDiagnostics::slowest_systems`] and
+/// [`SystemTimingDiagnostics::slowest_stages`]. The [`Display`](fmt::Display) implementation
+/// produces a human readable table.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SystemTimingReport {
+    /// The rows of the report, slowest first.
+    pub entries: Vec<SystemTimingReportEntry>,
+}
+
+impl SystemTimingReport {
+    /// Logs every entry of the report at the info level.
+    pub fn log(&self) {
+        for line in self.to_string().lines() {
+            info!(target: "bevy diagnostic", "{}", line);
+        }
+    }
+
+    /// Writes the report as CSV with a header row.
+    pub fn write_csv(&self, mut writer: impl io::Write) -> io::Result<()> {
+        writeln!(writer, "kind,name,last_ms,average_ms,smoothed_ms")?;
+        for entry in &self.entries {
+            writeln!(
+                writer,
+                "{},\"{}\",{},{},{}",
+                entry.kind.prefix(),
+                entry.name.replace('"', "\"\""),
+                entry.last_ms,
+                entry.average_ms,
+                entry.smoothed_ms
+            )?;
+        }
+        Ok(())
+    }
+
+    /// Returns the report as CSV with a header row.
+    pub fn to_csv(&self) -> String {
+        let mut csv = Vec::new();
+        self.write_csv(&mut csv)
+            .expect("writing to a Vec should not fail");
+        String::from_utf8(csv).expect("CSV report should be valid UTF-8")
+    }
+}
+
+impl fmt::Display for SystemTimingReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (rank, entry) in self.entries.iter().enumerate() {
+            writeln!(
+                f,
+                "{rank:>3}. {name:<name_width$}: {average:>11.6}ms (last {last:.6}ms, smoothed {smoothed:.6}ms)",
+                rank = rank + 1,
+                name = get_short_name(&entry.name),
+                name_width = crate::MAX_DIAGNOSTIC_NAME_WIDTH,
+                average = entry.average_ms,
+                last = entry.last_ms,
+                smoothed = entry.smoothed_ms,
+            )?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{SystemTimingDiagnostics, SystemTimingDiagnosticsPlugin, TimingKind};
+    use crate::{Diagnostics, DiagnosticsPlugin};
+    use bevy_app::App;
+
+    fn slow_system() {
+        std::thread::sleep(std::time::Duration::from_millis(2));
+    }
+
+    fn fast_system() {}
+
+    #[test]
+    fn reports_slowest_systems() {
+        let mut app = App::new();
+        app.add_plugin(DiagnosticsPlugin)
+            .add_plugin(SystemTimingDiagnosticsPlugin::default())
+            .add_system(slow_system)
+            .add_system(fast_system);
+        app.update();
+        app.update();
+
+        let state = app.world.resource::<SystemTimingDiagnostics>();
+        let diagnostics = app.world.resource::<Diagnostics>();
+        let report = state.slowest_systems(diagnostics, 1);
+        assert_eq!(report.entries.len(), 1);
+        assert!(report.entries[0].name.ends_with("slow_system"));
+        assert_eq!(report.entries[0].kind, TimingKind::System);
+        assert!(report.entries[0].average_ms >= 2.0);
+
+        let update = state.stage_diagnostic("CoreStage::Update").unwrap();
+        assert!(diagnostics.get(update).unwrap().average().unwrap() >= 2.0);
+
+        let csv = report.to_csv();
+        let mut lines = csv.lines();
+        assert_eq!(
+            lines.next(),
+            Some("kind,name,last_ms,average_ms,smoothed_ms")
+        );
+        assert!(lines.next().unwrap().starts_with("system,\""));
+    }
+}
@@ -1,4 +1,8 @@
-use crate::{schedule::SystemContainer, world::World};
+use crate::{
+    schedule::{timings::record_system_timing, ExecutionTimings, SystemContainer},
+    world::World,
+};
+use bevy_utils::Instant;
 use core::fmt::Debug;
 use downcast_rs::{impl_downcast, Downcast};
 
@@ -24,12 +28,17 @@ impl ParallelSystemExecutor for SingleThreadedExecutor {
     fn rebuild_cached_data(&mut self, _: &[SystemContainer]) {}
 
     fn run_systems(&mut self, systems: &mut [SystemContainer], world: &mut World) {
+        let record_timings = world.contains_resource::<ExecutionTimings>();
         for system in systems {
             if system.should_run() {
                 #[cfg(feature = "trace")]
                 let _system_span =
                     bevy_utils::tracing::info_span!("system", name = &*system.name()).entered();
+                let start = record_timings.then(Instant::now);
                 system.system_mut().run((), world);
+                if let Some(start) = start {
+                    record_system_timing(world, system.name(), start.elapsed());
+                }
             }
         }
     }
@@ -1,13 +1,14 @@
 use crate::{
     archetype::ArchetypeComponentId,
     query::Access,
-    schedule::{ParallelSystemExecutor, SystemContainer},
+    schedule::{ExecutionTimings, ParallelSystemExecutor, SystemContainer},
     world::World,
 };
 use async_channel::{Receiver, Sender};
 use bevy_tasks::{ComputeTaskPool, Scope, TaskPool};
 #[cfg(feature = "trace")]
 use bevy_utils::tracing::Instrument;
+use bevy_utils::{Duration, Instant};
 use event_listener::Event;
 use fixedbitset::FixedBitSet;
 
@@ -33,10 +34,11 @@ struct SystemSchedulingMetadata {
 pub struct ParallelExecutor {
     /// Cached metadata of every system.
     system_metadata: Vec<SystemSchedulingMetadata>,
-    /// Used by systems to notify the executor that they have finished.
-    finish_sender: Sender<usize>,
+    /// Used by systems to notify the executor that they have finished,
+    /// along with their run time if timings are being recorded.
+    finish_sender: Sender<(usize, Option<Duration>)>,
     /// Receives finish events from systems.
-    finish_receiver: Receiver<usize>,
+    finish_receiver: Receiver<(usize, Option<Duration>)>,
     /// Systems that should be started at next opportunity.
     queued: FixedBitSet,
     /// Systems that are currently running.
@@ -49,6 +51,8 @@ pub struct ParallelExecutor {
     active_archetype_component_access: Access<ArchetypeComponentId>,
     /// Scratch space to avoid reallocating a vector when updating dependency counters.
     dependants_scratch: Vec<usize>,
+    /// Run times of systems that finished this iteration, if timings are being recorded.
+    finished_timings: Vec<(usize, Duration)>,
     #[cfg(test)]
     events_sender: Option<Sender<SchedulingEvent>>,
 }
@@ -70,6 +74,7 @@ impl Default for ParallelExecutor {
             should_run: Default::default(),
             active_archetype_component_access: Default::default(),
             dependants_scratch: Default::default(),
+            finished_timings: Default::default(),
             #[cfg(test)]
             events_sender: None,
         }
@@ -124,8 +129,9 @@ impl ParallelSystemExecutor for ParallelExecutor {
             }
         }
 
+        let record_timings = world.contains_resource::<ExecutionTimings>();
         ComputeTaskPool::init(TaskPool::default).scope(|scope| {
-            self.prepare_systems(scope, systems, world);
+            self.prepare_systems(scope, systems, world, record_timings);
             if self.should_run.count_ones(..) == 0 {
                 return;
             }
@@ -136,15 +142,15 @@ impl ParallelSystemExecutor for ParallelExecutor {
                     // Avoid deadlocking if no systems were actually started.
                     if self.running.count_ones(..) != 0 {
                         // Wait until at least one system has finished.
-                        let index = self
+                        let (index, run_time) = self
                             .finish_receiver
                             .recv()
                             .await
                             .unwrap_or_else(|error| unreachable!("{}", error));
-                        self.process_finished_system(index);
+                        self.process_finished_system(index, run_time);
                         // Gather other systems than may have finished.
-                        while let Ok(index) = self.finish_receiver.try_recv() {
-                            self.process_finished_system(index);
+                        while let Ok((index, run_time)) = self.finish_receiver.try_recv() {
+                            self.process_finished_system(index, run_time);
                         }
                         // At least one system has finished, so active access is outdated.
                         self.rebuild_active_access();
@@ -158,6 +164,13 @@ impl ParallelSystemExecutor for ParallelExecutor {
             let parallel_executor = parallel_executor.instrument(span);
             scope.spawn(parallel_executor);
         });
+
+        if record_timings {
+            let mut timings = world.resource_mut::<ExecutionTimings>();
+            for (index, run_time) in self.finished_timings.drain(..) {
+                timings.record_system(systems[index].name(), run_time);
+            }
+        }
     }
 }
 
@@ -169,6 +182,7 @@ impl ParallelExecutor {
         scope: &Scope<'_, 'scope, ()>,
         systems: &'scope mut [SystemContainer],
         world: &'scope World,
+        record_timings: bool,
     ) {
         // These are used as a part of a unit test.
         #[cfg(test)]
@@ -214,19 +228,21 @@ impl ParallelExecutor {
             let mut run = move || {
                 #[cfg(feature = "trace")]
                 let _system_guard = system_span.enter();
+                let start = record_timings.then(Instant::now);
                 // SAFETY: the executor prevents two systems with conflicting access from running simultaneously.
                 unsafe { system.run_unsafe((), world) };
+                start.map(|start| start.elapsed())
             };
 
             if can_start {
                 let task = async move {
-                    run();
+                    let run_time = run();
                     // This will never panic:
                     //  - The channel is never closed or dropped.
                     //  - Overflowing the bounded size will just suspend until
                     //    there is capacity.
                     finish_sender
-                        .send(index)
+                        .send((index, run_time))
                         .await
                         .unwrap_or_else(|error| unreachable!("{}", error));
                 };
@@ -255,13 +271,13 @@ impl ParallelExecutor {
                 let start_listener = system_data.start.listen();
                 let task = async move {
                     start_listener.await;
-                    run();
+                    let run_time = run();
                     // This will never panic:
                     //  - The channel is never closed or dropped.
                     //  - Overflowing the bounded size will just suspend until
                     //    there is capacity.
                     finish_sender
-                        .send(index)
+                        .send((index, run_time))
                         .await
                         .unwrap_or_else(|error| unreachable!("{}", error));
                 };
@@ -339,8 +355,11 @@ impl ParallelExecutor {
     }
 
     /// Unmarks the system give index as running, caches indices of its dependants
-    /// in the `dependants_scratch`.
-    fn process_finished_system(&mut self, index: usize) {
+    /// in the `dependants_scratch` and its run time in `finished_timings`.
+    fn process_finished_system(&mut self, index: usize, run_time: Option<Duration>) {
+        if let Some(run_time) = run_time {
+            self.finished_timings.push((index, run_time));
+        }
         let system_data = &self.system_metadata[index];
         if !system_data.is_send {
             self.non_send_running = false;
@@ -14,6 +14,7 @@ mod state;
 mod system_container;
 mod system_descriptor;
 mod system_set;
+mod timings;
 
 pub use executor::*;
 pub use executor_parallel::*;
@@ -25,11 +26,12 @@ pub use state::*;
 pub use system_container::*;
 pub use system_descriptor::*;
 pub use system_set::*;
+pub use timings::*;
 
-use std::fmt::Debug;
+use std::{borrow::Cow, fmt::Debug};
 
 use crate::{system::IntoSystem, world::World};
-use bevy_utils::HashMap;
+use bevy_utils::{HashMap, Instant};
 
 /// A container of [`Stage`]s set to be run in a linear order.
 ///
@@ -367,7 +369,16 @@ impl Schedule {
             #[cfg(feature = "trace")]
             let _stage_span = bevy_utils::tracing::info_span!("stage", name = ?label).entered();
             let stage = self.stages.get_mut(label).unwrap();
-            stage.run(world);
+            if world.contains_resource::<ExecutionTimings>() {
+                let start = Instant::now();
+                stage.run(world);
+                let elapsed = start.elapsed();
+                if let Some(mut timings) = world.get_resource_mut::<ExecutionTimings>() {
+                    timings.record_stage(Cow::Borrowed(label.as_str()), elapsed);
+                }
+            } else {
+                stage.run(world);
+            }
         }
     }
 
@@ -5,15 +5,17 @@ use crate::{
     prelude::IntoSystem,
     schedule::{
         graph_utils::{self, DependencyGraphError},
-        BoxedRunCriteria, DuplicateLabelStrategy, ExclusiveInsertionPoint, GraphNode,
-        ParallelExecutor, ParallelSystemExecutor, RunCriteriaContainer, RunCriteriaDescriptor,
-        RunCriteriaDescriptorOrLabel, RunCriteriaInner, RunCriteriaLabelId, ShouldRun,
-        SingleThreadedExecutor, SystemContainer, SystemDescriptor, SystemLabelId, SystemSet,
+        timings::record_system_timing,
+        BoxedRunCriteria, DuplicateLabelStrategy, ExclusiveInsertionPoint, ExecutionTimings,
+        GraphNode, ParallelExecutor, ParallelSystemExecutor, RunCriteriaContainer,
+        RunCriteriaDescriptor, RunCriteriaDescriptorOrLabel, RunCriteriaInner, RunCriteriaLabelId,
+        ShouldRun, SingleThreadedExecutor, SystemContainer, SystemDescriptor, SystemLabelId,
+        SystemSet,
     },
     world::{World, WorldId},
 };
 use bevy_ecs_macros::Resource;
-use bevy_utils::{tracing::warn, HashMap, HashSet};
+use bevy_utils::{tracing::warn, HashMap, HashSet, Instant};
 use core::fmt::Debug;
 use downcast_rs::{impl_downcast, Downcast};
 
@@ -750,6 +752,7 @@ impl Stage for SystemStage {
                 }
             }
 
+            let record_timings = world.contains_resource::<ExecutionTimings>();
             let mut run_system_loop = true;
             let mut default_should_run = ShouldRun::Yes;
             while run_system_loop {
@@ -779,7 +782,11 @@ impl Stage for SystemStage {
                                 name = &*container.name()
                             )
                             .entered();
+                            let start = record_timings.then(Instant::now);
                             container.system_mut().run((), world);
+                            if let Some(start) = start {
+                                record_system_timing(world, container.name(), start.elapsed());
+                            }
                         }
                         {
                             #[cfg(feature = "trace")]
@@ -811,7 +818,11 @@ impl Stage for SystemStage {
                                 name = &*container.name()
                             )
                             .entered();
+                            let start = record_timings.then(Instant::now);
                             container.system_mut().run((), world);
+                            if let Some(start) = start {
+                                record_system_timing(world, container.name(), start.elapsed());
+                            }
                         }
                         {
                             #[cfg(feature = "trace")]
@@ -850,7 +861,11 @@ impl Stage for SystemStage {
                                 name = &*container.name()
                             )
                             .entered();
+                            let start = record_timings.then(Instant::now);
                             container.system_mut().run((), world);
+                            if let Some(start) = start {
+                                record_system_timing(world, container.name(), start.elapsed());
+                            }
                         }
                         {
                             #[cfg(feature = "trace")]
@@ -0,0 +1,120 @@
+use crate as bevy_ecs;
+use crate::{system::Resource, world::World};
+use bevy_utils::Duration;
+use std::borrow::Cow;
+
+/// Collects the wall time spent running each system and each stage.
+///
+/// Timings are only measured while this resource is present in the [`World`]; the
+/// [`Schedule`](super::Schedule), [`SystemStage`](super::SystemStage) and the built-in executors
+/// check for it every run. Recorded timings accumulate until they are drained, typically once per
+/// frame by a diagnostics plugin.
+#[derive(Resource, Debug, Default)]
+pub struct ExecutionTimings {
+    systems: Vec<(Cow<'static, str>, Duration)>,
+    stages: Vec<(Cow<'static, str>, Duration)>,
+}
+
+impl ExecutionTimings {
+    /// Records that the system named `name` ran for `duration`.
+    pub fn record_system(&mut self, name: Cow<'static, str>, duration: Duration) {
+        self.systems.push((name, duration));
+    }
+
+    /// Records that the stage labeled `name` ran for `duration`.
+    pub fn record_stage(&mut self, name: Cow<'static, str>, duration: Duration) {
+        self.stages.push((name, duration));
+    }
+
+    /// Returns the system timings recorded since they were last drained, in recording order.
+    pub fn systems(&self) -> &[(Cow<'static, str>, Duration)] {
+        &self.systems
+    }
+
+    /// Returns the stage timings recorded since they were last drained, in recording order.
+    pub fn stages(&self) -> &[(Cow<'static, str>, Duration)] {
+        &self.stages
+    }
+
+    /// Removes and returns all recorded system timings.
+    pub fn drain_systems(&mut self) -> impl Iterator<Item = (Cow<'static, str>, Duration)> + '_ {
+        self.systems.drain(..)
+    }
+
+    /// Removes and returns all recorded stage timings.
+    pub fn drain_stages(&mut self) -> impl Iterator<Item = (Cow<'static, str>, Duration)> + '_ {
+        self.stages.drain(..)
+    }
+
+    /// Removes all recorded timings.
+    pub fn clear(&mut self) {
+        self.systems.clear();
+        self.stages.clear();
+    }
+}
+
+/// Records a system timing if `world` contains an [`ExecutionTimings`] resource.
+pub(super) fn record_system_timing(world: &mut World, name: Cow<'static, str>, duration: Duration) {
+    if let Some(mut timings) = world.get_resource_mut::<ExecutionTimings>() {
+        timings.record_system(name, duration);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        schedule::{
+            ExecutionTimings, IntoSystemDescriptor, ParallelExecutor, Schedule,
+            SingleThreadedExecutor, Stage, SystemStage,
+        },
+        world::World,
+    };
+
+    fn parallel_system() {}
+
+    fn exclusive_system(_world: &mut World) {}
+
+    fn run_and_collect(stage: SystemStage) -> (Vec<String>, Vec<String>) {
+        let mut world = World::new();
+        world.init_resource::<ExecutionTimings>();
+        let mut schedule = Schedule::default();
+        schedule.add_stage("update", stage);
+        schedule.run(&mut world);
+
+        let mut timings = world.resource_mut::<ExecutionTimings>();
+        let systems = timings
+            .drain_systems()
+            .map(|(name, _)| name.into_owned())
+            .collect();
+        let stages = timings
+            .drain_stages()
+            .map(|(name, _)| name.into_owned())
+            .collect();
+        (systems, stages)
+    }
+
+    #[test]
+    fn records_parallel_executor() {
+        let stage = SystemStage::new(Box::<ParallelExecutor>::default())
+            .with_system(parallel_system)
+            .with_system(exclusive_system.at_start());
+        let (mut systems, stages) = run_and_collect(stage);
+        systems.sort();
+
+        assert_eq!(systems.len(), 2);
+        assert!(systems[0].ends_with("exclusive_system"));
+        assert!(systems[1].ends_with("parallel_system"));
+        assert_eq!(stages, vec!["update".to_string()]);
+    }
+
+    #[test]
+    fn records_single_threaded_executor() {
+        let stage =
+            SystemStage::new(Box::<SingleThreadedExecutor>::default()).with_system(parallel_system);
+        let (systems, stages) = run_and_collect(stage);
+
+        assert_eq!(systems.len(), 1);
+        assert!(systems[0].ends_with("parallel_system"));
+        assert_eq!(stages, vec!["update".to_string()]);
+    }
+}
@@ -113,6 +113,18 @@ impl ComponentSparseSet {
         self.dense.len() == 0
     }
 
+    /// Returns the number of bytes allocated for this sparse set's component data, change ticks
+    /// and entity mappings.
+    ///
+    /// This does not include heap memory owned by the components themselves.
+    pub fn allocated_bytes(&self) -> usize {
+        fn vec_bytes<T>(vec: &Vec<T>) -> usize {
+            vec.capacity() * std::mem::size_of::<T>()
+        }
+
+        self.dense.allocated_bytes() + vec_bytes(&self.entities) + vec_bytes(&self.sparse.values)
+    }
+
     /// Inserts the `entity` key and component `value` pair into this sparse
     /// set.
     ///
@@ -424,6 +436,11 @@ impl SparseSets {
         self.sets.get_mut(component_id)
     }
 
+    /// Returns an iterator over every [`ComponentSparseSet`] and the [`ComponentId`] it stores.
+    pub fn iter(&self) -> impl Iterator<Item = (ComponentId, &ComponentSparseSet)> {
+        self.sets.iter().map(|(id, set)| (*id, set))
+    }
+
     pub fn clear(&mut self) {
         for set in self.sets.values_mut() {
             set.clear();
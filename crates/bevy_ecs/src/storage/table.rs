@@ -53,6 +53,15 @@ impl Column {
         self.data.layout()
     }
 
+    /// Returns the number of bytes allocated for this column's component data and change ticks.
+    ///
+    /// This does not include heap memory owned by the components themselves.
+    #[inline]
+    pub fn allocated_bytes(&self) -> usize {
+        self.data.capacity() * self.data.layout().size()
+            + self.ticks.capacity() * std::mem::size_of::<UnsafeCell<ComponentTicks>>()
+    }
+
     /// Writes component data to the column at given row.
     /// Assumes the slot is uninitialized, drop is not called.
     /// To overwrite existing initialized value, use `replace` instead.
@@ -467,6 +476,17 @@ impl Table {
         self.entities.is_empty()
     }
 
+    /// Returns the number of bytes allocated for this table's columns and entity list.
+    ///
+    /// This does not include heap memory owned by the components themselves.
+    pub fn allocated_bytes(&self) -> usize {
+        self.columns
+            .values()
+            .map(Column::allocated_bytes)
+            .sum::<usize>()
+            + self.entities.capacity() * std::mem::size_of::<Entity>()
+    }
+
     pub(crate) fn check_change_ticks(&mut self, change_tick: u32) {
         for column in self.columns.values_mut() {
             column.check_change_ticks(change_tick);
@@ -633,4 +653,36 @@ mod tests {
         assert_eq!(table.entity_capacity(), 256);
         assert_eq!(table.entity_count(), 200);
     }
+
+    #[test]
+    fn table_allocated_bytes() {
+        let mut components = Components::default();
+        let mut storages = Storages::default();
+        let component_id = components.init_component::<W<usize>>(&mut storages);
+        let mut table = Table::with_capacity(0, 1);
+        table.add_column(components.get_info(component_id).unwrap());
+        assert_eq!(table.allocated_bytes(), 0);
+
+        for entity in (0..200).map(Entity::from_raw) {
+            // SAFETY: we allocate and immediately set data afterwards
+            unsafe {
+                let row = table.allocate(entity);
+                OwningPtr::make(W(row), |value_ptr| {
+                    table.get_column_mut(component_id).unwrap().initialize(
+                        row,
+                        value_ptr,
+                        ComponentTicks::new(0),
+                    );
+                });
+            };
+        }
+
+        assert_eq!(
+            table.allocated_bytes(),
+            table.entity_capacity()
+                * (std::mem::size_of::<usize>()
+                    + std::mem::size_of::<ComponentTicks>()
+                    + std::mem::size_of::<Entity>())
+        );
+    }
 }
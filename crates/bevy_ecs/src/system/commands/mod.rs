@@ -1041,7 +1041,7 @@ mod tests {
             .iter(&world)
             .map(|v| v.0)
             .collect::<Vec<_>>();
-        assert_eq!(results_after_u64, vec![]);
+        assert_eq!(results_after_u64, Vec::<u64>::new());
     }
 
     #[test]
@@ -1,6 +1,7 @@
 use anyhow::Result;
 use bevy_asset::{
-    AssetIoError, AssetLoader, AssetPath, BoxedFuture, Handle, LoadContext, LoadedAsset,
+    deserialize_settings, AssetIoError, AssetLoader, BoxedFuture, Handle, LoadContext, LoadedAsset,
+    SettingsDeserializer,
 };
 use bevy_core::Name;
 use bevy_core_pipeline::prelude::Camera3d;
@@ -40,6 +41,7 @@ use gltf::{
     texture::{MagFilter, MinFilter, WrappingMode},
     Material, Node, Primitive,
 };
+use serde::{Deserialize, Serialize};
 use std::{collections::VecDeque, path::Path};
 use thiserror::Error;
 
@@ -89,6 +91,26 @@ impl AssetLoader for GltfLoader {
     fn extensions(&self) -> &[&str] {
         &["gltf", "glb"]
     }
+
+    fn settings_deserializer(&self) -> Option<SettingsDeserializer> {
+        Some(deserialize_settings::<GltfLoaderSettings>)
+    }
+}
+
+/// Settings of the [`GltfLoader`], read from the `.meta` file of a glTF file.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(default)]
+pub struct GltfLoaderSettings {
+    /// Whether to load the animations of the file. Only used with the `bevy_animation` feature.
+    pub load_animations: bool,
+}
+
+impl Default for GltfLoaderSettings {
+    fn default() -> Self {
+        Self {
+            load_animations: true,
+        }
+    }
 }
 
 impl FromWorld for GltfLoader {
@@ -111,6 +133,11 @@ async fn load_gltf<'a, 'b>(
     supported_compressed_formats: CompressedImageFormats,
 ) -> Result<(), GltfError> {
     let gltf = gltf::Gltf::from_slice(bytes)?;
+    #[cfg(feature = "bevy_animation")]
+    let settings = load_context
+        .settings::<GltfLoaderSettings>()
+        .cloned()
+        .unwrap_or_default();
     let buffer_data = load_buffers(&gltf, load_context, load_context.path()).await?;
 
     let mut materials = vec![];
@@ -153,7 +180,7 @@ async fn load_gltf<'a, 'b>(
         let mut animations = vec![];
         let mut named_animations = HashMap::default();
         let mut animation_roots = HashSet::default();
-        for animation in gltf.animations() {
+        for animation in gltf.animations().filter(|_| settings.load_animations) {
             let mut animation_clip = bevy_animation::AnimationClip::default();
             for channel in animation.channels() {
                 match channel.sampler().interpolation() {
@@ -629,7 +656,7 @@ fn load_material(material: &Material, load_context: &mut LoadContext) -> Handle<
     let base_color_texture = pbr.base_color_texture().map(|info| {
         // TODO: handle info.tex_coord() (the *set* index for the right texcoords)
         let label = texture_label(&info.texture());
-        let path = AssetPath::new_ref(load_context.path(), Some(&label));
+        let path = load_context.labeled_asset_path(&label);
         load_context.get_handle(path)
     });
 
@@ -638,14 +665,14 @@ fn load_material(material: &Material, load_context: &mut LoadContext) -> Handle<
             // TODO: handle normal_texture.scale
             // TODO: handle normal_texture.tex_coord() (the *set* index for the right texcoords)
             let label = texture_label(&normal_texture.texture());
-            let path = AssetPath::new_ref(load_context.path(), Some(&label));
+            let path = load_context.labeled_asset_path(&label);
             load_context.get_handle(path)
         });
 
     let metallic_roughness_texture = pbr.metallic_roughness_texture().map(|info| {
         // TODO: handle info.tex_coord() (the *set* index for the right texcoords)
         let label = texture_label(&info.texture());
-        let path = AssetPath::new_ref(load_context.path(), Some(&label));
+        let path = load_context.labeled_asset_path(&label);
         load_context.get_handle(path)
     });
 
@@ -653,7 +680,7 @@ fn load_material(material: &Material, load_context: &mut LoadContext) -> Handle<
         // TODO: handle occlusion_texture.tex_coord() (the *set* index for the right texcoords)
         // TODO: handle occlusion_texture.strength() (a scalar multiplier for occlusion strength)
         let label = texture_label(&occlusion_texture.texture());
-        let path = AssetPath::new_ref(load_context.path(), Some(&label));
+        let path = load_context.labeled_asset_path(&label);
         load_context.get_handle(path)
     });
 
@@ -662,7 +689,7 @@ fn load_material(material: &Material, load_context: &mut LoadContext) -> Handle<
         // TODO: handle occlusion_texture.tex_coord() (the *set* index for the right texcoords)
         // TODO: handle occlusion_texture.strength() (a scalar multiplier for occlusion strength)
         let label = texture_label(&info.texture());
-        let path = AssetPath::new_ref(load_context.path(), Some(&label));
+        let path = load_context.labeled_asset_path(&label);
         load_context.get_handle(path)
     });
 
@@ -779,10 +806,8 @@ fn load_node(
 
                 let primitive_label = primitive_label(&mesh, &primitive);
                 let bounds = primitive.bounding_box();
-                let mesh_asset_path =
-                    AssetPath::new_ref(load_context.path(), Some(&primitive_label));
-                let material_asset_path =
-                    AssetPath::new_ref(load_context.path(), Some(&material_label));
+                let mesh_asset_path = load_context.labeled_asset_path(&primitive_label);
+                let material_asset_path = load_context.labeled_asset_path(&material_label);
 
                 let mut mesh_entity = parent.spawn(PbrBundle {
                     mesh: load_context.get_handle(mesh_asset_path),
@@ -5,6 +5,7 @@ use bevy_app::{PluginGroup, PluginGroupBuilder};
 /// * [`CorePlugin`](bevy_core::CorePlugin)
 /// * [`TimePlugin`](bevy_time::TimePlugin)
 /// * [`TransformPlugin`](bevy_transform::TransformPlugin)
+/// * [`TransformInterpolationPlugin`](bevy_transform::TransformInterpolationPlugin)
 /// * [`HierarchyPlugin`](bevy_hierarchy::HierarchyPlugin)
 /// * [`DiagnosticsPlugin`](bevy_diagnostic::DiagnosticsPlugin)
 /// * [`InputPlugin`](bevy_input::InputPlugin)
@@ -32,6 +33,7 @@ impl PluginGroup for DefaultPlugins {
             .add(bevy_core::CorePlugin::default())
             .add(bevy_time::TimePlugin::default())
             .add(bevy_transform::TransformPlugin::default())
+            .add(bevy_transform::TransformInterpolationPlugin::default())
             .add(bevy_hierarchy::HierarchyPlugin::default())
             .add(bevy_diagnostic::DiagnosticsPlugin::default())
             .add(bevy_input::InputPlugin::default())
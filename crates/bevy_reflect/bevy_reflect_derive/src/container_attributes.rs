@@ -5,14 +5,14 @@
 //! the derive helper attribute for `Reflect`, which looks like:
 //! `#[reflect(PartialEq, Default, ...)]` and `#[reflect_value(PartialEq, Default, ...)]`.
 
-use crate::utility;
-use proc_macro2::{Ident, Span};
+use crate::{utility, REFLECT_ATTRIBUTE_NAME};
+use proc_macro2::{Ident, Span, TokenStream, TokenTree};
 use quote::quote_spanned;
-use syn::parse::{Parse, ParseStream};
+use syn::parse::{Parse, ParseStream, Parser};
 use syn::punctuated::Punctuated;
 use syn::spanned::Spanned;
 use syn::token::Comma;
-use syn::{Meta, NestedMeta, Path};
+use syn::{Attribute, Meta, NestedMeta, Path, Token, WherePredicate};
 
 // The "special" trait idents that are used internally for reflection.
 // Received via attributes like `#[reflect(PartialEq, Hash, ...)]`
@@ -323,3 +323,38 @@ fn add_unique_ident(idents: &mut Vec<Ident>, ident: Ident) -> Result<(), syn::Er
     idents.push(ident);
     Ok(())
 }
+
+/// Parses the predicates of a `#[reflect(where T: Trait, ...)]` attribute.
+///
+/// The predicates are added to the where clause of the generated impls, so that a type is only
+/// reflected when they hold, without adding them to the bounds of the type itself:
+///
+/// ```ignore
+/// #[derive(Reflect)]
+/// #[reflect(where T: FromReflect + Eq + Hash)]
+/// struct Foo<T> {
+///     values: HashSet<T>,
+/// }
+/// ```
+///
+/// Returns `None` if `attribute` is not a `where` attribute.
+pub(crate) fn parse_where_predicates(
+    attribute: &Attribute,
+) -> syn::Result<Option<Punctuated<WherePredicate, Comma>>> {
+    if !attribute.path.is_ident(REFLECT_ATTRIBUTE_NAME) {
+        return Ok(None);
+    }
+    let Ok(tokens) = attribute.parse_args::<TokenStream>() else {
+        return Ok(None);
+    };
+    if !matches!(tokens.clone().into_iter().next(), Some(TokenTree::Ident(ident)) if ident == "where")
+    {
+        return Ok(None);
+    }
+
+    let parser = |input: ParseStream| {
+        input.parse::<Token![where]>()?;
+        Punctuated::<WherePredicate, Comma>::parse_terminated(input)
+    };
+    parser.parse2(tokens).map(Some)
+}
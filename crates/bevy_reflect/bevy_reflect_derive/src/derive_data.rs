@@ -1,4 +1,4 @@
-use crate::container_attributes::ReflectTraits;
+use crate::container_attributes::{parse_where_predicates, ReflectTraits};
 use crate::field_attributes::{parse_field_attrs, ReflectFieldAttr};
 use crate::utility::members_to_serialization_denylist;
 use bit_set::BitSet;
@@ -7,7 +7,9 @@ use quote::quote;
 use crate::{utility, REFLECT_ATTRIBUTE_NAME, REFLECT_VALUE_ATTRIBUTE_NAME};
 use syn::punctuated::Punctuated;
 use syn::spanned::Spanned;
-use syn::{Data, DeriveInput, Field, Fields, Generics, Ident, Meta, Path, Token, Variant};
+use syn::{
+    Data, DeriveInput, Field, Fields, Generics, Ident, Meta, Path, Token, Variant, WherePredicate,
+};
 
 pub(crate) enum ReflectDerive<'a> {
     Struct(ReflectStruct<'a>),
@@ -35,8 +37,8 @@ pub(crate) struct ReflectMeta<'a> {
     traits: ReflectTraits,
     /// The name of this type.
     type_name: &'a Ident,
-    /// The generics defined on this type.
-    generics: &'a Generics,
+    /// The generics defined on this type, with the predicates of `#[reflect(where ...)]`.
+    generics: Generics,
     /// A cached instance of the path to the `bevy_reflect` crate.
     bevy_reflect_path: Path,
     /// The documentation for this type, if any
@@ -135,6 +137,13 @@ impl<'a> ReflectDerive<'a> {
         #[cfg(feature = "documentation")]
         let mut doc = crate::documentation::Documentation::default();
 
+        let mut where_predicates = Vec::new();
+        for attribute in &input.attrs {
+            if let Some(predicates) = parse_where_predicates(attribute)? {
+                where_predicates.extend(predicates);
+            }
+        }
+
         for attribute in input.attrs.iter().filter_map(|attr| attr.parse_meta().ok()) {
             match attribute {
                 Meta::List(meta_list) if meta_list.path.is_ident(REFLECT_ATTRIBUTE_NAME) => {
@@ -181,7 +190,8 @@ impl<'a> ReflectDerive<'a> {
             }
         }
 
-        let meta = ReflectMeta::new(&input.ident, &input.generics, traits);
+        let meta = ReflectMeta::new(&input.ident, &input.generics, traits)
+            .with_where_predicates(where_predicates);
 
         #[cfg(feature = "documentation")]
         let meta = meta.with_docs(doc);
@@ -278,17 +288,30 @@ impl<'a> ReflectDerive<'a> {
 }
 
 impl<'a> ReflectMeta<'a> {
-    pub fn new(type_name: &'a Ident, generics: &'a Generics, traits: ReflectTraits) -> Self {
+    pub fn new(type_name: &'a Ident, generics: &Generics, traits: ReflectTraits) -> Self {
         Self {
             traits,
             type_name,
-            generics,
+            generics: generics.clone(),
             bevy_reflect_path: utility::get_bevy_reflect_path(),
             #[cfg(feature = "documentation")]
             docs: Default::default(),
         }
     }
 
+    /// Adds `predicates` to the where clause of the generated impls.
+    pub fn with_where_predicates(mut self, predicates: Vec<WherePredicate>) -> Self {
+        if !predicates.is_empty() {
+            let where_clause = self.generics.make_where_clause();
+            where_clause.predicates.extend(predicates);
+            // Some impls append their own predicates after the where clause
+            if !where_clause.predicates.empty_or_trailing() {
+                where_clause.predicates.push_punct(Default::default());
+            }
+        }
+        self
+    }
+
     /// Sets the documentation for this type.
     #[cfg(feature = "documentation")]
     pub fn with_docs(self, docs: crate::documentation::Documentation) -> Self {
@@ -306,8 +329,8 @@ impl<'a> ReflectMeta<'a> {
     }
 
     /// The generics associated with this struct.
-    pub fn generics(&self) -> &'a Generics {
-        self.generics
+    pub fn generics(&self) -> &Generics {
+        &self.generics
     }
 
     /// The cached `bevy_reflect` path.
@@ -321,7 +344,7 @@ impl<'a> ReflectMeta<'a> {
             self.type_name,
             &self.bevy_reflect_path,
             self.traits.idents(),
-            self.generics,
+            &self.generics,
             None,
         )
     }
@@ -0,0 +1,933 @@
+use std::{
+    fmt::{Display, Formatter},
+    ops::Range,
+};
+
+use crate::{List, Map, Reflect, ReflectMut, ReflectRef, Set, VariantType};
+use thiserror::Error;
+
+/// Identifies a field of a struct, tuple struct, tuple or enum variant, or an element of an
+/// array, in a [`Diff::Fields`].
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum FieldKey {
+    /// A named field of a struct or struct variant.
+    Name(String),
+    /// A field of a tuple struct, tuple or tuple variant, or an element of an array.
+    Index(usize),
+}
+
+impl Display for FieldKey {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FieldKey::Name(name) => write!(f, "`{name}`"),
+            FieldKey::Index(index) => write!(f, "{index}"),
+        }
+    }
+}
+
+/// The changes that turn one reflected value into another, computed with [`diff`].
+///
+/// Only the changed parts of a value are recorded, along with their previous contents, so a diff
+/// can be [applied](Diff::apply) to any value with the same layout and [inverted](Diff::invert) to
+/// undo it. Diffs are serialized with [`DiffSerializer`](crate::serde::DiffSerializer) and
+/// deserialized with [`DiffDeserializer`](crate::serde::DiffDeserializer).
+#[derive(Debug)]
+pub enum Diff {
+    /// The whole value changed.
+    ///
+    /// This is used for [value types](ReflectRef::Value), enum variant switches, and values whose
+    /// type or layout changed.
+    Replace {
+        old: Box<dyn Reflect>,
+        new: Box<dyn Reflect>,
+    },
+    /// Some fields of a struct, tuple struct, tuple or enum variant, or elements of an array,
+    /// changed.
+    Fields(Vec<(FieldKey, Diff)>),
+    /// Elements of a list changed, were inserted or were removed, in order.
+    List(Vec<ListChange>),
+    /// Entries of a map changed, were inserted or were removed.
+    Map(Vec<MapChange>),
+    /// Elements of a set were inserted or removed.
+    Set(Vec<SetChange>),
+}
+
+/// A change to a list in a [`Diff::List`].
+///
+/// The index of a change is the index in the list once the previous changes were applied.
+#[derive(Debug)]
+pub enum ListChange {
+    /// The element at `index` changed.
+    Modify { index: usize, diff: Diff },
+    /// `value` was inserted at `index`.
+    Insert {
+        index: usize,
+        value: Box<dyn Reflect>,
+    },
+    /// `value` was removed from `index`.
+    Remove {
+        index: usize,
+        value: Box<dyn Reflect>,
+    },
+}
+
+/// A change to a map in a [`Diff::Map`].
+#[derive(Debug)]
+pub enum MapChange {
+    /// The value of `key` changed.
+    Modify { key: Box<dyn Reflect>, diff: Diff },
+    /// `key` was inserted with `value`.
+    Insert {
+        key: Box<dyn Reflect>,
+        value: Box<dyn Reflect>,
+    },
+    /// `key` was removed, it had `value`.
+    Remove {
+        key: Box<dyn Reflect>,
+        value: Box<dyn Reflect>,
+    },
+}
+
+/// A change to a set in a [`Diff::Set`].
+#[derive(Debug)]
+pub enum SetChange {
+    /// The value was inserted.
+    Insert(Box<dyn Reflect>),
+    /// The value was removed.
+    Remove(Box<dyn Reflect>),
+}
+
+/// An error returned when a [`Diff`] cannot be applied to a value.
+#[derive(Debug, PartialEq, Eq, Error)]
+pub enum DiffError {
+    #[error("the value doesn't have the field {0}")]
+    MissingField(FieldKey),
+    #[error("the list doesn't have a value at the index {0}")]
+    InvalidListIndex(usize),
+    #[error("the map doesn't have a value for the key {0}")]
+    MissingKey(String),
+    #[error("the set doesn't have the value {0}")]
+    MissingValue(String),
+    #[error("expected {expected}, but found a different reflect value")]
+    KindMismatch { expected: &'static str },
+    #[error("cannot replace a value of type `{target}` with a value of type `{value}`")]
+    TypeMismatch { target: String, value: String },
+}
+
+/// Computes the changes that turn `old` into `new`, or returns `None` if they are equal.
+///
+/// Structs, tuple structs, tuples, arrays and enums of the same variant are compared field by
+/// field, lists by their longest common subsequence of equal elements, maps key by key, and sets
+/// by their inserted and removed elements. Any other change is recorded as a [`Diff::Replace`].
+pub fn diff(old: &dyn Reflect, new: &dyn Reflect) -> Option<Diff> {
+    if old.type_name() != new.type_name() {
+        return Some(replace(old, new));
+    }
+    match (old.reflect_ref(), new.reflect_ref()) {
+        (ReflectRef::Struct(old_struct), ReflectRef::Struct(new_struct)) => {
+            if old_struct.field_len() != new_struct.field_len() {
+                return Some(replace(old, new));
+            }
+            let mut fields = Vec::new();
+            for (index, new_field) in new_struct.iter_fields().enumerate() {
+                let name = new_struct.name_at(index).unwrap();
+                let Some(old_field) = old_struct.field(name) else {
+                    return Some(replace(old, new));
+                };
+                if let Some(diff) = diff(old_field, new_field) {
+                    fields.push((FieldKey::Name(name.to_string()), diff));
+                }
+            }
+            fields_diff(fields)
+        }
+        (ReflectRef::TupleStruct(old_tuple), ReflectRef::TupleStruct(new_tuple)) => {
+            if old_tuple.field_len() != new_tuple.field_len() {
+                return Some(replace(old, new));
+            }
+            indexed_diff(old_tuple.iter_fields().zip(new_tuple.iter_fields()))
+        }
+        (ReflectRef::Tuple(old_tuple), ReflectRef::Tuple(new_tuple)) => {
+            if old_tuple.field_len() != new_tuple.field_len() {
+                return Some(replace(old, new));
+            }
+            indexed_diff(old_tuple.iter_fields().zip(new_tuple.iter_fields()))
+        }
+        (ReflectRef::Array(old_array), ReflectRef::Array(new_array)) => {
+            if old_array.len() != new_array.len() {
+                return Some(replace(old, new));
+            }
+            indexed_diff(old_array.iter().zip(new_array.iter()))
+        }
+        (ReflectRef::List(old_list), ReflectRef::List(new_list)) => list_diff(old_list, new_list),
+        (ReflectRef::Map(old_map), ReflectRef::Map(new_map)) => map_diff(old_map, new_map),
+        (ReflectRef::Set(old_set), ReflectRef::Set(new_set)) => set_diff(old_set, new_set),
+        (ReflectRef::Enum(old_enum), ReflectRef::Enum(new_enum)) => {
+            if old_enum.variant_name() != new_enum.variant_name()
+                || old_enum.field_len() != new_enum.field_len()
+            {
+                return Some(replace(old, new));
+            }
+            match new_enum.variant_type() {
+                VariantType::Struct => {
+                    let mut fields = Vec::new();
+                    for (index, new_field) in new_enum.iter_fields().enumerate() {
+                        let name = new_enum.name_at(index).unwrap();
+                        let Some(old_field) = old_enum.field(name) else {
+                            return Some(replace(old, new));
+                        };
+                        if let Some(diff) = diff(old_field, new_field.value()) {
+                            fields.push((FieldKey::Name(name.to_string()), diff));
+                        }
+                    }
+                    fields_diff(fields)
+                }
+                VariantType::Tuple => indexed_diff(
+                    old_enum
+                        .iter_fields()
+                        .map(|field| field.value())
+                        .zip(new_enum.iter_fields().map(|field| field.value())),
+                ),
+                VariantType::Unit => None,
+            }
+        }
+        _ => {
+            if matches!(old.reflect_partial_eq(new), Some(true)) {
+                None
+            } else {
+                Some(replace(old, new))
+            }
+        }
+    }
+}
+
+fn replace(old: &dyn Reflect, new: &dyn Reflect) -> Diff {
+    Diff::Replace {
+        old: old.clone_value(),
+        new: new.clone_value(),
+    }
+}
+
+fn fields_diff(fields: Vec<(FieldKey, Diff)>) -> Option<Diff> {
+    if fields.is_empty() {
+        None
+    } else {
+        Some(Diff::Fields(fields))
+    }
+}
+
+fn indexed_diff<'a>(
+    fields: impl Iterator<Item = (&'a dyn Reflect, &'a dyn Reflect)>,
+) -> Option<Diff> {
+    fields_diff(
+        fields
+            .enumerate()
+            .filter_map(|(index, (old, new))| Some((FieldKey::Index(index), diff(old, new)?)))
+            .collect(),
+    )
+}
+
+fn list_diff(old: &dyn List, new: &dyn List) -> Option<Diff> {
+    let (old_len, new_len) = (old.len(), new.len());
+    let equal = |i: usize, j: usize| {
+        matches!(
+            old.get(i).unwrap().reflect_partial_eq(new.get(j).unwrap()),
+            Some(true)
+        )
+    };
+
+    let mut common = Vec::new();
+    common_subsequence(&equal, 0..old_len, 0..new_len, &mut common);
+    // the ends of the lists close the last run of changes
+    common.push((old_len, new_len));
+
+    let mut changes = Vec::new();
+    // the index of the next element in the list being patched
+    let mut index = 0;
+    let (mut i, mut j) = (0, 0);
+    for (common_i, common_j) in common {
+        // elements that were both removed and inserted between two common elements are
+        // modified in place
+        let (removed, inserted) = (i..common_i, j..common_j);
+        let modified = removed.len().min(inserted.len());
+        for (old_index, new_index) in removed.clone().zip(inserted.clone()) {
+            let old_value = old.get(old_index).unwrap();
+            let new_value = new.get(new_index).unwrap();
+            if let Some(diff) = diff(old_value, new_value) {
+                changes.push(ListChange::Modify { index, diff });
+            }
+            index += 1;
+        }
+        for old_index in removed.start + modified..removed.end {
+            changes.push(ListChange::Remove {
+                index,
+                value: old.get(old_index).unwrap().clone_value(),
+            });
+        }
+        for new_index in inserted.start + modified..inserted.end {
+            changes.push(ListChange::Insert {
+                index,
+                value: new.get(new_index).unwrap().clone_value(),
+            });
+            index += 1;
+        }
+
+        i = common_i + 1;
+        j = common_j + 1;
+        index += 1;
+    }
+
+    if changes.is_empty() {
+        None
+    } else {
+        Some(Diff::List(changes))
+    }
+}
+
+/// Appends the index pairs of a longest common subsequence of the `old` and `new` ranges of
+/// two lists to `common`, in order.
+///
+/// This is Hirschberg's algorithm, which only keeps two rows of lengths at a time, so diffing
+/// long lists doesn't need memory proportional to the product of their lengths.
+fn common_subsequence(
+    equal: &impl Fn(usize, usize) -> bool,
+    mut old: Range<usize>,
+    mut new: Range<usize>,
+    common: &mut Vec<(usize, usize)>,
+) {
+    // common prefixes and suffixes are part of the subsequence, and usually make up most of it
+    while !old.is_empty() && !new.is_empty() && equal(old.start, new.start) {
+        common.push((old.start, new.start));
+        old.start += 1;
+        new.start += 1;
+    }
+    let mut suffix = 0;
+    while !old.is_empty() && !new.is_empty() && equal(old.end - 1, new.end - 1) {
+        old.end -= 1;
+        new.end -= 1;
+        suffix += 1;
+    }
+
+    if old.len() == 1 {
+        if let Some(j) = new.clone().find(|&j| equal(old.start, j)) {
+            common.push((old.start, j));
+        }
+    } else if !old.is_empty() && !new.is_empty() {
+        // split the new range where the subsequences of both halves of the old range are longest
+        let middle = old.start + old.len() / 2;
+        let before = prefix_lengths(equal, old.start..middle, new.clone());
+        let after = suffix_lengths(equal, middle..old.end, new.clone());
+        let split = (0..=new.len())
+            .max_by_key(|&k| (before[k] + after[k], std::cmp::Reverse(k)))
+            .unwrap();
+        common_subsequence(
+            equal,
+            old.start..middle,
+            new.start..new.start + split,
+            common,
+        );
+        common_subsequence(equal, middle..old.end, new.start + split..new.end, common);
+    }
+
+    common.extend((0..suffix).map(|k| (old.end + k, new.end + k)));
+}
+
+/// Returns the lengths of the longest common subsequences of the `old` range and each prefix of
+/// the `new` range.
+fn prefix_lengths(
+    equal: &impl Fn(usize, usize) -> bool,
+    old: Range<usize>,
+    new: Range<usize>,
+) -> Vec<usize> {
+    let mut lengths = vec![0; new.len() + 1];
+    for i in old {
+        let mut diagonal = 0;
+        for (k, j) in new.clone().enumerate() {
+            let above = lengths[k + 1];
+            lengths[k + 1] = if equal(i, j) {
+                diagonal + 1
+            } else {
+                above.max(lengths[k])
+            };
+            diagonal = above;
+        }
+    }
+    lengths
+}
+
+/// Returns the lengths of the longest common subsequences of the `old` range and each suffix of
+/// the `new` range, indexed by where the suffix starts in the range.
+fn suffix_lengths(
+    equal: &impl Fn(usize, usize) -> bool,
+    old: Range<usize>,
+    new: Range<usize>,
+) -> Vec<usize> {
+    let mut lengths = vec![0; new.len() + 1];
+    for i in old.rev() {
+        let mut diagonal = 0;
+        for (k, j) in new.clone().enumerate().rev() {
+            let below = lengths[k];
+            lengths[k] = if equal(i, j) {
+                diagonal + 1
+            } else {
+                below.max(lengths[k + 1])
+            };
+            diagonal = below;
+        }
+    }
+    lengths
+}
+
+fn map_diff(old: &dyn Map, new: &dyn Map) -> Option<Diff> {
+    let mut changes = Vec::new();
+    for (key, old_value) in old.iter() {
+        match new.get(key) {
+            Some(new_value) => {
+                if let Some(diff) = diff(old_value, new_value) {
+                    changes.push(MapChange::Modify {
+                        key: key.clone_value(),
+                        diff,
+                    });
+                }
+            }
+            None => changes.push(MapChange::Remove {
+                key: key.clone_value(),
+                value: old_value.clone_value(),
+            }),
+        }
+    }
+    for (key, new_value) in new.iter() {
+        if old.get(key).is_none() {
+            changes.push(MapChange::Insert {
+                key: key.clone_value(),
+                value: new_value.clone_value(),
+            });
+        }
+    }
+
+    if changes.is_empty() {
+        None
+    } else {
+        Some(Diff::Map(changes))
+    }
+}
+
+fn set_diff(old: &dyn Set, new: &dyn Set) -> Option<Diff> {
+    let removed = old
+        .iter()
+        .filter(|value| !new.contains(*value))
+        .map(|value| SetChange::Remove(value.clone_value()));
+    let inserted = new
+        .iter()
+        .filter(|value| !old.contains(*value))
+        .map(|value| SetChange::Insert(value.clone_value()));
+    let changes: Vec<_> = removed.chain(inserted).collect();
+
+    if changes.is_empty() {
+        None
+    } else {
+        Some(Diff::Set(changes))
+    }
+}
+
+fn kind_name(value: ReflectRef) -> &'static str {
+    match value {
+        ReflectRef::Struct(_) => "a struct",
+        ReflectRef::TupleStruct(_) => "a tuple struct",
+        ReflectRef::Tuple(_) => "a tuple",
+        ReflectRef::List(_) => "a list",
+        ReflectRef::Array(_) => "an array",
+        ReflectRef::Map(_) => "a map",
+        ReflectRef::Set(_) => "a set",
+        ReflectRef::Enum(_) => "an enum",
+        ReflectRef::Value(_) => "a value",
+    }
+}
+
+impl Diff {
+    /// Applies the changes to `target`.
+    ///
+    /// `target` doesn't have to be the value the diff was computed from, only to have its
+    /// layout. Changes are applied in order until one fails, so `target` may be partially
+    /// changed when an error is returned.
+    pub fn apply(&self, target: &mut dyn Reflect) -> Result<(), DiffError> {
+        match self {
+            Diff::Replace { new, .. } => {
+                let Err(value) = target.set(new.clone_value()) else {
+                    return Ok(());
+                };
+                // values deserialized or cloned from non-value types are dynamic
+                let target_kind = kind_name(target.reflect_ref());
+                if matches!(target.reflect_ref(), ReflectRef::Value(_))
+                    || target_kind != kind_name(value.reflect_ref())
+                {
+                    return Err(DiffError::TypeMismatch {
+                        target: target.type_name().to_string(),
+                        value: value.type_name().to_string(),
+                    });
+                }
+                target.apply(&*value);
+                Ok(())
+            }
+            Diff::Fields(fields) => {
+                for (key, diff) in fields {
+                    let field = match (target.reflect_mut(), key) {
+                        (ReflectMut::Struct(value), FieldKey::Name(name)) => value.field_mut(name),
+                        (ReflectMut::Enum(value), FieldKey::Name(name)) => value.field_mut(name),
+                        (ReflectMut::TupleStruct(value), FieldKey::Index(index)) => {
+                            value.field_mut(*index)
+                        }
+                        (ReflectMut::Tuple(value), FieldKey::Index(index)) => {
+                            value.field_mut(*index)
+                        }
+                        (ReflectMut::Array(value), FieldKey::Index(index)) => value.get_mut(*index),
+                        (ReflectMut::List(value), FieldKey::Index(index)) => value.get_mut(*index),
+                        (ReflectMut::Enum(value), FieldKey::Index(index)) => {
+                            value.field_at_mut(*index)
+                        }
+                        _ => {
+                            return Err(DiffError::KindMismatch {
+                                expected: "a value with fields",
+                            })
+                        }
+                    };
+                    let field = field.ok_or_else(|| DiffError::MissingField(key.clone()))?;
+                    diff.apply(field)?;
+                }
+                Ok(())
+            }
+            Diff::List(changes) => {
+                let ReflectMut::List(list) = target.reflect_mut() else {
+                    return Err(DiffError::KindMismatch { expected: "a list" });
+                };
+                for change in changes {
+                    change.apply(list)?;
+                }
+                Ok(())
+            }
+            Diff::Map(changes) => {
+                let ReflectMut::Map(map) = target.reflect_mut() else {
+                    return Err(DiffError::KindMismatch { expected: "a map" });
+                };
+                for change in changes {
+                    change.apply(map)?;
+                }
+                Ok(())
+            }
+            Diff::Set(changes) => {
+                let ReflectMut::Set(set) = target.reflect_mut() else {
+                    return Err(DiffError::KindMismatch { expected: "a set" });
+                };
+                for change in changes {
+                    change.apply(set)?;
+                }
+                Ok(())
+            }
+        }
+    }
+
+    /// Returns the diff that undoes this one.
+    pub fn invert(self) -> Diff {
+        match self {
+            Diff::Replace { old, new } => Diff::Replace { old: new, new: old },
+            Diff::Fields(fields) => Diff::Fields(
+                fields
+                    .into_iter()
+                    .map(|(key, diff)| (key, diff.invert()))
+                    .collect(),
+            ),
+            Diff::List(changes) => {
+                Diff::List(changes.into_iter().rev().map(ListChange::invert).collect())
+            }
+            Diff::Map(changes) => Diff::Map(changes.into_iter().map(MapChange::invert).collect()),
+            Diff::Set(changes) => Diff::Set(changes.into_iter().map(SetChange::invert).collect()),
+        }
+    }
+}
+
+impl Clone for Diff {
+    fn clone(&self) -> Self {
+        match self {
+            Diff::Replace { old, new } => Diff::Replace {
+                old: old.clone_value(),
+                new: new.clone_value(),
+            },
+            Diff::Fields(fields) => Diff::Fields(fields.clone()),
+            Diff::List(changes) => Diff::List(changes.clone()),
+            Diff::Map(changes) => Diff::Map(changes.clone()),
+            Diff::Set(changes) => Diff::Set(changes.clone()),
+        }
+    }
+}
+
+impl ListChange {
+    fn apply(&self, list: &mut dyn List) -> Result<(), DiffError> {
+        match self {
+            ListChange::Modify { index, diff } => {
+                let element = list
+                    .get_mut(*index)
+                    .ok_or(DiffError::InvalidListIndex(*index))?;
+                diff.apply(element)
+            }
+            ListChange::Insert { index, value } => {
+                if *index > list.len() {
+                    return Err(DiffError::InvalidListIndex(*index));
+                }
+                let tail = split_off(list, *index);
+                list.push(value.clone_value());
+                tail.into_iter().rev().for_each(|value| list.push(value));
+                Ok(())
+            }
+            ListChange::Remove { index, .. } => {
+                if *index >= list.len() {
+                    return Err(DiffError::InvalidListIndex(*index));
+                }
+                let tail = split_off(list, *index + 1);
+                list.pop();
+                tail.into_iter().rev().for_each(|value| list.push(value));
+                Ok(())
+            }
+        }
+    }
+
+    fn invert(self) -> ListChange {
+        match self {
+            ListChange::Modify { index, diff } => ListChange::Modify {
+                index,
+                diff: diff.invert(),
+            },
+            ListChange::Insert { index, value } => ListChange::Remove { index, value },
+            ListChange::Remove { index, value } => ListChange::Insert { index, value },
+        }
+    }
+}
+
+/// Pops the elements of `list` from `index` on, last first.
+fn split_off(list: &mut dyn List, index: usize) -> Vec<Box<dyn Reflect>> {
+    (index..list.len()).filter_map(|_| list.pop()).collect()
+}
+
+impl Clone for ListChange {
+    fn clone(&self) -> Self {
+        match self {
+            ListChange::Modify { index, diff } => ListChange::Modify {
+                index: *index,
+                diff: diff.clone(),
+            },
+            ListChange::Insert { index, value } => ListChange::Insert {
+                index: *index,
+                value: value.clone_value(),
+            },
+            ListChange::Remove { index, value } => ListChange::Remove {
+                index: *index,
+                value: value.clone_value(),
+            },
+        }
+    }
+}
+
+impl MapChange {
+    fn apply(&self, map: &mut dyn Map) -> Result<(), DiffError> {
+        let missing_key = |key: &dyn Reflect| DiffError::MissingKey(format!("{key:?}"));
+        match self {
+            MapChange::Modify { key, diff } => {
+                let value = map.get_mut(&**key).ok_or_else(|| missing_key(&**key))?;
+                diff.apply(value)
+            }
+            MapChange::Insert { key, value } => {
+                map.insert_boxed(key.clone_value(), value.clone_value());
+                Ok(())
+            }
+            MapChange::Remove { key, .. } => {
+                map.remove(&**key).ok_or_else(|| missing_key(&**key))?;
+                Ok(())
+            }
+        }
+    }
+
+    fn invert(self) -> MapChange {
+        match self {
+            MapChange::Modify { key, diff } => MapChange::Modify {
+                key,
+                diff: diff.invert(),
+            },
+            MapChange::Insert { key, value } => MapChange::Remove { key, value },
+            MapChange::Remove { key, value } => MapChange::Insert { key, value },
+        }
+    }
+}
+
+impl Clone for MapChange {
+    fn clone(&self) -> Self {
+        match self {
+            MapChange::Modify { key, diff } => MapChange::Modify {
+                key: key.clone_value(),
+                diff: diff.clone(),
+            },
+            MapChange::Insert { key, value } => MapChange::Insert {
+                key: key.clone_value(),
+                value: value.clone_value(),
+            },
+            MapChange::Remove { key, value } => MapChange::Remove {
+                key: key.clone_value(),
+                value: value.clone_value(),
+            },
+        }
+    }
+}
+
+impl SetChange {
+    fn apply(&self, set: &mut dyn Set) -> Result<(), DiffError> {
+        match self {
+            SetChange::Insert(value) => {
+                set.insert_boxed(value.clone_value());
+                Ok(())
+            }
+            SetChange::Remove(value) => {
+                if set.remove(&**value) {
+                    Ok(())
+                } else {
+                    Err(DiffError::MissingValue(format!("{value:?}")))
+                }
+            }
+        }
+    }
+
+    fn invert(self) -> SetChange {
+        match self {
+            SetChange::Insert(value) => SetChange::Remove(value),
+            SetChange::Remove(value) => SetChange::Insert(value),
+        }
+    }
+}
+
+impl Clone for SetChange {
+    fn clone(&self) -> Self {
+        match self {
+            SetChange::Insert(value) => SetChange::Insert(value.clone_value()),
+            SetChange::Remove(value) => SetChange::Remove(value.clone_value()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        self as bevy_reflect,
+        serde::{DiffDeserializer, DiffSerializer},
+        FromReflect, TypeRegistry,
+    };
+    use bevy_utils::{HashMap, HashSet};
+    use bincode::Options;
+    use serde::de::DeserializeSeed;
+
+    #[derive(Reflect, FromReflect, Debug, Clone, PartialEq)]
+    struct Item {
+        id: u32,
+        count: u32,
+    }
+
+    #[derive(Reflect, FromReflect, Debug, Clone, PartialEq)]
+    enum State {
+        Idle,
+        Moving { speed: f32 },
+        Attacking(u32),
+    }
+
+    #[derive(Reflect, FromReflect, Debug, Clone, PartialEq)]
+    struct Player {
+        name: String,
+        position: (f32, f32),
+        inventory: Vec<Item>,
+        stats: HashMap<String, u32>,
+        tags: HashSet<String>,
+        state: State,
+    }
+
+    fn item(id: u32, count: u32) -> Item {
+        Item { id, count }
+    }
+
+    fn players() -> (Player, Player) {
+        let old = Player {
+            name: "player".to_string(),
+            position: (0.0, 1.0),
+            inventory: vec![item(1, 1), item(2, 1), item(3, 1), item(4, 1)],
+            stats: HashMap::from_iter([("strength".to_string(), 3), ("speed".to_string(), 5)]),
+            tags: HashSet::from_iter(["hero".to_string(), "tired".to_string()]),
+            state: State::Moving { speed: 1.0 },
+        };
+        let new = Player {
+            name: "player".to_string(),
+            position: (0.0, 2.0),
+            inventory: vec![
+                item(0, 1),
+                item(1, 1),
+                item(2, 1),
+                item(3, 2),
+                item(4, 1),
+                item(5, 1),
+            ],
+            stats: HashMap::from_iter([("strength".to_string(), 4), ("luck".to_string(), 1)]),
+            tags: HashSet::from_iter(["hero".to_string(), "fast".to_string()]),
+            state: State::Moving { speed: 2.0 },
+        };
+        (old, new)
+    }
+
+    #[test]
+    fn diff_records_only_changes() {
+        let (old, new) = players();
+        assert!(diff(&old, &old.clone()).is_none());
+
+        let Some(Diff::Fields(fields)) = diff(&old, &new) else {
+            panic!("expected a diff of the fields of the player");
+        };
+        let keys: Vec<_> = fields.iter().map(|(key, _)| key.clone()).collect();
+        assert_eq!(
+            keys,
+            ["position", "inventory", "stats", "tags", "state"]
+                .map(|name| FieldKey::Name(name.into()))
+        );
+
+        let Diff::List(changes) = &fields[1].1 else {
+            panic!("expected a list diff of the inventory");
+        };
+        assert!(matches!(
+            changes.as_slice(),
+            [
+                ListChange::Insert { index: 0, .. },
+                ListChange::Modify { index: 3, .. },
+                ListChange::Insert { index: 5, .. },
+            ]
+        ));
+        let Diff::Map(changes) = &fields[2].1 else {
+            panic!("expected a map diff of the stats");
+        };
+        assert_eq!(changes.len(), 3);
+        let Diff::Set(changes) = &fields[3].1 else {
+            panic!("expected a set diff of the tags");
+        };
+        assert!(matches!(
+            changes.as_slice(),
+            [SetChange::Remove(_), SetChange::Insert(_)]
+        ));
+    }
+
+    #[test]
+    fn apply_and_invert() {
+        let (old, new) = players();
+        let diff = diff(&old, &new).unwrap();
+
+        let mut value = old.clone();
+        diff.apply(&mut value).unwrap();
+        assert_eq!(value, new);
+
+        diff.invert().apply(&mut value).unwrap();
+        assert_eq!(value, old);
+
+        // list removals and enum variant switches
+        let mut value = new.clone();
+        let mut target = old.clone();
+        target.inventory.truncate(1);
+        target.state = State::Attacking(3);
+        let diff = super::diff(&value, &target).unwrap();
+        diff.apply(&mut value).unwrap();
+        assert_eq!(value, target);
+        diff.invert().apply(&mut value).unwrap();
+        assert_eq!(value, new);
+    }
+
+    #[test]
+    fn diff_long_lists() {
+        let old: Vec<u32> = (0..2000).collect();
+        let new: Vec<u32> = (0..2000)
+            .filter(|value| value % 7 != 0)
+            .map(|value| {
+                if value % 11 == 0 {
+                    value + 10_000
+                } else {
+                    value
+                }
+            })
+            .chain([1, 2, 3])
+            .collect();
+
+        let Some(Diff::List(changes)) = diff(&old, &new) else {
+            panic!("expected a list diff");
+        };
+        let removed = (0..2000).filter(|value| value % 7 == 0).count();
+        let modified = (0..2000)
+            .filter(|value| value % 7 != 0 && value % 11 == 0)
+            .count();
+        assert_eq!(changes.len(), removed + modified + 3);
+
+        let diff = Diff::List(changes);
+        let mut value = old.clone();
+        diff.apply(&mut value).unwrap();
+        assert_eq!(value, new);
+        diff.invert().apply(&mut value).unwrap();
+        assert_eq!(value, old);
+    }
+
+    #[test]
+    fn apply_errors() {
+        let diff = diff(&vec![1, 2, 3], &vec![1, 2, 4]).unwrap();
+        assert_eq!(
+            diff.apply(&mut vec![1]),
+            Err(DiffError::InvalidListIndex(2))
+        );
+        assert_eq!(
+            diff.apply(&mut 1u32),
+            Err(DiffError::KindMismatch { expected: "a list" })
+        );
+
+        let diff = super::diff(&1u32, &2u32).unwrap();
+        assert_eq!(
+            diff.apply(&mut 1.0f32),
+            Err(DiffError::TypeMismatch {
+                target: "f32".to_string(),
+                value: "u32".to_string()
+            })
+        );
+    }
+
+    #[test]
+    fn serialize_diff() {
+        let mut registry = TypeRegistry::default();
+        registry.register::<Player>();
+        registry.register::<Item>();
+        registry.register::<State>();
+        registry.register::<String>();
+        registry.register::<(f32, f32)>();
+        registry.register::<Vec<Item>>();
+        registry.register::<HashMap<String, u32>>();
+        registry.register::<HashSet<String>>();
+
+        let (old, new) = players();
+        let mut target = new.clone();
+        target.state = State::Idle;
+        let diff = super::diff(&old, &target).unwrap();
+
+        let serialized = ron::to_string(&DiffSerializer::new(&diff, &registry)).unwrap();
+        let mut deserializer = ron::de::Deserializer::from_str(&serialized).unwrap();
+        let deserialized = DiffDeserializer::new(&registry)
+            .deserialize(&mut deserializer)
+            .unwrap();
+
+        let mut value = old.clone();
+        deserialized.apply(&mut value).unwrap();
+        assert_eq!(value, target);
+        deserialized.invert().apply(&mut value).unwrap();
+        assert_eq!(value, old);
+
+        let bytes = bincode::serialize(&DiffSerializer::new(&diff, &registry)).unwrap();
+        let deserialized = bincode::DefaultOptions::new()
+            .with_fixint_encoding()
+            .deserialize_seed(DiffDeserializer::new(&registry), &bytes)
+            .unwrap();
+        let mut value = old.clone();
+        deserialized.apply(&mut value).unwrap();
+        assert_eq!(value, target);
+    }
+}
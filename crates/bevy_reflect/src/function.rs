@@ -0,0 +1,608 @@
+use crate::{FromReflect, Reflect, TypeInfo, Typed};
+use bevy_utils::HashMap;
+use std::{
+    any::{type_name, Any, TypeId},
+    borrow::Cow,
+    fmt::{Debug, Formatter},
+    sync::Arc,
+};
+use thiserror::Error;
+
+/// An error returned from a failed call to a [`DynamicFunction`] or [`DynamicMethod`].
+#[derive(Debug, PartialEq, Eq, Error)]
+pub enum FunctionError {
+    #[error("expected {expected} arguments, but received {received}")]
+    ArgCount { expected: usize, received: usize },
+    #[error("expected argument {index} to be of type `{expected}`, but received `{received}`")]
+    InvalidArgument {
+        index: usize,
+        expected: &'static str,
+        received: String,
+    },
+    #[error("expected a receiver of type `{expected}`, but received `{received}`")]
+    InvalidReceiver {
+        expected: &'static str,
+        received: String,
+    },
+    #[error("the method `{name}` requires a mutable receiver")]
+    ExpectedMutableReceiver { name: String },
+}
+
+/// Type information for an argument or the return value of a reflected function.
+#[derive(Clone)]
+pub struct ArgInfo {
+    type_name: &'static str,
+    type_id: TypeId,
+    type_info: &'static TypeInfo,
+}
+
+impl ArgInfo {
+    /// Creates a new [`ArgInfo`] for a value of type `T`.
+    pub fn new<T: Typed>() -> Self {
+        Self {
+            type_name: type_name::<T>(),
+            type_id: TypeId::of::<T>(),
+            type_info: T::type_info(),
+        }
+    }
+
+    /// The [type name] of the value.
+    ///
+    /// [type name]: std::any::type_name
+    pub fn type_name(&self) -> &'static str {
+        self.type_name
+    }
+
+    /// The [`TypeId`] of the value.
+    pub fn type_id(&self) -> TypeId {
+        self.type_id
+    }
+
+    /// The [`TypeInfo`] of the value.
+    pub fn type_info(&self) -> &'static TypeInfo {
+        self.type_info
+    }
+
+    /// Check if the given type matches the value type.
+    pub fn is<T: Any>(&self) -> bool {
+        TypeId::of::<T>() == self.type_id
+    }
+}
+
+impl Debug for ArgInfo {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("ArgInfo").field(&self.type_name).finish()
+    }
+}
+
+/// A container for compile-time info related to the signature of a reflected function.
+///
+/// For a [`DynamicMethod`], the receiver is not included in [`args`](Self::args).
+#[derive(Clone, Debug)]
+pub struct FunctionInfo {
+    name: Cow<'static, str>,
+    args: Box<[ArgInfo]>,
+    return_info: ArgInfo,
+}
+
+impl FunctionInfo {
+    /// Create a new [`FunctionInfo`].
+    ///
+    /// # Arguments
+    ///
+    /// * `name`: The name of the function
+    /// * `args`: The arguments of the function, in order
+    ///
+    pub fn new<TReturn: Typed>(name: impl Into<Cow<'static, str>>, args: &[ArgInfo]) -> Self {
+        Self {
+            name: name.into(),
+            args: args.to_vec().into_boxed_slice(),
+            return_info: ArgInfo::new::<TReturn>(),
+        }
+    }
+
+    /// The name of the function.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// The arguments of the function, in order.
+    pub fn args(&self) -> &[ArgInfo] {
+        &self.args
+    }
+
+    /// The number of arguments the function takes.
+    pub fn arg_count(&self) -> usize {
+        self.args.len()
+    }
+
+    /// The return value of the function.
+    pub fn return_info(&self) -> &ArgInfo {
+        &self.return_info
+    }
+}
+
+type BoxedFunction =
+    Arc<dyn Fn(Vec<Box<dyn Reflect>>) -> Result<Box<dyn Reflect>, FunctionError> + Send + Sync>;
+
+/// A function which can be called with reflected arguments.
+///
+/// A `DynamicFunction` is created from any [`Fn`] whose arguments implement [`FromReflect`]
+/// and whose arguments and return value implement [`Typed`], using [`IntoFunction`].
+/// Arguments are passed by value, so functions taking references can't be reflected directly.
+///
+/// # Example
+/// ```
+/// # use bevy_reflect::{IntoFunction, Reflect};
+/// fn add(a: i32, b: i32) -> i32 {
+///     a + b
+/// }
+///
+/// let function = add.into_function("add");
+/// let args: Vec<Box<dyn Reflect>> = vec![Box::new(2_i32), Box::new(3_i32)];
+/// let value = function.call(args).unwrap();
+/// assert_eq!(value.downcast_ref::<i32>(), Some(&5));
+/// ```
+#[derive(Clone)]
+pub struct DynamicFunction {
+    info: FunctionInfo,
+    func: BoxedFunction,
+}
+
+impl DynamicFunction {
+    /// The signature of the function.
+    pub fn info(&self) -> &FunctionInfo {
+        &self.info
+    }
+
+    /// The name of the function.
+    pub fn name(&self) -> &str {
+        self.info.name()
+    }
+
+    /// Calls the function with the given arguments.
+    ///
+    /// Each argument must either be of the expected type or be convertible into it
+    /// with [`FromReflect`], like a [`DynamicStruct`](crate::DynamicStruct) produced by
+    /// deserialization.
+    pub fn call(&self, args: Vec<Box<dyn Reflect>>) -> Result<Box<dyn Reflect>, FunctionError> {
+        check_arg_count(&self.info, &args)?;
+        (self.func)(args)
+    }
+}
+
+impl Debug for DynamicFunction {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("DynamicFunction")
+            .field("info", &self.info)
+            .finish()
+    }
+}
+
+type BoxedRefMethod = Arc<
+    dyn Fn(&dyn Reflect, Vec<Box<dyn Reflect>>) -> Result<Box<dyn Reflect>, FunctionError>
+        + Send
+        + Sync,
+>;
+type BoxedMutMethod = Arc<
+    dyn Fn(&mut dyn Reflect, Vec<Box<dyn Reflect>>) -> Result<Box<dyn Reflect>, FunctionError>
+        + Send
+        + Sync,
+>;
+
+#[derive(Clone)]
+enum MethodFn {
+    Ref(BoxedRefMethod),
+    Mut(BoxedMutMethod),
+}
+
+/// A method of a reflected type which can be called with reflected arguments.
+///
+/// A `DynamicMethod` is created from any [`Fn`] taking `&T` or `&mut T` as its first
+/// argument, using [`IntoMethod`]. This includes inherent methods like `T::method`.
+/// The remaining arguments follow the same rules as [`DynamicFunction`].
+#[derive(Clone)]
+pub struct DynamicMethod {
+    info: FunctionInfo,
+    receiver_type_name: &'static str,
+    receiver_type_id: TypeId,
+    func: MethodFn,
+}
+
+impl DynamicMethod {
+    /// The signature of the method, without its receiver.
+    pub fn info(&self) -> &FunctionInfo {
+        &self.info
+    }
+
+    /// The name of the method.
+    pub fn name(&self) -> &str {
+        self.info.name()
+    }
+
+    /// The [type name] of the receiver.
+    ///
+    /// [type name]: std::any::type_name
+    pub fn receiver_type_name(&self) -> &'static str {
+        self.receiver_type_name
+    }
+
+    /// The [`TypeId`] of the receiver.
+    pub fn receiver_type_id(&self) -> TypeId {
+        self.receiver_type_id
+    }
+
+    /// Returns true if the method takes its receiver by mutable reference.
+    pub fn is_mut(&self) -> bool {
+        matches!(self.func, MethodFn::Mut(_))
+    }
+
+    /// Calls the method on `receiver` with the given arguments.
+    pub fn call(
+        &self,
+        receiver: &mut dyn Reflect,
+        args: Vec<Box<dyn Reflect>>,
+    ) -> Result<Box<dyn Reflect>, FunctionError> {
+        check_arg_count(&self.info, &args)?;
+        match &self.func {
+            MethodFn::Ref(func) => func(receiver, args),
+            MethodFn::Mut(func) => func(receiver, args),
+        }
+    }
+
+    /// Calls the method on an immutable `receiver` with the given arguments.
+    ///
+    /// Returns [`FunctionError::ExpectedMutableReceiver`] if the method takes `&mut self`.
+    pub fn call_ref(
+        &self,
+        receiver: &dyn Reflect,
+        args: Vec<Box<dyn Reflect>>,
+    ) -> Result<Box<dyn Reflect>, FunctionError> {
+        check_arg_count(&self.info, &args)?;
+        match &self.func {
+            MethodFn::Ref(func) => func(receiver, args),
+            MethodFn::Mut(_) => Err(FunctionError::ExpectedMutableReceiver {
+                name: self.name().to_string(),
+            }),
+        }
+    }
+}
+
+impl Debug for DynamicMethod {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("DynamicMethod")
+            .field("receiver", &self.receiver_type_name)
+            .field("info", &self.info)
+            .finish()
+    }
+}
+
+/// The methods registered for a type, stored as its [`TypeData`](crate::TypeData).
+///
+/// Methods are added with [`TypeRegistry::register_method`](crate::TypeRegistry::register_method).
+#[derive(Clone, Debug, Default)]
+pub struct ReflectMethods {
+    methods: HashMap<String, DynamicMethod>,
+}
+
+impl ReflectMethods {
+    /// Adds a method, replacing any previous method with the same name.
+    pub fn insert(&mut self, method: DynamicMethod) {
+        self.methods.insert(method.name().to_string(), method);
+    }
+
+    /// Returns the method with the given name.
+    pub fn get(&self, name: &str) -> Option<&DynamicMethod> {
+        self.methods.get(name)
+    }
+
+    /// Returns an iterator over the methods.
+    pub fn iter(&self) -> impl Iterator<Item = &DynamicMethod> {
+        self.methods.values()
+    }
+
+    /// The number of methods.
+    pub fn len(&self) -> usize {
+        self.methods.len()
+    }
+
+    /// Returns true if there are no methods.
+    pub fn is_empty(&self) -> bool {
+        self.methods.is_empty()
+    }
+}
+
+/// Conversion into a [`DynamicFunction`].
+///
+/// This is implemented for functions and closures of up to 8 arguments.
+/// `Marker` only exists to keep the implementations for different signatures apart.
+pub trait IntoFunction<Marker> {
+    /// Converts `self` into a [`DynamicFunction`] with the given name.
+    fn into_function(self, name: impl Into<Cow<'static, str>>) -> DynamicFunction;
+}
+
+/// Conversion into a [`DynamicMethod`] of `T`.
+///
+/// This is implemented for functions and closures taking `&T` or `&mut T` followed by
+/// up to 8 arguments. `Marker` only exists to keep the implementations for different
+/// signatures apart.
+pub trait IntoMethod<T, Marker> {
+    /// Converts `self` into a [`DynamicMethod`] with the given name.
+    fn into_method(self, name: impl Into<Cow<'static, str>>) -> DynamicMethod;
+}
+
+fn check_arg_count(info: &FunctionInfo, args: &[Box<dyn Reflect>]) -> Result<(), FunctionError> {
+    if args.len() == info.arg_count() {
+        Ok(())
+    } else {
+        Err(FunctionError::ArgCount {
+            expected: info.arg_count(),
+            received: args.len(),
+        })
+    }
+}
+
+fn take_arg<T: FromReflect>(index: usize, arg: Box<dyn Reflect>) -> Result<T, FunctionError> {
+    arg.take::<T>().or_else(|arg| {
+        T::from_reflect(&*arg).ok_or_else(|| FunctionError::InvalidArgument {
+            index,
+            expected: type_name::<T>(),
+            received: arg.type_name().to_string(),
+        })
+    })
+}
+
+fn invalid_receiver<T>(receiver: &dyn Reflect) -> FunctionError {
+    FunctionError::InvalidReceiver {
+        expected: type_name::<T>(),
+        received: receiver.type_name().to_string(),
+    }
+}
+
+macro_rules! impl_into_function {
+    ($($arg:ident $value:ident),*) => {
+        impl<F, R, $($arg,)*> IntoFunction<fn($($arg),*) -> R> for F
+        where
+            F: Fn($($arg),*) -> R + Send + Sync + 'static,
+            R: Typed,
+            $($arg: FromReflect + Typed,)*
+        {
+            fn into_function(self, name: impl Into<Cow<'static, str>>) -> DynamicFunction {
+                DynamicFunction {
+                    info: FunctionInfo::new::<R>(name, &[$(ArgInfo::new::<$arg>()),*]),
+                    func: Arc::new(move |args| {
+                        #[allow(unused_mut, unused_variables)]
+                        let mut args = args.into_iter().enumerate();
+                        $(
+                            let (index, arg) = args.next().unwrap();
+                            let $value = take_arg::<$arg>(index, arg)?;
+                        )*
+                        Ok(Box::new(self($($value),*)))
+                    }),
+                }
+            }
+        }
+
+        impl<T, F, R, $($arg,)*> IntoMethod<T, fn(&T, $($arg),*) -> R> for F
+        where
+            T: Reflect,
+            F: Fn(&T, $($arg),*) -> R + Send + Sync + 'static,
+            R: Typed,
+            $($arg: FromReflect + Typed,)*
+        {
+            fn into_method(self, name: impl Into<Cow<'static, str>>) -> DynamicMethod {
+                DynamicMethod {
+                    info: FunctionInfo::new::<R>(name, &[$(ArgInfo::new::<$arg>()),*]),
+                    receiver_type_name: type_name::<T>(),
+                    receiver_type_id: TypeId::of::<T>(),
+                    func: MethodFn::Ref(Arc::new(move |receiver, args| {
+                        let receiver = receiver
+                            .downcast_ref::<T>()
+                            .ok_or_else(|| invalid_receiver::<T>(receiver))?;
+                        #[allow(unused_mut, unused_variables)]
+                        let mut args = args.into_iter().enumerate();
+                        $(
+                            let (index, arg) = args.next().unwrap();
+                            let $value = take_arg::<$arg>(index, arg)?;
+                        )*
+                        Ok(Box::new(self(receiver, $($value),*)))
+                    })),
+                }
+            }
+        }
+
+        impl<T, F, R, $($arg,)*> IntoMethod<T, fn(&mut T, $($arg),*) -> R> for F
+        where
+            T: Reflect,
+            F: Fn(&mut T, $($arg),*) -> R + Send + Sync + 'static,
+            R: Typed,
+            $($arg: FromReflect + Typed,)*
+        {
+            fn into_method(self, name: impl Into<Cow<'static, str>>) -> DynamicMethod {
+                DynamicMethod {
+                    info: FunctionInfo::new::<R>(name, &[$(ArgInfo::new::<$arg>()),*]),
+                    receiver_type_name: type_name::<T>(),
+                    receiver_type_id: TypeId::of::<T>(),
+                    func: MethodFn::Mut(Arc::new(move |receiver, args| {
+                        if !receiver.is::<T>() {
+                            return Err(invalid_receiver::<T>(receiver));
+                        }
+                        let receiver = receiver.downcast_mut::<T>().unwrap();
+                        #[allow(unused_mut, unused_variables)]
+                        let mut args = args.into_iter().enumerate();
+                        $(
+                            let (index, arg) = args.next().unwrap();
+                            let $value = take_arg::<$arg>(index, arg)?;
+                        )*
+                        Ok(Box::new(self(receiver, $($value),*)))
+                    })),
+                }
+            }
+        }
+    };
+}
+
+impl_into_function!();
+impl_into_function!(A a);
+impl_into_function!(A a, B b);
+impl_into_function!(A a, B b, C c);
+impl_into_function!(A a, B b, C c, D d);
+impl_into_function!(A a, B b, C c, D d, E e);
+impl_into_function!(A a, B b, C c, D d, E e, G g);
+impl_into_function!(A a, B b, C c, D d, E e, G g, H h);
+impl_into_function!(A a, B b, C c, D d, E e, G g, H h, I i);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate as bevy_reflect;
+    use crate::{DynamicStruct, TypeRegistry};
+
+    #[derive(Reflect, FromReflect, Debug, PartialEq)]
+    struct Damage {
+        amount: u32,
+    }
+
+    #[derive(Reflect, FromReflect, Debug, PartialEq)]
+    struct Player {
+        health: u32,
+    }
+
+    impl Player {
+        fn health(&self) -> u32 {
+            self.health
+        }
+
+        fn hit(&mut self, damage: Damage) -> bool {
+            self.health = self.health.saturating_sub(damage.amount);
+            self.health == 0
+        }
+    }
+
+    #[test]
+    fn call_function() {
+        fn scale(damage: Damage, factor: u32) -> Damage {
+            Damage {
+                amount: damage.amount * factor,
+            }
+        }
+
+        let function = scale.into_function("scale");
+        assert_eq!(function.name(), "scale");
+        assert_eq!(function.info().arg_count(), 2);
+        assert!(function.info().args()[0].is::<Damage>());
+        assert!(matches!(
+            function.info().args()[0].type_info(),
+            TypeInfo::Struct(_)
+        ));
+        assert!(function.info().return_info().is::<Damage>());
+
+        let value = function
+            .call(vec![Box::new(Damage { amount: 2 }), Box::new(3_u32)])
+            .unwrap();
+        assert_eq!(value.take::<Damage>().unwrap(), Damage { amount: 6 });
+
+        // dynamic arguments are converted with `FromReflect`
+        let mut damage = DynamicStruct::default();
+        damage.insert("amount", 4_u32);
+        let value = function
+            .call(vec![Box::new(damage), Box::new(2_u32)])
+            .unwrap();
+        assert_eq!(value.take::<Damage>().unwrap(), Damage { amount: 8 });
+
+        assert_eq!(
+            function.call(vec![Box::new(3_u32)]).unwrap_err(),
+            FunctionError::ArgCount {
+                expected: 2,
+                received: 1
+            }
+        );
+        assert_eq!(
+            function
+                .call(vec![Box::new(Damage { amount: 2 }), Box::new(3_i64)])
+                .unwrap_err(),
+            FunctionError::InvalidArgument {
+                index: 1,
+                expected: "u32",
+                received: "i64".to_string()
+            }
+        );
+    }
+
+    #[test]
+    fn call_closure() {
+        let offset = 10_i32;
+        let function = (move || offset).into_function("offset");
+        assert_eq!(
+            function.call(Vec::new()).unwrap().take::<i32>().unwrap(),
+            10
+        );
+
+        let function = (|_: String| ()).into_function("ignore");
+        assert!(function.info().return_info().is::<()>());
+        assert!(function
+            .call(vec![Box::new("text".to_string())])
+            .unwrap()
+            .is::<()>());
+    }
+
+    #[test]
+    fn registered_methods() {
+        let mut registry = TypeRegistry::default();
+        registry.register::<Player>();
+        registry.register_method("health", Player::health);
+        registry.register_method("hit", Player::hit);
+        registry.register_function("max_health", || 100_u32);
+
+        let methods = registry
+            .get_type_data::<ReflectMethods>(TypeId::of::<Player>())
+            .unwrap();
+        assert_eq!(methods.len(), 2);
+
+        let mut player = Player { health: 5 };
+        let hit = registry.get_method(TypeId::of::<Player>(), "hit").unwrap();
+        assert!(hit.is_mut());
+        assert_eq!(hit.receiver_type_name(), std::any::type_name::<Player>());
+        let dead = hit
+            .call(&mut player, vec![Box::new(Damage { amount: 3 })])
+            .unwrap();
+        assert!(!dead.take::<bool>().unwrap());
+        assert_eq!(player.health, 2);
+
+        assert_eq!(
+            hit.call_ref(&player, vec![Box::new(Damage { amount: 3 })])
+                .unwrap_err(),
+            FunctionError::ExpectedMutableReceiver {
+                name: "hit".to_string()
+            }
+        );
+        assert_eq!(
+            hit.call(
+                &mut Damage { amount: 1 },
+                vec![Box::new(Damage { amount: 3 })]
+            )
+            .unwrap_err(),
+            FunctionError::InvalidReceiver {
+                expected: std::any::type_name::<Player>(),
+                received: std::any::type_name::<Damage>().to_string()
+            }
+        );
+
+        let health = registry
+            .get_method(TypeId::of::<Player>(), "health")
+            .unwrap();
+        assert!(!health.is_mut());
+        assert_eq!(
+            health
+                .call_ref(&player, Vec::new())
+                .unwrap()
+                .take::<u32>()
+                .unwrap(),
+            2
+        );
+
+        let max_health = registry.get_function("max_health").unwrap();
+        assert_eq!(max_health.info().arg_count(), 0);
+        assert_eq!(registry.functions().count(), 1);
+    }
+}
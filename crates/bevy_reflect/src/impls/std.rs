@@ -1,11 +1,11 @@
 use crate::std_traits::ReflectDefault;
 use crate::{self as bevy_reflect, ReflectFromPtr, ReflectOwned};
 use crate::{
-    map_apply, map_partial_eq, Array, ArrayInfo, ArrayIter, DynamicEnum, DynamicMap, Enum,
-    EnumInfo, FromReflect, FromType, GetTypeRegistration, List, ListInfo, Map, MapInfo, MapIter,
-    Reflect, ReflectDeserialize, ReflectMut, ReflectRef, ReflectSerialize, TupleVariantInfo,
-    TypeInfo, TypeRegistration, Typed, UnitVariantInfo, UnnamedField, ValueInfo, VariantFieldIter,
-    VariantInfo, VariantType,
+    map_apply, map_partial_eq, set_apply, set_partial_eq, Array, ArrayInfo, ArrayIter, DynamicEnum,
+    DynamicMap, DynamicSet, Enum, EnumInfo, FromReflect, FromType, GetTypeRegistration, List,
+    ListInfo, Map, MapInfo, MapIter, Reflect, ReflectDeserialize, ReflectMut, ReflectRef,
+    ReflectSerialize, Set, SetInfo, SetIter, TupleVariantInfo, TypeInfo, TypeRegistration, Typed,
+    UnitVariantInfo, UnnamedField, ValueInfo, VariantFieldIter, VariantInfo, VariantType,
 };
 
 use crate::utility::{GenericTypeInfoCell, NonGenericTypeInfoCell};
@@ -21,7 +21,7 @@ use std::{
         NonZeroI128, NonZeroI16, NonZeroI32, NonZeroI64, NonZeroI8, NonZeroIsize, NonZeroU128,
         NonZeroU16, NonZeroU32, NonZeroU64, NonZeroU8, NonZeroUsize,
     },
-    collections::VecDeque,
+    collections::{BTreeMap, BTreeSet, VecDeque},
     ops::{Range, RangeFrom, RangeFull, RangeInclusive, RangeTo, RangeToInclusive},
     path::{Path, PathBuf},
 };
@@ -101,7 +101,6 @@ impl_reflect_value!(PathBuf(
     Default
 ));
 impl_reflect_value!(Result<T: Clone + Reflect + 'static, E: Clone + Reflect + 'static>());
-impl_reflect_value!(HashSet<T: Hash + Eq + Clone + Send + Sync + 'static>());
 impl_reflect_value!(Range<T: Clone + Send + Sync + 'static>());
 impl_reflect_value!(RangeInclusive<T: Clone + Send + Sync + 'static>());
 impl_reflect_value!(RangeFrom<T: Clone + Send + Sync + 'static>());
@@ -156,7 +155,6 @@ impl_from_reflect_value!(f64);
 impl_from_reflect_value!(String);
 impl_from_reflect_value!(PathBuf);
 impl_from_reflect_value!(OsString);
-impl_from_reflect_value!(HashSet<T: Hash + Eq + Clone + Send + Sync + 'static>);
 impl_from_reflect_value!(Range<T: Clone + Send + Sync + 'static>);
 impl_from_reflect_value!(RangeInclusive<T: Clone + Send + Sync + 'static>);
 impl_from_reflect_value!(RangeFrom<T: Clone + Send + Sync + 'static>);
@@ -324,82 +322,6 @@ impl<T: FromReflect> FromReflect for Vec<T> {
     }
 }
 
-impl<K: FromReflect + Eq + Hash, V: FromReflect> Map for HashMap<K, V> {
-    fn get(&self, key: &dyn Reflect) -> Option<&dyn Reflect> {
-        key.downcast_ref::<K>()
-            .and_then(|key| HashMap::get(self, key))
-            .map(|value| value as &dyn Reflect)
-    }
-
-    fn get_mut(&mut self, key: &dyn Reflect) -> Option<&mut dyn Reflect> {
-        key.downcast_ref::<K>()
-            .and_then(move |key| HashMap::get_mut(self, key))
-            .map(|value| value as &mut dyn Reflect)
-    }
-
-    fn get_at(&self, index: usize) -> Option<(&dyn Reflect, &dyn Reflect)> {
-        self.iter()
-            .nth(index)
-            .map(|(key, value)| (key as &dyn Reflect, value as &dyn Reflect))
-    }
-
-    fn len(&self) -> usize {
-        Self::len(self)
-    }
-
-    fn iter(&self) -> MapIter {
-        MapIter {
-            map: self,
-            index: 0,
-        }
-    }
-
-    fn drain(self: Box<Self>) -> Vec<(Box<dyn Reflect>, Box<dyn Reflect>)> {
-        self.into_iter()
-            .map(|(key, value)| {
-                (
-                    Box::new(key) as Box<dyn Reflect>,
-                    Box::new(value) as Box<dyn Reflect>,
-                )
-            })
-            .collect()
-    }
-
-    fn clone_dynamic(&self) -> DynamicMap {
-        let mut dynamic_map = DynamicMap::default();
-        dynamic_map.set_name(self.type_name().to_string());
-        for (k, v) in self {
-            dynamic_map.insert_boxed(k.clone_value(), v.clone_value());
-        }
-        dynamic_map
-    }
-
-    fn insert_boxed(
-        &mut self,
-        key: Box<dyn Reflect>,
-        value: Box<dyn Reflect>,
-    ) -> Option<Box<dyn Reflect>> {
-        let key = key.take::<K>().unwrap_or_else(|key| {
-            K::from_reflect(&*key).unwrap_or_else(|| {
-                panic!(
-                    "Attempted to insert invalid key of type {}.",
-                    key.type_name()
-                )
-            })
-        });
-        let value = value.take::<V>().unwrap_or_else(|value| {
-            V::from_reflect(&*value).unwrap_or_else(|| {
-                panic!(
-                    "Attempted to insert invalid value of type {}.",
-                    value.type_name()
-                )
-            })
-        });
-        self.insert(key, value)
-            .map(|old_value| Box::new(old_value) as Box<dyn Reflect>)
-    }
-}
-
 impl<T: FromReflect> Array for VecDeque<T> {
     #[inline]
     fn get(&self, index: usize) -> Option<&dyn Reflect> {
@@ -546,105 +468,349 @@ impl<T: FromReflect> FromReflect for VecDeque<T> {
     }
 }
 
-impl<K: FromReflect + Eq + Hash, V: FromReflect> Reflect for HashMap<K, V> {
-    fn type_name(&self) -> &str {
-        std::any::type_name::<Self>()
-    }
+macro_rules! impl_reflect_for_map {
+    ($ty:ident<K: $key_bound:ident $(+ $key_bounds:ident)*>) => {
+        impl<K: FromReflect + $key_bound $(+ $key_bounds)*, V: FromReflect> Map for $ty<K, V> {
+            fn get(&self, key: &dyn Reflect) -> Option<&dyn Reflect> {
+                key.downcast_ref::<K>()
+                    .and_then(|key| $ty::get(self, key))
+                    .map(|value| value as &dyn Reflect)
+            }
 
-    fn get_type_info(&self) -> &'static TypeInfo {
-        <Self as Typed>::type_info()
-    }
+            fn get_mut(&mut self, key: &dyn Reflect) -> Option<&mut dyn Reflect> {
+                key.downcast_ref::<K>()
+                    .and_then(move |key| $ty::get_mut(self, key))
+                    .map(|value| value as &mut dyn Reflect)
+            }
 
-    fn into_any(self: Box<Self>) -> Box<dyn Any> {
-        self
-    }
+            fn get_at(&self, index: usize) -> Option<(&dyn Reflect, &dyn Reflect)> {
+                self.iter()
+                    .nth(index)
+                    .map(|(key, value)| (key as &dyn Reflect, value as &dyn Reflect))
+            }
 
-    fn as_any(&self) -> &dyn Any {
-        self
-    }
+            fn len(&self) -> usize {
+                Self::len(self)
+            }
 
-    fn as_any_mut(&mut self) -> &mut dyn Any {
-        self
-    }
+            fn iter(&self) -> MapIter {
+                MapIter {
+                    map: self,
+                    index: 0,
+                }
+            }
 
-    #[inline]
-    fn into_reflect(self: Box<Self>) -> Box<dyn Reflect> {
-        self
-    }
+            fn drain(self: Box<Self>) -> Vec<(Box<dyn Reflect>, Box<dyn Reflect>)> {
+                self.into_iter()
+                    .map(|(key, value)| {
+                        (
+                            Box::new(key) as Box<dyn Reflect>,
+                            Box::new(value) as Box<dyn Reflect>,
+                        )
+                    })
+                    .collect()
+            }
 
-    fn as_reflect(&self) -> &dyn Reflect {
-        self
-    }
+            fn clone_dynamic(&self) -> DynamicMap {
+                let mut dynamic_map = DynamicMap::default();
+                dynamic_map.set_name(self.type_name().to_string());
+                for (k, v) in self {
+                    dynamic_map.insert_boxed(k.clone_value(), v.clone_value());
+                }
+                dynamic_map
+            }
 
-    fn as_reflect_mut(&mut self) -> &mut dyn Reflect {
-        self
-    }
+            fn insert_boxed(
+                &mut self,
+                key: Box<dyn Reflect>,
+                value: Box<dyn Reflect>,
+            ) -> Option<Box<dyn Reflect>> {
+                let key = key.take::<K>().unwrap_or_else(|key| {
+                    K::from_reflect(&*key).unwrap_or_else(|| {
+                        panic!(
+                            "Attempted to insert invalid key of type {}.",
+                            key.type_name()
+                        )
+                    })
+                });
+                let value = value.take::<V>().unwrap_or_else(|value| {
+                    V::from_reflect(&*value).unwrap_or_else(|| {
+                        panic!(
+                            "Attempted to insert invalid value of type {}.",
+                            value.type_name()
+                        )
+                    })
+                });
+                self.insert(key, value)
+                    .map(|old_value| Box::new(old_value) as Box<dyn Reflect>)
+            }
 
-    fn apply(&mut self, value: &dyn Reflect) {
-        map_apply(self, value);
-    }
+            fn remove(&mut self, key: &dyn Reflect) -> Option<Box<dyn Reflect>> {
+                let mut from_reflect = None;
+                key.downcast_ref::<K>()
+                    .or_else(|| {
+                        from_reflect = K::from_reflect(key);
+                        from_reflect.as_ref()
+                    })
+                    .and_then(|key| $ty::remove(self, key))
+                    .map(|value| Box::new(value) as Box<dyn Reflect>)
+            }
+        }
 
-    fn set(&mut self, value: Box<dyn Reflect>) -> Result<(), Box<dyn Reflect>> {
-        *self = value.take()?;
-        Ok(())
-    }
+        impl<K: FromReflect + $key_bound $(+ $key_bounds)*, V: FromReflect> Reflect for $ty<K, V> {
+            fn type_name(&self) -> &str {
+                std::any::type_name::<Self>()
+            }
 
-    fn reflect_ref(&self) -> ReflectRef {
-        ReflectRef::Map(self)
-    }
+            fn get_type_info(&self) -> &'static TypeInfo {
+                <Self as Typed>::type_info()
+            }
 
-    fn reflect_mut(&mut self) -> ReflectMut {
-        ReflectMut::Map(self)
-    }
+            fn into_any(self: Box<Self>) -> Box<dyn Any> {
+                self
+            }
 
-    fn reflect_owned(self: Box<Self>) -> ReflectOwned {
-        ReflectOwned::Map(self)
-    }
+            fn as_any(&self) -> &dyn Any {
+                self
+            }
 
-    fn clone_value(&self) -> Box<dyn Reflect> {
-        Box::new(self.clone_dynamic())
-    }
+            fn as_any_mut(&mut self) -> &mut dyn Any {
+                self
+            }
 
-    fn reflect_partial_eq(&self, value: &dyn Reflect) -> Option<bool> {
-        map_partial_eq(self, value)
-    }
-}
+            #[inline]
+            fn into_reflect(self: Box<Self>) -> Box<dyn Reflect> {
+                self
+            }
 
-impl<K: FromReflect + Eq + Hash, V: FromReflect> Typed for HashMap<K, V> {
-    fn type_info() -> &'static TypeInfo {
-        static CELL: GenericTypeInfoCell = GenericTypeInfoCell::new();
-        CELL.get_or_insert::<Self, _>(|| TypeInfo::Map(MapInfo::new::<Self, K, V>()))
-    }
-}
+            fn as_reflect(&self) -> &dyn Reflect {
+                self
+            }
 
-impl<K, V> GetTypeRegistration for HashMap<K, V>
-where
-    K: FromReflect + Eq + Hash,
-    V: FromReflect,
-{
-    fn get_type_registration() -> TypeRegistration {
-        let mut registration = TypeRegistration::of::<HashMap<K, V>>();
-        registration.insert::<ReflectFromPtr>(FromType::<HashMap<K, V>>::from_type());
-        registration
-    }
+            fn as_reflect_mut(&mut self) -> &mut dyn Reflect {
+                self
+            }
+
+            fn apply(&mut self, value: &dyn Reflect) {
+                map_apply(self, value);
+            }
+
+            fn set(&mut self, value: Box<dyn Reflect>) -> Result<(), Box<dyn Reflect>> {
+                *self = value.take()?;
+                Ok(())
+            }
+
+            fn reflect_ref(&self) -> ReflectRef {
+                ReflectRef::Map(self)
+            }
+
+            fn reflect_mut(&mut self) -> ReflectMut {
+                ReflectMut::Map(self)
+            }
+
+            fn reflect_owned(self: Box<Self>) -> ReflectOwned {
+                ReflectOwned::Map(self)
+            }
+
+            fn clone_value(&self) -> Box<dyn Reflect> {
+                Box::new(Map::clone_dynamic(self))
+            }
+
+            fn reflect_partial_eq(&self, value: &dyn Reflect) -> Option<bool> {
+                map_partial_eq(self, value)
+            }
+        }
+
+        impl<K: FromReflect + $key_bound $(+ $key_bounds)*, V: FromReflect> Typed for $ty<K, V> {
+            fn type_info() -> &'static TypeInfo {
+                static CELL: GenericTypeInfoCell = GenericTypeInfoCell::new();
+                CELL.get_or_insert::<Self, _>(|| TypeInfo::Map(MapInfo::new::<Self, K, V>()))
+            }
+        }
+
+        impl<K: FromReflect + $key_bound $(+ $key_bounds)*, V: FromReflect> GetTypeRegistration for $ty<K, V> {
+            fn get_type_registration() -> TypeRegistration {
+                let mut registration = TypeRegistration::of::<$ty<K, V>>();
+                registration.insert::<ReflectFromPtr>(FromType::<$ty<K, V>>::from_type());
+                registration
+            }
+        }
+
+        impl<K: FromReflect + $key_bound $(+ $key_bounds)*, V: FromReflect> FromReflect for $ty<K, V> {
+            fn from_reflect(reflect: &dyn Reflect) -> Option<Self> {
+                if let ReflectRef::Map(ref_map) = reflect.reflect_ref() {
+                    ref_map
+                        .iter()
+                        .map(|(key, value)| Some((K::from_reflect(key)?, V::from_reflect(value)?)))
+                        .collect()
+                } else {
+                    None
+                }
+            }
+        }
+    };
 }
 
-impl<K: FromReflect + Eq + Hash, V: FromReflect> FromReflect for HashMap<K, V> {
-    fn from_reflect(reflect: &dyn Reflect) -> Option<Self> {
-        if let ReflectRef::Map(ref_map) = reflect.reflect_ref() {
-            let mut new_map = Self::with_capacity(ref_map.len());
-            for (key, value) in ref_map.iter() {
-                let new_key = K::from_reflect(key)?;
-                let new_value = V::from_reflect(value)?;
-                new_map.insert(new_key, new_value);
+impl_reflect_for_map!(HashMap<K: Eq + Hash>);
+impl_reflect_for_map!(BTreeMap<K: Ord>);
+
+macro_rules! impl_reflect_for_set {
+    ($ty:ident<V: $value_bound:ident $(+ $value_bounds:ident)*>) => {
+        impl<V: FromReflect + $value_bound $(+ $value_bounds)*> Set for $ty<V> {
+            fn get(&self, value: &dyn Reflect) -> Option<&dyn Reflect> {
+                value
+                    .downcast_ref::<V>()
+                    .and_then(|value| $ty::get(self, value))
+                    .map(|value| value as &dyn Reflect)
+            }
+
+            fn get_at(&self, index: usize) -> Option<&dyn Reflect> {
+                self.iter().nth(index).map(|value| value as &dyn Reflect)
+            }
+
+            fn len(&self) -> usize {
+                Self::len(self)
+            }
+
+            fn iter(&self) -> SetIter {
+                SetIter {
+                    set: self,
+                    index: 0,
+                }
+            }
+
+            fn drain(self: Box<Self>) -> Vec<Box<dyn Reflect>> {
+                self.into_iter()
+                    .map(|value| Box::new(value) as Box<dyn Reflect>)
+                    .collect()
+            }
+
+            fn clone_dynamic(&self) -> DynamicSet {
+                let mut dynamic_set = DynamicSet::default();
+                dynamic_set.set_name(self.type_name().to_string());
+                for value in self {
+                    dynamic_set.insert_boxed(value.clone_value());
+                }
+                dynamic_set
+            }
+
+            fn insert_boxed(&mut self, value: Box<dyn Reflect>) -> bool {
+                let value = value.take::<V>().unwrap_or_else(|value| {
+                    V::from_reflect(&*value).unwrap_or_else(|| {
+                        panic!(
+                            "Attempted to insert invalid value of type {}.",
+                            value.type_name()
+                        )
+                    })
+                });
+                self.insert(value)
+            }
+
+            fn remove(&mut self, value: &dyn Reflect) -> bool {
+                if let Some(value) = value.downcast_ref::<V>() {
+                    $ty::remove(self, value)
+                } else if let Some(value) = V::from_reflect(value) {
+                    $ty::remove(self, &value)
+                } else {
+                    false
+                }
             }
-            Some(new_map)
-        } else {
-            None
         }
-    }
+
+        impl<V: FromReflect + $value_bound $(+ $value_bounds)*> Reflect for $ty<V> {
+            fn type_name(&self) -> &str {
+                std::any::type_name::<Self>()
+            }
+
+            fn get_type_info(&self) -> &'static TypeInfo {
+                <Self as Typed>::type_info()
+            }
+
+            fn into_any(self: Box<Self>) -> Box<dyn Any> {
+                self
+            }
+
+            fn as_any(&self) -> &dyn Any {
+                self
+            }
+
+            fn as_any_mut(&mut self) -> &mut dyn Any {
+                self
+            }
+
+            #[inline]
+            fn into_reflect(self: Box<Self>) -> Box<dyn Reflect> {
+                self
+            }
+
+            fn as_reflect(&self) -> &dyn Reflect {
+                self
+            }
+
+            fn as_reflect_mut(&mut self) -> &mut dyn Reflect {
+                self
+            }
+
+            fn apply(&mut self, value: &dyn Reflect) {
+                set_apply(self, value);
+            }
+
+            fn set(&mut self, value: Box<dyn Reflect>) -> Result<(), Box<dyn Reflect>> {
+                *self = value.take()?;
+                Ok(())
+            }
+
+            fn reflect_ref(&self) -> ReflectRef {
+                ReflectRef::Set(self)
+            }
+
+            fn reflect_mut(&mut self) -> ReflectMut {
+                ReflectMut::Set(self)
+            }
+
+            fn reflect_owned(self: Box<Self>) -> ReflectOwned {
+                ReflectOwned::Set(self)
+            }
+
+            fn clone_value(&self) -> Box<dyn Reflect> {
+                Box::new(Set::clone_dynamic(self))
+            }
+
+            fn reflect_partial_eq(&self, value: &dyn Reflect) -> Option<bool> {
+                set_partial_eq(self, value)
+            }
+        }
+
+        impl<V: FromReflect + $value_bound $(+ $value_bounds)*> Typed for $ty<V> {
+            fn type_info() -> &'static TypeInfo {
+                static CELL: GenericTypeInfoCell = GenericTypeInfoCell::new();
+                CELL.get_or_insert::<Self, _>(|| TypeInfo::Set(SetInfo::new::<Self, V>()))
+            }
+        }
+
+        impl<V: FromReflect + $value_bound $(+ $value_bounds)*> GetTypeRegistration for $ty<V> {
+            fn get_type_registration() -> TypeRegistration {
+                let mut registration = TypeRegistration::of::<$ty<V>>();
+                registration.insert::<ReflectFromPtr>(FromType::<$ty<V>>::from_type());
+                registration
+            }
+        }
+
+        impl<V: FromReflect + $value_bound $(+ $value_bounds)*> FromReflect for $ty<V> {
+            fn from_reflect(reflect: &dyn Reflect) -> Option<Self> {
+                if let ReflectRef::Set(ref_set) = reflect.reflect_ref() {
+                    ref_set.iter().map(V::from_reflect).collect()
+                } else {
+                    None
+                }
+            }
+        }
+    };
 }
 
+impl_reflect_for_set!(HashSet<V: Eq + Hash>);
+impl_reflect_for_set!(BTreeSet<V: Ord>);
+
 impl<T: Reflect, const N: usize> Array for [T; N] {
     #[inline]
     fn get(&self, index: usize) -> Option<&dyn Reflect> {
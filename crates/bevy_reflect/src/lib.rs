@@ -1,11 +1,14 @@
 #![doc = include_str!("../README.md")]
 
 mod array;
+mod diff;
 mod fields;
+mod function;
 mod list;
 mod map;
 mod path;
 mod reflect;
+mod set;
 mod struct_trait;
 mod tuple;
 mod tuple_struct;
@@ -45,13 +48,16 @@ pub mod prelude {
 }
 
 pub use array::*;
+pub use diff::*;
 pub use enums::*;
 pub use fields::*;
+pub use function::*;
 pub use impls::*;
 pub use list::*;
 pub use map::*;
 pub use path::*;
 pub use reflect::*;
+pub use set::*;
 pub use struct_trait::*;
 pub use tuple::*;
 pub use tuple_struct::*;
@@ -1019,6 +1025,35 @@ mod tests {
         let _ = trait_object.as_reflect();
     }
 
+    #[test]
+    fn reflect_where_clause() {
+        #[derive(Reflect, FromReflect)]
+        #[reflect(where T: FromReflect + Eq + std::hash::Hash)]
+        struct Foo<T: 'static> {
+            values: bevy_utils::HashSet<T>,
+        }
+
+        // Not reflected, but still usable as `Foo<NotReflected>`
+        struct NotReflected;
+        let _ = Foo::<NotReflected> {
+            values: Default::default(),
+        };
+
+        let mut foo = Foo::<u32> {
+            values: Default::default(),
+        };
+        let mut values = DynamicSet::default();
+        values.insert(3u32);
+        let mut patch = DynamicStruct::default();
+        patch.insert("values", values);
+        foo.apply(&patch);
+        assert!(foo.values.contains(&3));
+
+        let registration = <Foo<u32> as GetTypeRegistration>::get_type_registration();
+        assert_eq!(registration.type_name(), std::any::type_name::<Foo<u32>>());
+        assert!(Foo::<u32>::from_reflect(&foo).is_some());
+    }
+
     #[test]
     fn should_reflect_debug() {
         #[derive(Reflect)]
@@ -1,6 +1,5 @@
 use std::any::{Any, TypeId};
 use std::fmt::{Debug, Formatter};
-use std::hash::Hash;
 
 use bevy_utils::{Entry, HashMap};
 
@@ -57,6 +56,12 @@ pub trait Map: Reflect {
         key: Box<dyn Reflect>,
         value: Box<dyn Reflect>,
     ) -> Option<Box<dyn Reflect>>;
+
+    /// Removes an entry from the map.
+    ///
+    /// If the map did not have this key present, `None` is returned.
+    /// If the map did have this key present, the removed value is returned.
+    fn remove(&mut self, key: &dyn Reflect) -> Option<Box<dyn Reflect>>;
 }
 
 /// A container for compile-time map info.
@@ -74,7 +79,7 @@ pub struct MapInfo {
 
 impl MapInfo {
     /// Create a new [`MapInfo`].
-    pub fn new<TMap: Map, TKey: Hash + Reflect, TValue: Reflect>() -> Self {
+    pub fn new<TMap: Map, TKey: Reflect, TValue: Reflect>() -> Self {
         Self {
             type_name: std::any::type_name::<TMap>(),
             type_id: TypeId::of::<TMap>(),
@@ -246,6 +251,19 @@ impl Map for DynamicMap {
         }
     }
 
+    fn remove(&mut self, key: &dyn Reflect) -> Option<Box<dyn Reflect>> {
+        let index = self
+            .indices
+            .remove(&key.reflect_hash().expect(HASH_ERROR))?;
+        let (_key, value) = self.values.remove(index);
+        for other_index in self.indices.values_mut() {
+            if *other_index > index {
+                *other_index -= 1;
+            }
+        }
+        Some(value)
+    }
+
     fn drain(self: Box<Self>) -> Vec<(Box<dyn Reflect>, Box<dyn Reflect>)> {
         self.values
     }
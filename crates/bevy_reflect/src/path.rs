@@ -1,6 +1,7 @@
+use std::borrow::Cow;
 use std::num::ParseIntError;
 
-use crate::{Array, Reflect, ReflectMut, ReflectRef};
+use crate::{Array, Map, Reflect, ReflectMut, ReflectRef, VariantType};
 use thiserror::Error;
 
 /// An error returned from a failed path string query.
@@ -17,6 +18,14 @@ pub enum ReflectPathError<'a> {
     },
     #[error("the current list doesn't have a value at the index {list_index}")]
     InvalidListIndex { index: usize, list_index: usize },
+    #[error("the current map doesn't have a value for the key {key}")]
+    InvalidMapKey { index: usize, key: &'a str },
+    #[error("the current enum variant `{variant}` doesn't have a field `{field}`")]
+    InvalidVariantField {
+        index: usize,
+        variant: String,
+        field: &'a str,
+    },
     #[error("encountered an unexpected token `{token}`")]
     UnexpectedToken { index: usize, token: &'a str },
     #[error("expected token `{token}`, but it wasn't there.")]
@@ -25,6 +34,8 @@ pub enum ReflectPathError<'a> {
     ExpectedStruct { index: usize },
     #[error("expected a list, but found a different reflect value")]
     ExpectedList { index: usize },
+    #[error("expected a map, but found a different reflect value")]
+    ExpectedMap { index: usize },
     #[error("failed to parse a usize")]
     IndexParseError(#[from] ParseIntError),
     #[error("failed to downcast to the path result to the given type")]
@@ -36,19 +47,26 @@ pub enum ReflectPathError<'a> {
 /// Path strings use Rust syntax:
 /// - [`Struct`] items are accessed with a dot and a field name: `.field_name`
 /// - [`TupleStruct`] and [`Tuple`] items are accessed with a dot and a number: `.0`
-/// - [`List`] items are accessed with brackets: `[0]`
+/// - [`List`] and [`Array`] items are accessed with brackets: `[0]`
+/// - [`Map`] values are accessed with brackets and a quoted string or integer key:
+///   `["key"]` or `[0]`, where `\"` and `\\` escape quotes and backslashes in a string key
+/// - The fields of the current variant of an [`Enum`] are accessed like struct or
+///   tuple struct fields: `.field_name` or `.0`
 ///
-/// If the initial path element is a field of a struct, tuple struct, or tuple,
+/// If the initial path element is a field of a struct, tuple struct, tuple or enum,
 /// the initial '.' may be omitted.
 ///
 /// For example, given a struct with a field `foo` which is a reflected list of
 /// 2-tuples (like a `Vec<(T, U)>`), the path string `foo[3].0` would access tuple
 /// element 0 of element 3 of `foo`.
 ///
+/// Paths which are used repeatedly can be parsed once into a [`ParsedPath`].
+///
 /// [`Struct`]: crate::Struct
 /// [`TupleStruct`]: crate::TupleStruct
 /// [`Tuple`]: crate::Tuple
 /// [`List`]: crate::List
+/// [`Enum`]: crate::Enum
 pub trait GetPath {
     /// Returns a reference to the value specified by `path`.
     ///
@@ -104,61 +122,11 @@ impl<T: Reflect> GetPath for T {
 
 impl GetPath for dyn Reflect {
     fn path<'r, 'p>(&'r self, path: &'p str) -> Result<&'r dyn Reflect, ReflectPathError<'p>> {
-        let mut index = 0;
         let mut current: &dyn Reflect = self;
-        while let Some(token) = next_token(path, &mut index) {
-            let current_index = index;
-            match token {
-                Token::Dot => {
-                    if let Some(Token::Ident(value)) = next_token(path, &mut index) {
-                        current = read_field(current, value, current_index)?;
-                    } else {
-                        return Err(ReflectPathError::ExpectedIdent {
-                            index: current_index,
-                        });
-                    }
-                }
-                Token::OpenBracket => {
-                    if let Some(Token::Ident(value)) = next_token(path, &mut index) {
-                        match current.reflect_ref() {
-                            ReflectRef::List(reflect_list) => {
-                                current = read_array_entry(reflect_list, value, current_index)?;
-                            }
-                            ReflectRef::Array(reflect_arr) => {
-                                current = read_array_entry(reflect_arr, value, current_index)?;
-                            }
-                            _ => {
-                                return Err(ReflectPathError::ExpectedList {
-                                    index: current_index,
-                                })
-                            }
-                        }
-                    } else {
-                        return Err(ReflectPathError::ExpectedIdent {
-                            index: current_index,
-                        });
-                    }
-
-                    if let Some(Token::CloseBracket) = next_token(path, &mut index) {
-                    } else {
-                        return Err(ReflectPathError::ExpectedToken {
-                            index: current_index,
-                            token: "]",
-                        });
-                    }
-                }
-                Token::CloseBracket => {
-                    return Err(ReflectPathError::UnexpectedToken {
-                        index: current_index,
-                        token: "]",
-                    })
-                }
-                Token::Ident(value) => {
-                    current = read_field(current, value, current_index)?;
-                }
-            }
+        for access in PathParser::new(path) {
+            let (access, index) = access?;
+            current = access.as_access().read(current, index)?;
         }
-
         Ok(current)
     }
 
@@ -166,74 +134,294 @@ impl GetPath for dyn Reflect {
         &'r mut self,
         path: &'p str,
     ) -> Result<&'r mut dyn Reflect, ReflectPathError<'p>> {
-        let mut index = 0;
         let mut current: &mut dyn Reflect = self;
-        while let Some(token) = next_token(path, &mut index) {
-            let current_index = index;
-            match token {
-                Token::Dot => {
-                    if let Some(Token::Ident(value)) = next_token(path, &mut index) {
-                        current = read_field_mut(current, value, current_index)?;
-                    } else {
-                        return Err(ReflectPathError::ExpectedIdent {
-                            index: current_index,
-                        });
-                    }
-                }
-                Token::OpenBracket => {
-                    if let Some(Token::Ident(value)) = next_token(path, &mut index) {
-                        match current.reflect_mut() {
-                            ReflectMut::List(reflect_list) => {
-                                current = read_array_entry_mut(reflect_list, value, current_index)?;
-                            }
-                            ReflectMut::Array(reflect_arr) => {
-                                current = read_array_entry_mut(reflect_arr, value, current_index)?;
-                            }
-                            _ => {
-                                return Err(ReflectPathError::ExpectedStruct {
-                                    index: current_index,
-                                })
-                            }
-                        }
-                    } else {
-                        return Err(ReflectPathError::ExpectedIdent {
-                            index: current_index,
-                        });
-                    }
+        for access in PathParser::new(path) {
+            let (access, index) = access?;
+            current = access.as_access().read_mut(current, index)?;
+        }
+        Ok(current)
+    }
+}
 
-                    if let Some(Token::CloseBracket) = next_token(path, &mut index) {
-                    } else {
-                        return Err(ReflectPathError::ExpectedToken {
-                            index: current_index,
-                            token: "]",
-                        });
-                    }
-                }
-                Token::CloseBracket => {
-                    return Err(ReflectPathError::UnexpectedToken {
-                        index: current_index,
-                        token: "]",
-                    })
-                }
-                Token::Ident(value) => {
-                    current = read_field_mut(current, value, current_index)?;
-                }
-            }
+/// A path to a nested value, parsed once from a path string.
+///
+/// Looking up a value with a `ParsedPath` skips the parsing done by each call to
+/// [`GetPath::path`], which makes it better suited to paths that are used repeatedly,
+/// like the targets of animation curves.
+///
+/// # Example
+/// ```
+/// # use bevy_reflect::{ParsedPath, Reflect};
+/// #[derive(Reflect)]
+/// struct Player {
+///     position: (f32, f32),
+/// }
+///
+/// let path = ParsedPath::parse("position.1").unwrap();
+/// let mut player = Player { position: (0.0, 0.0) };
+/// for _ in 0..10 {
+///     *path.get_element_mut::<f32>(&mut player).unwrap() += 1.0;
+/// }
+/// assert_eq!(player.position.1, 10.0);
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParsedPath {
+    accesses: Vec<(OwnedAccess, usize)>,
+}
+
+impl ParsedPath {
+    /// Parses a path string, see [`GetPath`] for its syntax.
+    pub fn parse(path: &str) -> Result<Self, ReflectPathError<'_>> {
+        let accesses = PathParser::new(path)
+            .map(|access| access.map(|(access, index)| (access.into_owned(), index)))
+            .collect::<Result<_, _>>()?;
+        Ok(Self { accesses })
+    }
+
+    /// Returns a reference to the value specified by this path in `root`.
+    pub fn element<'r, 'p>(
+        &'p self,
+        root: &'r dyn Reflect,
+    ) -> Result<&'r dyn Reflect, ReflectPathError<'p>> {
+        let mut current = root;
+        for (access, index) in &self.accesses {
+            current = access.as_access().read(current, *index)?;
         }
+        Ok(current)
+    }
 
+    /// Returns a mutable reference to the value specified by this path in `root`.
+    pub fn element_mut<'r, 'p>(
+        &'p self,
+        root: &'r mut dyn Reflect,
+    ) -> Result<&'r mut dyn Reflect, ReflectPathError<'p>> {
+        let mut current = root;
+        for (access, index) in &self.accesses {
+            current = access.as_access().read_mut(current, *index)?;
+        }
         Ok(current)
     }
+
+    /// Returns a statically typed reference to the value specified by this path in `root`.
+    pub fn get_element<'r, 'p, T: Reflect>(
+        &'p self,
+        root: &'r dyn Reflect,
+    ) -> Result<&'r T, ReflectPathError<'p>> {
+        self.element(root).and_then(|p| {
+            p.downcast_ref::<T>()
+                .ok_or(ReflectPathError::InvalidDowncast)
+        })
+    }
+
+    /// Returns a statically typed mutable reference to the value specified by this path in
+    /// `root`.
+    pub fn get_element_mut<'r, 'p, T: Reflect>(
+        &'p self,
+        root: &'r mut dyn Reflect,
+    ) -> Result<&'r mut T, ReflectPathError<'p>> {
+        self.element_mut(root).and_then(|p| {
+            p.downcast_mut::<T>()
+                .ok_or(ReflectPathError::InvalidDowncast)
+        })
+    }
+}
+
+/// A single step of a path, borrowing the key of a map access.
+#[derive(Clone, Copy)]
+enum Access<'k, 'a> {
+    /// A named or numbered field: `.field` or `.0`.
+    Field(&'a str),
+    /// A list or array index, or an integer map key: `[0]`.
+    Index(usize),
+    /// A string map key: `["key"]`, with its escapes resolved, and as written in the path.
+    Key { key: &'k String, raw: &'a str },
+}
+
+/// A single step of a path, as parsed from a path string.
+enum PathAccess<'a> {
+    Field(&'a str),
+    Index(usize),
+    Key { key: String, raw: &'a str },
+}
+
+impl<'a> PathAccess<'a> {
+    fn as_access(&self) -> Access<'_, 'a> {
+        match self {
+            PathAccess::Field(field) => Access::Field(field),
+            PathAccess::Index(index) => Access::Index(*index),
+            PathAccess::Key { key, raw } => Access::Key { key, raw },
+        }
+    }
+
+    fn into_owned(self) -> OwnedAccess {
+        match self {
+            PathAccess::Field(field) => OwnedAccess::Field(field.to_string()),
+            PathAccess::Index(index) => OwnedAccess::Index(index),
+            PathAccess::Key { key, raw } => OwnedAccess::Key {
+                key,
+                raw: raw.to_string(),
+            },
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum OwnedAccess {
+    Field(String),
+    Index(usize),
+    Key { key: String, raw: String },
+}
+
+impl OwnedAccess {
+    fn as_access(&self) -> Access<'_, '_> {
+        match self {
+            OwnedAccess::Field(field) => Access::Field(field),
+            OwnedAccess::Index(index) => Access::Index(*index),
+            OwnedAccess::Key { key, raw } => Access::Key { key, raw },
+        }
+    }
+}
+
+impl<'k, 'a> Access<'k, 'a> {
+    fn read<'r>(
+        self,
+        current: &'r dyn Reflect,
+        current_index: usize,
+    ) -> Result<&'r dyn Reflect, ReflectPathError<'a>> {
+        match (self, current.reflect_ref()) {
+            (Access::Field(field), reflect_ref) => read_field(reflect_ref, field, current_index),
+            (Access::Index(list_index), ReflectRef::List(reflect_list)) => {
+                read_array_entry(reflect_list, list_index, current_index)
+            }
+            (Access::Index(list_index), ReflectRef::Array(reflect_arr)) => {
+                read_array_entry(reflect_arr, list_index, current_index)
+            }
+            (Access::Index(_) | Access::Key { .. }, ReflectRef::Map(reflect_map)) => {
+                read_map_entry(reflect_map, self).ok_or_else(|| self.invalid_key(current_index))
+            }
+            (Access::Index(_), _) => Err(ReflectPathError::ExpectedList {
+                index: current_index,
+            }),
+            (Access::Key { .. }, _) => Err(ReflectPathError::ExpectedMap {
+                index: current_index,
+            }),
+        }
+    }
+
+    fn read_mut<'r>(
+        self,
+        current: &'r mut dyn Reflect,
+        current_index: usize,
+    ) -> Result<&'r mut dyn Reflect, ReflectPathError<'a>> {
+        match (self, current.reflect_mut()) {
+            (Access::Field(field), reflect_mut) => {
+                read_field_mut(reflect_mut, field, current_index)
+            }
+            (Access::Index(list_index), ReflectMut::List(reflect_list)) => {
+                read_array_entry_mut(reflect_list, list_index, current_index)
+            }
+            (Access::Index(list_index), ReflectMut::Array(reflect_arr)) => {
+                read_array_entry_mut(reflect_arr, list_index, current_index)
+            }
+            (Access::Index(_) | Access::Key { .. }, ReflectMut::Map(reflect_map)) => {
+                read_map_entry_mut(reflect_map, self).ok_or_else(|| self.invalid_key(current_index))
+            }
+            (Access::Index(_), _) => Err(ReflectPathError::ExpectedList {
+                index: current_index,
+            }),
+            (Access::Key { .. }, _) => Err(ReflectPathError::ExpectedMap {
+                index: current_index,
+            }),
+        }
+    }
+
+    /// Returns the key of a map access, as the most common key type for it.
+    fn map_key(&self) -> Option<&dyn Reflect> {
+        match self {
+            Access::Key { key, .. } => Some(*key),
+            Access::Index(index) => Some(index),
+            Access::Field(_) => None,
+        }
+    }
+
+    fn invalid_key(self, current_index: usize) -> ReflectPathError<'a> {
+        match self {
+            Access::Field(field) => ReflectPathError::InvalidField {
+                index: current_index,
+                field,
+            },
+            Access::Index(list_index) => ReflectPathError::InvalidListIndex {
+                index: current_index,
+                list_index,
+            },
+            Access::Key { raw, .. } => ReflectPathError::InvalidMapKey {
+                index: current_index,
+                key: raw,
+            },
+        }
+    }
 }
 
-fn read_array_entry<'r, 'p, T>(
-    list: &'r T,
-    value: &'p str,
+fn read_map_entry<'r>(map: &'r dyn Map, access: Access) -> Option<&'r dyn Reflect> {
+    // try the most common key types first, as a lookup doesn't need to go through the entries
+    let key = access.map_key()?;
+    match map.get(key) {
+        Some(value) => Some(value),
+        None => map.get(&*find_map_key(map, access)?),
+    }
+}
+
+fn read_map_entry_mut<'r>(map: &'r mut dyn Map, access: Access) -> Option<&'r mut dyn Reflect> {
+    let key = access.map_key()?;
+    if map.get(key).is_some() {
+        return map.get_mut(key);
+    }
+    let key = find_map_key(map, access)?;
+    map.get_mut(&*key)
+}
+
+/// Returns the key of `map` identified by a map access, among the keys of other types.
+///
+/// String keys match `String` and `Cow<str>` keys, and integer keys match keys of any
+/// integer type.
+fn find_map_key(map: &dyn Map, access: Access) -> Option<Box<dyn Reflect>> {
+    map.iter()
+        .map(|(key, _)| key)
+        .find(|key| match access {
+            Access::Key { key: text, .. } => {
+                key.downcast_ref::<String>() == Some(text)
+                    || key.downcast_ref::<Cow<'static, str>>().map(AsRef::as_ref)
+                        == Some(text.as_str())
+            }
+            Access::Index(index) => integer_key(*key) == Some(index as i128),
+            Access::Field(_) => false,
+        })
+        .map(|key| key.clone_value())
+}
+
+fn integer_key(key: &dyn Reflect) -> Option<i128> {
+    macro_rules! integer_key {
+        ($($ty:ty),*) => {
+            $(
+                if let Some(key) = key.downcast_ref::<$ty>() {
+                    return i128::try_from(*key).ok();
+                }
+            )*
+        };
+    }
+
+    integer_key!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize);
+    None
+}
+
+fn read_array_entry<'p, T>(
+    list: &T,
+    list_index: usize,
     current_index: usize,
-) -> Result<&'r dyn Reflect, ReflectPathError<'p>>
+) -> Result<&dyn Reflect, ReflectPathError<'p>>
 where
     T: Array + ?Sized,
 {
-    let list_index = value.parse::<usize>()?;
     list.get(list_index)
         .ok_or(ReflectPathError::InvalidListIndex {
             index: current_index,
@@ -241,15 +429,14 @@ where
         })
 }
 
-fn read_array_entry_mut<'r, 'p, T>(
-    list: &'r mut T,
-    value: &'p str,
+fn read_array_entry_mut<'p, T>(
+    list: &mut T,
+    list_index: usize,
     current_index: usize,
-) -> Result<&'r mut dyn Reflect, ReflectPathError<'p>>
+) -> Result<&mut dyn Reflect, ReflectPathError<'p>>
 where
     T: Array + ?Sized,
 {
-    let list_index = value.parse::<usize>()?;
     list.get_mut(list_index)
         .ok_or(ReflectPathError::InvalidListIndex {
             index: current_index,
@@ -258,11 +445,11 @@ where
 }
 
 fn read_field<'r, 'p>(
-    current: &'r dyn Reflect,
+    current: ReflectRef<'r>,
     field: &'p str,
     current_index: usize,
 ) -> Result<&'r dyn Reflect, ReflectPathError<'p>> {
-    match current.reflect_ref() {
+    match current {
         ReflectRef::Struct(reflect_struct) => {
             Ok(reflect_struct
                 .field(field)
@@ -280,6 +467,27 @@ fn read_field<'r, 'p>(
                 },
             )?)
         }
+        ReflectRef::Tuple(reflect_tuple) => {
+            let tuple_index = field.parse::<usize>()?;
+            Ok(reflect_tuple.field(tuple_index).ok_or(
+                ReflectPathError::InvalidTupleStructIndex {
+                    index: current_index,
+                    tuple_struct_index: tuple_index,
+                },
+            )?)
+        }
+        ReflectRef::Enum(reflect_enum) => {
+            let value = match reflect_enum.variant_type() {
+                VariantType::Struct => reflect_enum.field(field),
+                VariantType::Tuple => reflect_enum.field_at(field.parse::<usize>()?),
+                VariantType::Unit => None,
+            };
+            value.ok_or_else(|| ReflectPathError::InvalidVariantField {
+                index: current_index,
+                variant: reflect_enum.variant_name().to_string(),
+                field,
+            })
+        }
         _ => Err(ReflectPathError::ExpectedStruct {
             index: current_index,
         }),
@@ -287,11 +495,11 @@ fn read_field<'r, 'p>(
 }
 
 fn read_field_mut<'r, 'p>(
-    current: &'r mut dyn Reflect,
+    current: ReflectMut<'r>,
     field: &'p str,
     current_index: usize,
 ) -> Result<&'r mut dyn Reflect, ReflectPathError<'p>> {
-    match current.reflect_mut() {
+    match current {
         ReflectMut::Struct(reflect_struct) => {
             Ok(reflect_struct
                 .field_mut(field)
@@ -309,17 +517,112 @@ fn read_field_mut<'r, 'p>(
                 },
             )?)
         }
+        ReflectMut::Tuple(reflect_tuple) => {
+            let tuple_index = field.parse::<usize>()?;
+            Ok(reflect_tuple.field_mut(tuple_index).ok_or(
+                ReflectPathError::InvalidTupleStructIndex {
+                    index: current_index,
+                    tuple_struct_index: tuple_index,
+                },
+            )?)
+        }
+        ReflectMut::Enum(reflect_enum) => {
+            let variant = reflect_enum.variant_name().to_string();
+            let value = match reflect_enum.variant_type() {
+                VariantType::Struct => reflect_enum.field_mut(field),
+                VariantType::Tuple => reflect_enum.field_at_mut(field.parse::<usize>()?),
+                VariantType::Unit => None,
+            };
+            value.ok_or(ReflectPathError::InvalidVariantField {
+                index: current_index,
+                variant,
+                field,
+            })
+        }
         _ => Err(ReflectPathError::ExpectedStruct {
             index: current_index,
         }),
     }
 }
 
+/// Parses a path string into its accesses, along with their index in the string.
+struct PathParser<'a> {
+    path: &'a str,
+    index: usize,
+}
+
+impl<'a> PathParser<'a> {
+    fn new(path: &'a str) -> Self {
+        Self { path, index: 0 }
+    }
+}
+
+impl<'a> Iterator for PathParser<'a> {
+    type Item = Result<(PathAccess<'a>, usize), ReflectPathError<'a>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let token = next_token(self.path, &mut self.index)?;
+        let current_index = self.index;
+        let access = match token {
+            Token::Dot => {
+                if let Some(Token::Ident(value)) = next_token(self.path, &mut self.index) {
+                    PathAccess::Field(value)
+                } else {
+                    return Some(Err(ReflectPathError::ExpectedIdent {
+                        index: current_index,
+                    }));
+                }
+            }
+            Token::OpenBracket => {
+                let access = match next_token(self.path, &mut self.index) {
+                    Some(Token::Ident(value)) => match value.parse::<usize>() {
+                        Ok(index) => PathAccess::Index(index),
+                        Err(error) => return Some(Err(error.into())),
+                    },
+                    Some(Token::Quoted(raw)) => PathAccess::Key {
+                        key: unescape_key(raw),
+                        raw,
+                    },
+                    _ => {
+                        return Some(Err(ReflectPathError::ExpectedIdent {
+                            index: current_index,
+                        }))
+                    }
+                };
+
+                if let Some(Token::CloseBracket) = next_token(self.path, &mut self.index) {
+                } else {
+                    return Some(Err(ReflectPathError::ExpectedToken {
+                        index: current_index,
+                        token: "]",
+                    }));
+                }
+                access
+            }
+            Token::CloseBracket => {
+                return Some(Err(ReflectPathError::UnexpectedToken {
+                    index: current_index,
+                    token: "]",
+                }))
+            }
+            Token::Quoted(_) => {
+                return Some(Err(ReflectPathError::UnexpectedToken {
+                    index: current_index,
+                    token: "\"",
+                }))
+            }
+            Token::Ident(value) => PathAccess::Field(value),
+        };
+        Some(Ok((access, current_index)))
+    }
+}
+
 enum Token<'a> {
     Dot,
     OpenBracket,
     CloseBracket,
     Ident(&'a str),
+    Quoted(&'a str),
 }
 
 fn next_token<'a>(path: &'a str, index: &mut usize) -> Option<Token<'a>> {
@@ -340,13 +643,28 @@ fn next_token<'a>(path: &'a str, index: &mut usize) -> Option<Token<'a>> {
             *index += 1;
             return Some(Token::CloseBracket);
         }
+        '"' => {
+            // quoted strings end at the next unescaped quote, or at the end of the path
+            let start = *index + 1;
+            let mut escaped = false;
+            let end = path[start..]
+                .char_indices()
+                .find(|&(_, character)| {
+                    let end = !escaped && character == '"';
+                    escaped = !escaped && character == '\\';
+                    end
+                })
+                .map_or(path.len(), |(end, _)| start + end);
+            *index = (end + 1).min(path.len());
+            return Some(Token::Quoted(&path[start..end]));
+        }
         _ => {}
     }
 
     // we can assume we are parsing an ident now
-    for (char_index, character) in path[*index..].chars().enumerate() {
+    for (char_index, character) in path[*index..].char_indices() {
         match character {
-            '.' | '[' | ']' => {
+            '.' | '[' | ']' | '"' => {
                 let ident = Token::Ident(&path[*index..*index + char_index]);
                 *index += char_index;
                 return Some(ident);
@@ -359,12 +677,27 @@ fn next_token<'a>(path: &'a str, index: &mut usize) -> Option<Token<'a>> {
     Some(ident)
 }
 
+/// Resolves the escapes of a quoted map key, where a backslash escapes the next character.
+fn unescape_key(raw: &str) -> String {
+    let mut key = String::with_capacity(raw.len());
+    let mut chars = raw.chars();
+    while let Some(character) = chars.next() {
+        match character {
+            '\\' => key.extend(chars.next()),
+            _ => key.push(character),
+        }
+    }
+    key
+}
+
 #[cfg(test)]
 #[allow(clippy::float_cmp, clippy::approx_constant)]
 mod tests {
-    use super::GetPath;
+    use super::{GetPath, ParsedPath};
     use crate as bevy_reflect;
     use crate::*;
+    use bevy_utils::HashMap;
+    use std::collections::BTreeMap;
 
     #[test]
     fn reflect_array_behaves_like_list() {
@@ -482,4 +815,148 @@ mod tests {
             Err(ReflectPathError::IndexParseError(_))
         ));
     }
+
+    #[test]
+    fn reflect_path_map_keys() {
+        #[derive(Reflect, FromReflect, Debug, PartialEq)]
+        struct Item {
+            damage: u32,
+        }
+
+        #[derive(Reflect)]
+        struct Player {
+            inventory: HashMap<String, Item>,
+            slots: BTreeMap<u8, String>,
+            cooldowns: HashMap<usize, f32>,
+        }
+
+        let mut player = Player {
+            inventory: HashMap::from_iter([
+                ("sword".to_string(), Item { damage: 5 }),
+                ("\"big\" \\ axe".to_string(), Item { damage: 8 }),
+            ]),
+            slots: BTreeMap::from([(1, "sword".to_string())]),
+            cooldowns: HashMap::from_iter([(3, 0.5)]),
+        };
+
+        assert_eq!(
+            *player
+                .get_path::<u32>("inventory[\"sword\"].damage")
+                .unwrap(),
+            5
+        );
+        assert_eq!(
+            *player
+                .get_path::<u32>(r#"inventory["\"big\" \\ axe"].damage"#)
+                .unwrap(),
+            8
+        );
+        assert_eq!(player.get_path::<String>("slots[1]").unwrap(), "sword");
+        assert_eq!(*player.get_path::<f32>("cooldowns[3]").unwrap(), 0.5);
+
+        *player
+            .get_path_mut::<u32>("inventory[\"sword\"].damage")
+            .unwrap() = 10;
+        assert_eq!(player.inventory["sword"], Item { damage: 10 });
+        *player.get_path_mut::<String>("slots[1]").unwrap() = "bow".to_string();
+        assert_eq!(player.slots[&1], "bow");
+
+        assert_eq!(
+            player.path("inventory[\"shield\"]").err().unwrap(),
+            ReflectPathError::InvalidMapKey {
+                index: 10,
+                key: "shield"
+            }
+        );
+        assert_eq!(
+            player.path("slots[2]").err().unwrap(),
+            ReflectPathError::InvalidListIndex {
+                index: 6,
+                list_index: 2
+            }
+        );
+        assert_eq!(
+            player.path("slots[1][\"key\"]").err().unwrap(),
+            ReflectPathError::ExpectedMap { index: 9 }
+        );
+        assert_eq!(
+            player.path("inventory[\"sword\"").err().unwrap(),
+            ReflectPathError::ExpectedToken {
+                index: 10,
+                token: "]"
+            }
+        );
+    }
+
+    #[test]
+    fn reflect_path_enum_fields() {
+        #[derive(Reflect)]
+        enum Shape {
+            Circle { radius: f32 },
+            Pair(f32, f32),
+            Empty,
+        }
+
+        #[derive(Reflect)]
+        struct A {
+            shape: Shape,
+        }
+
+        let mut a = A {
+            shape: Shape::Circle { radius: 2.0 },
+        };
+        assert_eq!(*a.get_path::<f32>("shape.radius").unwrap(), 2.0);
+        *a.get_path_mut::<f32>("shape.radius").unwrap() = 3.0;
+        assert!(matches!(a.shape, Shape::Circle { radius } if radius == 3.0));
+
+        a.shape = Shape::Pair(1.0, 4.0);
+        assert_eq!(*a.get_path::<f32>("shape.1").unwrap(), 4.0);
+        assert_eq!(
+            a.path("shape.radius").err().unwrap(),
+            ReflectPathError::IndexParseError("radius".parse::<usize>().unwrap_err())
+        );
+
+        a.shape = Shape::Empty;
+        assert_eq!(
+            a.path("shape.0").err().unwrap(),
+            ReflectPathError::InvalidVariantField {
+                index: 6,
+                variant: "Empty".to_string(),
+                field: "0"
+            }
+        );
+    }
+
+    #[test]
+    fn parsed_path() {
+        #[derive(Reflect)]
+        struct A {
+            values: HashMap<String, Vec<(f32, f32)>>,
+        }
+
+        let mut a = A {
+            values: HashMap::from_iter([("x".to_string(), vec![(0.0, 0.0), (1.0, 2.0)])]),
+        };
+
+        let path = ParsedPath::parse("values[\"x\"][1].1").unwrap();
+        assert_eq!(*path.get_element::<f32>(&a).unwrap(), 2.0);
+        for _ in 0..3 {
+            *path.get_element_mut::<f32>(&mut a).unwrap() += 1.0;
+        }
+        assert_eq!(a.values["x"][1].1, 5.0);
+
+        a.values.insert("\"y\"".to_string(), vec![(3.0, 4.0)]);
+        let escaped = ParsedPath::parse(r#"values["\"y\""][0].0"#).unwrap();
+        assert_eq!(*escaped.get_element::<f32>(&a).unwrap(), 3.0);
+
+        let missing = ParsedPath::parse("values[\"z\"]").unwrap();
+        assert_eq!(
+            missing.element(&a).err().unwrap(),
+            ReflectPathError::InvalidMapKey { index: 7, key: "z" }
+        );
+        assert!(matches!(
+            ParsedPath::parse("values[x]"),
+            Err(ReflectPathError::IndexParseError(_))
+        ));
+    }
 }
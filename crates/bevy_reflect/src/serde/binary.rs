@@ -0,0 +1,361 @@
+use crate::serde::{TypedReflectDeserializer, TypedReflectSerializer};
+use crate::{Reflect, TypeRegistry};
+use bevy_utils::HashMap;
+use serde::de::{DeserializeSeed, Error as _, SeqAccess, Visitor};
+use serde::ser::{Error as _, SerializeSeq, SerializeTuple};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::fmt::Formatter;
+
+/// Stable numeric ids for the names of reflected types.
+///
+/// The binary reflect format writes these ids in place of type names, so the same manifest
+/// must be used to serialize and deserialize a value. A manifest is itself serializable as
+/// the list of its type names, ordered by id, so it can be stored next to save files or
+/// exchanged once at the start of a network session.
+///
+/// Ids are assigned in insertion order and never change, so a manifest can be extended with
+/// newly registered types without invalidating data written with it before.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct TypeManifest {
+    type_names: Vec<String>,
+    ids: HashMap<String, u32>,
+}
+
+impl TypeManifest {
+    /// Creates an empty manifest.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a manifest of all types in `registry`, with ids assigned in the order of
+    /// their type names.
+    pub fn from_registry(registry: &TypeRegistry) -> Self {
+        let mut manifest = Self::new();
+        manifest.extend_from_registry(registry);
+        manifest
+    }
+
+    /// Adds the types in `registry` which aren't in the manifest yet, with ids assigned in
+    /// the order of their type names.
+    pub fn extend_from_registry(&mut self, registry: &TypeRegistry) {
+        let mut type_names = registry
+            .iter()
+            .map(|registration| registration.type_name())
+            .filter(|type_name| !self.ids.contains_key(*type_name))
+            .collect::<Vec<_>>();
+        type_names.sort_unstable();
+        for type_name in type_names {
+            self.insert(type_name);
+        }
+    }
+
+    /// Adds a type name to the manifest, returning its id.
+    ///
+    /// If the type name is already in the manifest, its existing id is returned.
+    pub fn insert(&mut self, type_name: &str) -> u32 {
+        if let Some(id) = self.ids.get(type_name) {
+            return *id;
+        }
+        let id = self.type_names.len() as u32;
+        self.type_names.push(type_name.to_string());
+        self.ids.insert(type_name.to_string(), id);
+        id
+    }
+
+    /// Returns the id of the given type name.
+    pub fn id(&self, type_name: &str) -> Option<u32> {
+        self.ids.get(type_name).copied()
+    }
+
+    /// Returns the type name with the given id.
+    pub fn type_name(&self, id: u32) -> Option<&str> {
+        self.type_names.get(id as usize).map(String::as_str)
+    }
+
+    /// Returns an iterator over the type names, ordered by id.
+    pub fn iter(&self) -> impl Iterator<Item = &str> {
+        self.type_names.iter().map(String::as_str)
+    }
+
+    /// The number of types in the manifest.
+    pub fn len(&self) -> usize {
+        self.type_names.len()
+    }
+
+    /// Returns true if the manifest has no types.
+    pub fn is_empty(&self) -> bool {
+        self.type_names.is_empty()
+    }
+}
+
+impl Serialize for TypeManifest {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut state = serializer.serialize_seq(Some(self.type_names.len()))?;
+        for type_name in &self.type_names {
+            state.serialize_element(type_name)?;
+        }
+        state.end()
+    }
+}
+
+impl<'de> Deserialize<'de> for TypeManifest {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let mut manifest = TypeManifest::new();
+        for type_name in Vec::<String>::deserialize(deserializer)? {
+            let id = manifest.type_names.len();
+            if manifest.insert(&type_name) as usize != id {
+                return Err(D::Error::custom(format_args!(
+                    "duplicate type name `{type_name}` in manifest"
+                )));
+            }
+        }
+        Ok(manifest)
+    }
+}
+
+/// A serializer for reflected values which writes the id of the type from a
+/// [`TypeManifest`] instead of its name.
+///
+/// The value is serialized as a tuple of the id and the value itself, which makes this
+/// suited to compact, non-self-describing formats like `bincode` or `postcard`. Use
+/// [`BinaryReflectDeserializer`] with the same manifest to read it back.
+///
+/// # Example
+/// ```
+/// # use bevy_reflect::{FromReflect, Reflect, TypeRegistry};
+/// # use bevy_reflect::serde::{BinaryReflectDeserializer, BinaryReflectSerializer, TypeManifest};
+/// # use bincode::Options;
+/// #[derive(Reflect, FromReflect, PartialEq, Debug)]
+/// struct Position(f32, f32);
+///
+/// let mut registry = TypeRegistry::default();
+/// registry.register::<Position>();
+/// let manifest = TypeManifest::from_registry(&registry);
+///
+/// let value = Position(1.0, 2.0);
+/// let bytes = bincode::serialize(&BinaryReflectSerializer::new(&value, &manifest, &registry)).unwrap();
+///
+/// let deserialized = bincode::DefaultOptions::new()
+///     .with_fixint_encoding()
+///     .deserialize_seed(BinaryReflectDeserializer::new(&manifest, &registry), &bytes)
+///     .unwrap();
+/// assert_eq!(Position::from_reflect(&*deserialized), Some(value));
+/// ```
+pub struct BinaryReflectSerializer<'a> {
+    pub value: &'a dyn Reflect,
+    pub manifest: &'a TypeManifest,
+    pub registry: &'a TypeRegistry,
+}
+
+impl<'a> BinaryReflectSerializer<'a> {
+    pub fn new(
+        value: &'a dyn Reflect,
+        manifest: &'a TypeManifest,
+        registry: &'a TypeRegistry,
+    ) -> Self {
+        Self {
+            value,
+            manifest,
+            registry,
+        }
+    }
+}
+
+impl<'a> Serialize for BinaryReflectSerializer<'a> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let type_name = self.value.type_name();
+        let id = self.manifest.id(type_name).ok_or_else(|| {
+            S::Error::custom(format_args!("`{type_name}` is not in the type manifest"))
+        })?;
+
+        let mut state = serializer.serialize_tuple(2)?;
+        state.serialize_element(&id)?;
+        state.serialize_element(&TypedReflectSerializer::new(self.value, self.registry))?;
+        state.end()
+    }
+}
+
+/// A deserializer for values written by [`BinaryReflectSerializer`].
+///
+/// Like [`UntypedReflectDeserializer`](crate::serde::UntypedReflectDeserializer), this
+/// returns the dynamic equivalent of the value for non-value types, which can be converted
+/// with [`FromReflect`](crate::FromReflect).
+pub struct BinaryReflectDeserializer<'a> {
+    manifest: &'a TypeManifest,
+    registry: &'a TypeRegistry,
+}
+
+impl<'a> BinaryReflectDeserializer<'a> {
+    pub fn new(manifest: &'a TypeManifest, registry: &'a TypeRegistry) -> Self {
+        Self { manifest, registry }
+    }
+}
+
+impl<'a, 'de> DeserializeSeed<'de> for BinaryReflectDeserializer<'a> {
+    type Value = Box<dyn Reflect>;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_tuple(
+            2,
+            BinaryReflectVisitor {
+                manifest: self.manifest,
+                registry: self.registry,
+            },
+        )
+    }
+}
+
+struct BinaryReflectVisitor<'a> {
+    manifest: &'a TypeManifest,
+    registry: &'a TypeRegistry,
+}
+
+impl<'a, 'de> Visitor<'de> for BinaryReflectVisitor<'a> {
+    type Value = Box<dyn Reflect>;
+
+    fn expecting(&self, formatter: &mut Formatter) -> std::fmt::Result {
+        formatter.write_str("tuple containing the type id and the reflected value")
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        let id = seq
+            .next_element::<u32>()?
+            .ok_or_else(|| A::Error::invalid_length(0, &self))?;
+        let type_name = self
+            .manifest
+            .type_name(id)
+            .ok_or_else(|| A::Error::custom(format_args!("no type with id {id} in manifest")))?;
+        let registration = self.registry.get_with_name(type_name).ok_or_else(|| {
+            A::Error::custom(format_args!("No registration found for `{type_name}`"))
+        })?;
+        seq.next_element_seed(TypedReflectDeserializer::new(registration, self.registry))?
+            .ok_or_else(|| A::Error::invalid_length(1, &self))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate as bevy_reflect;
+    use crate::serde::ReflectSerializer;
+    use crate::FromReflect;
+    use bincode::Options;
+
+    #[derive(Reflect, FromReflect, Debug, PartialEq)]
+    struct Player {
+        name: String,
+        position: (f32, f32),
+        state: State,
+        items: Vec<Option<u16>>,
+    }
+
+    #[derive(Reflect, FromReflect, Debug, PartialEq)]
+    enum State {
+        Idle,
+        Moving { speed: f32 },
+    }
+
+    fn registry() -> TypeRegistry {
+        let mut registry = TypeRegistry::default();
+        registry.register::<Player>();
+        registry.register::<State>();
+        registry.register::<String>();
+        registry.register::<(f32, f32)>();
+        registry.register::<u16>();
+        registry.register::<Option<u16>>();
+        registry.register::<Vec<Option<u16>>>();
+        registry
+    }
+
+    fn player() -> Player {
+        Player {
+            name: "Ferris".to_string(),
+            position: (1.0, -2.5),
+            state: State::Moving { speed: 3.0 },
+            items: vec![Some(4), None],
+        }
+    }
+
+    #[test]
+    fn roundtrip_binary() {
+        let registry = registry();
+        let manifest = TypeManifest::from_registry(&registry);
+        let player = player();
+
+        let serializer = BinaryReflectSerializer::new(&player, &manifest, &registry);
+        let bytes = bincode::serialize(&serializer).unwrap();
+        let named = bincode::serialize(&ReflectSerializer::new(&player, &registry)).unwrap();
+        assert!(bytes.len() + std::any::type_name::<Player>().len() <= named.len());
+
+        let deserialized = bincode::DefaultOptions::new()
+            .with_fixint_encoding()
+            .deserialize_seed(BinaryReflectDeserializer::new(&manifest, &registry), &bytes)
+            .unwrap();
+        assert_eq!(Player::from_reflect(&*deserialized).as_ref(), Some(&player));
+
+        let bytes = rmp_serde::to_vec(&serializer).unwrap();
+        let deserialized = BinaryReflectDeserializer::new(&manifest, &registry)
+            .deserialize(&mut rmp_serde::Deserializer::new(bytes.as_slice()))
+            .unwrap();
+        assert_eq!(Player::from_reflect(&*deserialized).as_ref(), Some(&player));
+    }
+
+    #[test]
+    fn manifest_ids() {
+        let mut registry = TypeRegistry::empty();
+        registry.register::<u16>();
+        registry.register::<String>();
+        let mut manifest = TypeManifest::from_registry(&registry);
+        assert_eq!(
+            manifest.iter().collect::<Vec<_>>(),
+            vec!["alloc::string::String", "u16"]
+        );
+
+        // new types are appended, existing ids are kept
+        registry.register::<bool>();
+        manifest.extend_from_registry(&registry);
+        assert_eq!(manifest.id("alloc::string::String"), Some(0));
+        assert_eq!(manifest.id("u16"), Some(1));
+        assert_eq!(manifest.id("bool"), Some(2));
+        assert_eq!(manifest.type_name(2), Some("bool"));
+
+        let bytes = bincode::serialize(&manifest).unwrap();
+        let deserialized: TypeManifest = bincode::deserialize(&bytes).unwrap();
+        assert_eq!(deserialized, manifest);
+
+        let bytes = bincode::serialize(&vec!["u16", "u16"]).unwrap();
+        assert!(bincode::deserialize::<TypeManifest>(&bytes).is_err());
+    }
+
+    #[test]
+    fn missing_type() {
+        let registry = registry();
+        let manifest = TypeManifest::new();
+        let serializer = BinaryReflectSerializer::new(&1_u16, &manifest, &registry);
+        assert!(bincode::serialize(&serializer).is_err());
+
+        let mut other = TypeManifest::new();
+        other.insert("u16");
+        let bytes =
+            bincode::serialize(&BinaryReflectSerializer::new(&1_u16, &other, &registry)).unwrap();
+        let result = bincode::DefaultOptions::new()
+            .with_fixint_encoding()
+            .deserialize_seed(BinaryReflectDeserializer::new(&manifest, &registry), &bytes);
+        assert!(result.is_err());
+    }
+}
@@ -1,14 +1,15 @@
-use crate::serde::SerializationData;
+use crate::serde::{ReflectMigration, SerializationData, VERSION_FIELD};
 use crate::{
-    ArrayInfo, DynamicArray, DynamicEnum, DynamicList, DynamicMap, DynamicStruct, DynamicTuple,
-    DynamicTupleStruct, DynamicVariant, EnumInfo, ListInfo, Map, MapInfo, NamedField, Reflect,
-    ReflectDeserialize, StructInfo, StructVariantInfo, Tuple, TupleInfo, TupleStruct,
-    TupleStructInfo, TupleVariantInfo, TypeInfo, TypeRegistration, TypeRegistry, UnnamedField,
-    VariantInfo,
+    ArrayInfo, DynamicArray, DynamicEnum, DynamicList, DynamicMap, DynamicSet, DynamicStruct,
+    DynamicTuple, DynamicTupleStruct, DynamicVariant, EnumInfo, ListInfo, Map, MapInfo, NamedField,
+    Reflect, ReflectDeserialize, ReflectRef, Set, SetInfo, Struct, StructInfo, StructVariantInfo,
+    Tuple, TupleInfo, TupleStruct, TupleStructInfo, TupleVariantInfo, TypeInfo, TypeRegistration,
+    TypeRegistry, UnnamedField, VariantInfo,
 };
 use erased_serde::Deserializer;
 use serde::de::{
-    self, DeserializeSeed, EnumAccess, Error, MapAccess, SeqAccess, VariantAccess, Visitor,
+    self, DeserializeSeed, EnumAccess, Error, IntoDeserializer, MapAccess, SeqAccess,
+    VariantAccess, Visitor,
 };
 use serde::Deserialize;
 use std::any::TypeId;
@@ -336,9 +337,13 @@ impl<'a, 'de> DeserializeSeed<'de> for TypedReflectDeserializer<'a> {
 
         match self.registration.type_info() {
             TypeInfo::Struct(struct_info) => {
+                let field_names = self
+                    .registration
+                    .data::<ReflectMigration>()
+                    .map_or(struct_info.field_names(), ReflectMigration::field_names);
                 let mut dynamic_struct = deserializer.deserialize_struct(
                     struct_info.name(),
-                    struct_info.field_names(),
+                    field_names,
                     StructVisitor {
                         struct_info,
                         registration: self.registration,
@@ -388,6 +393,14 @@ impl<'a, 'de> DeserializeSeed<'de> for TypedReflectDeserializer<'a> {
                 dynamic_map.set_name(map_info.type_name().to_string());
                 Ok(Box::new(dynamic_map))
             }
+            TypeInfo::Set(set_info) => {
+                let mut dynamic_set = deserializer.deserialize_seq(SetVisitor {
+                    set_info,
+                    registry: self.registry,
+                })?;
+                dynamic_set.set_name(set_info.type_name().to_string());
+                Ok(Box::new(dynamic_set))
+            }
             TypeInfo::Tuple(tuple_info) => {
                 let mut dynamic_tuple = deserializer.deserialize_tuple(
                     tuple_info.field_len(),
@@ -456,6 +469,9 @@ impl<'a, 'de> Visitor<'de> for StructVisitor<'a> {
     where
         V: MapAccess<'de>,
     {
+        if let Some(migration) = self.registration.data::<ReflectMigration>() {
+            return visit_versioned_struct(&mut map, self.struct_info, migration, self.registry);
+        }
         visit_struct(&mut map, self.struct_info, self.registry)
     }
 
@@ -466,6 +482,21 @@ impl<'a, 'de> Visitor<'de> for StructVisitor<'a> {
         let mut index = 0usize;
         let mut output = DynamicStruct::default();
 
+        if let Some(migration) = self.registration.data::<ReflectMigration>() {
+            let version = seq
+                .next_element::<u32>()?
+                .ok_or_else(|| Error::invalid_length(0, &"a version"))?;
+            if version != migration.version() {
+                // without field names, older layouts can't be told apart from the current one
+                return Err(Error::custom(format_args!(
+                    "cannot migrate `{}` from version {} to {} without a self-describing format",
+                    self.struct_info.type_name(),
+                    version,
+                    migration.version()
+                )));
+            }
+        }
+
         let ignored_len = self
             .registration
             .data::<SerializationData>()
@@ -703,6 +734,39 @@ impl<'a, 'de> Visitor<'de> for MapVisitor<'a> {
     }
 }
 
+struct SetVisitor<'a> {
+    set_info: &'static SetInfo,
+    registry: &'a TypeRegistry,
+}
+
+impl<'a, 'de> Visitor<'de> for SetVisitor<'a> {
+    type Value = DynamicSet;
+
+    fn expecting(&self, formatter: &mut Formatter) -> std::fmt::Result {
+        formatter.write_str("reflected set value")
+    }
+
+    fn visit_seq<V>(self, mut set: V) -> Result<Self::Value, V::Error>
+    where
+        V: SeqAccess<'de>,
+    {
+        let mut dynamic_set = DynamicSet::default();
+        let value_registration = get_registration(
+            self.set_info.value_type_id(),
+            self.set_info.value_type_name(),
+            self.registry,
+        )?;
+        while let Some(value) = set.next_element_seed(TypedReflectDeserializer {
+            registration: value_registration,
+            registry: self.registry,
+        })? {
+            dynamic_set.insert_boxed(value);
+        }
+
+        Ok(dynamic_set)
+    }
+}
+
 struct EnumVisitor<'a> {
     enum_info: &'static EnumInfo,
     registration: &'a TypeRegistration,
@@ -988,6 +1052,344 @@ where
     Ok(dynamic_struct)
 }
 
+fn visit_versioned_struct<'de, V>(
+    map: &mut V,
+    info: &'static StructInfo,
+    migration: &ReflectMigration,
+    registry: &TypeRegistry,
+) -> Result<DynamicStruct, V::Error>
+where
+    V: MapAccess<'de>,
+{
+    // the version is always serialized first, so the fields can be read knowing whether
+    // they are in the current layout or not
+    let mut key = map.next_key::<Ident>()?;
+    let version = match &key {
+        Some(Ident(name)) if name == VERSION_FIELD => {
+            let version = map.next_value::<u32>()?;
+            if version > migration.version() {
+                return Err(Error::custom(format_args!(
+                    "`{}` was serialized with version {}, but the current version is {}",
+                    info.type_name(),
+                    version,
+                    migration.version()
+                )));
+            }
+            key = map.next_key::<Ident>()?;
+            version
+        }
+        _ => 0,
+    };
+
+    let mut dynamic_struct = DynamicStruct::default();
+    while let Some(Ident(name)) = key {
+        if name == VERSION_FIELD {
+            return Err(Error::custom(format_args!(
+                "`{VERSION_FIELD}` must be the first entry of `{}`",
+                info.type_name()
+            )));
+        }
+
+        let value = if version < migration.version() {
+            // the field may have been renamed, removed or changed type since, so it is
+            // left to the migrations and only converted to its current type afterwards
+            map.next_value_seed(UntypedValueDeserializer)?
+        } else {
+            let field = info.field(&name).ok_or_else(|| {
+                let fields = info.iter_fields().map(|field| field.name());
+                Error::custom(format_args!(
+                    "unknown field `{}`, expected one of {:?}",
+                    name,
+                    ExpectedValues(fields.collect())
+                ))
+            })?;
+            let registration = get_registration(field.type_id(), field.type_name(), registry)?;
+            map.next_value_seed(TypedReflectDeserializer {
+                registration,
+                registry,
+            })?
+        };
+        dynamic_struct.insert_boxed(&name, value);
+        key = map.next_key::<Ident>()?;
+    }
+
+    if version == migration.version() {
+        return Ok(dynamic_struct);
+    }
+
+    migration.migrate(version, &mut dynamic_struct);
+    let mut output = DynamicStruct::default();
+    for (index, value) in dynamic_struct.iter_fields().enumerate() {
+        let name = dynamic_struct.name_at(index).unwrap();
+        let value = match info.field(name) {
+            Some(field) if value.type_name() != field.type_name() => {
+                let registration = get_registration(field.type_id(), field.type_name(), registry)?;
+                TypedReflectDeserializer {
+                    registration,
+                    registry,
+                }
+                .deserialize(UntypedValueReplay(value))
+                .map_err(|err| {
+                    Error::custom(format_args!(
+                        "cannot convert field `{}` of `{}` from version {}: {}",
+                        name,
+                        info.type_name(),
+                        version,
+                        err
+                    ))
+                })?
+            }
+            // the value is already of the current type, or will be ignored
+            _ => value.clone_value(),
+        };
+        output.insert_boxed(name, value);
+    }
+    Ok(output)
+}
+
+/// Deserializes a value of a self-describing format without any type information.
+struct UntypedValueDeserializer;
+
+impl<'de> DeserializeSeed<'de> for UntypedValueDeserializer {
+    type Value = Box<dyn Reflect>;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        deserializer.deserialize_any(UntypedValueVisitor)
+    }
+}
+
+struct UntypedValueVisitor;
+
+impl<'de> Visitor<'de> for UntypedValueVisitor {
+    type Value = Box<dyn Reflect>;
+
+    fn expecting(&self, formatter: &mut Formatter) -> fmt::Result {
+        formatter.write_str("any value")
+    }
+
+    fn visit_bool<E: Error>(self, v: bool) -> Result<Self::Value, E> {
+        Ok(Box::new(v))
+    }
+
+    fn visit_i64<E: Error>(self, v: i64) -> Result<Self::Value, E> {
+        Ok(Box::new(v))
+    }
+
+    fn visit_u64<E: Error>(self, v: u64) -> Result<Self::Value, E> {
+        Ok(Box::new(v))
+    }
+
+    fn visit_f64<E: Error>(self, v: f64) -> Result<Self::Value, E> {
+        Ok(Box::new(v))
+    }
+
+    fn visit_char<E: Error>(self, v: char) -> Result<Self::Value, E> {
+        Ok(Box::new(v))
+    }
+
+    fn visit_str<E: Error>(self, v: &str) -> Result<Self::Value, E> {
+        Ok(Box::new(v.to_string()))
+    }
+
+    fn visit_string<E: Error>(self, v: String) -> Result<Self::Value, E> {
+        Ok(Box::new(v))
+    }
+
+    fn visit_unit<E: Error>(self) -> Result<Self::Value, E> {
+        Ok(Box::new(()))
+    }
+
+    fn visit_none<E: Error>(self) -> Result<Self::Value, E> {
+        let mut option = DynamicEnum::default();
+        option.set_variant("None", ());
+        Ok(Box::new(option))
+    }
+
+    fn visit_some<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let mut value = DynamicTuple::default();
+        value.insert_boxed(UntypedValueDeserializer.deserialize(deserializer)?);
+        let mut option = DynamicEnum::default();
+        option.set_variant("Some", value);
+        Ok(Box::new(option))
+    }
+
+    fn visit_newtype_struct<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        UntypedValueDeserializer.deserialize(deserializer)
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        let mut list = DynamicList::default();
+        while let Some(value) = seq.next_element_seed(UntypedValueDeserializer)? {
+            list.push_box(value);
+        }
+        Ok(Box::new(list))
+    }
+
+    fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        let mut entries = Vec::new();
+        while let Some(key) = map.next_key_seed(UntypedValueDeserializer)? {
+            entries.push((key, map.next_value_seed(UntypedValueDeserializer)?));
+        }
+
+        // string keys are most likely the fields of a struct
+        if entries.iter().all(|(key, _)| key.is::<String>()) {
+            let mut dynamic_struct = DynamicStruct::default();
+            for (key, value) in entries {
+                dynamic_struct.insert_boxed(key.downcast_ref::<String>().unwrap(), value);
+            }
+            Ok(Box::new(dynamic_struct))
+        } else {
+            let mut dynamic_map = DynamicMap::default();
+            for (key, value) in entries {
+                dynamic_map.insert_boxed(key, value);
+            }
+            Ok(Box::new(dynamic_map))
+        }
+    }
+}
+
+/// Replays a value read by [`UntypedValueDeserializer`] into a [`Visitor`], so it can be
+/// deserialized again with type information.
+struct UntypedValueReplay<'a>(&'a dyn Reflect);
+
+impl<'a, 'de> serde::Deserializer<'de> for UntypedValueReplay<'a> {
+    type Error = de::value::Error;
+
+    fn deserialize_any<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        if let Some(value) = self.0.downcast_ref::<bool>() {
+            return visitor.visit_bool(*value);
+        }
+        if let Some(value) = self.0.downcast_ref::<i64>() {
+            return visitor.visit_i64(*value);
+        }
+        if let Some(value) = self.0.downcast_ref::<u64>() {
+            return visitor.visit_u64(*value);
+        }
+        if let Some(value) = self.0.downcast_ref::<f64>() {
+            return visitor.visit_f64(*value);
+        }
+        if let Some(value) = self.0.downcast_ref::<char>() {
+            return visitor.visit_char(*value);
+        }
+        if let Some(value) = self.0.downcast_ref::<String>() {
+            return visitor.visit_str(value);
+        }
+        if self.0.is::<()>() {
+            return visitor.visit_unit();
+        }
+
+        match self.0.reflect_ref() {
+            ReflectRef::List(list) => {
+                visitor.visit_seq(de::value::SeqDeserializer::new(list.iter().map(Self)))
+            }
+            ReflectRef::Struct(value) => {
+                let entries = value
+                    .iter_fields()
+                    .enumerate()
+                    .map(|(index, field)| (value.name_at(index).unwrap().to_string(), Self(field)));
+                visitor.visit_map(de::value::MapDeserializer::new(entries))
+            }
+            ReflectRef::Map(map) => visitor.visit_map(de::value::MapDeserializer::new(
+                map.iter().map(|(key, value)| (Self(key), Self(value))),
+            )),
+            ReflectRef::Enum(value) if value.variant_name() == "None" => visitor.visit_none(),
+            ReflectRef::Enum(value) if value.variant_name() == "Some" => {
+                visitor.visit_some(Self(value.field_at(0).unwrap()))
+            }
+            _ => Err(Error::custom(format_args!(
+                "cannot replay a value of type `{}`",
+                self.0.type_name()
+            ))),
+        }
+    }
+
+    fn deserialize_option<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        match self.0.reflect_ref() {
+            ReflectRef::Enum(_) => self.deserialize_any(visitor),
+            // formats without options, like JSON, use a unit for `None` and the value for `Some`
+            _ if self.0.is::<()>() => visitor.visit_none(),
+            _ => visitor.visit_some(self),
+        }
+    }
+
+    fn deserialize_enum<V>(
+        self,
+        name: &'static str,
+        _variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        // externally tagged variants are only self-describing in some formats, like JSON
+        if let Some(variant) = self.0.downcast_ref::<String>() {
+            return visitor.visit_enum(variant.as_str().into_deserializer());
+        }
+        match self.0.reflect_ref() {
+            ReflectRef::Struct(value) if value.field_len() == 1 => {
+                self.deserialize_any(EnumMapVisitor(visitor))
+            }
+            _ => Err(Error::custom(format_args!(
+                "the variant of enum `{name}` cannot be read without type information"
+            ))),
+        }
+    }
+
+    serde::forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf unit unit_struct newtype_struct seq tuple
+        tuple_struct map struct identifier ignored_any
+    }
+}
+
+impl<'a, 'de> de::IntoDeserializer<'de, de::value::Error> for UntypedValueReplay<'a> {
+    type Deserializer = Self;
+
+    fn into_deserializer(self) -> Self::Deserializer {
+        self
+    }
+}
+
+/// Reads a map with a single entry as an externally tagged enum variant.
+struct EnumMapVisitor<V>(V);
+
+impl<'de, V: Visitor<'de>> Visitor<'de> for EnumMapVisitor<V> {
+    type Value = V::Value;
+
+    fn expecting(&self, formatter: &mut Formatter) -> fmt::Result {
+        self.0.expecting(formatter)
+    }
+
+    fn visit_map<A>(self, map: A) -> Result<Self::Value, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        self.0
+            .visit_enum(de::value::MapAccessDeserializer::new(map))
+    }
+}
+
 fn visit_tuple<'de, T, V>(
     seq: &mut V,
     info: &T,
@@ -0,0 +1,576 @@
+use crate::serde::{ReflectSerializer, UntypedReflectDeserializer};
+use crate::{Diff, FieldKey, ListChange, MapChange, SetChange, TypeRegistry};
+use serde::de::{DeserializeSeed, EnumAccess, Error as _, SeqAccess, VariantAccess, Visitor};
+use serde::ser::{SerializeSeq, SerializeTupleVariant};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::fmt::Formatter;
+use std::marker::PhantomData;
+
+const DIFF_VARIANTS: &[&str] = &["Replace", "Fields", "List", "Map", "Set"];
+const FIELD_KEY_VARIANTS: &[&str] = &["Name", "Index"];
+const CHANGE_VARIANTS: &[&str] = &["Modify", "Insert", "Remove"];
+const SET_CHANGE_VARIANTS: &[&str] = &["Insert", "Remove"];
+
+/// A serializer for [`Diff`]s.
+///
+/// The reflected values of the diff are serialized like with a [`ReflectSerializer`], so their
+/// types must be registered in the [`TypeRegistry`].
+pub struct DiffSerializer<'a> {
+    pub diff: &'a Diff,
+    pub registry: &'a TypeRegistry,
+}
+
+impl<'a> DiffSerializer<'a> {
+    pub fn new(diff: &'a Diff, registry: &'a TypeRegistry) -> Self {
+        DiffSerializer { diff, registry }
+    }
+}
+
+impl<'a> Serialize for DiffSerializer<'a> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let registry = self.registry;
+        match self.diff {
+            Diff::Replace { old, new } => {
+                let mut state = serializer.serialize_tuple_variant("Diff", 0, "Replace", 2)?;
+                state.serialize_field(&ReflectSerializer::new(&**old, registry))?;
+                state.serialize_field(&ReflectSerializer::new(&**new, registry))?;
+                state.end()
+            }
+            Diff::Fields(fields) => serializer.serialize_newtype_variant(
+                "Diff",
+                1,
+                "Fields",
+                &SeqSerializer(fields, |(key, diff): &'a (FieldKey, Diff)| {
+                    (key, DiffSerializer::new(diff, registry))
+                }),
+            ),
+            Diff::List(changes) => serializer.serialize_newtype_variant(
+                "Diff",
+                2,
+                "List",
+                &SeqSerializer(changes, |change| ListChangeSerializer { change, registry }),
+            ),
+            Diff::Map(changes) => serializer.serialize_newtype_variant(
+                "Diff",
+                3,
+                "Map",
+                &SeqSerializer(changes, |change| MapChangeSerializer { change, registry }),
+            ),
+            Diff::Set(changes) => serializer.serialize_newtype_variant(
+                "Diff",
+                4,
+                "Set",
+                &SeqSerializer(changes, |change| SetChangeSerializer { change, registry }),
+            ),
+        }
+    }
+}
+
+/// Serializes a slice as a sequence of the serializers returned by the function.
+struct SeqSerializer<'a, T, F>(&'a [T], F);
+
+impl<'a, T, F, U> Serialize for SeqSerializer<'a, T, F>
+where
+    F: Fn(&'a T) -> U,
+    U: Serialize,
+{
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut state = serializer.serialize_seq(Some(self.0.len()))?;
+        for element in self.0 {
+            state.serialize_element(&(self.1)(element))?;
+        }
+        state.end()
+    }
+}
+
+impl Serialize for FieldKey {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match self {
+            FieldKey::Name(name) => {
+                serializer.serialize_newtype_variant("FieldKey", 0, "Name", name)
+            }
+            FieldKey::Index(index) => {
+                serializer.serialize_newtype_variant("FieldKey", 1, "Index", index)
+            }
+        }
+    }
+}
+
+struct ListChangeSerializer<'a> {
+    change: &'a ListChange,
+    registry: &'a TypeRegistry,
+}
+
+impl<'a> Serialize for ListChangeSerializer<'a> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let (variant_index, index) = match self.change {
+            ListChange::Modify { index, .. } => (0, index),
+            ListChange::Insert { index, .. } => (1, index),
+            ListChange::Remove { index, .. } => (2, index),
+        };
+        let mut state = serializer.serialize_tuple_variant(
+            "ListChange",
+            variant_index,
+            CHANGE_VARIANTS[variant_index as usize],
+            2,
+        )?;
+        state.serialize_field(index)?;
+        match self.change {
+            ListChange::Modify { diff, .. } => {
+                state.serialize_field(&DiffSerializer::new(diff, self.registry))?;
+            }
+            ListChange::Insert { value, .. } | ListChange::Remove { value, .. } => {
+                state.serialize_field(&ReflectSerializer::new(&**value, self.registry))?;
+            }
+        }
+        state.end()
+    }
+}
+
+struct MapChangeSerializer<'a> {
+    change: &'a MapChange,
+    registry: &'a TypeRegistry,
+}
+
+impl<'a> Serialize for MapChangeSerializer<'a> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let (variant_index, key) = match self.change {
+            MapChange::Modify { key, .. } => (0, key),
+            MapChange::Insert { key, .. } => (1, key),
+            MapChange::Remove { key, .. } => (2, key),
+        };
+        let mut state = serializer.serialize_tuple_variant(
+            "MapChange",
+            variant_index,
+            CHANGE_VARIANTS[variant_index as usize],
+            2,
+        )?;
+        state.serialize_field(&ReflectSerializer::new(&**key, self.registry))?;
+        match self.change {
+            MapChange::Modify { diff, .. } => {
+                state.serialize_field(&DiffSerializer::new(diff, self.registry))?;
+            }
+            MapChange::Insert { value, .. } | MapChange::Remove { value, .. } => {
+                state.serialize_field(&ReflectSerializer::new(&**value, self.registry))?;
+            }
+        }
+        state.end()
+    }
+}
+
+struct SetChangeSerializer<'a> {
+    change: &'a SetChange,
+    registry: &'a TypeRegistry,
+}
+
+impl<'a> Serialize for SetChangeSerializer<'a> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let (variant_index, value) = match self.change {
+            SetChange::Insert(value) => (0, value),
+            SetChange::Remove(value) => (1, value),
+        };
+        serializer.serialize_newtype_variant(
+            "SetChange",
+            variant_index,
+            SET_CHANGE_VARIANTS[variant_index as usize],
+            &ReflectSerializer::new(&**value, self.registry),
+        )
+    }
+}
+
+/// A deserializer for [`Diff`]s serialized with a [`DiffSerializer`].
+///
+/// The reflected values of the diff are deserialized like with an
+/// [`UntypedReflectDeserializer`], so they are dynamic for non-value types.
+#[derive(Clone, Copy)]
+pub struct DiffDeserializer<'a> {
+    registry: &'a TypeRegistry,
+}
+
+impl<'a> DiffDeserializer<'a> {
+    pub fn new(registry: &'a TypeRegistry) -> Self {
+        Self { registry }
+    }
+}
+
+impl<'a, 'de> DeserializeSeed<'de> for DiffDeserializer<'a> {
+    type Value = Diff;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_enum("Diff", DIFF_VARIANTS, self)
+    }
+}
+
+impl<'a, 'de> Visitor<'de> for DiffDeserializer<'a> {
+    type Value = Diff;
+
+    fn expecting(&self, formatter: &mut Formatter) -> std::fmt::Result {
+        formatter.write_str("a reflect diff")
+    }
+
+    fn visit_enum<A>(self, data: A) -> Result<Self::Value, A::Error>
+    where
+        A: EnumAccess<'de>,
+    {
+        let registry = self.registry;
+        let (variant, access) = data.variant_seed(VariantIdentifier(DIFF_VARIANTS))?;
+        match variant {
+            0 => access.tuple_variant(
+                2,
+                PairVisitor {
+                    first: UntypedReflectDeserializer::new(registry),
+                    second: UntypedReflectDeserializer::new(registry),
+                    expecting: "the old and new values",
+                    combine: |old, new| Diff::Replace { old, new },
+                },
+            ),
+            1 => access
+                .newtype_variant_seed(SeqDeserializer(FieldDeserializer(self)))
+                .map(Diff::Fields),
+            2 => access
+                .newtype_variant_seed(SeqDeserializer(ListChangeDeserializer(self)))
+                .map(Diff::List),
+            3 => access
+                .newtype_variant_seed(SeqDeserializer(MapChangeDeserializer(self)))
+                .map(Diff::Map),
+            _ => access
+                .newtype_variant_seed(SeqDeserializer(SetChangeDeserializer(self)))
+                .map(Diff::Set),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for FieldKey {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        struct FieldKeyVisitor;
+
+        impl<'de> Visitor<'de> for FieldKeyVisitor {
+            type Value = FieldKey;
+
+            fn expecting(&self, formatter: &mut Formatter) -> std::fmt::Result {
+                formatter.write_str("a field name or index")
+            }
+
+            fn visit_enum<A>(self, data: A) -> Result<Self::Value, A::Error>
+            where
+                A: EnumAccess<'de>,
+            {
+                match data.variant_seed(VariantIdentifier(FIELD_KEY_VARIANTS))? {
+                    (0, access) => access.newtype_variant().map(FieldKey::Name),
+                    (_, access) => access.newtype_variant().map(FieldKey::Index),
+                }
+            }
+        }
+
+        deserializer.deserialize_enum("FieldKey", FIELD_KEY_VARIANTS, FieldKeyVisitor)
+    }
+}
+
+/// Deserializes the name or index of a variant into its index in the list of names.
+struct VariantIdentifier(&'static [&'static str]);
+
+impl<'de> DeserializeSeed<'de> for VariantIdentifier {
+    type Value = usize;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_identifier(self)
+    }
+}
+
+impl<'de> Visitor<'de> for VariantIdentifier {
+    type Value = usize;
+
+    fn expecting(&self, formatter: &mut Formatter) -> std::fmt::Result {
+        write!(formatter, "one of the variants {:?}", self.0)
+    }
+
+    fn visit_u64<E>(self, index: u64) -> Result<Self::Value, E>
+    where
+        E: serde::de::Error,
+    {
+        match usize::try_from(index) {
+            Ok(index) if index < self.0.len() => Ok(index),
+            _ => Err(E::invalid_value(
+                serde::de::Unexpected::Unsigned(index),
+                &self,
+            )),
+        }
+    }
+
+    fn visit_str<E>(self, name: &str) -> Result<Self::Value, E>
+    where
+        E: serde::de::Error,
+    {
+        self.0
+            .iter()
+            .position(|variant| *variant == name)
+            .ok_or_else(|| E::unknown_variant(name, self.0))
+    }
+}
+
+/// Deserializes a sequence of the values of a seed.
+struct SeqDeserializer<S>(S);
+
+impl<'de, S: DeserializeSeed<'de> + Copy> DeserializeSeed<'de> for SeqDeserializer<S> {
+    type Value = Vec<S::Value>;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_seq(self)
+    }
+}
+
+impl<'de, S: DeserializeSeed<'de> + Copy> Visitor<'de> for SeqDeserializer<S> {
+    type Value = Vec<S::Value>;
+
+    fn expecting(&self, formatter: &mut Formatter) -> std::fmt::Result {
+        formatter.write_str("a sequence")
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        let mut values = Vec::with_capacity(seq.size_hint().unwrap_or_default());
+        while let Some(value) = seq.next_element_seed(self.0)? {
+            values.push(value);
+        }
+        Ok(values)
+    }
+}
+
+/// Visits a sequence of two values, and combines them.
+struct PairVisitor<A, B, F> {
+    first: A,
+    second: B,
+    expecting: &'static str,
+    combine: F,
+}
+
+impl<'de, A, B, F, T> Visitor<'de> for PairVisitor<A, B, F>
+where
+    A: DeserializeSeed<'de>,
+    B: DeserializeSeed<'de>,
+    F: FnOnce(A::Value, B::Value) -> T,
+{
+    type Value = T;
+
+    fn expecting(&self, formatter: &mut Formatter) -> std::fmt::Result {
+        formatter.write_str(self.expecting)
+    }
+
+    fn visit_seq<S>(self, mut seq: S) -> Result<Self::Value, S::Error>
+    where
+        S: SeqAccess<'de>,
+    {
+        let first = seq
+            .next_element_seed(self.first)?
+            .ok_or_else(|| S::Error::invalid_length(0, &self.expecting))?;
+        let second = seq
+            .next_element_seed(self.second)?
+            .ok_or_else(|| S::Error::invalid_length(1, &self.expecting))?;
+        Ok((self.combine)(first, second))
+    }
+}
+
+#[derive(Clone, Copy)]
+struct FieldDeserializer<'a>(DiffDeserializer<'a>);
+
+impl<'a, 'de> DeserializeSeed<'de> for FieldDeserializer<'a> {
+    type Value = (FieldKey, Diff);
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_tuple(
+            2,
+            PairVisitor {
+                first: PhantomData::<FieldKey>,
+                second: self.0,
+                expecting: "a field key and its diff",
+                combine: |key, diff| (key, diff),
+            },
+        )
+    }
+}
+
+#[derive(Clone, Copy)]
+struct ListChangeDeserializer<'a>(DiffDeserializer<'a>);
+
+impl<'a, 'de> DeserializeSeed<'de> for ListChangeDeserializer<'a> {
+    type Value = ListChange;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_enum("ListChange", CHANGE_VARIANTS, self)
+    }
+}
+
+impl<'a, 'de> Visitor<'de> for ListChangeDeserializer<'a> {
+    type Value = ListChange;
+
+    fn expecting(&self, formatter: &mut Formatter) -> std::fmt::Result {
+        formatter.write_str("a list change")
+    }
+
+    fn visit_enum<A>(self, data: A) -> Result<Self::Value, A::Error>
+    where
+        A: EnumAccess<'de>,
+    {
+        let value = UntypedReflectDeserializer::new(self.0.registry);
+        match data.variant_seed(VariantIdentifier(CHANGE_VARIANTS))? {
+            (0, access) => access.tuple_variant(
+                2,
+                PairVisitor {
+                    first: PhantomData::<usize>,
+                    second: self.0,
+                    expecting: "an index and a diff",
+                    combine: |index, diff| ListChange::Modify { index, diff },
+                },
+            ),
+            (1, access) => access.tuple_variant(
+                2,
+                PairVisitor {
+                    first: PhantomData::<usize>,
+                    second: value,
+                    expecting: "an index and a value",
+                    combine: |index, value| ListChange::Insert { index, value },
+                },
+            ),
+            (_, access) => access.tuple_variant(
+                2,
+                PairVisitor {
+                    first: PhantomData::<usize>,
+                    second: value,
+                    expecting: "an index and a value",
+                    combine: |index, value| ListChange::Remove { index, value },
+                },
+            ),
+        }
+    }
+}
+
+#[derive(Clone, Copy)]
+struct MapChangeDeserializer<'a>(DiffDeserializer<'a>);
+
+impl<'a, 'de> DeserializeSeed<'de> for MapChangeDeserializer<'a> {
+    type Value = MapChange;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_enum("MapChange", CHANGE_VARIANTS, self)
+    }
+}
+
+impl<'a, 'de> Visitor<'de> for MapChangeDeserializer<'a> {
+    type Value = MapChange;
+
+    fn expecting(&self, formatter: &mut Formatter) -> std::fmt::Result {
+        formatter.write_str("a map change")
+    }
+
+    fn visit_enum<A>(self, data: A) -> Result<Self::Value, A::Error>
+    where
+        A: EnumAccess<'de>,
+    {
+        let registry = self.0.registry;
+        let key = UntypedReflectDeserializer::new(registry);
+        let value = UntypedReflectDeserializer::new(registry);
+        match data.variant_seed(VariantIdentifier(CHANGE_VARIANTS))? {
+            (0, access) => access.tuple_variant(
+                2,
+                PairVisitor {
+                    first: key,
+                    second: self.0,
+                    expecting: "a key and a diff",
+                    combine: |key, diff| MapChange::Modify { key, diff },
+                },
+            ),
+            (1, access) => access.tuple_variant(
+                2,
+                PairVisitor {
+                    first: key,
+                    second: value,
+                    expecting: "a key and a value",
+                    combine: |key, value| MapChange::Insert { key, value },
+                },
+            ),
+            (_, access) => access.tuple_variant(
+                2,
+                PairVisitor {
+                    first: key,
+                    second: value,
+                    expecting: "a key and a value",
+                    combine: |key, value| MapChange::Remove { key, value },
+                },
+            ),
+        }
+    }
+}
+
+#[derive(Clone, Copy)]
+struct SetChangeDeserializer<'a>(DiffDeserializer<'a>);
+
+impl<'a, 'de> DeserializeSeed<'de> for SetChangeDeserializer<'a> {
+    type Value = SetChange;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_enum("SetChange", SET_CHANGE_VARIANTS, self)
+    }
+}
+
+impl<'a, 'de> Visitor<'de> for SetChangeDeserializer<'a> {
+    type Value = SetChange;
+
+    fn expecting(&self, formatter: &mut Formatter) -> std::fmt::Result {
+        formatter.write_str("a set change")
+    }
+
+    fn visit_enum<A>(self, data: A) -> Result<Self::Value, A::Error>
+    where
+        A: EnumAccess<'de>,
+    {
+        let value = UntypedReflectDeserializer::new(self.0.registry);
+        match data.variant_seed(VariantIdentifier(SET_CHANGE_VARIANTS))? {
+            (0, access) => access.newtype_variant_seed(value).map(SetChange::Insert),
+            (_, access) => access.newtype_variant_seed(value).map(SetChange::Remove),
+        }
+    }
+}
@@ -1,8 +1,16 @@
+mod binary;
 mod de;
+mod diff;
+#[cfg(feature = "json_schema")]
+mod schema;
 mod ser;
 mod type_data;
 
+pub use binary::*;
 pub use de::*;
+pub use diff::*;
+#[cfg(feature = "json_schema")]
+pub use schema::*;
 pub use ser::*;
 pub use type_data::*;
 
@@ -10,11 +18,13 @@ pub use type_data::*;
 mod tests {
     use crate::{self as bevy_reflect, DynamicTupleStruct};
     use crate::{
-        serde::{ReflectSerializer, UntypedReflectDeserializer},
+        serde::{ReflectMigration, ReflectSerializer, UntypedReflectDeserializer},
         type_registry::TypeRegistry,
-        DynamicStruct, Reflect,
+        DynamicSet, DynamicStruct, FromReflect, Reflect, Set,
     };
+    use bincode::Options;
     use serde::de::DeserializeSeed;
+    use std::collections::{BTreeMap, BTreeSet};
 
     #[test]
     fn test_serialization_struct() {
@@ -92,4 +102,163 @@ mod tests {
             "Expected {expected:?} found {deserialized:?}"
         );
     }
+
+    #[test]
+    fn test_serialization_sets() {
+        let mut registry = TypeRegistry::default();
+        registry.register::<u32>();
+        registry.register::<String>();
+        registry.register::<BTreeSet<u32>>();
+        registry.register::<BTreeMap<String, BTreeSet<u32>>>();
+
+        let value = BTreeMap::from([
+            ("even".to_string(), BTreeSet::from([2, 4])),
+            ("odd".to_string(), BTreeSet::from([1, 3, 5])),
+        ]);
+
+        let serializer = ReflectSerializer::new(&value, &registry);
+        let serialized = ron::ser::to_string(&serializer).unwrap();
+        assert_eq!(
+            serialized,
+            r#"{"alloc::collections::btree::map::BTreeMap<alloc::string::String, alloc::collections::btree::set::BTreeSet<u32>>":{"even":[2,4],"odd":[1,3,5]}}"#
+        );
+
+        let mut deserializer = ron::de::Deserializer::from_str(&serialized).unwrap();
+        let reflect_deserializer = UntypedReflectDeserializer::new(&registry);
+        let deserialized = reflect_deserializer.deserialize(&mut deserializer).unwrap();
+        assert_eq!(
+            BTreeMap::<String, BTreeSet<u32>>::from_reflect(&*deserialized),
+            Some(value)
+        );
+
+        let serialized = ron::ser::to_string(&ReflectSerializer::new(
+            &BTreeSet::from([1u32, 2]),
+            &registry,
+        ))
+        .unwrap();
+        let mut deserializer = ron::de::Deserializer::from_str(&serialized).unwrap();
+        let deserialized = UntypedReflectDeserializer::new(&registry)
+            .deserialize(&mut deserializer)
+            .unwrap()
+            .take::<DynamicSet>()
+            .unwrap();
+        assert_eq!(deserialized.len(), 2);
+    }
+
+    #[test]
+    fn test_serialization_migration() {
+        #[derive(Reflect, FromReflect, Debug, PartialEq)]
+        struct Player {
+            health: u32,
+            name: String,
+        }
+
+        // version 0 stored the health as `hp`
+        fn rename_hp(value: &mut DynamicStruct) {
+            let hp = value.remove("hp").unwrap();
+            value.insert("health", *hp.downcast_ref::<u64>().unwrap() as u32);
+        }
+
+        let mut registry = TypeRegistry::default();
+        registry.register::<u32>();
+        registry.register::<String>();
+        registry.register::<Player>();
+        registry
+            .get_mut(std::any::TypeId::of::<Player>())
+            .unwrap()
+            .insert(ReflectMigration::new::<Player>(1).with_migration(0, rename_hp));
+
+        let deserialize = |input: &str| {
+            let mut deserializer = ron::de::Deserializer::from_str(input).unwrap();
+            UntypedReflectDeserializer::new(&registry)
+                .deserialize(&mut deserializer)
+                .map(|value| Player::from_reflect(&*value))
+        };
+        let player = Some(Player {
+            health: 10,
+            name: "Ferris".to_string(),
+        });
+
+        let name = std::any::type_name::<Player>();
+        let old = format!(r#"{{"{name}": (hp: 10, name: "Ferris")}}"#);
+        assert_eq!(deserialize(&old).unwrap(), player);
+        let old = format!(r#"{{"{name}": (__version: 0, name: "Ferris", hp: 10)}}"#);
+        assert_eq!(deserialize(&old).unwrap(), player);
+
+        let serialized =
+            ron::ser::to_string(&ReflectSerializer::new(player.as_ref().unwrap(), &registry))
+                .unwrap();
+        assert_eq!(
+            serialized,
+            format!(r#"{{"{name}":(__version:1,health:10,name:"Ferris")}}"#)
+        );
+        assert_eq!(deserialize(&serialized).unwrap(), player);
+
+        let unknown = format!(r#"{{"{name}": (__version: 1, hp: 10, name: "Ferris")}}"#);
+        assert!(deserialize(&unknown).is_err());
+        let newer = format!(r#"{{"{name}": (__version: 2, health: 10, name: "Ferris")}}"#);
+        assert!(deserialize(&newer).is_err());
+
+        let serializer = ReflectSerializer::new(player.as_ref().unwrap(), &registry);
+        let bytes = bincode::serialize(&serializer).unwrap();
+        let deserialized = bincode::DefaultOptions::new()
+            .with_fixint_encoding()
+            .deserialize_seed(UntypedReflectDeserializer::new(&registry), &bytes)
+            .unwrap();
+        assert_eq!(Player::from_reflect(&*deserialized), player);
+    }
+
+    #[test]
+    fn test_serialization_migration_type_change() {
+        #[derive(Reflect, FromReflect, Debug, PartialEq)]
+        struct Player {
+            health: u32,
+            target: Option<u32>,
+            position: (f32, f32),
+        }
+
+        // version 0 stored the health as a fraction of 100
+        fn scale_health(value: &mut DynamicStruct) {
+            let health = value.remove("health").unwrap();
+            value.insert(
+                "health",
+                (*health.downcast_ref::<f64>().unwrap() * 100.0) as u64,
+            );
+        }
+
+        let mut registry = TypeRegistry::default();
+        registry.register::<u32>();
+        registry.register::<f32>();
+        registry.register::<Option<u32>>();
+        registry.register::<(f32, f32)>();
+        registry.register::<Player>();
+        registry
+            .get_mut(std::any::TypeId::of::<Player>())
+            .unwrap()
+            .insert(ReflectMigration::new::<Player>(1).with_migration(0, scale_health));
+
+        let deserialize = |input: &str| {
+            let mut deserializer = ron::de::Deserializer::from_str(input).unwrap();
+            UntypedReflectDeserializer::new(&registry)
+                .deserialize(&mut deserializer)
+                .map(|value| Player::from_reflect(&*value))
+        };
+
+        let name = std::any::type_name::<Player>();
+        let old = format!(
+            r#"{{"{name}": (__version: 0, health: 0.5, target: Some(3), position: (1.0, 2.5))}}"#
+        );
+        assert_eq!(
+            deserialize(&old).unwrap(),
+            Some(Player {
+                health: 50,
+                target: Some(3),
+                position: (1.0, 2.5),
+            })
+        );
+
+        let late_version =
+            format!(r#"{{"{name}": (health: 50, __version: 1, target: None, position: (0, 0))}}"#);
+        assert!(deserialize(&late_version).is_err());
+    }
 }
@@ -0,0 +1,509 @@
+use crate::serde::{ReflectMigration, SerializationData, VERSION_FIELD};
+use crate::{EnumInfo, TypeInfo, TypeRegistration, TypeRegistry, VariantInfo};
+use serde_json::{json, Map, Value};
+use std::any::TypeId;
+use std::borrow::Cow;
+use std::num::{
+    NonZeroI128, NonZeroI16, NonZeroI32, NonZeroI64, NonZeroI8, NonZeroIsize, NonZeroU128,
+    NonZeroU16, NonZeroU32, NonZeroU64, NonZeroU8, NonZeroUsize,
+};
+use std::path::PathBuf;
+use std::time::Duration;
+
+/// The JSON Schema dialect of the schemas generated from a [`TypeRegistry`].
+pub const JSON_SCHEMA_DIALECT: &str = "https://json-schema.org/draft/2020-12/schema";
+
+/// Returns a JSON Schema document validating the values serialized with a
+/// [`ReflectSerializer`](crate::serde::ReflectSerializer): a map from the name of a registered
+/// type to a value of that type.
+///
+/// The schema of every registered type is defined in the `$defs` of the document, see
+/// [`json_schema_defs`].
+pub fn registry_json_schema(registry: &TypeRegistry) -> Value {
+    let properties: Map<String, Value> = registry
+        .iter()
+        .map(|registration| {
+            (
+                registration.type_name().to_string(),
+                json_schema_ref(registration.type_name()),
+            )
+        })
+        .collect();
+    json!({
+        "$schema": JSON_SCHEMA_DIALECT,
+        "type": "object",
+        "properties": properties,
+        "additionalProperties": false,
+        "minProperties": 1,
+        "maxProperties": 1,
+        "$defs": json_schema_defs(registry),
+    })
+}
+
+/// Returns the JSON Schemas of the values of every registered type, serialized with a
+/// [`TypedReflectSerializer`](crate::serde::TypedReflectSerializer), by type name.
+///
+/// They are meant to be the `$defs` of a schema document, and reference each other with
+/// [`json_schema_ref`].
+pub fn json_schema_defs(registry: &TypeRegistry) -> Map<String, Value> {
+    registry
+        .iter()
+        .map(|registration| {
+            (
+                registration.type_name().to_string(),
+                type_json_schema(registration, registry),
+            )
+        })
+        .collect()
+}
+
+/// Returns a schema referencing the definition of a type in the [`json_schema_defs`] of a
+/// document.
+pub fn json_schema_ref(type_name: &str) -> Value {
+    json!({ "$ref": format!("#/$defs/{}", json_pointer_fragment(type_name)) })
+}
+
+/// Escapes a type name as a JSON pointer token, in a URI fragment.
+fn json_pointer_fragment(type_name: &str) -> String {
+    let mut fragment = String::with_capacity(type_name.len());
+    for byte in type_name.bytes() {
+        match byte {
+            b'~' => fragment.push_str("~0"),
+            b'/' => fragment.push_str("~1"),
+            b'a'..=b'z' | b'A'..=b'Z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b':' | b'@' => {
+                fragment.push(byte as char);
+            }
+            _ => fragment.push_str(&format!("%{:02X}", byte)),
+        }
+    }
+    fragment
+}
+
+/// Returns the JSON Schema of the values of a registered type, serialized with a
+/// [`TypedReflectSerializer`](crate::serde::TypedReflectSerializer).
+///
+/// Fields, elements and variants reference the definitions of their types with
+/// [`json_schema_ref`]. With the `documentation` feature, the docs of the type, its fields and
+/// its variants are included as descriptions.
+pub fn type_json_schema(registration: &TypeRegistration, registry: &TypeRegistry) -> Value {
+    let type_info = registration.type_info();
+    let mut schema = match type_info {
+        TypeInfo::Struct(info) => {
+            let serialization_data = registration.data::<SerializationData>();
+            let mut properties = Map::new();
+            let mut required = Vec::new();
+            if let Some(migration) = registration.data::<ReflectMigration>() {
+                properties.insert(
+                    VERSION_FIELD.to_string(),
+                    json!({ "type": "integer", "minimum": 0, "maximum": migration.version() }),
+                );
+            }
+            for (index, field) in info.iter().enumerate() {
+                if matches!(serialization_data, Some(data) if data.is_ignored_field(index)) {
+                    continue;
+                }
+                #[allow(unused_mut)]
+                let mut field_schema =
+                    field_json_schema(field.type_id(), field.type_name(), registry);
+                #[cfg(feature = "documentation")]
+                describe(&mut field_schema, field.docs());
+                properties.insert(field.name().to_string(), field_schema);
+                required.push(field.name());
+            }
+            json!({
+                "type": "object",
+                "properties": properties,
+                "required": required,
+                "additionalProperties": false,
+            })
+        }
+        TypeInfo::TupleStruct(info) => {
+            let serialization_data = registration.data::<SerializationData>();
+            let fields = info
+                .iter()
+                .enumerate()
+                .filter(|(index, _)| {
+                    !matches!(serialization_data, Some(data) if data.is_ignored_field(*index))
+                })
+                .map(|(_, field)| {
+                    #[allow(unused_mut)]
+                    let mut field_schema =
+                        field_json_schema(field.type_id(), field.type_name(), registry);
+                    #[cfg(feature = "documentation")]
+                    describe(&mut field_schema, field.docs());
+                    field_schema
+                })
+                .collect();
+            tuple_json_schema(fields)
+        }
+        TypeInfo::Tuple(info) => tuple_json_schema(
+            info.iter()
+                .map(|field| field_json_schema(field.type_id(), field.type_name(), registry))
+                .collect(),
+        ),
+        TypeInfo::List(info) => json!({
+            "type": "array",
+            "items": field_json_schema(info.item_type_id(), info.item_type_name(), registry),
+        }),
+        TypeInfo::Array(info) => json!({
+            "type": "array",
+            "items": field_json_schema(info.item_type_id(), info.item_type_name(), registry),
+            "minItems": info.capacity(),
+            "maxItems": info.capacity(),
+        }),
+        TypeInfo::Set(info) => json!({
+            "type": "array",
+            "items": field_json_schema(info.value_type_id(), info.value_type_name(), registry),
+            "uniqueItems": true,
+        }),
+        TypeInfo::Map(info) => json!({
+            "type": "object",
+            "additionalProperties":
+                field_json_schema(info.value_type_id(), info.value_type_name(), registry),
+        }),
+        TypeInfo::Enum(info) => enum_json_schema(info, registry),
+        TypeInfo::Value(info) => value_json_schema(info.type_id()),
+        TypeInfo::Dynamic(_) => json!({}),
+    };
+
+    let object = schema.as_object_mut().unwrap();
+    object.insert("title".to_string(), registration.short_name().into());
+    #[cfg(feature = "documentation")]
+    describe(&mut schema, type_info.docs());
+    schema
+}
+
+fn field_json_schema(type_id: TypeId, type_name: &str, registry: &TypeRegistry) -> Value {
+    if registry.get(type_id).is_some() {
+        json_schema_ref(type_name)
+    } else {
+        json!({ "$comment": format!("`{}` is not registered", type_name) })
+    }
+}
+
+fn tuple_json_schema(fields: Vec<Value>) -> Value {
+    let len = fields.len();
+    json!({
+        "type": "array",
+        "prefixItems": fields,
+        "minItems": len,
+        "maxItems": len,
+    })
+}
+
+fn enum_json_schema(info: &EnumInfo, registry: &TypeRegistry) -> Value {
+    if info.type_name().starts_with("core::option::Option") {
+        // options are serialized as serde options, see `EnumSerializer`
+        let some = match info.variant("Some") {
+            Some(VariantInfo::Tuple(variant)) => variant.field_at(0).unwrap(),
+            _ => return json!({}),
+        };
+        return json!({
+            "anyOf": [
+                { "type": "null" },
+                field_json_schema(some.type_id(), some.type_name(), registry),
+            ],
+        });
+    }
+
+    let variants: Vec<_> = info
+        .iter()
+        .map(|variant| {
+            let fields = match variant {
+                VariantInfo::Unit(_) => {
+                    return json!({ "const": variant.name() });
+                }
+                VariantInfo::Tuple(variant) if variant.field_len() == 1 => {
+                    let field = variant.field_at(0).unwrap();
+                    field_json_schema(field.type_id(), field.type_name(), registry)
+                }
+                VariantInfo::Tuple(variant) => tuple_json_schema(
+                    variant
+                        .iter()
+                        .map(|field| {
+                            field_json_schema(field.type_id(), field.type_name(), registry)
+                        })
+                        .collect(),
+                ),
+                VariantInfo::Struct(variant) => {
+                    let properties: Map<String, Value> = variant
+                        .iter()
+                        .map(|field| {
+                            #[allow(unused_mut)]
+                            let mut field_schema =
+                                field_json_schema(field.type_id(), field.type_name(), registry);
+                            #[cfg(feature = "documentation")]
+                            describe(&mut field_schema, field.docs());
+                            (field.name().to_string(), field_schema)
+                        })
+                        .collect();
+                    json!({
+                        "type": "object",
+                        "properties": properties,
+                        "required": variant.field_names(),
+                        "additionalProperties": false,
+                    })
+                }
+            };
+            json!({
+                "type": "object",
+                "properties": { variant.name(): fields },
+                "required": [variant.name()],
+                "additionalProperties": false,
+            })
+        })
+        .collect();
+
+    #[cfg(feature = "documentation")]
+    let variants: Vec<_> = variants
+        .into_iter()
+        .zip(info.iter())
+        .map(|(mut variant_schema, variant)| {
+            describe(&mut variant_schema, variant.docs());
+            variant_schema
+        })
+        .collect();
+
+    json!({ "oneOf": variants })
+}
+
+/// Returns the schema of the [`Serialize`](serde::Serialize) implementation of the known value
+/// types, or a schema accepting any value.
+fn value_json_schema(type_id: TypeId) -> Value {
+    macro_rules! is_any {
+        ($($ty:ty),*) => {
+            [$(TypeId::of::<$ty>()),*].contains(&type_id)
+        };
+    }
+
+    if type_id == TypeId::of::<bool>() {
+        json!({ "type": "boolean" })
+    } else if is_any!(u8, u16, u32, u64, u128, usize) {
+        json!({ "type": "integer", "minimum": 0 })
+    } else if is_any!(i8, i16, i32, i64, i128, isize) {
+        json!({ "type": "integer" })
+    } else if is_any!(
+        NonZeroU8,
+        NonZeroU16,
+        NonZeroU32,
+        NonZeroU64,
+        NonZeroU128,
+        NonZeroUsize
+    ) {
+        json!({ "type": "integer", "minimum": 1 })
+    } else if is_any!(
+        NonZeroI8,
+        NonZeroI16,
+        NonZeroI32,
+        NonZeroI64,
+        NonZeroI128,
+        NonZeroIsize
+    ) {
+        json!({ "type": "integer", "not": { "const": 0 } })
+    } else if is_any!(f32, f64) {
+        json!({ "type": "number" })
+    } else if is_any!(String, Cow<'static, str>, PathBuf) {
+        json!({ "type": "string" })
+    } else if type_id == TypeId::of::<char>() {
+        json!({ "type": "string", "minLength": 1, "maxLength": 1 })
+    } else if type_id == TypeId::of::<Duration>() {
+        json!({
+            "type": "object",
+            "properties": {
+                "secs": { "type": "integer", "minimum": 0 },
+                "nanos": { "type": "integer", "minimum": 0 },
+            },
+            "required": ["secs", "nanos"],
+            "additionalProperties": false,
+        })
+    } else {
+        glam_json_schema(type_id).unwrap_or_else(|| json!({}))
+    }
+}
+
+#[cfg(feature = "glam")]
+fn glam_json_schema(type_id: TypeId) -> Option<Value> {
+    if [TypeId::of::<glam::Quat>(), TypeId::of::<glam::DQuat>()].contains(&type_id) {
+        Some(json!({
+            "type": "array",
+            "items": { "type": "number" },
+            "minItems": 4,
+            "maxItems": 4,
+        }))
+    } else {
+        None
+    }
+}
+
+#[cfg(not(feature = "glam"))]
+fn glam_json_schema(_type_id: TypeId) -> Option<Value> {
+    None
+}
+
+#[cfg(feature = "documentation")]
+fn describe(schema: &mut Value, docs: Option<&str>) {
+    if let (Some(object), Some(docs)) = (schema.as_object_mut(), docs) {
+        let description = docs.lines().map(str::trim).collect::<Vec<_>>().join("\n");
+        object.insert("description".to_string(), description.trim().into());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{self as bevy_reflect, FromReflect, Reflect};
+    use bevy_utils::HashMap;
+
+    /// A character of the game.
+    #[derive(Reflect)]
+    struct Player {
+        /// The displayed name.
+        name: String,
+        health: u32,
+        #[reflect(skip_serializing)]
+        cached: f32,
+        inventory: Vec<Item>,
+        stats: HashMap<String, f32>,
+        state: State,
+        target: Option<u32>,
+        size: Size,
+    }
+
+    #[derive(Reflect, FromReflect)]
+    struct Item {
+        id: u32,
+    }
+
+    #[derive(Reflect)]
+    struct Size(f32, f32);
+
+    #[derive(Reflect)]
+    enum State {
+        Idle,
+        Moving { speed: f32 },
+        Attacking(u32),
+        Blocking(f32, f32),
+    }
+
+    fn registry() -> TypeRegistry {
+        let mut registry = TypeRegistry::default();
+        registry.register::<Player>();
+        registry.register::<Item>();
+        registry.register::<Size>();
+        registry.register::<State>();
+        registry.register::<String>();
+        registry.register::<Vec<Item>>();
+        registry.register::<HashMap<String, f32>>();
+        registry.register::<Option<u32>>();
+        registry
+    }
+
+    #[test]
+    fn struct_schema() {
+        let registry = registry();
+        let registration = registry.get(TypeId::of::<Player>()).unwrap();
+        let schema = type_json_schema(registration, &registry);
+
+        assert_eq!(schema["title"], "Player");
+        assert_eq!(schema["type"], "object");
+        assert_eq!(
+            schema["required"],
+            json!([
+                "name",
+                "health",
+                "inventory",
+                "stats",
+                "state",
+                "target",
+                "size"
+            ])
+        );
+        assert_eq!(
+            schema["properties"]["name"]["$ref"],
+            "#/$defs/alloc::string::String"
+        );
+        assert_eq!(
+            schema["properties"]["inventory"]["$ref"],
+            "#/$defs/alloc::vec::Vec%3Cbevy_reflect::serde::schema::tests::Item%3E"
+        );
+        #[cfg(feature = "documentation")]
+        {
+            assert_eq!(schema["description"], "A character of the game.");
+            assert_eq!(
+                schema["properties"]["name"]["description"],
+                "The displayed name."
+            );
+        }
+
+        let size = type_json_schema(registry.get(TypeId::of::<Size>()).unwrap(), &registry);
+        assert_eq!(size["prefixItems"].as_array().unwrap().len(), 2);
+        assert_eq!(size["maxItems"], 2);
+
+        let u32_schema = type_json_schema(registry.get(TypeId::of::<u32>()).unwrap(), &registry);
+        assert_eq!(u32_schema["type"], "integer");
+        assert_eq!(u32_schema["minimum"], 0);
+    }
+
+    #[test]
+    fn enum_schema() {
+        let registry = registry();
+        let schema = type_json_schema(registry.get(TypeId::of::<State>()).unwrap(), &registry);
+        let variants = schema["oneOf"].as_array().unwrap();
+        assert_eq!(variants[0], json!({ "const": "Idle" }));
+        assert_eq!(
+            variants[1]["properties"]["Moving"]["properties"]["speed"]["$ref"],
+            "#/$defs/f32"
+        );
+        assert_eq!(
+            variants[2]["properties"]["Attacking"]["$ref"],
+            "#/$defs/u32"
+        );
+        assert_eq!(variants[3]["properties"]["Blocking"]["type"], "array");
+
+        let option = type_json_schema(
+            registry.get(TypeId::of::<Option<u32>>()).unwrap(),
+            &registry,
+        );
+        assert_eq!(option["anyOf"][0]["type"], "null");
+        assert_eq!(option["anyOf"][1]["$ref"], "#/$defs/u32");
+    }
+
+    #[test]
+    fn registry_schema() {
+        let registry = registry();
+        let schema = registry_json_schema(&registry);
+        assert_eq!(schema["$schema"], JSON_SCHEMA_DIALECT);
+        assert_eq!(schema["maxProperties"], 1);
+
+        // every reference points to a definition of the document
+        let defs = schema["$defs"].as_object().unwrap();
+        let mut references = Vec::new();
+        collect_references(&schema, &mut references);
+        assert!(!references.is_empty());
+        for reference in references {
+            let escaped = reference.strip_prefix("#/$defs/").unwrap();
+            assert!(
+                defs.keys()
+                    .any(|name| json_pointer_fragment(name) == escaped),
+                "dangling reference {reference}"
+            );
+        }
+    }
+
+    fn collect_references(schema: &Value, references: &mut Vec<String>) {
+        match schema {
+            Value::Object(object) => {
+                if let Some(Value::String(reference)) = object.get("$ref") {
+                    references.push(reference.clone());
+                }
+                object
+                    .values()
+                    .for_each(|value| collect_references(value, references));
+            }
+            Value::Array(array) => array
+                .iter()
+                .for_each(|value| collect_references(value, references)),
+            _ => {}
+        }
+    }
+}
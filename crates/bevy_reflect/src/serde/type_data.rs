@@ -1,4 +1,9 @@
-use std::collections::HashSet;
+use crate::{DynamicStruct, TypeInfo, Typed};
+use bevy_utils::HashMap;
+use once_cell::race::OnceBox;
+use parking_lot::RwLock;
+use std::any::TypeId;
+use std::collections::{BTreeMap, HashSet};
 
 /// Contains data relevant to the automatic reflect powered serialization of a type
 #[derive(Debug, Clone)]
@@ -42,3 +47,119 @@ impl SerializationData {
         self.ignored_field_indices.is_empty()
     }
 }
+
+/// The name of the entry holding the schema version of a struct with [`ReflectMigration`].
+pub const VERSION_FIELD: &str = "__version";
+
+/// A migration which upgrades the fields of a deserialized struct by one version.
+pub type MigrationFn = fn(&mut DynamicStruct);
+
+/// The schema version of a reflected struct, along with the migrations which upgrade data
+/// serialized by its older versions.
+///
+/// When a type registration contains this data, the reflect serializer writes the version
+/// as a [`VERSION_FIELD`] entry before the fields of the struct. The reflect deserializer
+/// runs the migrations for every version between the serialized one and the current one
+/// on the deserialized [`DynamicStruct`], so it can be converted with `FromReflect` as
+/// usual. Data without a version entry is treated as version 0.
+///
+/// The fields of older data are deserialized without type information, since they may have
+/// been renamed or changed type since, so migrations receive integers as `i64` or `u64`,
+/// floats as `f64`, strings as `String`, sequences as a `DynamicList` and maps as a
+/// `DynamicStruct` or `DynamicMap`. After the migrations, the fields are converted to their
+/// current types. This requires a self-describing format, like RON or JSON, and a migration
+/// for enum fields in formats which don't keep variant names, like RON.
+///
+/// # Example
+/// ```
+/// # use bevy_reflect::{DynamicStruct, Reflect, TypeRegistry};
+/// # use bevy_reflect::serde::ReflectMigration;
+/// #[derive(Reflect)]
+/// struct Player {
+///     // was `hp` in version 0
+///     health: u32,
+/// }
+///
+/// fn rename_hp(value: &mut DynamicStruct) {
+///     if let Some(hp) = value.remove("hp") {
+///         let hp = *hp.downcast_ref::<u64>().unwrap();
+///         value.insert("health", hp as u32);
+///     }
+/// }
+///
+/// let mut registry = TypeRegistry::default();
+/// registry.register::<Player>();
+/// registry
+///     .get_mut(std::any::TypeId::of::<Player>())
+///     .unwrap()
+///     .insert(ReflectMigration::new::<Player>(1).with_migration(0, rename_hp));
+/// ```
+#[derive(Clone)]
+pub struct ReflectMigration {
+    version: u32,
+    field_names: &'static [&'static str],
+    migrations: BTreeMap<u32, MigrationFn>,
+}
+
+impl ReflectMigration {
+    /// Creates a new `ReflectMigration` for the struct `T` at the given version.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `T` is not a struct.
+    pub fn new<T: Typed>(version: u32) -> Self {
+        let TypeInfo::Struct(struct_info) = T::type_info() else {
+            panic!(
+                "attempted to create a `ReflectMigration` for `{}`, which is not a struct",
+                std::any::type_name::<T>()
+            );
+        };
+
+        static FIELD_NAMES: OnceBox<RwLock<HashMap<TypeId, &'static [&'static str]>>> =
+            OnceBox::new();
+        let field_names = FIELD_NAMES.get_or_init(Box::default);
+        let type_id = struct_info.type_id();
+        let cached = field_names.read().get(&type_id).copied();
+        let field_names = cached.unwrap_or_else(|| {
+            *field_names.write().entry(type_id).or_insert_with(|| {
+                let field_names = std::iter::once(VERSION_FIELD)
+                    .chain(struct_info.field_names().iter().copied())
+                    .collect::<Vec<_>>();
+                // leaked once per type, since deserializers require static field names
+                Box::leak(field_names.into_boxed_slice())
+            })
+        });
+
+        Self {
+            version,
+            field_names,
+            migrations: BTreeMap::new(),
+        }
+    }
+
+    /// Adds a migration which upgrades data of version `from` to version `from + 1`.
+    ///
+    /// Versions without a migration are upgraded without changes.
+    #[must_use]
+    pub fn with_migration(mut self, from: u32, migration: MigrationFn) -> Self {
+        self.migrations.insert(from, migration);
+        self
+    }
+
+    /// The current version of the struct.
+    pub fn version(&self) -> u32 {
+        self.version
+    }
+
+    /// The names of the serialized entries of the struct, starting with [`VERSION_FIELD`].
+    pub fn field_names(&self) -> &'static [&'static str] {
+        self.field_names
+    }
+
+    /// Upgrades `value` from version `from` to the current version.
+    pub fn migrate(&self, from: u32, value: &mut DynamicStruct) {
+        for (_, migration) in self.migrations.range(from..self.version) {
+            migration(value);
+        }
+    }
+}
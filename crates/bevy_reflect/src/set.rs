@@ -0,0 +1,496 @@
+use std::any::{Any, TypeId};
+use std::fmt::{Debug, Formatter};
+
+use bevy_utils::{Entry, HashMap};
+
+use crate::utility::NonGenericTypeInfoCell;
+use crate::{DynamicInfo, Reflect, ReflectMut, ReflectOwned, ReflectRef, TypeInfo, Typed};
+
+/// An unordered set of unique [`Reflect`] values.
+///
+/// Because the values are reflected, the underlying types of the values
+/// may differ between elements.
+///
+/// Values are assumed to return a non-`None` hash. The ordering of `Set`
+/// elements is not guaranteed to be stable across runs or between instances.
+///
+/// This trait corresponds to types like [`std::collections::HashSet`] and
+/// [`std::collections::BTreeSet`].
+pub trait Set: Reflect {
+    /// Returns a reference to the element equal to the given value.
+    ///
+    /// If no element is equal to `value`, returns `None`.
+    fn get(&self, value: &dyn Reflect) -> Option<&dyn Reflect>;
+
+    /// Returns the element at `index` by reference, or `None` if out of bounds.
+    fn get_at(&self, index: usize) -> Option<&dyn Reflect>;
+
+    /// Returns the number of elements in the set.
+    fn len(&self) -> usize;
+
+    /// Returns `true` if the set contains no elements.
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns `true` if the set contains an element equal to the given value.
+    fn contains(&self, value: &dyn Reflect) -> bool {
+        self.get(value).is_some()
+    }
+
+    /// Returns an iterator over the elements of the set.
+    fn iter(&self) -> SetIter;
+
+    /// Drain the elements of this set to get a vector of owned values.
+    fn drain(self: Box<Self>) -> Vec<Box<dyn Reflect>>;
+
+    /// Clones the set, producing a [`DynamicSet`].
+    fn clone_dynamic(&self) -> DynamicSet;
+
+    /// Inserts a value into the set.
+    ///
+    /// If the set did not have an equal element present, `true` is returned.
+    /// If the set did have an equal element present, the set is not modified and `false` is
+    /// returned.
+    fn insert_boxed(&mut self, value: Box<dyn Reflect>) -> bool;
+
+    /// Removes the element equal to the given value from the set.
+    ///
+    /// Returns whether such an element was present.
+    fn remove(&mut self, value: &dyn Reflect) -> bool;
+}
+
+/// A container for compile-time set info.
+#[derive(Clone, Debug)]
+pub struct SetInfo {
+    type_name: &'static str,
+    type_id: TypeId,
+    value_type_name: &'static str,
+    value_type_id: TypeId,
+    #[cfg(feature = "documentation")]
+    docs: Option<&'static str>,
+}
+
+impl SetInfo {
+    /// Create a new [`SetInfo`].
+    pub fn new<TSet: Set, TValue: Reflect>() -> Self {
+        Self {
+            type_name: std::any::type_name::<TSet>(),
+            type_id: TypeId::of::<TSet>(),
+            value_type_name: std::any::type_name::<TValue>(),
+            value_type_id: TypeId::of::<TValue>(),
+            #[cfg(feature = "documentation")]
+            docs: None,
+        }
+    }
+
+    /// Sets the docstring for this set.
+    #[cfg(feature = "documentation")]
+    pub fn with_docs(self, docs: Option<&'static str>) -> Self {
+        Self { docs, ..self }
+    }
+
+    /// The [type name] of the set.
+    ///
+    /// [type name]: std::any::type_name
+    pub fn type_name(&self) -> &'static str {
+        self.type_name
+    }
+
+    /// The [`TypeId`] of the set.
+    pub fn type_id(&self) -> TypeId {
+        self.type_id
+    }
+
+    /// Check if the given type matches the set type.
+    pub fn is<T: Any>(&self) -> bool {
+        TypeId::of::<T>() == self.type_id
+    }
+
+    /// The [type name] of the value.
+    ///
+    /// [type name]: std::any::type_name
+    pub fn value_type_name(&self) -> &'static str {
+        self.value_type_name
+    }
+
+    /// The [`TypeId`] of the value.
+    pub fn value_type_id(&self) -> TypeId {
+        self.value_type_id
+    }
+
+    /// Check if the given type matches the value type.
+    pub fn value_is<T: Any>(&self) -> bool {
+        TypeId::of::<T>() == self.value_type_id
+    }
+
+    /// The docstring of this set, if any.
+    #[cfg(feature = "documentation")]
+    pub fn docs(&self) -> Option<&'static str> {
+        self.docs
+    }
+}
+
+const HASH_ERROR: &str = "the given value does not support hashing";
+
+/// A set of reflected values.
+#[derive(Default)]
+pub struct DynamicSet {
+    name: String,
+    values: Vec<Box<dyn Reflect>>,
+    indices: HashMap<u64, usize>,
+}
+
+impl DynamicSet {
+    /// Returns the type name of the set.
+    ///
+    /// The value returned by this method is the same value returned by
+    /// [`Reflect::type_name`].
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Sets the type name of the set.
+    ///
+    /// The value set by this method is the same value returned by
+    /// [`Reflect::type_name`].
+    pub fn set_name(&mut self, name: String) {
+        self.name = name;
+    }
+
+    /// Inserts a typed value into the set.
+    pub fn insert<V: Reflect>(&mut self, value: V) {
+        self.insert_boxed(Box::new(value));
+    }
+}
+
+impl Set for DynamicSet {
+    fn get(&self, value: &dyn Reflect) -> Option<&dyn Reflect> {
+        self.indices
+            .get(&value.reflect_hash().expect(HASH_ERROR))
+            .map(|index| &**self.values.get(*index).unwrap())
+    }
+
+    fn get_at(&self, index: usize) -> Option<&dyn Reflect> {
+        self.values.get(index).map(|value| &**value)
+    }
+
+    fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    fn iter(&self) -> SetIter {
+        SetIter {
+            set: self,
+            index: 0,
+        }
+    }
+
+    fn drain(self: Box<Self>) -> Vec<Box<dyn Reflect>> {
+        self.values
+    }
+
+    fn clone_dynamic(&self) -> DynamicSet {
+        DynamicSet {
+            name: self.name.clone(),
+            values: self
+                .values
+                .iter()
+                .map(|value| value.clone_value())
+                .collect(),
+            indices: self.indices.clone(),
+        }
+    }
+
+    fn insert_boxed(&mut self, value: Box<dyn Reflect>) -> bool {
+        match self.indices.entry(value.reflect_hash().expect(HASH_ERROR)) {
+            Entry::Occupied(_) => false,
+            Entry::Vacant(entry) => {
+                entry.insert(self.values.len());
+                self.values.push(value);
+                true
+            }
+        }
+    }
+
+    fn remove(&mut self, value: &dyn Reflect) -> bool {
+        let hash = value.reflect_hash().expect(HASH_ERROR);
+        let Some(index) = self.indices.remove(&hash) else {
+            return false;
+        };
+        self.values.remove(index);
+        for other_index in self.indices.values_mut() {
+            if *other_index > index {
+                *other_index -= 1;
+            }
+        }
+        true
+    }
+}
+
+impl Reflect for DynamicSet {
+    fn type_name(&self) -> &str {
+        &self.name
+    }
+
+    #[inline]
+    fn get_type_info(&self) -> &'static TypeInfo {
+        <Self as Typed>::type_info()
+    }
+
+    fn into_any(self: Box<Self>) -> Box<dyn Any> {
+        self
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    #[inline]
+    fn into_reflect(self: Box<Self>) -> Box<dyn Reflect> {
+        self
+    }
+
+    #[inline]
+    fn as_reflect(&self) -> &dyn Reflect {
+        self
+    }
+
+    #[inline]
+    fn as_reflect_mut(&mut self) -> &mut dyn Reflect {
+        self
+    }
+
+    fn apply(&mut self, value: &dyn Reflect) {
+        set_apply(self, value);
+    }
+
+    fn set(&mut self, value: Box<dyn Reflect>) -> Result<(), Box<dyn Reflect>> {
+        *self = value.take()?;
+        Ok(())
+    }
+
+    fn reflect_ref(&self) -> ReflectRef {
+        ReflectRef::Set(self)
+    }
+
+    fn reflect_mut(&mut self) -> ReflectMut {
+        ReflectMut::Set(self)
+    }
+
+    fn reflect_owned(self: Box<Self>) -> ReflectOwned {
+        ReflectOwned::Set(self)
+    }
+
+    fn clone_value(&self) -> Box<dyn Reflect> {
+        Box::new(self.clone_dynamic())
+    }
+
+    fn reflect_partial_eq(&self, value: &dyn Reflect) -> Option<bool> {
+        set_partial_eq(self, value)
+    }
+
+    fn debug(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "DynamicSet(")?;
+        set_debug(self, f)?;
+        write!(f, ")")
+    }
+}
+
+impl Debug for DynamicSet {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        self.debug(f)
+    }
+}
+
+impl Typed for DynamicSet {
+    fn type_info() -> &'static TypeInfo {
+        static CELL: NonGenericTypeInfoCell = NonGenericTypeInfoCell::new();
+        CELL.get_or_set(|| TypeInfo::Dynamic(DynamicInfo::new::<Self>()))
+    }
+}
+
+/// An iterator over the elements of a [`Set`].
+pub struct SetIter<'a> {
+    pub(crate) set: &'a dyn Set,
+    pub(crate) index: usize,
+}
+
+impl<'a> Iterator for SetIter<'a> {
+    type Item = &'a dyn Reflect;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let value = self.set.get_at(self.index);
+        self.index += 1;
+        value
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let size = self.set.len();
+        (size, Some(size))
+    }
+}
+
+impl IntoIterator for DynamicSet {
+    type Item = Box<dyn Reflect>;
+    type IntoIter = std::vec::IntoIter<Self::Item>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.values.into_iter()
+    }
+}
+
+impl<'a> ExactSizeIterator for SetIter<'a> {}
+
+/// Compares a [`Set`] with a [`Reflect`] value.
+///
+/// Returns true if and only if all of the following are true:
+/// - `b` is a set;
+/// - `b` is the same length as `a`;
+/// - For each element in `a`, `b` contains an equal element.
+///
+/// Returns [`None`] if the comparison couldn't even be performed.
+#[inline]
+pub fn set_partial_eq<S: Set>(a: &S, b: &dyn Reflect) -> Option<bool> {
+    let ReflectRef::Set(set) = b.reflect_ref() else {
+        return Some(false);
+    };
+
+    if a.len() != set.len() {
+        return Some(false);
+    }
+
+    for value in a.iter() {
+        if let Some(set_value) = set.get(value) {
+            let eq_result = value.reflect_partial_eq(set_value);
+            if let failed @ (Some(false) | None) = eq_result {
+                return failed;
+            }
+        } else {
+            return Some(false);
+        }
+    }
+
+    Some(true)
+}
+
+/// The default debug formatter for [`Set`] types.
+///
+/// # Example
+/// ```
+/// # use bevy_utils::HashSet;
+/// use bevy_reflect::Reflect;
+///
+/// let mut my_set = HashSet::new();
+/// my_set.insert(String::from("Hello"));
+/// println!("{:#?}", &my_set as &dyn Reflect);
+///
+/// // Output:
+///
+/// // {
+/// //   "Hello",
+/// // }
+/// ```
+#[inline]
+pub fn set_debug(dyn_set: &dyn Set, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    let mut debug = f.debug_set();
+    for value in dyn_set.iter() {
+        debug.entry(&value as &dyn Debug);
+    }
+    debug.finish()
+}
+
+/// Inserts the elements of reflected set `b` that are missing from set `a`.
+///
+/// # Panics
+///
+/// This function panics if `b` is not a reflected set.
+#[inline]
+pub fn set_apply<S: Set>(a: &mut S, b: &dyn Reflect) {
+    if let ReflectRef::Set(set_value) = b.reflect_ref() {
+        for value in set_value.iter() {
+            if !a.contains(value) {
+                a.insert_boxed(value.clone_value());
+            }
+        }
+    } else {
+        panic!("Attempted to apply a non-set type to a set type.");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{DynamicSet, Set};
+    use crate::{FromReflect, Reflect, ReflectRef, TypeInfo, Typed};
+    use bevy_utils::HashSet;
+    use std::collections::{BTreeMap, BTreeSet};
+
+    #[test]
+    fn dynamic_set() {
+        let mut set = DynamicSet::default();
+        set.insert(1usize);
+        set.insert(2usize);
+        set.insert(3usize);
+        assert!(!set.insert_boxed(Box::new(2usize)));
+        assert_eq!(set.len(), 3);
+
+        assert!(set.remove(&1usize));
+        assert!(!set.remove(&1usize));
+        assert!(set.contains(&3usize));
+        assert_eq!(set.get(&3usize).unwrap().downcast_ref(), Some(&3usize));
+
+        let values: Vec<_> = set
+            .into_iter()
+            .map(|value| value.take::<usize>().unwrap())
+            .collect();
+        assert_eq!(values, vec![2, 3]);
+    }
+
+    #[test]
+    fn hash_set() {
+        let mut set = HashSet::from_iter([1u32, 2, 3]);
+        let ReflectRef::Set(reflect_set) = set.reflect_ref() else {
+            panic!("expected a set");
+        };
+        assert_eq!(reflect_set.len(), 3);
+        assert!(reflect_set.contains(&2u32));
+
+        let mut patch = DynamicSet::default();
+        patch.insert(4u32);
+        set.apply(&patch);
+        assert!(set.contains(&4));
+
+        assert!(Set::remove(&mut set, &1u32));
+        let dynamic = set.clone_dynamic();
+        assert!(set.reflect_partial_eq(&dynamic).unwrap());
+        assert_eq!(HashSet::<u32>::from_reflect(&dynamic), Some(set));
+
+        let TypeInfo::Set(info) = HashSet::<u32>::type_info() else {
+            panic!("expected `TypeInfo::Set`");
+        };
+        assert!(info.value_is::<u32>());
+    }
+
+    #[test]
+    fn btree_collections() {
+        let mut set = BTreeSet::from([String::from("a"), String::from("b")]);
+        set.insert_boxed(Box::new(String::from("c")));
+        let values: Vec<_> = Set::iter(&set)
+            .map(|value| value.downcast_ref::<String>().unwrap().as_str())
+            .collect();
+        assert_eq!(values, vec!["a", "b", "c"]);
+        let cloned = BTreeSet::<String>::from_reflect(&set.clone_dynamic()).unwrap();
+        assert_eq!(cloned, set);
+
+        let mut map = BTreeMap::from([(1usize, 1.0f32), (2, 2.0)]);
+        let mut patch = BTreeMap::from([(2usize, 4.0f32)]);
+        patch.insert(3, 9.0);
+        map.apply(&patch);
+        assert_eq!(map, BTreeMap::from([(1, 1.0), (2, 4.0), (3, 9.0)]));
+        assert!(map.reflect_partial_eq(&*map.clone_value()).unwrap());
+    }
+}
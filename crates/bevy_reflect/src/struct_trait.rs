@@ -313,6 +313,20 @@ impl DynamicStruct {
         }
     }
 
+    /// Removes the field named `name` from the struct, returning its value.
+    ///
+    /// The fields after it keep their order.
+    pub fn remove(&mut self, name: &str) -> Option<Box<dyn Reflect>> {
+        let index = self.field_indices.remove(name)?;
+        self.field_names.remove(index);
+        for field_index in self.field_indices.values_mut() {
+            if *field_index > index {
+                *field_index -= 1;
+            }
+        }
+        Some(self.fields.remove(index))
+    }
+
     /// Gets the index of the field with the given name.
     pub fn index_of(&self, name: &str) -> Option<usize> {
         self.field_indices.get(name).copied()
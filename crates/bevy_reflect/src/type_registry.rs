@@ -1,10 +1,13 @@
-use crate::{serde::Serializable, Reflect, TypeInfo, Typed};
+use crate::{
+    serde::Serializable, DynamicFunction, DynamicMethod, IntoFunction, IntoMethod, Reflect,
+    ReflectMethods, TypeInfo, Typed,
+};
 use bevy_ptr::{Ptr, PtrMut};
 use bevy_utils::{HashMap, HashSet};
 use downcast_rs::{impl_downcast, Downcast};
 use parking_lot::{RwLock, RwLockReadGuard, RwLockWriteGuard};
 use serde::Deserialize;
-use std::{any::TypeId, fmt::Debug, sync::Arc};
+use std::{any::TypeId, borrow::Cow, fmt::Debug, sync::Arc};
 
 /// A registry of reflected types.
 pub struct TypeRegistry {
@@ -12,6 +15,7 @@ pub struct TypeRegistry {
     short_name_to_id: HashMap<String, TypeId>,
     full_name_to_id: HashMap<String, TypeId>,
     ambiguous_names: HashSet<String>,
+    functions: HashMap<String, DynamicFunction>,
 }
 
 // TODO:  remove this wrapper once we migrate to Atelier Assets and the Scene AssetLoader doesn't
@@ -49,6 +53,7 @@ impl TypeRegistry {
             short_name_to_id: Default::default(),
             full_name_to_id: Default::default(),
             ambiguous_names: Default::default(),
+            functions: Default::default(),
         }
     }
 
@@ -135,6 +140,77 @@ impl TypeRegistry {
         data.insert(D::from_type());
     }
 
+    /// Registers a free function under the given name, replacing any previous function
+    /// with that name.
+    ///
+    /// # Example
+    /// ```rust
+    /// use bevy_reflect::{Reflect, TypeRegistry};
+    ///
+    /// fn add(a: i32, b: i32) -> i32 {
+    ///     a + b
+    /// }
+    ///
+    /// let mut type_registry = TypeRegistry::default();
+    /// type_registry.register_function("add", add);
+    ///
+    /// let add = type_registry.get_function("add").unwrap();
+    /// let args: Vec<Box<dyn Reflect>> = vec![Box::new(1_i32), Box::new(2_i32)];
+    /// assert_eq!(add.call(args).unwrap().downcast_ref::<i32>(), Some(&3));
+    /// ```
+    pub fn register_function<F, Marker>(&mut self, name: impl Into<Cow<'static, str>>, function: F)
+    where
+        F: IntoFunction<Marker>,
+    {
+        let function = function.into_function(name);
+        self.functions.insert(function.name().to_string(), function);
+    }
+
+    /// Registers a method of the type `T` under the given name, storing it in the
+    /// [`ReflectMethods`] of `T`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `T` has not been registered.
+    pub fn register_method<T, F, Marker>(&mut self, name: impl Into<Cow<'static, str>>, method: F)
+    where
+        T: Reflect,
+        F: IntoMethod<T, Marker>,
+    {
+        let registration = self.get_mut(TypeId::of::<T>()).unwrap_or_else(|| {
+            panic!(
+                "attempted to call `TypeRegistry::register_method` for type `{T}` without registering `{T}` first",
+                T = std::any::type_name::<T>(),
+            )
+        });
+        let method = method.into_method(name);
+        if let Some(methods) = registration.data_mut::<ReflectMethods>() {
+            methods.insert(method);
+        } else {
+            let mut methods = ReflectMethods::default();
+            methods.insert(method);
+            registration.insert(methods);
+        }
+    }
+
+    /// Returns the free function registered with the given name.
+    pub fn get_function(&self, name: &str) -> Option<&DynamicFunction> {
+        self.functions.get(name)
+    }
+
+    /// Returns an iterator over the registered free functions.
+    pub fn functions(&self) -> impl Iterator<Item = &DynamicFunction> {
+        self.functions.values()
+    }
+
+    /// Returns the method with the given name of the type with the given [`TypeId`].
+    ///
+    /// If the type has not been registered, or has no method with that name, returns `None`.
+    pub fn get_method(&self, type_id: TypeId, name: &str) -> Option<&DynamicMethod> {
+        self.get_type_data::<ReflectMethods>(type_id)
+            .and_then(|methods| methods.get(name))
+    }
+
     /// Returns a reference to the [`TypeRegistration`] of the type with the
     /// given [`TypeId`].
     ///
@@ -0,0 +1,4 @@
+//! Diagnostic providers for `bevy_diagnostic`.
+
+mod render_memory_diagnostics_plugin;
+pub use render_memory_diagnostics_plugin::RenderMemoryDiagnosticsPlugin;
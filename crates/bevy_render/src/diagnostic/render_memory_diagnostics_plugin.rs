@@ -0,0 +1,71 @@
+use crate::renderer::RenderDevice;
+use bevy_app::prelude::*;
+use bevy_diagnostic::{Diagnostic, DiagnosticId, Diagnostics, MemoryUsage, MemoryUsageEntry};
+use bevy_ecs::system::{Res, ResMut};
+
+/// Adds GPU memory diagnostics to an App.
+///
+/// Reports the bytes of every buffer and texture created through the [`RenderDevice`], both as
+/// [`Diagnostic`]s and as entries of the [`MemoryUsage::RENDER_RESOURCES`] category of the
+/// [`MemoryUsage`] resource. See [`RenderMemoryTracker`](crate::renderer::RenderMemoryTracker)
+/// for how the sizes are estimated.
+#[derive(Default)]
+pub struct RenderMemoryDiagnosticsPlugin;
+
+impl Plugin for RenderMemoryDiagnosticsPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<MemoryUsage>()
+            .add_startup_system(Self::setup_system)
+            .add_system_to_stage(CoreStage::Last, Self::diagnostic_system);
+    }
+}
+
+impl RenderMemoryDiagnosticsPlugin {
+    pub const GPU_BUFFER_BYTES: DiagnosticId =
+        DiagnosticId::from_u128(96024578314509316722402351374596426125);
+    pub const GPU_TEXTURE_BYTES: DiagnosticId =
+        DiagnosticId::from_u128(316447924917451437460307297513218561094);
+
+    pub fn setup_system(mut diagnostics: ResMut<Diagnostics>) {
+        diagnostics.add(Diagnostic::new(
+            Self::GPU_BUFFER_BYTES,
+            "gpu_buffer_bytes",
+            20,
+        ));
+        diagnostics.add(Diagnostic::new(
+            Self::GPU_TEXTURE_BYTES,
+            "gpu_texture_bytes",
+            20,
+        ));
+    }
+
+    pub fn diagnostic_system(
+        mut diagnostics: ResMut<Diagnostics>,
+        mut memory_usage: ResMut<MemoryUsage>,
+        render_device: Option<Res<RenderDevice>>,
+    ) {
+        let render_device = match render_device {
+            Some(render_device) => render_device,
+            None => return,
+        };
+        let memory = render_device.memory();
+
+        diagnostics.add_measurement(Self::GPU_BUFFER_BYTES, || memory.buffer_bytes() as f64);
+        diagnostics.add_measurement(Self::GPU_TEXTURE_BYTES, || memory.texture_bytes() as f64);
+        memory_usage.set_category(
+            MemoryUsage::RENDER_RESOURCES,
+            vec![
+                MemoryUsageEntry {
+                    name: "buffers".to_string(),
+                    bytes: memory.buffer_bytes(),
+                    count: memory.buffer_count(),
+                },
+                MemoryUsageEntry {
+                    name: "textures".to_string(),
+                    bytes: memory.texture_bytes(),
+                    count: memory.texture_count(),
+                },
+            ],
+        );
+    }
+}
@@ -2,6 +2,7 @@ extern crate core;
 
 pub mod camera;
 pub mod color;
+pub mod diagnostic;
 pub mod extract_component;
 mod extract_param;
 pub mod extract_resource;
@@ -8,6 +8,7 @@ use crate::{
     render_resource::{Buffer, VertexBufferLayout},
     renderer::RenderDevice,
 };
+use bevy_asset::diagnostic::AssetMemoryUsage;
 use bevy_core::cast_slice;
 use bevy_derive::EnumVariantMeta;
 use bevy_ecs::system::{lifetimeless::SRes, SystemParamItem};
@@ -828,6 +829,17 @@ pub enum GpuBufferInfo {
     },
 }
 
+impl AssetMemoryUsage for Mesh {
+    fn heap_size_bytes(&self) -> usize {
+        let attribute_bytes: usize = self
+            .attributes
+            .values()
+            .map(|data| data.values.get_bytes().len())
+            .sum();
+        attribute_bytes + self.get_index_buffer_bytes().map_or(0, <[u8]>::len)
+    }
+}
+
 impl RenderAsset for Mesh {
     type ExtractedAsset = Mesh;
     type PreparedAsset = GpuMesh;
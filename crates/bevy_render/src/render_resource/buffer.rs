@@ -1,3 +1,4 @@
+use crate::renderer::TrackedAllocation;
 use bevy_utils::Uuid;
 use std::{
     ops::{Bound, Deref, RangeBounds},
@@ -11,6 +12,7 @@ pub struct BufferId(Uuid);
 pub struct Buffer {
     id: BufferId,
     value: Arc<wgpu::Buffer>,
+    allocation: Option<Arc<TrackedAllocation>>,
 }
 
 impl Buffer {
@@ -36,6 +38,11 @@ impl Buffer {
     pub fn unmap(&self) {
         self.value.unmap();
     }
+
+    pub(crate) fn with_allocation(mut self, allocation: Arc<TrackedAllocation>) -> Self {
+        self.allocation = Some(allocation);
+        self
+    }
 }
 
 impl From<wgpu::Buffer> for Buffer {
@@ -43,6 +50,7 @@ impl From<wgpu::Buffer> for Buffer {
         Buffer {
             id: BufferId(Uuid::new_v4()),
             value: Arc::new(value),
+            allocation: None,
         }
     }
 }
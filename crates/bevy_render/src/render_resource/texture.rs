@@ -1,3 +1,4 @@
+use crate::renderer::TrackedAllocation;
 use bevy_utils::Uuid;
 use std::{ops::Deref, sync::Arc};
 
@@ -13,6 +14,7 @@ pub struct TextureId(Uuid);
 pub struct Texture {
     id: TextureId,
     value: Arc<wgpu::Texture>,
+    allocation: Option<Arc<TrackedAllocation>>,
 }
 
 impl Texture {
@@ -26,6 +28,11 @@ impl Texture {
     pub fn create_view(&self, desc: &wgpu::TextureViewDescriptor) -> TextureView {
         TextureView::from(self.value.create_view(desc))
     }
+
+    pub(crate) fn with_allocation(mut self, allocation: Arc<TrackedAllocation>) -> Self {
+        self.allocation = Some(allocation);
+        self
+    }
 }
 
 impl From<wgpu::Texture> for Texture {
@@ -33,6 +40,7 @@ impl From<wgpu::Texture> for Texture {
         Texture {
             id: TextureId(Uuid::new_v4()),
             value: Arc::new(value),
+            allocation: None,
         }
     }
 }
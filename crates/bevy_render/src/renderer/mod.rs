@@ -1,10 +1,13 @@
 mod graph_runner;
 mod render_device;
+mod render_memory;
 
 use bevy_derive::{Deref, DerefMut};
 use bevy_utils::tracing::{error, info, info_span};
 pub use graph_runner::*;
 pub use render_device::*;
+pub use render_memory::RenderMemoryTracker;
+pub(crate) use render_memory::TrackedAllocation;
 
 use crate::{
     render_graph::RenderGraph,
@@ -6,17 +6,21 @@ use bevy_ecs::system::Resource;
 use std::sync::Arc;
 use wgpu::{util::DeviceExt, BufferAsyncError, BufferBindingType};
 
-use super::RenderQueue;
+use super::{RenderMemoryTracker, RenderQueue};
 
 /// This GPU device is responsible for the creation of most rendering and compute resources.
 #[derive(Resource, Clone)]
 pub struct RenderDevice {
     device: Arc<wgpu::Device>,
+    memory: RenderMemoryTracker,
 }
 
 impl From<Arc<wgpu::Device>> for RenderDevice {
     fn from(device: Arc<wgpu::Device>) -> Self {
-        Self { device }
+        Self {
+            device,
+            memory: RenderMemoryTracker::default(),
+        }
     }
 }
 
@@ -114,13 +118,14 @@ impl RenderDevice {
     /// Creates a [`Buffer`].
     pub fn create_buffer(&self, desc: &wgpu::BufferDescriptor) -> Buffer {
         let wgpu_buffer = self.device.create_buffer(desc);
-        Buffer::from(wgpu_buffer)
+        Buffer::from(wgpu_buffer).with_allocation(self.memory.track_buffer(desc.size))
     }
 
     /// Creates a [`Buffer`] and initializes it with the specified data.
     pub fn create_buffer_with_data(&self, desc: &wgpu::util::BufferInitDescriptor) -> Buffer {
         let wgpu_buffer = self.device.create_buffer_init(desc);
         Buffer::from(wgpu_buffer)
+            .with_allocation(self.memory.track_buffer(desc.contents.len() as u64))
     }
 
     /// Creates a new [`Texture`] and initializes it with the specified data.
@@ -136,7 +141,7 @@ impl RenderDevice {
         let wgpu_texture = self
             .device
             .create_texture_with_data(render_queue.as_ref(), desc, data);
-        Texture::from(wgpu_texture)
+        Texture::from(wgpu_texture).with_allocation(self.memory.track_texture(desc))
     }
 
     /// Creates a new [`Texture`].
@@ -144,7 +149,7 @@ impl RenderDevice {
     /// `desc` specifies the general format of the texture.
     pub fn create_texture(&self, desc: &wgpu::TextureDescriptor) -> Texture {
         let wgpu_texture = self.device.create_texture(desc);
-        Texture::from(wgpu_texture)
+        Texture::from(wgpu_texture).with_allocation(self.memory.track_texture(desc))
     }
 
     /// Creates a new [`Sampler`].
@@ -165,6 +170,11 @@ impl RenderDevice {
         surface.configure(&self.device, config);
     }
 
+    /// Returns the [`RenderMemoryTracker`] counting the buffers and textures created by this device.
+    pub fn memory(&self) -> &RenderMemoryTracker {
+        &self.memory
+    }
+
     /// Returns the wgpu [`Device`](wgpu::Device).
     pub fn wgpu_device(&self) -> &wgpu::Device {
         &self.device
@@ -0,0 +1,195 @@
+use std::sync::{
+    atomic::{AtomicUsize, Ordering},
+    Arc,
+};
+
+/// Tracks the GPU memory allocated through a [`RenderDevice`](super::RenderDevice).
+///
+/// Every [`Buffer`](crate::render_resource::Buffer) and [`Texture`](crate::render_resource::Texture)
+/// created by the device is counted until its last clone is dropped. The byte counts are estimates
+/// derived from the resource descriptors; drivers may add padding and alignment on top of them.
+#[derive(Clone, Debug, Default)]
+pub struct RenderMemoryTracker {
+    counters: Arc<RenderMemoryCounters>,
+}
+
+#[derive(Debug, Default)]
+struct RenderMemoryCounters {
+    buffer_bytes: AtomicUsize,
+    buffer_count: AtomicUsize,
+    texture_bytes: AtomicUsize,
+    texture_count: AtomicUsize,
+}
+
+impl RenderMemoryTracker {
+    /// Returns the number of bytes used by all live buffers.
+    pub fn buffer_bytes(&self) -> usize {
+        self.counters.buffer_bytes.load(Ordering::Relaxed)
+    }
+
+    /// Returns the number of live buffers.
+    pub fn buffer_count(&self) -> usize {
+        self.counters.buffer_count.load(Ordering::Relaxed)
+    }
+
+    /// Returns the estimated number of bytes used by all live textures.
+    pub fn texture_bytes(&self) -> usize {
+        self.counters.texture_bytes.load(Ordering::Relaxed)
+    }
+
+    /// Returns the number of live textures.
+    pub fn texture_count(&self) -> usize {
+        self.counters.texture_count.load(Ordering::Relaxed)
+    }
+
+    pub(crate) fn track_buffer(&self, bytes: u64) -> Arc<TrackedAllocation> {
+        self.track(RenderResourceKind::Buffer, bytes as usize)
+    }
+
+    pub(crate) fn track_texture(&self, desc: &wgpu::TextureDescriptor) -> Arc<TrackedAllocation> {
+        self.track(RenderResourceKind::Texture, texture_size_bytes(desc))
+    }
+
+    fn track(&self, kind: RenderResourceKind, bytes: usize) -> Arc<TrackedAllocation> {
+        let (total, count) = self.counters.get(kind);
+        total.fetch_add(bytes, Ordering::Relaxed);
+        count.fetch_add(1, Ordering::Relaxed);
+        Arc::new(TrackedAllocation {
+            counters: self.counters.clone(),
+            kind,
+            bytes,
+        })
+    }
+}
+
+impl RenderMemoryCounters {
+    fn get(&self, kind: RenderResourceKind) -> (&AtomicUsize, &AtomicUsize) {
+        match kind {
+            RenderResourceKind::Buffer => (&self.buffer_bytes, &self.buffer_count),
+            RenderResourceKind::Texture => (&self.texture_bytes, &self.texture_count),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+enum RenderResourceKind {
+    Buffer,
+    Texture,
+}
+
+/// Keeps a GPU resource counted by a [`RenderMemoryTracker`] until it is dropped.
+#[derive(Debug)]
+pub(crate) struct TrackedAllocation {
+    counters: Arc<RenderMemoryCounters>,
+    kind: RenderResourceKind,
+    bytes: usize,
+}
+
+impl Drop for TrackedAllocation {
+    fn drop(&mut self) {
+        let (total, count) = self.counters.get(self.kind);
+        total.fetch_sub(self.bytes, Ordering::Relaxed);
+        count.fetch_sub(1, Ordering::Relaxed);
+    }
+}
+
+/// Estimates the size of a texture from its descriptor, including all mip levels and samples.
+fn texture_size_bytes(desc: &wgpu::TextureDescriptor) -> usize {
+    let format_info = desc.format.describe();
+    let (block_width, block_height) = (
+        format_info.block_dimensions.0 as u32,
+        format_info.block_dimensions.1 as u32,
+    );
+    let layers = match desc.dimension {
+        wgpu::TextureDimension::D3 => 1,
+        _ => desc.size.depth_or_array_layers,
+    };
+
+    let mut bytes = 0;
+    for level in 0..desc.mip_level_count {
+        if let Some(size) = desc.mip_level_size(level) {
+            let blocks_x = ((size.width + block_width - 1) / block_width).max(1);
+            let blocks_y = ((size.height + block_height - 1) / block_height).max(1);
+            let depth = match desc.dimension {
+                wgpu::TextureDimension::D3 => size.depth_or_array_layers,
+                _ => 1,
+            };
+            bytes += blocks_x as usize
+                * blocks_y as usize
+                * depth as usize
+                * format_info.block_size as usize;
+        }
+    }
+    bytes * layers as usize * desc.sample_count as usize
+}
+
+#[cfg(test)]
+mod tests {
+    use super::texture_size_bytes;
+
+    fn descriptor(
+        format: wgpu::TextureFormat,
+        (width, height, depth_or_array_layers): (u32, u32, u32),
+        dimension: wgpu::TextureDimension,
+    ) -> wgpu::TextureDescriptor<'static> {
+        wgpu::TextureDescriptor {
+            label: None,
+            size: wgpu::Extent3d {
+                width,
+                height,
+                depth_or_array_layers,
+            },
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension,
+            format,
+            usage: wgpu::TextureUsages::TEXTURE_BINDING,
+        }
+    }
+
+    #[test]
+    fn texture_sizes() {
+        use wgpu::{TextureDimension::*, TextureFormat::*};
+
+        let rgba = descriptor(Rgba8UnormSrgb, (256, 256, 1), D2);
+        assert_eq!(texture_size_bytes(&rgba), 256 * 256 * 4);
+
+        let mipmapped = wgpu::TextureDescriptor {
+            mip_level_count: 9,
+            ..rgba.clone()
+        };
+        let mips: usize = (0..9)
+            .map(|level| (256 >> level) * (256 >> level) * 4)
+            .sum();
+        assert_eq!(texture_size_bytes(&mipmapped), mips);
+
+        let multisampled = wgpu::TextureDescriptor {
+            sample_count: 4,
+            ..rgba
+        };
+        assert_eq!(texture_size_bytes(&multisampled), 256 * 256 * 4 * 4);
+
+        let depth = descriptor(Depth32Float, (100, 50, 1), D2);
+        assert_eq!(texture_size_bytes(&depth), 100 * 50 * 4);
+
+        let half = descriptor(Rgba16Float, (10, 10, 1), D2);
+        assert_eq!(texture_size_bytes(&half), 10 * 10 * 8);
+    }
+
+    #[test]
+    fn texture_sizes_with_layers_and_blocks() {
+        use wgpu::{TextureDimension::*, TextureFormat::*};
+
+        let cube = descriptor(Rgba8Unorm, (32, 32, 6), D2);
+        assert_eq!(texture_size_bytes(&cube), 6 * 32 * 32 * 4);
+
+        let volume = descriptor(R8Unorm, (16, 16, 8), D3);
+        assert_eq!(texture_size_bytes(&volume), 16 * 16 * 8);
+
+        // 4x4 blocks of 8 bytes, partial blocks are rounded up
+        let compressed = descriptor(Bc1RgbaUnorm, (64, 64, 1), D2);
+        assert_eq!(texture_size_bytes(&compressed), 16 * 16 * 8);
+        let partial = descriptor(Bc1RgbaUnorm, (6, 6, 1), D2);
+        assert_eq!(texture_size_bytes(&partial), 2 * 2 * 8);
+    }
+}
@@ -11,7 +11,7 @@ use crate::{
     renderer::{RenderDevice, RenderQueue},
     texture::BevyDefault,
 };
-use bevy_asset::HandleUntyped;
+use bevy_asset::{diagnostic::AssetMemoryUsage, HandleUntyped};
 use bevy_derive::{Deref, DerefMut};
 use bevy_ecs::system::{lifetimeless::SRes, Resource, SystemParamItem};
 use bevy_math::Vec2;
@@ -610,6 +610,12 @@ pub struct GpuImage {
     pub size: Vec2,
 }
 
+impl AssetMemoryUsage for Image {
+    fn heap_size_bytes(&self) -> usize {
+        self.data.len()
+    }
+}
+
 impl RenderAsset for Image {
     type ExtractedAsset = Image;
     type PreparedAsset = GpuImage;
@@ -1,12 +1,15 @@
 use anyhow::Result;
-use bevy_asset::{AssetLoader, LoadContext, LoadedAsset};
+use bevy_asset::{
+    deserialize_settings, AssetLoader, LoadContext, LoadedAsset, SettingsDeserializer,
+};
 use bevy_ecs::prelude::{FromWorld, World};
 use bevy_utils::BoxedFuture;
+use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
 use crate::{
     renderer::RenderDevice,
-    texture::{Image, ImageType, TextureError},
+    texture::{Image, ImageSampler, ImageType, TextureError},
 };
 
 use super::CompressedImageFormats;
@@ -36,6 +39,47 @@ const FILE_EXTENSIONS: &[&str] = &[
     "ktx2",
 ];
 
+/// Settings of the [`ImageTextureLoader`], read from the `.meta` file of an image.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ImageLoaderSettings {
+    /// The sampler used to render the image.
+    pub sampler: ImageLoaderSampler,
+    /// Whether the image data is in the sRGB color space, for formats that do not specify it.
+    pub is_srgb: bool,
+}
+
+impl Default for ImageLoaderSettings {
+    fn default() -> Self {
+        Self {
+            sampler: ImageLoaderSampler::Default,
+            is_srgb: true,
+        }
+    }
+}
+
+/// The sampler of an image loaded by the [`ImageTextureLoader`], see [`ImageSampler`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ImageLoaderSampler {
+    /// Uses the default sampler of the [`ImagePlugin`](super::ImagePlugin).
+    #[default]
+    Default,
+    /// Uses [`ImageSampler::linear`].
+    Linear,
+    /// Uses [`ImageSampler::nearest`].
+    Nearest,
+}
+
+impl From<ImageLoaderSampler> for ImageSampler {
+    fn from(sampler: ImageLoaderSampler) -> Self {
+        match sampler {
+            ImageLoaderSampler::Default => ImageSampler::Default,
+            ImageLoaderSampler::Linear => ImageSampler::linear(),
+            ImageLoaderSampler::Nearest => ImageSampler::nearest(),
+        }
+    }
+}
+
 impl AssetLoader for ImageTextureLoader {
     fn load<'a>(
         &'a self,
@@ -45,17 +89,22 @@ impl AssetLoader for ImageTextureLoader {
         Box::pin(async move {
             // use the file extension for the image type
             let ext = load_context.path().extension().unwrap().to_str().unwrap();
+            let settings = load_context
+                .settings::<ImageLoaderSettings>()
+                .cloned()
+                .unwrap_or_default();
 
-            let dyn_img = Image::from_buffer(
+            let mut dyn_img = Image::from_buffer(
                 bytes,
                 ImageType::Extension(ext),
                 self.supported_compressed_formats,
-                true,
+                settings.is_srgb,
             )
             .map_err(|err| FileTextureError {
                 error: err,
                 path: format!("{}", load_context.path().display()),
             })?;
+            dyn_img.sampler_descriptor = settings.sampler.into();
 
             load_context.set_default_asset(LoadedAsset::new(dyn_img));
             Ok(())
@@ -65,6 +114,10 @@ impl AssetLoader for ImageTextureLoader {
     fn extensions(&self) -> &[&str] {
         FILE_EXTENSIONS
     }
+
+    fn settings_deserializer(&self) -> Option<SettingsDeserializer> {
+        Some(deserialize_settings::<ImageLoaderSettings>)
+    }
 }
 
 impl FromWorld for ImageTextureLoader {
@@ -1,15 +1,17 @@
 use crate::{serde::SceneSerializer, DynamicSceneBuilder, Scene, SceneSpawnError};
 use anyhow::Result;
 use bevy_app::AppTypeRegistry;
+use bevy_asset::AssetPath;
 use bevy_ecs::{
-    entity::EntityMap,
-    reflect::{ReflectComponent, ReflectMapEntities},
+    entity::{Entity, EntityMap},
+    reflect::{ReflectComponent, ReflectMapEntities, ReflectResource},
     world::World,
 };
-use bevy_reflect::{Reflect, TypeRegistryArc, TypeUuid};
+use bevy_reflect::{ParsedPath, Reflect, TypeRegistry, TypeRegistryArc, TypeUuid};
 use serde::Serialize;
 
-/// A collection of serializable dynamic entities, each with its own run-time defined set of components.
+/// A collection of serializable dynamic entities, each with its own run-time defined set of components,
+/// along with serializable resources and instances of other scenes.
 /// To spawn a dynamic scene, you can use either:
 /// * [`SceneSpawner::spawn_dynamic`](crate::SceneSpawner::spawn_dynamic)
 /// * adding the [`DynamicSceneBundle`](crate::DynamicSceneBundle) to an entity
@@ -19,7 +21,12 @@ use serde::Serialize;
 #[derive(Default, TypeUuid)]
 #[uuid = "749479b1-fb8c-4ff8-a775-623aa76014f5"]
 pub struct DynamicScene {
+    /// Boxed resources which implement the `Reflect` trait, ordered by type name.
+    pub resources: Vec<Box<dyn Reflect>>,
     pub entities: Vec<DynamicEntity>,
+    /// Instances of other scenes nested in this scene, which are only spawned by the
+    /// [`SceneSpawner`](crate::SceneSpawner).
+    pub instances: Vec<DynamicSceneInstance>,
 }
 
 /// A reflection-powered serializable representation of an entity and its components.
@@ -31,6 +38,98 @@ pub struct DynamicEntity {
     pub components: Vec<Box<dyn Reflect>>,
 }
 
+/// An instance of another [`DynamicScene`] nested in a scene, such as one of the trees of a level.
+///
+/// When the nested scene is reloaded, the [`SceneSpawner`](crate::SceneSpawner) rewrites every
+/// one of its instances and applies their overrides again.
+#[derive(Debug, Clone)]
+pub struct DynamicSceneInstance {
+    /// The asset path of the nested scene.
+    pub scene: AssetPath<'static>,
+    /// The entity of the outer scene that the root entities of the instance are added to as
+    /// children, if any.
+    pub parent: Option<u32>,
+    /// The properties in which this instance differs from the nested scene.
+    pub overrides: Vec<SceneOverride>,
+}
+
+/// A reflected value replacing a property of a component in an instance of a nested scene.
+#[derive(Debug)]
+pub struct SceneOverride {
+    /// The entity of the nested scene whose component is overridden, as identified in that scene.
+    pub entity: u32,
+    /// The type name of the overridden component.
+    pub component: String,
+    /// The [path](bevy_reflect::GetPath) of the property in the component. An empty path
+    /// overrides the whole component.
+    pub path: String,
+    /// The value applied to the property.
+    pub value: Box<dyn Reflect>,
+}
+
+impl Clone for SceneOverride {
+    fn clone(&self) -> Self {
+        Self {
+            entity: self.entity,
+            component: self.component.clone(),
+            path: self.path.clone(),
+            value: self.value.clone_value(),
+        }
+    }
+}
+
+impl SceneOverride {
+    /// Apply the override to the instance of the nested scene that was written using `entity_map`.
+    ///
+    /// This method will return a [`SceneSpawnError`] if the component isn't registered with
+    /// [`ReflectComponent`] type data, or if the instance doesn't have the property.
+    pub fn apply(
+        &self,
+        world: &mut World,
+        entity_map: &EntityMap,
+        type_registry: &TypeRegistry,
+    ) -> Result<(), SceneSpawnError> {
+        let invalid = |reason: String| SceneSpawnError::InvalidOverride {
+            entity: self.entity,
+            type_name: self.component.clone(),
+            path: self.path.clone(),
+            reason,
+        };
+
+        let registration = type_registry
+            .get_with_name(&self.component)
+            .ok_or_else(|| SceneSpawnError::UnregisteredType {
+                type_name: self.component.clone(),
+            })?;
+        let reflect_component = registration.data::<ReflectComponent>().ok_or_else(|| {
+            SceneSpawnError::UnregisteredComponent {
+                type_name: self.component.clone(),
+            }
+        })?;
+        let path = ParsedPath::parse(&self.path).map_err(|err| invalid(err.to_string()))?;
+
+        let entity = entity_map
+            .get(Entity::from_raw(self.entity))
+            .map_err(|_| invalid("the entity does not exist in the nested scene".to_string()))?;
+        let mut component = reflect_component
+            .reflect_mut(world, entity)
+            .ok_or_else(|| invalid("the entity does not have the component".to_string()))?;
+        let property = path
+            .element_mut(&mut *component)
+            .map_err(|err| invalid(err.to_string()))?;
+
+        if property.type_name() != self.value.type_name() {
+            return Err(invalid(format!(
+                "expected a value of type `{}`, found `{}`",
+                property.type_name(),
+                self.value.type_name()
+            )));
+        }
+        property.apply(&*self.value);
+        Ok(())
+    }
+}
+
 impl DynamicScene {
     /// Create a new dynamic scene from a given scene.
     pub fn from_scene(scene: &Scene, type_registry: &AppTypeRegistry) -> Self {
@@ -38,6 +137,9 @@ impl DynamicScene {
     }
 
     /// Create a new dynamic scene from a given world.
+    ///
+    /// Only the entities of the world are extracted. Resources can be added to a scene with
+    /// [`DynamicSceneBuilder::extract_resource`].
     pub fn from_world(world: &World, type_registry: &AppTypeRegistry) -> Self {
         let mut builder =
             DynamicSceneBuilder::from_world_with_type_registry(world, type_registry.clone());
@@ -48,10 +150,12 @@ impl DynamicScene {
     }
 
     /// Write the dynamic entities and their corresponding components to the given world.
+    /// Resources of the scene are only inserted if the world doesn't have them yet, so that
+    /// spawning or reloading a scene never overwrites the live resources of the world.
     ///
     /// This method will return a [`SceneSpawnError`] if a type either is not registered
     /// in the provided [`AppTypeRegistry`] resource, or doesn't reflect the
-    /// [`Component`](bevy_ecs::component::Component) trait.
+    /// [`Component`](bevy_ecs::component::Component) or [`Resource`](bevy_ecs::system::Resource) trait.
     pub fn write_to_world_with(
         &self,
         world: &mut World,
@@ -60,6 +164,23 @@ impl DynamicScene {
     ) -> Result<(), SceneSpawnError> {
         let type_registry = type_registry.read();
 
+        for resource in &self.resources {
+            let registration = type_registry
+                .get_with_name(resource.type_name())
+                .ok_or_else(|| SceneSpawnError::UnregisteredType {
+                    type_name: resource.type_name().to_string(),
+                })?;
+            let reflect_resource = registration.data::<ReflectResource>().ok_or_else(|| {
+                SceneSpawnError::UnregisteredResource {
+                    type_name: resource.type_name().to_string(),
+                }
+            })?;
+
+            if reflect_resource.reflect(world).is_none() {
+                reflect_resource.insert(world, &**resource);
+            }
+        }
+
         for scene_entity in &self.entities {
             // Fetch the entity with the given entity id from the `entity_map`
             // or spawn a new entity with a transiently unique id if there is
@@ -101,10 +222,12 @@ impl DynamicScene {
     }
 
     /// Write the dynamic entities and their corresponding components to the given world.
+    /// Resources of the scene are only inserted if the world doesn't have them yet, so that
+    /// spawning or reloading a scene never overwrites the live resources of the world.
     ///
     /// This method will return a [`SceneSpawnError`] if a type either is not registered
     /// in the world's [`AppTypeRegistry`] resource, or doesn't reflect the
-    /// [`Component`](bevy_ecs::component::Component) trait.
+    /// [`Component`](bevy_ecs::component::Component) or [`Resource`](bevy_ecs::system::Resource) trait.
     pub fn write_to_world(
         &self,
         world: &mut World,
@@ -1,10 +1,21 @@
 use crate::{DynamicEntity, DynamicScene};
 use bevy_app::AppTypeRegistry;
-use bevy_ecs::{prelude::Entity, reflect::ReflectComponent, world::World};
-use bevy_utils::default;
-use std::collections::BTreeMap;
-
-/// A [`DynamicScene`] builder, used to build a scene from a [`World`] by extracting some entities.
+use bevy_ecs::{
+    component::Component,
+    prelude::Entity,
+    query::{QueryState, ReadOnlyWorldQuery},
+    reflect::{ReflectComponent, ReflectResource},
+    system::Resource,
+    world::World,
+};
+use bevy_reflect::{Reflect, TypeRegistration};
+use bevy_utils::{default, HashSet};
+use std::{any::TypeId, collections::BTreeMap};
+
+type ComponentPredicate<'w> = Box<dyn Fn(&TypeRegistration) -> bool + 'w>;
+
+/// A [`DynamicScene`] builder, used to build a scene from a [`World`] by extracting some entities
+/// and resources.
 ///
 /// # Entity Order
 ///
@@ -12,6 +23,16 @@ use std::collections::BTreeMap;
 /// This means that inserting `Entity(1v0)` then `Entity(0v0)` will always result in the entities
 /// being ordered as `[Entity(0v0), Entity(1v0)]`.
 ///
+/// Extracted resources are likewise ordered by their type name.
+///
+/// # Component Filters
+///
+/// By default, every component registered with [`ReflectComponent`] type data is extracted.
+/// [`allow_component`](Self::allow_component), [`deny_component`](Self::deny_component) and
+/// [`filter_components`](Self::filter_components) narrow this down, for example to keep
+/// derived state such as `GlobalTransform` out of save files. Filters only apply to entities
+/// extracted after they are set, and never to resources.
+///
 /// # Example
 /// ```
 /// # use bevy_scene::DynamicSceneBuilder;
@@ -32,6 +53,10 @@ use std::collections::BTreeMap;
 /// ```
 pub struct DynamicSceneBuilder<'w> {
     entities: BTreeMap<u32, DynamicEntity>,
+    resources: BTreeMap<&'static str, Box<dyn Reflect>>,
+    allowed_components: Option<HashSet<TypeId>>,
+    denied_components: HashSet<TypeId>,
+    component_predicate: Option<ComponentPredicate<'w>>,
     type_registry: AppTypeRegistry,
     world: &'w World,
 }
@@ -42,6 +67,10 @@ impl<'w> DynamicSceneBuilder<'w> {
     pub fn from_world(world: &'w World) -> Self {
         Self {
             entities: default(),
+            resources: default(),
+            allowed_components: None,
+            denied_components: default(),
+            component_predicate: None,
             type_registry: world.resource::<AppTypeRegistry>().clone(),
             world,
         }
@@ -52,6 +81,10 @@ impl<'w> DynamicSceneBuilder<'w> {
     pub fn from_world_with_type_registry(world: &'w World, type_registry: AppTypeRegistry) -> Self {
         Self {
             entities: default(),
+            resources: default(),
+            allowed_components: None,
+            denied_components: default(),
+            component_predicate: None,
             type_registry,
             world,
         }
@@ -60,7 +93,78 @@ impl<'w> DynamicSceneBuilder<'w> {
     /// Consume the builder, producing a [`DynamicScene`].
     pub fn build(self) -> DynamicScene {
         DynamicScene {
+            resources: self.resources.into_values().collect(),
             entities: self.entities.into_values().collect(),
+            instances: Vec::new(),
+        }
+    }
+
+    /// Only extract the component `T` and other allowed components from now on.
+    ///
+    /// Until a component is allowed, every component that isn't denied is extracted.
+    pub fn allow_component<T: Component>(&mut self) -> &mut Self {
+        self.allow_component_by_id(TypeId::of::<T>())
+    }
+
+    /// Only extract the component with the given [`TypeId`] and other allowed components from
+    /// now on.
+    ///
+    /// Until a component is allowed, every component that isn't denied is extracted.
+    pub fn allow_component_by_id(&mut self, type_id: TypeId) -> &mut Self {
+        self.allowed_components
+            .get_or_insert_with(HashSet::default)
+            .insert(type_id);
+        self
+    }
+
+    /// Never extract the component `T` from now on, even if it was allowed.
+    pub fn deny_component<T: Component>(&mut self) -> &mut Self {
+        self.deny_component_by_id(TypeId::of::<T>())
+    }
+
+    /// Never extract the component with the given [`TypeId`] from now on, even if it was allowed.
+    pub fn deny_component_by_id(&mut self, type_id: TypeId) -> &mut Self {
+        self.denied_components.insert(type_id);
+        self
+    }
+
+    /// Only extract components for whose registration `predicate` returns `true` from now on.
+    ///
+    /// The predicate is checked in addition to the allowed and denied components, and replaces
+    /// any previously set predicate.
+    ///
+    /// ```
+    /// # use bevy_scene::DynamicSceneBuilder;
+    /// # use bevy_app::AppTypeRegistry;
+    /// # use bevy_ecs::world::World;
+    /// # let mut world = World::default();
+    /// # world.init_resource::<AppTypeRegistry>();
+    /// let mut builder = DynamicSceneBuilder::from_world(&world);
+    /// builder.filter_components(|registration| !registration.type_name().starts_with("bevy_render"));
+    /// ```
+    pub fn filter_components(
+        &mut self,
+        predicate: impl Fn(&TypeRegistration) -> bool + 'w,
+    ) -> &mut Self {
+        self.component_predicate = Some(Box::new(predicate));
+        self
+    }
+
+    /// Returns `true` if the component with the given registration passes the builder's filters.
+    pub fn is_component_allowed(&self, registration: &TypeRegistration) -> bool {
+        let type_id = registration.type_id();
+        if self.denied_components.contains(&type_id) {
+            return false;
+        }
+        if let Some(allowed) = &self.allowed_components {
+            if !allowed.contains(&type_id) {
+                return false;
+            }
+        }
+
+        match &self.component_predicate {
+            Some(predicate) => predicate(registration),
+            None => true,
         }
     }
 
@@ -117,6 +221,7 @@ impl<'w> DynamicSceneBuilder<'w> {
                     .components()
                     .get_info(component_id)
                     .and_then(|info| type_registry.get(info.type_id().unwrap()))
+                    .filter(|registration| self.is_component_allowed(registration))
                     .and_then(|registration| registration.data::<ReflectComponent>());
 
                 if let Some(reflect_component) = reflect_component {
@@ -131,13 +236,99 @@ impl<'w> DynamicSceneBuilder<'w> {
         drop(type_registry);
         self
     }
+
+    /// Extract the entities matching the query filter `F` from the builder's [`World`].
+    ///
+    /// The query has to be created beforehand, as doing so requires mutable access to the world.
+    ///
+    /// ```
+    /// # use bevy_scene::DynamicSceneBuilder;
+    /// # use bevy_app::AppTypeRegistry;
+    /// # use bevy_ecs::{
+    /// #     component::Component, prelude::Entity, query::Without, reflect::ReflectComponent, world::World,
+    /// # };
+    /// # use bevy_reflect::Reflect;
+    /// #[derive(Component, Default, Reflect)]
+    /// #[reflect(Component)]
+    /// struct Player;
+    ///
+    /// #[derive(Component)]
+    /// struct Transient;
+    ///
+    /// # let mut world = World::default();
+    /// # world.init_resource::<AppTypeRegistry>();
+    /// # world.resource::<AppTypeRegistry>().write().register::<Player>();
+    /// # world.spawn(Player);
+    /// # world.spawn((Player, Transient));
+    /// let mut query = world.query_filtered::<Entity, Without<Transient>>();
+    ///
+    /// let mut builder = DynamicSceneBuilder::from_world(&world);
+    /// builder.extract_query(&mut query);
+    /// let scene = builder.build();
+    /// # assert_eq!(scene.entities.len(), 1);
+    /// ```
+    pub fn extract_query<F: ReadOnlyWorldQuery>(
+        &mut self,
+        query: &mut QueryState<Entity, F>,
+    ) -> &mut Self {
+        self.extract_entities(query.iter(self.world))
+    }
+
+    /// Extract the resource of type `R` from the builder's [`World`].
+    ///
+    /// Nothing is extracted if `R` is not registered with [`ReflectResource`] type data, or if
+    /// the world doesn't contain it. Re-extracting a resource that was already extracted will
+    /// have no effect.
+    ///
+    /// ```
+    /// # use bevy_scene::DynamicSceneBuilder;
+    /// # use bevy_app::AppTypeRegistry;
+    /// # use bevy_ecs::{prelude::Resource, reflect::ReflectResource, world::World};
+    /// # use bevy_reflect::Reflect;
+    /// #[derive(Resource, Default, Reflect)]
+    /// #[reflect(Resource)]
+    /// struct Score(u32);
+    ///
+    /// # let mut world = World::default();
+    /// # world.init_resource::<AppTypeRegistry>();
+    /// # world.resource::<AppTypeRegistry>().write().register::<Score>();
+    /// world.insert_resource(Score(42));
+    ///
+    /// let mut builder = DynamicSceneBuilder::from_world(&world);
+    /// builder.extract_resource::<Score>();
+    /// let scene = builder.build();
+    /// # assert_eq!(scene.resources.len(), 1);
+    /// ```
+    pub fn extract_resource<R: Resource>(&mut self) -> &mut Self {
+        let type_registry = self.type_registry.read();
+        let resource = type_registry
+            .get(TypeId::of::<R>())
+            .filter(|registration| !self.resources.contains_key(registration.type_name()))
+            .and_then(|registration| {
+                let reflect_resource = registration.data::<ReflectResource>()?;
+                Some((
+                    registration.type_name(),
+                    reflect_resource.reflect(self.world)?,
+                ))
+            });
+        if let Some((type_name, resource)) = resource {
+            self.resources.insert(type_name, resource.clone_value());
+        }
+
+        drop(type_registry);
+        self
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use bevy_app::AppTypeRegistry;
     use bevy_ecs::{
-        component::Component, prelude::Entity, query::With, reflect::ReflectComponent, world::World,
+        component::Component,
+        prelude::{Entity, Resource},
+        query::{With, Without},
+        reflect::{ReflectComponent, ReflectResource},
+        world::World,
     };
 
     use bevy_reflect::Reflect;
@@ -150,6 +341,16 @@ mod tests {
     #[derive(Component, Reflect, Default, Eq, PartialEq, Debug)]
     #[reflect(Component)]
     struct ComponentB;
+    #[derive(Component, Reflect, Default, Eq, PartialEq, Debug)]
+    #[reflect(Component)]
+    struct ComponentC;
+
+    #[derive(Resource, Reflect, Default, Eq, PartialEq, Debug)]
+    #[reflect(Resource)]
+    struct ResourceA(u32);
+    #[derive(Resource, Reflect, Default, Eq, PartialEq, Debug)]
+    #[reflect(Resource)]
+    struct ResourceB;
 
     #[test]
     fn extract_one_entity() {
@@ -270,4 +471,88 @@ mod tests {
         scene_entities.sort();
         assert_eq!(scene_entities, [entity_a_b.index(), entity_a.index()]);
     }
+
+    #[test]
+    fn extract_query_filter() {
+        let mut world = World::default();
+
+        let atr = AppTypeRegistry::default();
+        atr.write().register::<ComponentA>();
+        world.insert_resource(atr);
+
+        let entity_a = world.spawn(ComponentA).id();
+        let _entity_a_b = world.spawn((ComponentA, ComponentB)).id();
+
+        let mut query = world.query_filtered::<Entity, Without<ComponentB>>();
+        let mut builder = DynamicSceneBuilder::from_world(&world);
+        builder.extract_query(&mut query);
+        let scene = builder.build();
+
+        assert_eq!(scene.entities.len(), 1);
+        assert_eq!(scene.entities[0].entity, entity_a.index());
+    }
+
+    #[test]
+    fn extract_allowed_and_denied_components() {
+        let mut world = World::default();
+
+        let atr = AppTypeRegistry::default();
+        {
+            let mut register = atr.write();
+            register.register::<ComponentA>();
+            register.register::<ComponentB>();
+            register.register::<ComponentC>();
+        }
+        world.insert_resource(atr);
+
+        let entity_a = world.spawn((ComponentA, ComponentB, ComponentC)).id();
+        let entity_b = world.spawn((ComponentA, ComponentB, ComponentC)).id();
+        let entity_c = world.spawn((ComponentA, ComponentB, ComponentC)).id();
+
+        let mut builder = DynamicSceneBuilder::from_world(&world);
+        builder.deny_component::<ComponentB>();
+        builder.extract_entity(entity_a);
+        builder.allow_component::<ComponentA>();
+        builder.allow_component::<ComponentB>();
+        builder.extract_entity(entity_b);
+        builder.filter_components(|registration| {
+            registration.type_name() != std::any::type_name::<ComponentA>()
+        });
+        builder.extract_entity(entity_c);
+        let scene = builder.build();
+
+        let components = &scene.entities[0].components;
+        assert_eq!(components.len(), 2);
+        assert!(components[0].represents::<ComponentA>());
+        assert!(components[1].represents::<ComponentC>());
+
+        // `ComponentB` stays denied
+        let components = &scene.entities[1].components;
+        assert_eq!(components.len(), 1);
+        assert!(components[0].represents::<ComponentA>());
+
+        assert!(scene.entities[2].components.is_empty());
+    }
+
+    #[test]
+    fn extract_one_resource() {
+        let mut world = World::default();
+
+        let atr = AppTypeRegistry::default();
+        atr.write().register::<ResourceA>();
+        world.insert_resource(atr);
+
+        world.insert_resource(ResourceA(1));
+        world.insert_resource(ResourceB);
+
+        let mut builder = DynamicSceneBuilder::from_world(&world);
+        builder.extract_resource::<ResourceA>();
+        builder.extract_resource::<ResourceA>();
+        // not registered
+        builder.extract_resource::<ResourceB>();
+        let scene = builder.build();
+
+        assert_eq!(scene.resources.len(), 1);
+        assert!(scene.resources[0].represents::<ResourceA>());
+    }
 }
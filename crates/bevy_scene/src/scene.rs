@@ -56,9 +56,7 @@ impl Scene {
         world: &mut World,
         type_registry: &AppTypeRegistry,
     ) -> Result<InstanceInfo, SceneSpawnError> {
-        let mut instance_info = InstanceInfo {
-            entity_map: EntityMap::default(),
-        };
+        let mut instance_info = InstanceInfo::default();
 
         let type_registry = type_registry.read();
         for archetype in self.world.archetypes().iter() {
@@ -33,7 +33,13 @@ impl AssetLoader for SceneLoader {
                 type_registry: &self.type_registry.read(),
             };
             let scene = scene_deserializer.deserialize(&mut deserializer)?;
-            load_context.set_default_asset(LoadedAsset::new(scene));
+            let nested_scenes = scene
+                .instances
+                .iter()
+                .map(|instance| instance.scene.clone())
+                .collect();
+            load_context
+                .set_default_asset(LoadedAsset::new(scene).with_dependencies(nested_scenes));
             Ok(())
         })
     }
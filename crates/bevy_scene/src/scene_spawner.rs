@@ -1,6 +1,6 @@
-use crate::{DynamicScene, Scene};
+use crate::{DynamicScene, DynamicSceneInstance, Scene, SceneOverride};
 use bevy_app::AppTypeRegistry;
-use bevy_asset::{AssetEvent, Assets, Handle};
+use bevy_asset::{AssetEvent, Assets, Handle, HandleId};
 use bevy_ecs::{
     entity::{Entity, EntityMap},
     event::{Events, ManualEventReader},
@@ -13,10 +13,17 @@ use thiserror::Error;
 use uuid::Uuid;
 
 /// Informations about a scene instance.
-#[derive(Debug)]
+#[derive(Debug, Default)]
 pub struct InstanceInfo {
     /// Mapping of entities from the scene world to the instance world.
     pub entity_map: EntityMap,
+    /// Instances of the scenes nested in this instance, in the order of
+    /// [`DynamicScene::instances`].
+    pub nested_instances: Vec<InstanceId>,
+    /// The overrides applied to this instance by the scene it is nested in, if any.
+    pub overrides: Vec<SceneOverride>,
+    /// The entity the root entities of this instance are children of, if any.
+    pub parent: Option<Entity>,
 }
 
 #[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
@@ -45,8 +52,21 @@ pub struct SceneSpawner {
 pub enum SceneSpawnError {
     #[error("scene contains the unregistered component `{type_name}`. consider adding `#[reflect(Component)]` to your type")]
     UnregisteredComponent { type_name: String },
+    #[error("scene contains the unregistered resource `{type_name}`. consider adding `#[reflect(Resource)]` to your type")]
+    UnregisteredResource { type_name: String },
     #[error("scene contains the unregistered type `{type_name}`. consider registering the type using `app.register_type::<T>()`")]
     UnregisteredType { type_name: String },
+    #[error("invalid override of `{path}` in the component `{type_name}` of the nested scene entity {entity}: {reason}")]
+    InvalidOverride {
+        entity: u32,
+        type_name: String,
+        path: String,
+        reason: String,
+    },
+    #[error("scene `{path}` contains an instance of itself")]
+    RecursiveInstance { path: String },
+    #[error("the parent {entity} of the instance of `{path}` does not exist in the scene")]
+    NonExistentInstanceParent { path: String, entity: u32 },
     #[error("scene does not exist")]
     NonExistentScene { handle: Handle<DynamicScene> },
     #[error("scene does not exist")]
@@ -111,6 +131,9 @@ impl SceneSpawner {
             for entity in instance.entity_map.values() {
                 let _ = world.despawn(entity);
             }
+            for nested_instance_id in &instance.nested_instances {
+                self.despawn_instance_sync(world, nested_instance_id);
+            }
         }
     }
 
@@ -119,11 +142,8 @@ impl SceneSpawner {
         world: &mut World,
         scene_handle: &Handle<DynamicScene>,
     ) -> Result<(), SceneSpawnError> {
-        let mut entity_map = EntityMap::default();
-        Self::spawn_dynamic_internal(world, scene_handle, &mut entity_map)?;
         let instance_id = InstanceId::new();
-        self.spawned_instances
-            .insert(instance_id, InstanceInfo { entity_map });
+        self.spawn_dynamic_internal(world, scene_handle, instance_id)?;
         let spawned = self
             .spawned_dynamic_scenes
             .entry(scene_handle.clone())
@@ -133,21 +153,148 @@ impl SceneSpawner {
     }
 
     fn spawn_dynamic_internal(
+        &mut self,
         world: &mut World,
         scene_handle: &Handle<DynamicScene>,
-        entity_map: &mut EntityMap,
+        instance_id: InstanceId,
     ) -> Result<(), SceneSpawnError> {
         world.resource_scope(|world, scenes: Mut<Assets<DynamicScene>>| {
-            let scene =
-                scenes
-                    .get(scene_handle)
-                    .ok_or_else(|| SceneSpawnError::NonExistentScene {
-                        handle: scene_handle.clone_weak(),
-                    })?;
-            scene.write_to_world(world, entity_map)
+            // Nothing is written to the world until all the nested scenes are loaded, so that
+            // spawning the scene can be retried later.
+            Self::check_nested_scenes(&scenes, scene_handle, &mut Vec::new())?;
+
+            let mut instance_info = self
+                .spawned_instances
+                .remove(&instance_id)
+                .unwrap_or_default();
+            let result =
+                self.write_dynamic_instance(world, &scenes, scene_handle, &mut instance_info);
+            self.spawned_instances.insert(instance_id, instance_info);
+            result
         })
     }
 
+    fn check_nested_scenes(
+        scenes: &Assets<DynamicScene>,
+        scene_handle: &Handle<DynamicScene>,
+        ancestors: &mut Vec<HandleId>,
+    ) -> Result<(), SceneSpawnError> {
+        let scene = scenes
+            .get(scene_handle)
+            .ok_or_else(|| SceneSpawnError::NonExistentScene {
+                handle: scene_handle.clone_weak(),
+            })?;
+
+        ancestors.push(scene_handle.id());
+        for instance in &scene.instances {
+            let nested_handle = nested_scene_handle(instance);
+            if ancestors.contains(&nested_handle.id()) {
+                return Err(SceneSpawnError::RecursiveInstance {
+                    path: instance.scene.to_string(),
+                });
+            }
+            Self::check_nested_scenes(scenes, &nested_handle, ancestors)?;
+        }
+        ancestors.pop();
+        Ok(())
+    }
+
+    /// Write the scene to the world as the given instance, then write its nested instances and
+    /// apply their overrides.
+    fn write_dynamic_instance(
+        &mut self,
+        world: &mut World,
+        scenes: &Assets<DynamicScene>,
+        scene_handle: &Handle<DynamicScene>,
+        instance_info: &mut InstanceInfo,
+    ) -> Result<(), SceneSpawnError> {
+        let scene = scenes
+            .get(scene_handle)
+            .ok_or_else(|| SceneSpawnError::NonExistentScene {
+                handle: scene_handle.clone_weak(),
+            })?;
+        let type_registry = world.resource::<AppTypeRegistry>().clone();
+
+        scene.write_to_world_with(world, &mut instance_info.entity_map, &type_registry)?;
+        for scene_override in &instance_info.overrides {
+            scene_override.apply(world, &instance_info.entity_map, &type_registry.read())?;
+        }
+        // Reapplied on every write, as a reloaded scene may have new root entities
+        if let Some(parent) = instance_info.parent {
+            let entities: Vec<Entity> = instance_info.entity_map.values().collect();
+            for entity in entities {
+                // Only the root entities of the scene don't have a parent yet
+                if !world
+                    .get_entity(entity)
+                    .map(|entity| entity.contains::<Parent>())
+                    .unwrap_or(true)
+                {
+                    AddChild {
+                        parent,
+                        child: entity,
+                    }
+                    .write(world);
+                }
+            }
+        }
+
+        // The scene may have lost some of its nested instances when it was reloaded
+        while instance_info.nested_instances.len() > scene.instances.len() {
+            if let Some(nested_instance_id) = instance_info.nested_instances.pop() {
+                self.despawn_instance_sync(world, &nested_instance_id);
+            }
+        }
+
+        for (index, instance) in scene.instances.iter().enumerate() {
+            let nested_handle = nested_scene_handle(instance);
+            let nested_instance_id = match instance_info.nested_instances.get(index) {
+                Some(nested_instance_id) => *nested_instance_id,
+                None => {
+                    let nested_instance_id = InstanceId::new();
+                    instance_info.nested_instances.push(nested_instance_id);
+                    self.spawned_dynamic_scenes
+                        .entry(nested_handle.clone())
+                        .or_default()
+                        .push(nested_instance_id);
+                    nested_instance_id
+                }
+            };
+
+            let parent = instance
+                .parent
+                .map(|parent| {
+                    instance_info
+                        .entity_map
+                        .get(Entity::from_raw(parent))
+                        .map_err(|_| SceneSpawnError::NonExistentInstanceParent {
+                            path: instance.scene.to_string(),
+                            entity: parent,
+                        })
+                })
+                .transpose()?;
+
+            // The overrides and the parent are kept with the nested instance, so that they are
+            // applied again when only the nested scene is reloaded.
+            let mut nested_instance_info = self
+                .spawned_instances
+                .remove(&nested_instance_id)
+                .unwrap_or_default();
+            nested_instance_info.overrides = instance.overrides.clone();
+            nested_instance_info.parent = parent;
+            let result = self.write_dynamic_instance(
+                world,
+                scenes,
+                &nested_handle,
+                &mut nested_instance_info,
+            );
+            self.spawned_instances
+                .insert(nested_instance_id, nested_instance_info);
+            result?;
+        }
+
+        Ok(())
+    }
+
     pub fn spawn_sync(
         &mut self,
         world: &mut World,
@@ -190,13 +337,9 @@ impl SceneSpawner {
     ) -> Result<(), SceneSpawnError> {
         for scene_handle in scene_handles {
             if let Some(spawned_instances) = self.spawned_dynamic_scenes.get(scene_handle) {
-                for instance_id in spawned_instances {
-                    if let Some(instance_info) = self.spawned_instances.get_mut(instance_id) {
-                        Self::spawn_dynamic_internal(
-                            world,
-                            scene_handle,
-                            &mut instance_info.entity_map,
-                        )?;
+                for instance_id in spawned_instances.clone() {
+                    if self.spawned_instances.contains_key(&instance_id) {
+                        self.spawn_dynamic_internal(world, scene_handle, instance_id)?;
                     }
                 }
             }
@@ -225,12 +368,8 @@ impl SceneSpawner {
         let scenes_to_spawn = std::mem::take(&mut self.dynamic_scenes_to_spawn);
 
         for (scene_handle, instance_id) in scenes_to_spawn {
-            let mut entity_map = EntityMap::default();
-
-            match Self::spawn_dynamic_internal(world, &scene_handle, &mut entity_map) {
+            match self.spawn_dynamic_internal(world, &scene_handle, instance_id) {
                 Ok(_) => {
-                    self.spawned_instances
-                        .insert(instance_id, InstanceInfo { entity_map });
                     let spawned = self
                         .spawned_dynamic_scenes
                         .entry(scene_handle.clone())
@@ -264,8 +403,8 @@ impl SceneSpawner {
         let scenes_with_parent = std::mem::take(&mut self.scenes_with_parent);
 
         for (instance_id, parent) in scenes_with_parent {
-            if let Some(instance) = self.spawned_instances.get(&instance_id) {
-                for entity in instance.entity_map.values() {
+            if self.spawned_instances.contains_key(&instance_id) {
+                for entity in self.iter_instance_entities(instance_id) {
                     // Add the `Parent` component to the scene root, and update the `Children` component of
                     // the scene parent
                     if !world
@@ -295,7 +434,8 @@ impl SceneSpawner {
         self.spawned_instances.contains_key(&instance_id)
     }
 
-    /// Get an iterator over the entities in an instance, once it's spawned.
+    /// Get an iterator over the entities in an instance, once it's spawned, including the
+    /// entities of its nested instances.
     ///
     /// Before the scene is spawned, the iterator will be empty. Use [`Self::instance_is_ready`]
     /// to check if the instance is ready.
@@ -303,14 +443,31 @@ impl SceneSpawner {
         &'_ self,
         instance_id: InstanceId,
     ) -> impl Iterator<Item = Entity> + '_ {
-        self.spawned_instances
-            .get(&instance_id)
-            .map(|instance| instance.entity_map.values())
-            .into_iter()
-            .flatten()
+        let mut entities = Vec::new();
+        self.collect_instance_entities(instance_id, &mut entities);
+        entities.into_iter()
+    }
+
+    fn collect_instance_entities(&self, instance_id: InstanceId, entities: &mut Vec<Entity>) {
+        if let Some(instance) = self.spawned_instances.get(&instance_id) {
+            entities.extend(instance.entity_map.values());
+            for nested_instance_id in &instance.nested_instances {
+                self.collect_instance_entities(*nested_instance_id, entities);
+            }
+        }
+    }
+
+    /// Get the informations about an instance, once it's spawned.
+    pub fn instance_info(&self, instance_id: InstanceId) -> Option<&InstanceInfo> {
+        self.spawned_instances.get(&instance_id)
     }
 }
 
+/// A weak handle to the scene nested in `instance`, which is found by the asset path.
+fn nested_scene_handle(instance: &DynamicSceneInstance) -> Handle<DynamicScene> {
+    Handle::weak(HandleId::from(instance.scene.clone()))
+}
+
 pub fn scene_spawner_system(world: &mut World) {
     world.resource_scope(|world, mut scene_spawner: Mut<SceneSpawner>| {
         let scene_asset_events = world.resource::<Events<AssetEvent<DynamicScene>>>();
@@ -339,3 +496,216 @@ pub fn scene_spawner_system(world: &mut World) {
         scene_spawner.set_scene_instance_parent_sync(world);
     });
 }
+
+#[cfg(test)]
+mod tests {
+    use bevy_app::App;
+    use bevy_asset::{AddAsset, AssetPlugin, Assets, Handle, HandleId};
+    use bevy_ecs::{
+        component::Component,
+        reflect::{ReflectComponent, ReflectResource},
+        system::Resource,
+        world::{Mut, World},
+    };
+    use bevy_hierarchy::Parent;
+    use bevy_reflect::Reflect;
+
+    use crate::{
+        DynamicEntity, DynamicScene, DynamicSceneInstance, SceneOverride, SceneSpawnError,
+        SceneSpawner,
+    };
+
+    #[derive(Component, Reflect, Default)]
+    #[reflect(Component)]
+    struct Tree {
+        height: f32,
+        scale: f32,
+    }
+
+    #[derive(Resource, Reflect, Default)]
+    #[reflect(Resource)]
+    struct Forest {
+        trees: u32,
+    }
+
+    fn create_app() -> App {
+        let mut app = App::new();
+        app.add_plugin(AssetPlugin::default())
+            .add_asset::<DynamicScene>()
+            .init_resource::<SceneSpawner>()
+            .register_type::<Tree>()
+            .register_type::<Forest>();
+        app
+    }
+
+    fn tree_scene(height: f32) -> DynamicScene {
+        DynamicScene {
+            entities: vec![DynamicEntity {
+                entity: 0,
+                components: vec![Box::new(Tree { height, scale: 1.0 })],
+            }],
+            ..Default::default()
+        }
+    }
+
+    /// A level with a root entity and two trees as its children, the second one being scaled up.
+    fn level_scene() -> DynamicScene {
+        let tree = |overrides| DynamicSceneInstance {
+            scene: "tree.scn.ron".into(),
+            parent: Some(0),
+            overrides,
+        };
+        DynamicScene {
+            entities: vec![DynamicEntity {
+                entity: 0,
+                components: Vec::new(),
+            }],
+            instances: vec![
+                tree(Vec::new()),
+                tree(vec![SceneOverride {
+                    entity: 0,
+                    component: std::any::type_name::<Tree>().to_string(),
+                    path: "scale".to_string(),
+                    value: Box::new(2.0f32),
+                }]),
+            ],
+            ..Default::default()
+        }
+    }
+
+    fn trees(world: &mut World) -> Vec<(f32, f32)> {
+        let mut trees: Vec<_> = world
+            .query::<(&Tree, &Parent)>()
+            .iter(world)
+            .map(|(tree, _)| (tree.height, tree.scale))
+            .collect();
+        trees.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        trees
+    }
+
+    #[test]
+    fn spawn_nested_instances() {
+        let mut app = create_app();
+        let world = &mut app.world;
+
+        let mut scenes = world.resource_mut::<Assets<DynamicScene>>();
+        scenes.set_untracked("tree.scn.ron", tree_scene(3.0));
+        let level = scenes.add(level_scene());
+
+        world.resource_scope(|world, mut scene_spawner: Mut<SceneSpawner>| {
+            scene_spawner.spawn_dynamic_sync(world, &level).unwrap();
+        });
+        assert_eq!(trees(world), [(3.0, 1.0), (3.0, 2.0)]);
+        assert_eq!(world.entities().len(), 3);
+
+        // Reloading the tree keeps the scale of the second instance
+        world
+            .resource_mut::<Assets<DynamicScene>>()
+            .set_untracked("tree.scn.ron", tree_scene(5.0));
+        world.resource_scope(|world, mut scene_spawner: Mut<SceneSpawner>| {
+            let tree = Handle::weak(HandleId::from("tree.scn.ron"));
+            scene_spawner.update_spawned_scenes(world, &[tree]).unwrap();
+        });
+        assert_eq!(trees(world), [(5.0, 1.0), (5.0, 2.0)]);
+        assert_eq!(world.entities().len(), 3);
+
+        // New root entities of a reloaded tree are parented like the others
+        let mut tree = tree_scene(5.0);
+        tree.entities.push(DynamicEntity {
+            entity: 1,
+            components: vec![Box::new(Tree {
+                height: 1.0,
+                scale: 1.0,
+            })],
+        });
+        world
+            .resource_mut::<Assets<DynamicScene>>()
+            .set_untracked("tree.scn.ron", tree);
+        world.resource_scope(|world, mut scene_spawner: Mut<SceneSpawner>| {
+            let tree = Handle::weak(HandleId::from("tree.scn.ron"));
+            scene_spawner.update_spawned_scenes(world, &[tree]).unwrap();
+        });
+        assert_eq!(
+            trees(world),
+            [(1.0, 1.0), (1.0, 1.0), (5.0, 1.0), (5.0, 2.0)]
+        );
+        assert_eq!(world.entities().len(), 5);
+
+        // Despawning the level despawns the trees as well
+        world.resource_scope(|world, mut scene_spawner: Mut<SceneSpawner>| {
+            scene_spawner.despawn_sync(world, level).unwrap();
+        });
+        assert_eq!(world.entities().len(), 0);
+    }
+
+    #[test]
+    fn spawn_keeps_live_resources() {
+        let mut app = create_app();
+        let world = &mut app.world;
+
+        let mut tree = tree_scene(3.0);
+        tree.resources.push(Box::new(Forest { trees: 1 }));
+        let mut scenes = world.resource_mut::<Assets<DynamicScene>>();
+        scenes.set_untracked("tree.scn.ron", tree);
+        let tree = Handle::weak(HandleId::from("tree.scn.ron"));
+        let level = scenes.add(level_scene());
+
+        // The first spawn inserts the missing resource
+        world.resource_scope(|world, mut scene_spawner: Mut<SceneSpawner>| {
+            scene_spawner.spawn_dynamic_sync(world, &tree).unwrap();
+        });
+        assert_eq!(world.resource::<Forest>().trees, 1);
+
+        // Later spawns, also as nested instances, keep its live value
+        world.resource_mut::<Forest>().trees = 10;
+        world.resource_scope(|world, mut scene_spawner: Mut<SceneSpawner>| {
+            scene_spawner.spawn_dynamic_sync(world, &tree).unwrap();
+            scene_spawner.spawn_dynamic_sync(world, &level).unwrap();
+        });
+        assert_eq!(world.resource::<Forest>().trees, 10);
+        assert_eq!(world.query::<&Tree>().iter(world).count(), 4);
+    }
+
+    #[test]
+    fn spawn_waits_for_nested_scenes() {
+        let mut app = create_app();
+        let world = &mut app.world;
+
+        let level = world
+            .resource_mut::<Assets<DynamicScene>>()
+            .add(level_scene());
+        world.resource_scope(|world, mut scene_spawner: Mut<SceneSpawner>| {
+            let result = scene_spawner.spawn_dynamic_sync(world, &level);
+            assert!(matches!(
+                result,
+                Err(SceneSpawnError::NonExistentScene { .. })
+            ));
+        });
+        assert_eq!(world.entities().len(), 0);
+    }
+
+    #[test]
+    fn spawn_recursive_instance() {
+        let mut app = create_app();
+        let world = &mut app.world;
+
+        let mut scene = tree_scene(3.0);
+        scene.instances.push(DynamicSceneInstance {
+            scene: "tree.scn.ron".into(),
+            parent: None,
+            overrides: Vec::new(),
+        });
+        world
+            .resource_mut::<Assets<DynamicScene>>()
+            .set_untracked("tree.scn.ron", scene);
+
+        world.resource_scope(|world, mut scene_spawner: Mut<SceneSpawner>| {
+            let tree = Handle::weak(HandleId::from("tree.scn.ron"));
+            let result = scene_spawner.spawn_dynamic_sync(world, &tree);
+            assert!(matches!(
+                result,
+                Err(SceneSpawnError::RecursiveInstance { .. })
+            ));
+        });
+    }
+}
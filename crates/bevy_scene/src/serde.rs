@@ -1,9 +1,19 @@
-use crate::{DynamicEntity, DynamicScene};
+use crate::{DynamicEntity, DynamicScene, DynamicSceneInstance, SceneOverride};
 use anyhow::Result;
-use bevy_reflect::serde::{TypedReflectDeserializer, TypedReflectSerializer};
+use bevy_asset::AssetPath;
+#[cfg(feature = "json_schema")]
+use bevy_ecs::reflect::{ReflectComponent, ReflectResource};
+#[cfg(feature = "json_schema")]
+use bevy_reflect::serde::{json_schema_defs, json_schema_ref, JSON_SCHEMA_DIALECT};
+use bevy_reflect::serde::{
+    BinaryReflectDeserializer, BinaryReflectSerializer, ReflectSerializer, TypeManifest,
+    TypedReflectDeserializer, TypedReflectSerializer,
+};
+#[cfg(feature = "json_schema")]
+use bevy_reflect::TypeRegistration;
 use bevy_reflect::{serde::UntypedReflectDeserializer, Reflect, TypeRegistry, TypeRegistryArc};
 use bevy_utils::HashSet;
-use serde::ser::SerializeMap;
+use serde::ser::{SerializeMap, SerializeSeq};
 use serde::{
     de::{DeserializeSeed, Error, MapAccess, SeqAccess, Visitor},
     ser::SerializeStruct,
@@ -12,11 +22,127 @@ use serde::{
 use std::fmt::Formatter;
 
 pub const SCENE_STRUCT: &str = "Scene";
+pub const SCENE_RESOURCES: &str = "resources";
 pub const SCENE_ENTITIES: &str = "entities";
+pub const SCENE_INSTANCES: &str = "instances";
 
 pub const ENTITY_STRUCT: &str = "Entity";
 pub const ENTITY_FIELD_COMPONENTS: &str = "components";
 
+pub const INSTANCE_STRUCT: &str = "Instance";
+pub const INSTANCE_FIELD_SCENE: &str = "scene";
+pub const INSTANCE_FIELD_PARENT: &str = "parent";
+pub const INSTANCE_FIELD_OVERRIDES: &str = "overrides";
+
+pub const OVERRIDE_STRUCT: &str = "Override";
+pub const OVERRIDE_FIELD_ENTITY: &str = "entity";
+pub const OVERRIDE_FIELD_COMPONENT: &str = "component";
+pub const OVERRIDE_FIELD_PATH: &str = "path";
+pub const OVERRIDE_FIELD_VALUE: &str = "value";
+
+/// Returns a JSON Schema document validating serialized [`DynamicScene`] files, such as the
+/// `.scn.ron` files written with [`DynamicScene::serialize_ron`].
+///
+/// The resources of the scene are the registered types with [`ReflectResource`] type data, the
+/// components of the entities are the registered types with [`ReflectComponent`] type data,
+/// and every registered type is defined in the `$defs` of the document. The values of the
+/// overrides of nested scene instances can be of any registered type.
+#[cfg(feature = "json_schema")]
+pub fn scene_json_schema(registry: &TypeRegistry) -> serde_json::Value {
+    let refs = |has_data: fn(&TypeRegistration) -> bool| {
+        registry
+            .iter()
+            .filter(|registration| has_data(registration))
+            .map(|registration| {
+                (
+                    registration.type_name().to_string(),
+                    json_schema_ref(registration.type_name()),
+                )
+            })
+            .collect::<serde_json::Map<String, serde_json::Value>>()
+    };
+    let resources = refs(|registration| registration.data::<ReflectResource>().is_some());
+    let components = refs(|registration| registration.data::<ReflectComponent>().is_some());
+    let values = refs(|_| true);
+
+    serde_json::json!({
+        "$schema": JSON_SCHEMA_DIALECT,
+        "title": SCENE_STRUCT,
+        "type": "object",
+        "properties": {
+            SCENE_RESOURCES: {
+                "type": "object",
+                "properties": resources,
+                "additionalProperties": false,
+            },
+            SCENE_ENTITIES: {
+                "type": "object",
+                "propertyNames": { "pattern": "^[0-9]+$" },
+                "additionalProperties": {
+                    "title": ENTITY_STRUCT,
+                    "type": "object",
+                    "properties": {
+                        ENTITY_FIELD_COMPONENTS: {
+                            "type": "object",
+                            "properties": components,
+                            "additionalProperties": false,
+                        },
+                    },
+                    "required": [ENTITY_FIELD_COMPONENTS],
+                    "additionalProperties": false,
+                },
+            },
+            SCENE_INSTANCES: {
+                "type": "array",
+                "items": {
+                    "title": INSTANCE_STRUCT,
+                    "type": "object",
+                    "properties": {
+                        INSTANCE_FIELD_SCENE: { "type": "string" },
+                        INSTANCE_FIELD_PARENT: { "type": ["integer", "null"], "minimum": 0 },
+                        INSTANCE_FIELD_OVERRIDES: {
+                            "type": "array",
+                            "items": {
+                                "title": OVERRIDE_STRUCT,
+                                "type": "object",
+                                "properties": {
+                                    OVERRIDE_FIELD_ENTITY: { "type": "integer", "minimum": 0 },
+                                    OVERRIDE_FIELD_COMPONENT: { "type": "string" },
+                                    OVERRIDE_FIELD_PATH: { "type": "string" },
+                                    OVERRIDE_FIELD_VALUE: {
+                                        "type": "object",
+                                        "properties": values,
+                                        "additionalProperties": false,
+                                        "minProperties": 1,
+                                        "maxProperties": 1,
+                                    },
+                                },
+                                "required": [
+                                    OVERRIDE_FIELD_ENTITY,
+                                    OVERRIDE_FIELD_COMPONENT,
+                                    OVERRIDE_FIELD_VALUE,
+                                ],
+                                "additionalProperties": false,
+                            },
+                        },
+                    },
+                    "required": [INSTANCE_FIELD_SCENE],
+                    "additionalProperties": false,
+                },
+            },
+        },
+        "required": [SCENE_ENTITIES],
+        "additionalProperties": false,
+        "$defs": json_schema_defs(registry),
+    })
+}
+
+/// A serializer for [`DynamicScene`]s, which writes component types by name.
+///
+/// Formats that are not self-describing, like `bincode` or `postcard`, get the layout of
+/// scenes without resources and nested instances, so that scenes written before they existed
+/// still load. Serializing a scene with resources or instances fails in those formats: use
+/// [`BinarySceneSerializer`] for them instead.
 pub struct SceneSerializer<'a> {
     pub scene: &'a DynamicScene,
     pub registry: &'a TypeRegistryArc,
@@ -33,7 +159,31 @@ impl<'a> Serialize for SceneSerializer<'a> {
     where
         S: serde::Serializer,
     {
-        let mut state = serializer.serialize_struct(SCENE_STRUCT, 1)?;
+        if !serializer.is_human_readable() {
+            if !self.scene.resources.is_empty() || !self.scene.instances.is_empty() {
+                return Err(serde::ser::Error::custom(
+                    "scenes with resources or instances need `BinarySceneSerializer` in formats that are not self-describing",
+                ));
+            }
+            let mut state = serializer.serialize_struct(SCENE_STRUCT, 1)?;
+            state.serialize_field(
+                SCENE_ENTITIES,
+                &EntitiesSerializer {
+                    entities: &self.scene.entities,
+                    registry: self.registry,
+                },
+            )?;
+            return state.end();
+        }
+
+        let mut state = serializer.serialize_struct(SCENE_STRUCT, 3)?;
+        state.serialize_field(
+            SCENE_RESOURCES,
+            &ComponentsSerializer {
+                components: &self.scene.resources,
+                registry: self.registry,
+            },
+        )?;
         state.serialize_field(
             SCENE_ENTITIES,
             &EntitiesSerializer {
@@ -41,6 +191,14 @@ impl<'a> Serialize for SceneSerializer<'a> {
                 registry: self.registry,
             },
         )?;
+        state.serialize_field(
+            SCENE_INSTANCES,
+            &InstancesSerializer {
+                instances: &self.scene.instances,
+                manifest: None,
+                registry: &self.registry.read(),
+            },
+        )?;
         state.end()
     }
 }
@@ -115,7 +273,9 @@ impl<'a> Serialize for ComponentsSerializer<'a> {
 #[derive(Deserialize)]
 #[serde(field_identifier, rename_all = "lowercase")]
 enum SceneField {
+    Resources,
     Entities,
+    Instances,
 }
 
 #[derive(Deserialize)]
@@ -135,9 +295,15 @@ impl<'a, 'de> DeserializeSeed<'de> for SceneDeserializer<'a> {
     where
         D: serde::Deserializer<'de>,
     {
+        // formats that are not self-describing only have the entities, see `SceneSerializer`
+        let fields: &'static [&'static str] = if deserializer.is_human_readable() {
+            &[SCENE_RESOURCES, SCENE_ENTITIES, SCENE_INSTANCES]
+        } else {
+            &[SCENE_ENTITIES]
+        };
         deserializer.deserialize_struct(
             SCENE_STRUCT,
-            &[SCENE_ENTITIES],
+            fields,
             SceneVisitor {
                 type_registry: self.type_registry,
             },
@@ -160,9 +326,19 @@ impl<'a, 'de> Visitor<'de> for SceneVisitor<'a> {
     where
         A: MapAccess<'de>,
     {
+        let mut resources = None;
         let mut entities = None;
+        let mut instances = None;
         while let Some(key) = map.next_key()? {
             match key {
+                SceneField::Resources => {
+                    if resources.is_some() {
+                        return Err(Error::duplicate_field(SCENE_RESOURCES));
+                    }
+                    resources = Some(map.next_value_seed(ComponentDeserializer {
+                        registry: self.type_registry,
+                    })?);
+                }
                 SceneField::Entities => {
                     if entities.is_some() {
                         return Err(Error::duplicate_field(SCENE_ENTITIES));
@@ -171,66 +347,864 @@ impl<'a, 'de> Visitor<'de> for SceneVisitor<'a> {
                         type_registry: self.type_registry,
                     })?);
                 }
+                SceneField::Instances => {
+                    if instances.is_some() {
+                        return Err(Error::duplicate_field(SCENE_INSTANCES));
+                    }
+                    instances = Some(map.next_value_seed(InstancesDeserializer {
+                        manifest: None,
+                        registry: self.type_registry,
+                    })?);
+                }
             }
         }
 
+        // scenes written before resources and instances were supported don't have any
+        let resources = resources.unwrap_or_default();
         let entities = entities.ok_or_else(|| Error::missing_field(SCENE_ENTITIES))?;
+        let instances = instances.unwrap_or_default();
+
+        Ok(DynamicScene {
+            resources,
+            entities,
+            instances,
+        })
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        let entities = seq
+            .next_element_seed(SceneEntitiesDeserializer {
+                type_registry: self.type_registry,
+            })?
+            .ok_or_else(|| Error::missing_field(SCENE_ENTITIES))?;
+
+        Ok(DynamicScene {
+            resources: Vec::new(),
+            entities,
+            instances: Vec::new(),
+        })
+    }
+}
+
+pub struct SceneEntitiesDeserializer<'a> {
+    pub type_registry: &'a TypeRegistry,
+}
+
+impl<'a, 'de> DeserializeSeed<'de> for SceneEntitiesDeserializer<'a> {
+    type Value = Vec<DynamicEntity>;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_map(SceneEntitiesVisitor {
+            type_registry: self.type_registry,
+        })
+    }
+}
+
+struct SceneEntitiesVisitor<'a> {
+    pub type_registry: &'a TypeRegistry,
+}
+
+impl<'a, 'de> Visitor<'de> for SceneEntitiesVisitor<'a> {
+    type Value = Vec<DynamicEntity>;
+
+    fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+        formatter.write_str("map of entities")
+    }
+
+    fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        let mut entities = Vec::new();
+        while let Some(id) = map.next_key::<u32>()? {
+            let entity = map.next_value_seed(SceneEntityDeserializer {
+                id,
+                type_registry: self.type_registry,
+            })?;
+            entities.push(entity);
+        }
+
+        Ok(entities)
+    }
+}
+
+pub struct SceneEntityDeserializer<'a> {
+    pub id: u32,
+    pub type_registry: &'a TypeRegistry,
+}
+
+impl<'a, 'de> DeserializeSeed<'de> for SceneEntityDeserializer<'a> {
+    type Value = DynamicEntity;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        deserializer.deserialize_struct(
+            ENTITY_STRUCT,
+            &[ENTITY_FIELD_COMPONENTS],
+            SceneEntityVisitor {
+                id: self.id,
+                registry: self.type_registry,
+            },
+        )
+    }
+}
+
+struct SceneEntityVisitor<'a> {
+    pub id: u32,
+    pub registry: &'a TypeRegistry,
+}
+
+impl<'a, 'de> Visitor<'de> for SceneEntityVisitor<'a> {
+    type Value = DynamicEntity;
+
+    fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+        formatter.write_str("entities")
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> std::result::Result<Self::Value, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        let components = seq
+            .next_element_seed(ComponentDeserializer {
+                registry: self.registry,
+            })?
+            .ok_or_else(|| Error::missing_field(ENTITY_FIELD_COMPONENTS))?;
+
+        Ok(DynamicEntity {
+            entity: self.id,
+            components,
+        })
+    }
+
+    fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        let mut components = None;
+        while let Some(key) = map.next_key()? {
+            match key {
+                EntityField::Components => {
+                    if components.is_some() {
+                        return Err(Error::duplicate_field(ENTITY_FIELD_COMPONENTS));
+                    }
+
+                    components = Some(map.next_value_seed(ComponentDeserializer {
+                        registry: self.registry,
+                    })?);
+                }
+            }
+        }
+
+        let components = components
+            .take()
+            .ok_or_else(|| Error::missing_field(ENTITY_FIELD_COMPONENTS))?;
+        Ok(DynamicEntity {
+            entity: self.id,
+            components,
+        })
+    }
+}
+
+pub struct ComponentDeserializer<'a> {
+    pub registry: &'a TypeRegistry,
+}
+
+impl<'a, 'de> DeserializeSeed<'de> for ComponentDeserializer<'a> {
+    type Value = Vec<Box<dyn Reflect>>;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        deserializer.deserialize_map(ComponentVisitor {
+            registry: self.registry,
+        })
+    }
+}
+
+struct ComponentVisitor<'a> {
+    pub registry: &'a TypeRegistry,
+}
+
+impl<'a, 'de> Visitor<'de> for ComponentVisitor<'a> {
+    type Value = Vec<Box<dyn Reflect>>;
+
+    fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+        formatter.write_str("map of components")
+    }
+
+    fn visit_map<A>(self, mut map: A) -> std::result::Result<Self::Value, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        let mut added = HashSet::new();
+        let mut components = Vec::new();
+        while let Some(key) = map.next_key::<&str>()? {
+            if !added.insert(key) {
+                return Err(Error::custom(format!("duplicate entry: `{key}`")));
+            }
+
+            let registration = self
+                .registry
+                .get_with_name(key)
+                .ok_or_else(|| Error::custom(format!("no registration found for `{key}`")))?;
+            components.push(
+                map.next_value_seed(TypedReflectDeserializer::new(registration, self.registry))?,
+            );
+        }
+
+        Ok(components)
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        let mut dynamic_properties = Vec::new();
+        while let Some(entity) =
+            seq.next_element_seed(UntypedReflectDeserializer::new(self.registry))?
+        {
+            dynamic_properties.push(entity);
+        }
+
+        Ok(dynamic_properties)
+    }
+}
+
+/// Writes the nested instances of a scene. Override values are written with their type name,
+/// or with their id in `manifest` when there is one.
+struct InstancesSerializer<'a> {
+    instances: &'a [DynamicSceneInstance],
+    manifest: Option<&'a TypeManifest>,
+    registry: &'a TypeRegistry,
+}
+
+impl<'a> Serialize for InstancesSerializer<'a> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut state = serializer.serialize_seq(Some(self.instances.len()))?;
+        for instance in self.instances {
+            state.serialize_element(&InstanceSerializer {
+                instance,
+                manifest: self.manifest,
+                registry: self.registry,
+            })?;
+        }
+        state.end()
+    }
+}
+
+struct InstanceSerializer<'a> {
+    instance: &'a DynamicSceneInstance,
+    manifest: Option<&'a TypeManifest>,
+    registry: &'a TypeRegistry,
+}
+
+impl<'a> Serialize for InstanceSerializer<'a> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut state = serializer.serialize_struct(INSTANCE_STRUCT, 3)?;
+        state.serialize_field(INSTANCE_FIELD_SCENE, &self.instance.scene.to_string())?;
+        state.serialize_field(INSTANCE_FIELD_PARENT, &self.instance.parent)?;
+        state.serialize_field(
+            INSTANCE_FIELD_OVERRIDES,
+            &OverridesSerializer {
+                overrides: &self.instance.overrides,
+                manifest: self.manifest,
+                registry: self.registry,
+            },
+        )?;
+        state.end()
+    }
+}
+
+struct OverridesSerializer<'a> {
+    overrides: &'a [SceneOverride],
+    manifest: Option<&'a TypeManifest>,
+    registry: &'a TypeRegistry,
+}
+
+impl<'a> Serialize for OverridesSerializer<'a> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut state = serializer.serialize_seq(Some(self.overrides.len()))?;
+        for scene_override in self.overrides {
+            state.serialize_element(&OverrideSerializer {
+                scene_override,
+                manifest: self.manifest,
+                registry: self.registry,
+            })?;
+        }
+        state.end()
+    }
+}
+
+struct OverrideSerializer<'a> {
+    scene_override: &'a SceneOverride,
+    manifest: Option<&'a TypeManifest>,
+    registry: &'a TypeRegistry,
+}
+
+impl<'a> Serialize for OverrideSerializer<'a> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut state = serializer.serialize_struct(OVERRIDE_STRUCT, 4)?;
+        state.serialize_field(OVERRIDE_FIELD_ENTITY, &self.scene_override.entity)?;
+        state.serialize_field(OVERRIDE_FIELD_COMPONENT, &self.scene_override.component)?;
+        state.serialize_field(OVERRIDE_FIELD_PATH, &self.scene_override.path)?;
+        let value = &*self.scene_override.value;
+        match self.manifest {
+            Some(manifest) => state.serialize_field(
+                OVERRIDE_FIELD_VALUE,
+                &BinaryReflectSerializer::new(value, manifest, self.registry),
+            )?,
+            None => state.serialize_field(
+                OVERRIDE_FIELD_VALUE,
+                &ReflectSerializer::new(value, self.registry),
+            )?,
+        }
+        state.end()
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(field_identifier, rename_all = "lowercase")]
+enum InstanceField {
+    Scene,
+    Parent,
+    Overrides,
+}
+
+#[derive(Deserialize)]
+#[serde(field_identifier, rename_all = "lowercase")]
+enum OverrideField {
+    Entity,
+    Component,
+    Path,
+    Value,
+}
+
+/// Reads the nested instances written by [`InstancesSerializer`].
+struct InstancesDeserializer<'a> {
+    manifest: Option<&'a TypeManifest>,
+    registry: &'a TypeRegistry,
+}
+
+impl<'a, 'de> DeserializeSeed<'de> for InstancesDeserializer<'a> {
+    type Value = Vec<DynamicSceneInstance>;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_seq(self)
+    }
+}
+
+impl<'a, 'de> Visitor<'de> for InstancesDeserializer<'a> {
+    type Value = Vec<DynamicSceneInstance>;
+
+    fn expecting(&self, formatter: &mut Formatter) -> std::fmt::Result {
+        formatter.write_str("sequence of scene instances")
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        let mut instances = Vec::new();
+        while let Some(instance) = seq.next_element_seed(InstanceDeserializer {
+            manifest: self.manifest,
+            registry: self.registry,
+        })? {
+            instances.push(instance);
+        }
+
+        Ok(instances)
+    }
+}
+
+struct InstanceDeserializer<'a> {
+    manifest: Option<&'a TypeManifest>,
+    registry: &'a TypeRegistry,
+}
+
+impl<'a, 'de> DeserializeSeed<'de> for InstanceDeserializer<'a> {
+    type Value = DynamicSceneInstance;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_struct(
+            INSTANCE_STRUCT,
+            &[
+                INSTANCE_FIELD_SCENE,
+                INSTANCE_FIELD_PARENT,
+                INSTANCE_FIELD_OVERRIDES,
+            ],
+            self,
+        )
+    }
+}
+
+impl<'a, 'de> Visitor<'de> for InstanceDeserializer<'a> {
+    type Value = DynamicSceneInstance;
+
+    fn expecting(&self, formatter: &mut Formatter) -> std::fmt::Result {
+        formatter.write_str("scene instance struct")
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        let scene = seq
+            .next_element::<String>()?
+            .ok_or_else(|| Error::missing_field(INSTANCE_FIELD_SCENE))?;
+        let parent = seq
+            .next_element::<Option<u32>>()?
+            .ok_or_else(|| Error::missing_field(INSTANCE_FIELD_PARENT))?;
+        let overrides = seq
+            .next_element_seed(OverridesDeserializer {
+                manifest: self.manifest,
+                registry: self.registry,
+            })?
+            .ok_or_else(|| Error::missing_field(INSTANCE_FIELD_OVERRIDES))?;
+
+        Ok(DynamicSceneInstance {
+            scene: AssetPath::from(scene),
+            parent,
+            overrides,
+        })
+    }
+
+    fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        let mut scene = None;
+        let mut parent = None;
+        let mut overrides = None;
+        while let Some(key) = map.next_key()? {
+            match key {
+                InstanceField::Scene => {
+                    if scene.is_some() {
+                        return Err(Error::duplicate_field(INSTANCE_FIELD_SCENE));
+                    }
+                    scene = Some(map.next_value::<String>()?);
+                }
+                InstanceField::Parent => {
+                    if parent.is_some() {
+                        return Err(Error::duplicate_field(INSTANCE_FIELD_PARENT));
+                    }
+                    parent = Some(map.next_value::<Option<u32>>()?);
+                }
+                InstanceField::Overrides => {
+                    if overrides.is_some() {
+                        return Err(Error::duplicate_field(INSTANCE_FIELD_OVERRIDES));
+                    }
+                    overrides = Some(map.next_value_seed(OverridesDeserializer {
+                        manifest: self.manifest,
+                        registry: self.registry,
+                    })?);
+                }
+            }
+        }
+
+        let scene = scene.ok_or_else(|| Error::missing_field(INSTANCE_FIELD_SCENE))?;
+
+        Ok(DynamicSceneInstance {
+            scene: AssetPath::from(scene),
+            parent: parent.flatten(),
+            overrides: overrides.unwrap_or_default(),
+        })
+    }
+}
+
+struct OverridesDeserializer<'a> {
+    manifest: Option<&'a TypeManifest>,
+    registry: &'a TypeRegistry,
+}
+
+impl<'a, 'de> DeserializeSeed<'de> for OverridesDeserializer<'a> {
+    type Value = Vec<SceneOverride>;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_seq(self)
+    }
+}
+
+impl<'a, 'de> Visitor<'de> for OverridesDeserializer<'a> {
+    type Value = Vec<SceneOverride>;
+
+    fn expecting(&self, formatter: &mut Formatter) -> std::fmt::Result {
+        formatter.write_str("sequence of overrides")
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        let mut overrides = Vec::new();
+        while let Some(scene_override) = seq.next_element_seed(OverrideDeserializer {
+            manifest: self.manifest,
+            registry: self.registry,
+        })? {
+            overrides.push(scene_override);
+        }
+
+        Ok(overrides)
+    }
+}
+
+struct OverrideDeserializer<'a> {
+    manifest: Option<&'a TypeManifest>,
+    registry: &'a TypeRegistry,
+}
+
+impl<'a> OverrideDeserializer<'a> {
+    fn next_value<'de, A>(&self, seq: &mut A) -> Result<Option<Box<dyn Reflect>>, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        match self.manifest {
+            Some(manifest) => {
+                seq.next_element_seed(BinaryReflectDeserializer::new(manifest, self.registry))
+            }
+            None => seq.next_element_seed(UntypedReflectDeserializer::new(self.registry)),
+        }
+    }
+}
+
+impl<'a, 'de> DeserializeSeed<'de> for OverrideDeserializer<'a> {
+    type Value = SceneOverride;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_struct(
+            OVERRIDE_STRUCT,
+            &[
+                OVERRIDE_FIELD_ENTITY,
+                OVERRIDE_FIELD_COMPONENT,
+                OVERRIDE_FIELD_PATH,
+                OVERRIDE_FIELD_VALUE,
+            ],
+            self,
+        )
+    }
+}
+
+impl<'a, 'de> Visitor<'de> for OverrideDeserializer<'a> {
+    type Value = SceneOverride;
+
+    fn expecting(&self, formatter: &mut Formatter) -> std::fmt::Result {
+        formatter.write_str("override struct")
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        let entity = seq
+            .next_element::<u32>()?
+            .ok_or_else(|| Error::missing_field(OVERRIDE_FIELD_ENTITY))?;
+        let component = seq
+            .next_element::<String>()?
+            .ok_or_else(|| Error::missing_field(OVERRIDE_FIELD_COMPONENT))?;
+        let path = seq
+            .next_element::<String>()?
+            .ok_or_else(|| Error::missing_field(OVERRIDE_FIELD_PATH))?;
+        let value = self
+            .next_value(&mut seq)?
+            .ok_or_else(|| Error::missing_field(OVERRIDE_FIELD_VALUE))?;
+
+        Ok(SceneOverride {
+            entity,
+            component,
+            path,
+            value,
+        })
+    }
+
+    fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        let mut entity = None;
+        let mut component = None;
+        let mut path = None;
+        let mut value = None;
+        while let Some(key) = map.next_key()? {
+            match key {
+                OverrideField::Entity => {
+                    if entity.is_some() {
+                        return Err(Error::duplicate_field(OVERRIDE_FIELD_ENTITY));
+                    }
+                    entity = Some(map.next_value::<u32>()?);
+                }
+                OverrideField::Component => {
+                    if component.is_some() {
+                        return Err(Error::duplicate_field(OVERRIDE_FIELD_COMPONENT));
+                    }
+                    component = Some(map.next_value::<String>()?);
+                }
+                OverrideField::Path => {
+                    if path.is_some() {
+                        return Err(Error::duplicate_field(OVERRIDE_FIELD_PATH));
+                    }
+                    path = Some(map.next_value::<String>()?);
+                }
+                OverrideField::Value => {
+                    if value.is_some() {
+                        return Err(Error::duplicate_field(OVERRIDE_FIELD_VALUE));
+                    }
+                    value = Some(match self.manifest {
+                        Some(manifest) => map.next_value_seed(BinaryReflectDeserializer::new(
+                            manifest,
+                            self.registry,
+                        ))?,
+                        None => {
+                            map.next_value_seed(UntypedReflectDeserializer::new(self.registry))?
+                        }
+                    });
+                }
+            }
+        }
+
+        Ok(SceneOverride {
+            entity: entity.ok_or_else(|| Error::missing_field(OVERRIDE_FIELD_ENTITY))?,
+            component: component.ok_or_else(|| Error::missing_field(OVERRIDE_FIELD_COMPONENT))?,
+            path: path.unwrap_or_default(),
+            value: value.ok_or_else(|| Error::missing_field(OVERRIDE_FIELD_VALUE))?,
+        })
+    }
+}
+
+/// A serializer for [`DynamicScene`]s which writes component types as ids from a
+/// [`TypeManifest`] instead of their names, for compact binary formats like `bincode` or
+/// `postcard`.
+///
+/// Each entity is written as a tuple of its id and the sequence of its components, which are
+/// written with [`BinaryReflectSerializer`]. Use [`BinarySceneDeserializer`] with the same
+/// manifest to read the scene back.
+pub struct BinarySceneSerializer<'a> {
+    pub scene: &'a DynamicScene,
+    pub manifest: &'a TypeManifest,
+    pub registry: &'a TypeRegistryArc,
+}
+
+impl<'a> BinarySceneSerializer<'a> {
+    pub fn new(
+        scene: &'a DynamicScene,
+        manifest: &'a TypeManifest,
+        registry: &'a TypeRegistryArc,
+    ) -> Self {
+        BinarySceneSerializer {
+            scene,
+            manifest,
+            registry,
+        }
+    }
+}
+
+impl<'a> Serialize for BinarySceneSerializer<'a> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let registry = self.registry.read();
+        let mut state = serializer.serialize_struct(SCENE_STRUCT, 3)?;
+        state.serialize_field(
+            SCENE_RESOURCES,
+            &BinaryComponentsSerializer {
+                components: &self.scene.resources,
+                manifest: self.manifest,
+                registry: &registry,
+            },
+        )?;
+        state.serialize_field(
+            SCENE_ENTITIES,
+            &BinaryEntitiesSerializer {
+                entities: &self.scene.entities,
+                manifest: self.manifest,
+                registry: &registry,
+            },
+        )?;
+        state.serialize_field(
+            SCENE_INSTANCES,
+            &InstancesSerializer {
+                instances: &self.scene.instances,
+                manifest: Some(self.manifest),
+                registry: &registry,
+            },
+        )?;
+        state.end()
+    }
+}
+
+struct BinaryEntitiesSerializer<'a> {
+    entities: &'a [DynamicEntity],
+    manifest: &'a TypeManifest,
+    registry: &'a TypeRegistry,
+}
+
+impl<'a> Serialize for BinaryEntitiesSerializer<'a> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut state = serializer.serialize_seq(Some(self.entities.len()))?;
+        for entity in self.entities {
+            state.serialize_element(&(
+                entity.entity,
+                BinaryComponentsSerializer {
+                    components: &entity.components,
+                    manifest: self.manifest,
+                    registry: self.registry,
+                },
+            ))?;
+        }
+        state.end()
+    }
+}
+
+struct BinaryComponentsSerializer<'a> {
+    components: &'a [Box<dyn Reflect>],
+    manifest: &'a TypeManifest,
+    registry: &'a TypeRegistry,
+}
+
+impl<'a> Serialize for BinaryComponentsSerializer<'a> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut state = serializer.serialize_seq(Some(self.components.len()))?;
+        for component in self.components {
+            state.serialize_element(&BinaryReflectSerializer::new(
+                &**component,
+                self.manifest,
+                self.registry,
+            ))?;
+        }
+        state.end()
+    }
+}
+
+/// A deserializer for [`DynamicScene`]s written by [`BinarySceneSerializer`].
+pub struct BinarySceneDeserializer<'a> {
+    pub manifest: &'a TypeManifest,
+    pub type_registry: &'a TypeRegistry,
+}
+
+impl<'a, 'de> DeserializeSeed<'de> for BinarySceneDeserializer<'a> {
+    type Value = DynamicScene;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_struct(
+            SCENE_STRUCT,
+            &[SCENE_RESOURCES, SCENE_ENTITIES, SCENE_INSTANCES],
+            self,
+        )
+    }
+}
+
+impl<'a, 'de> Visitor<'de> for BinarySceneDeserializer<'a> {
+    type Value = DynamicScene;
 
-        Ok(DynamicScene { entities })
+    fn expecting(&self, formatter: &mut Formatter) -> std::fmt::Result {
+        formatter.write_str("binary scene struct")
     }
 
     fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
     where
         A: SeqAccess<'de>,
     {
+        let resources = seq
+            .next_element_seed(BinaryComponentsDeserializer {
+                manifest: self.manifest,
+                registry: self.type_registry,
+            })?
+            .ok_or_else(|| Error::missing_field(SCENE_RESOURCES))?;
         let entities = seq
-            .next_element_seed(SceneEntitiesDeserializer {
-                type_registry: self.type_registry,
+            .next_element_seed(BinaryEntitiesDeserializer {
+                manifest: self.manifest,
+                registry: self.type_registry,
             })?
             .ok_or_else(|| Error::missing_field(SCENE_ENTITIES))?;
+        let instances = seq
+            .next_element_seed(InstancesDeserializer {
+                manifest: Some(self.manifest),
+                registry: self.type_registry,
+            })?
+            .ok_or_else(|| Error::missing_field(SCENE_INSTANCES))?;
 
-        Ok(DynamicScene { entities })
+        Ok(DynamicScene {
+            resources,
+            entities,
+            instances,
+        })
     }
 }
 
-pub struct SceneEntitiesDeserializer<'a> {
-    pub type_registry: &'a TypeRegistry,
+struct BinaryEntitiesDeserializer<'a> {
+    manifest: &'a TypeManifest,
+    registry: &'a TypeRegistry,
 }
 
-impl<'a, 'de> DeserializeSeed<'de> for SceneEntitiesDeserializer<'a> {
+impl<'a, 'de> DeserializeSeed<'de> for BinaryEntitiesDeserializer<'a> {
     type Value = Vec<DynamicEntity>;
 
     fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
     where
         D: Deserializer<'de>,
     {
-        deserializer.deserialize_map(SceneEntitiesVisitor {
-            type_registry: self.type_registry,
-        })
+        deserializer.deserialize_seq(self)
     }
 }
 
-struct SceneEntitiesVisitor<'a> {
-    pub type_registry: &'a TypeRegistry,
-}
-
-impl<'a, 'de> Visitor<'de> for SceneEntitiesVisitor<'a> {
+impl<'a, 'de> Visitor<'de> for BinaryEntitiesDeserializer<'a> {
     type Value = Vec<DynamicEntity>;
 
-    fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
-        formatter.write_str("map of entities")
+    fn expecting(&self, formatter: &mut Formatter) -> std::fmt::Result {
+        formatter.write_str("sequence of entities")
     }
 
-    fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
     where
-        A: MapAccess<'de>,
+        A: SeqAccess<'de>,
     {
         let mut entities = Vec::new();
-        while let Some(id) = map.next_key::<u32>()? {
-            let entity = map.next_value_seed(SceneEntityDeserializer {
-                id,
-                type_registry: self.type_registry,
-            })?;
+        while let Some(entity) = seq.next_element_seed(BinaryEntityDeserializer {
+            manifest: self.manifest,
+            registry: self.registry,
+        })? {
             entities.push(entity);
         }
 
@@ -238,160 +1212,99 @@ impl<'a, 'de> Visitor<'de> for SceneEntitiesVisitor<'a> {
     }
 }
 
-pub struct SceneEntityDeserializer<'a> {
-    pub id: u32,
-    pub type_registry: &'a TypeRegistry,
+struct BinaryEntityDeserializer<'a> {
+    manifest: &'a TypeManifest,
+    registry: &'a TypeRegistry,
 }
 
-impl<'a, 'de> DeserializeSeed<'de> for SceneEntityDeserializer<'a> {
+impl<'a, 'de> DeserializeSeed<'de> for BinaryEntityDeserializer<'a> {
     type Value = DynamicEntity;
 
     fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
     where
-        D: serde::Deserializer<'de>,
+        D: Deserializer<'de>,
     {
-        deserializer.deserialize_struct(
-            ENTITY_STRUCT,
-            &[ENTITY_FIELD_COMPONENTS],
-            SceneEntityVisitor {
-                id: self.id,
-                registry: self.type_registry,
-            },
-        )
+        deserializer.deserialize_tuple(2, self)
     }
 }
 
-struct SceneEntityVisitor<'a> {
-    pub id: u32,
-    pub registry: &'a TypeRegistry,
-}
-
-impl<'a, 'de> Visitor<'de> for SceneEntityVisitor<'a> {
+impl<'a, 'de> Visitor<'de> for BinaryEntityDeserializer<'a> {
     type Value = DynamicEntity;
 
-    fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
-        formatter.write_str("entities")
+    fn expecting(&self, formatter: &mut Formatter) -> std::fmt::Result {
+        formatter.write_str("tuple containing the entity id and its components")
     }
 
-    fn visit_seq<A>(self, mut seq: A) -> std::result::Result<Self::Value, A::Error>
+    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
     where
         A: SeqAccess<'de>,
     {
+        let entity = seq
+            .next_element::<u32>()?
+            .ok_or_else(|| Error::invalid_length(0, &self))?;
         let components = seq
-            .next_element_seed(ComponentDeserializer {
+            .next_element_seed(BinaryComponentsDeserializer {
+                manifest: self.manifest,
                 registry: self.registry,
             })?
-            .ok_or_else(|| Error::missing_field(ENTITY_FIELD_COMPONENTS))?;
-
-        Ok(DynamicEntity {
-            entity: self.id,
-            components,
-        })
-    }
-
-    fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
-    where
-        A: MapAccess<'de>,
-    {
-        let mut components = None;
-        while let Some(key) = map.next_key()? {
-            match key {
-                EntityField::Components => {
-                    if components.is_some() {
-                        return Err(Error::duplicate_field(ENTITY_FIELD_COMPONENTS));
-                    }
-
-                    components = Some(map.next_value_seed(ComponentDeserializer {
-                        registry: self.registry,
-                    })?);
-                }
-            }
-        }
+            .ok_or_else(|| Error::invalid_length(1, &self))?;
 
-        let components = components
-            .take()
-            .ok_or_else(|| Error::missing_field(ENTITY_FIELD_COMPONENTS))?;
-        Ok(DynamicEntity {
-            entity: self.id,
-            components,
-        })
+        Ok(DynamicEntity { entity, components })
     }
 }
 
-pub struct ComponentDeserializer<'a> {
-    pub registry: &'a TypeRegistry,
+struct BinaryComponentsDeserializer<'a> {
+    manifest: &'a TypeManifest,
+    registry: &'a TypeRegistry,
 }
 
-impl<'a, 'de> DeserializeSeed<'de> for ComponentDeserializer<'a> {
+impl<'a, 'de> DeserializeSeed<'de> for BinaryComponentsDeserializer<'a> {
     type Value = Vec<Box<dyn Reflect>>;
 
     fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
     where
-        D: serde::Deserializer<'de>,
+        D: Deserializer<'de>,
     {
-        deserializer.deserialize_map(ComponentVisitor {
-            registry: self.registry,
-        })
+        deserializer.deserialize_seq(self)
     }
 }
 
-struct ComponentVisitor<'a> {
-    pub registry: &'a TypeRegistry,
-}
-
-impl<'a, 'de> Visitor<'de> for ComponentVisitor<'a> {
+impl<'a, 'de> Visitor<'de> for BinaryComponentsDeserializer<'a> {
     type Value = Vec<Box<dyn Reflect>>;
 
-    fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
-        formatter.write_str("map of components")
-    }
-
-    fn visit_map<A>(self, mut map: A) -> std::result::Result<Self::Value, A::Error>
-    where
-        A: MapAccess<'de>,
-    {
-        let mut added = HashSet::new();
-        let mut components = Vec::new();
-        while let Some(key) = map.next_key::<&str>()? {
-            if !added.insert(key) {
-                return Err(Error::custom(format!("duplicate component: `{key}`")));
-            }
-
-            let registration = self
-                .registry
-                .get_with_name(key)
-                .ok_or_else(|| Error::custom(format!("no registration found for `{key}`")))?;
-            components.push(
-                map.next_value_seed(TypedReflectDeserializer::new(registration, self.registry))?,
-            );
-        }
-
-        Ok(components)
+    fn expecting(&self, formatter: &mut Formatter) -> std::fmt::Result {
+        formatter.write_str("sequence of components")
     }
 
     fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
     where
         A: SeqAccess<'de>,
     {
-        let mut dynamic_properties = Vec::new();
-        while let Some(entity) =
-            seq.next_element_seed(UntypedReflectDeserializer::new(self.registry))?
+        let mut components = Vec::new();
+        while let Some(component) =
+            seq.next_element_seed(BinaryReflectDeserializer::new(self.manifest, self.registry))?
         {
-            dynamic_properties.push(entity);
+            components.push(component);
         }
 
-        Ok(dynamic_properties)
+        Ok(components)
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::serde::{SceneDeserializer, SceneSerializer};
-    use crate::{DynamicScene, DynamicSceneBuilder};
+    use crate::serde::{
+        BinarySceneDeserializer, BinarySceneSerializer, SceneDeserializer, SceneSerializer,
+    };
+    use crate::{
+        DynamicEntity, DynamicScene, DynamicSceneBuilder, DynamicSceneInstance, SceneOverride,
+    };
     use bevy_app::AppTypeRegistry;
     use bevy_ecs::entity::EntityMap;
-    use bevy_ecs::prelude::{Component, ReflectComponent, World};
+    use bevy_ecs::prelude::{Component, ReflectComponent, ReflectResource, Resource, World};
+    use bevy_reflect::serde::TypeManifest;
     use bevy_reflect::{FromReflect, Reflect, ReflectSerialize};
+    use bevy_utils::Instant;
     use bincode::Options;
     use serde::de::DeserializeSeed;
 
@@ -405,6 +1318,20 @@ mod tests {
     #[reflect(Component)]
     struct Baz(i32);
 
+    #[derive(Resource, Reflect, Default)]
+    #[reflect(Resource)]
+    struct Score(u32);
+
+    #[derive(Resource, Reflect)]
+    #[reflect(Resource)]
+    struct Clock(Instant);
+
+    impl Default for Clock {
+        fn default() -> Self {
+            Self(Instant::now())
+        }
+    }
+
     #[derive(Component, Reflect, Default)]
     #[reflect(Component)]
     struct MyComponent {
@@ -431,6 +1358,7 @@ mod tests {
             registry.register::<Foo>();
             registry.register::<Bar>();
             registry.register::<Baz>();
+            registry.register::<Score>();
             registry.register::<MyComponent>();
             registry.register::<MyEnum>();
             registry.register::<String>();
@@ -455,6 +1383,7 @@ mod tests {
         let scene = builder.build();
 
         let expected = r#"(
+  resources: {},
   entities: {
     0: (
       components: {
@@ -475,6 +1404,7 @@ mod tests {
       },
     ),
   },
+  instances: [],
 )"#;
         let output = scene
             .serialize_ron(&world.resource::<AppTypeRegistry>().0)
@@ -529,6 +1459,138 @@ mod tests {
         assert_eq!(1, dst_world.query::<&Baz>().iter(&dst_world).count());
     }
 
+    #[test]
+    fn should_not_extract_resources_from_world() {
+        let mut world = create_world();
+        world
+            .resource::<AppTypeRegistry>()
+            .write()
+            .register::<Clock>();
+        world.init_resource::<Clock>();
+        world.insert_resource(Score(42));
+        world.spawn(Foo(123));
+
+        let registry = world.resource::<AppTypeRegistry>();
+        let scene = DynamicScene::from_world(&world, registry);
+        assert!(scene.resources.is_empty());
+        // `Instant` can't be serialized, so this would fail if `Clock` had been extracted
+        scene.serialize_ron(&registry.0).unwrap();
+
+        let mut dst_world = create_world();
+        dst_world.insert_resource(Score(0));
+        scene
+            .write_to_world(&mut dst_world, &mut EntityMap::default())
+            .unwrap();
+        assert_eq!(0, dst_world.resource::<Score>().0);
+        assert_eq!(1, dst_world.query::<&Foo>().iter(&dst_world).count());
+    }
+
+    #[test]
+    fn should_roundtrip_resources() {
+        let mut world = create_world();
+        world.insert_resource(Score(42));
+        world.spawn(Foo(123));
+
+        let mut builder = DynamicSceneBuilder::from_world(&world);
+        builder.extract_entities(world.iter_entities());
+        builder.extract_resource::<Score>();
+        let scene = builder.build();
+        assert_eq!(1, scene.resources.len());
+
+        let registry = world.resource::<AppTypeRegistry>();
+
+        let serialized_scene = scene.serialize_ron(&registry.0).unwrap();
+        assert!(serialized_scene.contains(r#""bevy_scene::serde::tests::Score": (42)"#));
+
+        let mut deserializer = ron::de::Deserializer::from_str(&serialized_scene).unwrap();
+        let scene_deserializer = SceneDeserializer {
+            type_registry: &registry.read(),
+        };
+        let deserialized_scene = scene_deserializer.deserialize(&mut deserializer).unwrap();
+        assert_scene_eq(&scene, &deserialized_scene);
+
+        // the layout of formats that are not self-describing has no room for resources
+        let scene_serializer = SceneSerializer::new(&scene, &registry.0);
+        assert!(postcard::to_allocvec(&scene_serializer).is_err());
+
+        let mut dst_world = create_world();
+        deserialized_scene
+            .write_to_world(&mut dst_world, &mut EntityMap::default())
+            .unwrap();
+        assert_eq!(42, dst_world.resource::<Score>().0);
+        assert_eq!(1, dst_world.query::<&Foo>().iter(&dst_world).count());
+
+        // writing the scene again doesn't overwrite the live resource
+        dst_world.resource_mut::<Score>().0 = 7;
+        deserialized_scene
+            .write_to_world(&mut dst_world, &mut EntityMap::default())
+            .unwrap();
+        assert_eq!(7, dst_world.resource::<Score>().0);
+        assert_eq!(2, dst_world.query::<&Foo>().iter(&dst_world).count());
+    }
+
+    #[test]
+    fn should_roundtrip_instances() {
+        let world = create_world();
+        let registry = world.resource::<AppTypeRegistry>();
+
+        let scene = DynamicScene {
+            resources: Vec::new(),
+            entities: vec![DynamicEntity {
+                entity: 0,
+                components: vec![Box::new(Foo(123))],
+            }],
+            instances: vec![DynamicSceneInstance {
+                scene: "tree.scn.ron".into(),
+                parent: Some(0),
+                overrides: vec![SceneOverride {
+                    entity: 1,
+                    component: std::any::type_name::<Bar>().to_string(),
+                    path: "0".to_string(),
+                    value: Box::new(345),
+                }],
+            }],
+        };
+
+        let expected = r#"instances: [
+    (
+      scene: "tree.scn.ron",
+      parent: Some(0),
+      overrides: [
+        (
+          entity: 1,
+          component: "bevy_scene::serde::tests::Bar",
+          path: "0",
+          value: {
+            "i32": 345,
+          },
+        ),
+      ],
+    ),
+  ],"#;
+        let serialized_scene = scene.serialize_ron(&registry.0).unwrap();
+        assert!(serialized_scene.contains(expected), "{serialized_scene}");
+
+        let scene_deserializer = SceneDeserializer {
+            type_registry: &registry.read(),
+        };
+        let mut deserializer = ron::de::Deserializer::from_str(&serialized_scene).unwrap();
+        let deserialized_scene = scene_deserializer.deserialize(&mut deserializer).unwrap();
+        assert_instances_eq(&scene, &deserialized_scene);
+
+        let manifest = TypeManifest::from_registry(&registry.read());
+        let scene_serializer = BinarySceneSerializer::new(&scene, &manifest, &registry.0);
+        let serialized_scene = postcard::to_allocvec(&scene_serializer).unwrap();
+        let scene_deserializer = BinarySceneDeserializer {
+            manifest: &manifest,
+            type_registry: &registry.read(),
+        };
+        let deserialized_scene = scene_deserializer
+            .deserialize(&mut postcard::Deserializer::from_bytes(&serialized_scene))
+            .unwrap();
+        assert_instances_eq(&scene, &deserialized_scene);
+    }
+
     #[test]
     fn should_roundtrip_postcard() {
         let mut world = create_world();
@@ -609,8 +1671,69 @@ mod tests {
         assert_scene_eq(&scene, &deserialized_scene);
     }
 
+    #[test]
+    fn should_roundtrip_binary() {
+        let mut world = create_world();
+
+        world.spawn(MyComponent {
+            foo: [1, 2, 3],
+            bar: (1.3, 3.7),
+            baz: MyEnum::Tuple("Hello World!".to_string()),
+        });
+        world.spawn((Foo(123), Bar(345)));
+
+        let registry = world.resource::<AppTypeRegistry>();
+        let manifest = TypeManifest::from_registry(&registry.read());
+
+        let scene = DynamicScene::from_world(&world, registry);
+
+        let scene_serializer = BinarySceneSerializer::new(&scene, &manifest, &registry.0);
+        let serialized_scene = bincode::serialize(&scene_serializer).unwrap();
+        let named_scene = bincode::serialize(&SceneSerializer::new(&scene, &registry.0)).unwrap();
+        assert!(serialized_scene.len() < named_scene.len());
+
+        let scene_deserializer = BinarySceneDeserializer {
+            manifest: &manifest,
+            type_registry: &registry.0.read(),
+        };
+        let deserialized_scene = bincode::DefaultOptions::new()
+            .with_fixint_encoding()
+            .deserialize_seed(scene_deserializer, &serialized_scene)
+            .unwrap();
+
+        assert_eq!(2, deserialized_scene.entities.len());
+        assert_scene_eq(&scene, &deserialized_scene);
+
+        let serialized_scene = postcard::to_allocvec(&scene_serializer).unwrap();
+        let scene_deserializer = BinarySceneDeserializer {
+            manifest: &manifest,
+            type_registry: &registry.0.read(),
+        };
+        let deserialized_scene = scene_deserializer
+            .deserialize(&mut postcard::Deserializer::from_bytes(&serialized_scene))
+            .unwrap();
+        assert_scene_eq(&scene, &deserialized_scene);
+    }
+
     /// A crude equality checker for [`DynamicScene`], used solely for testing purposes.
     fn assert_scene_eq(expected: &DynamicScene, received: &DynamicScene) {
+        assert_eq!(
+            expected.resources.len(),
+            received.resources.len(),
+            "resource count did not match",
+        );
+
+        for (expected, received) in expected.resources.iter().zip(&received.resources) {
+            assert!(
+                expected
+                    .reflect_partial_eq(received.as_ref())
+                    .unwrap_or_default(),
+                "resources did not match: (expected: `{:?}`, received: `{:?}`)",
+                expected,
+                received
+            );
+        }
+
         assert_eq!(
             expected.entities.len(),
             received.entities.len(),
@@ -647,6 +1770,25 @@ mod tests {
         }
     }
 
+    fn assert_instances_eq(expected: &DynamicScene, received: &DynamicScene) {
+        assert_scene_eq(expected, received);
+        assert_eq!(expected.instances.len(), received.instances.len());
+        for (expected, received) in expected.instances.iter().zip(&received.instances) {
+            assert_eq!(expected.scene, received.scene);
+            assert_eq!(expected.parent, received.parent);
+            assert_eq!(expected.overrides.len(), received.overrides.len());
+            for (expected, received) in expected.overrides.iter().zip(&received.overrides) {
+                assert_eq!(expected.entity, received.entity);
+                assert_eq!(expected.component, received.component);
+                assert_eq!(expected.path, received.path);
+                assert!(expected
+                    .value
+                    .reflect_partial_eq(received.value.as_ref())
+                    .unwrap_or_default());
+            }
+        }
+    }
+
     /// These tests just verify that that the [`assert_scene_eq`] function is working properly for our tests.
     mod assert_scene_eq_tests {
         use super::*;
@@ -722,4 +1864,69 @@ mod tests {
             assert_scene_eq(&scene_a, &scene_b);
         }
     }
+
+    #[cfg(feature = "json_schema")]
+    #[test]
+    fn should_describe_scene_json_schema() {
+        use crate::serde::scene_json_schema;
+        use serde_json::json;
+
+        let world = create_world();
+        let registry = world.resource::<AppTypeRegistry>().read();
+        let schema = scene_json_schema(&registry);
+
+        assert_eq!(schema["title"], "Scene");
+        assert_eq!(schema["required"], json!(["entities"]));
+        assert_eq!(schema["additionalProperties"], false);
+
+        let properties = &schema["properties"];
+        assert_eq!(
+            properties["resources"]["properties"],
+            json!({
+                "bevy_scene::serde::tests::Score": {
+                    "$ref": "#/$defs/bevy_scene::serde::tests::Score"
+                },
+            })
+        );
+
+        let entity = &properties["entities"]["additionalProperties"];
+        assert_eq!(entity["title"], "Entity");
+        assert_eq!(entity["required"], json!(["components"]));
+        let components = entity["properties"]["components"]["properties"]
+            .as_object()
+            .unwrap();
+        let mut component_names = components.keys().cloned().collect::<Vec<_>>();
+        component_names.sort();
+        assert_eq!(
+            component_names,
+            [
+                "bevy_scene::serde::tests::Bar",
+                "bevy_scene::serde::tests::Baz",
+                "bevy_scene::serde::tests::Foo",
+                "bevy_scene::serde::tests::MyComponent",
+            ]
+        );
+        for (type_name, reference) in components {
+            assert_eq!(
+                reference,
+                &json!({ "$ref": format!("#/$defs/{type_name}") })
+            );
+            assert!(schema["$defs"].get(type_name).is_some());
+        }
+
+        let instance = &properties["instances"]["items"];
+        assert_eq!(properties["instances"]["type"], "array");
+        assert_eq!(instance["title"], "Instance");
+        assert_eq!(instance["required"], json!(["scene"]));
+        let overrides = &instance["properties"]["overrides"]["items"];
+        assert_eq!(overrides["title"], "Override");
+        assert_eq!(
+            overrides["required"],
+            json!(["entity", "component", "value"])
+        );
+        assert_eq!(
+            overrides["properties"]["value"]["properties"]["bevy_scene::serde::tests::MyEnum"],
+            json!({ "$ref": "#/$defs/bevy_scene::serde::tests::MyEnum" })
+        );
+    }
 }
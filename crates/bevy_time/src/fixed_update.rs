@@ -0,0 +1,283 @@
+use crate::Time;
+use bevy_ecs::{
+    schedule::{ShouldRun, StageLabel},
+    system::{Res, ResMut, Resource},
+};
+use bevy_utils::Duration;
+use std::ops::Deref;
+
+/// The label of the stage that runs its systems at a fixed timestep.
+///
+/// The stage is added by [`TimePlugin`](crate::TimePlugin) right before
+/// [`CoreStage::Update`](bevy_app::CoreStage::Update). Every frame, the virtual time that passed
+/// (see [`Time::delta`]) is added to the [`FixedTime`] accumulator, and the stage runs once for
+/// every whole step that fits in it. Depending on the frame rate it may therefore run several
+/// times in a frame, or not at all, but never more than [`FixedTime::max_steps_per_frame`] times.
+///
+/// Systems in this stage should read [`FixedTime`] instead of [`Time`]: its delta is always exactly
+/// one step.
+#[derive(Debug, Hash, PartialEq, Eq, Clone, StageLabel)]
+pub struct FixedUpdateStage;
+
+/// The clock of the [`FixedUpdateStage`].
+///
+/// Dereferences to a [`Time`] that advances by exactly [`FixedTime::step`] every time the stage
+/// runs, so systems in the stage can use [`Time::delta_seconds`] and [`Time::elapsed`] as usual.
+///
+/// Insert this resource before adding the [`TimePlugin`](crate::TimePlugin) to change the
+/// default step of 1/60th of a second.
+#[derive(Resource, Debug, Clone)]
+pub struct FixedTime {
+    time: Time,
+    step: Duration,
+    accumulator: Duration,
+    max_steps_per_frame: u32,
+    looping: bool,
+}
+
+impl Default for FixedTime {
+    fn default() -> Self {
+        Self::from_seconds(1.0 / 60.0)
+    }
+}
+
+impl FixedTime {
+    /// The default of [`FixedTime::max_steps_per_frame`].
+    pub const DEFAULT_MAX_STEPS_PER_FRAME: u32 = 10;
+
+    /// Creates a [`FixedTime`] that ticks once every `step`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `step` is zero.
+    pub fn new(step: Duration) -> Self {
+        assert!(!step.is_zero(), "the fixed timestep must not be zero");
+        let mut time = Time::default();
+        // The first update of a `Time` has no delta, so it is done here rather than in the stage.
+        time.update_with_instant(time.startup());
+        Self {
+            time,
+            step,
+            accumulator: Duration::ZERO,
+            max_steps_per_frame: Self::DEFAULT_MAX_STEPS_PER_FRAME,
+            looping: false,
+        }
+    }
+
+    /// Creates a [`FixedTime`] that ticks once every `step` seconds.
+    pub fn from_seconds(step: f64) -> Self {
+        Self::new(Duration::from_secs_f64(step))
+    }
+
+    /// Creates a [`FixedTime`] that ticks `rate` times per second.
+    pub fn from_steps_per_second(rate: f64) -> Self {
+        Self::from_seconds(1.0 / rate)
+    }
+
+    /// The amount of time each step takes.
+    #[inline]
+    pub fn step(&self) -> Duration {
+        self.step
+    }
+
+    /// Sets the amount of time each step takes.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `step` is zero.
+    pub fn set_step(&mut self, step: Duration) {
+        assert!(!step.is_zero(), "the fixed timestep must not be zero");
+        self.step = step;
+    }
+
+    /// The maximum number of steps made in a single frame.
+    ///
+    /// When a frame takes longer than this many steps, for example after the app was suspended or
+    /// because the steps themselves are too slow to keep up, the whole steps beyond it are
+    /// dropped instead of being caught up on in later frames.
+    #[inline]
+    pub fn max_steps_per_frame(&self) -> u32 {
+        self.max_steps_per_frame
+    }
+
+    /// Sets the maximum number of steps made in a single frame.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `max_steps` is zero.
+    pub fn set_max_steps_per_frame(&mut self, max_steps: u32) {
+        assert!(
+            max_steps > 0,
+            "the maximum number of fixed steps per frame must not be zero"
+        );
+        self.max_steps_per_frame = max_steps;
+    }
+
+    /// The number of steps made in a second.
+    #[inline]
+    pub fn steps_per_second(&self) -> f64 {
+        1.0 / self.step.as_secs_f64()
+    }
+
+    /// The amount of time left over from the last step.
+    #[inline]
+    pub fn accumulated(&self) -> Duration {
+        self.accumulator
+    }
+
+    /// The percentage of "step" stored inside the accumulator. Calculated as accumulator / step.
+    ///
+    /// Outside of the [`FixedUpdateStage`] this is how far the current frame is between the last
+    /// fixed step and the next one, which is what rendering should interpolate by.
+    #[inline]
+    pub fn overstep_percentage(&self) -> f64 {
+        self.accumulator.as_secs_f64() / self.step.as_secs_f64()
+    }
+
+    /// Returns the fixed-step clock.
+    #[inline]
+    pub fn time(&self) -> &Time {
+        &self.time
+    }
+
+    /// Adds `delta` to the accumulator.
+    ///
+    /// The accumulator is clamped to [`FixedTime::max_steps_per_frame`] whole steps, along with
+    /// the fraction of a step it holds.
+    pub fn tick(&mut self, delta: Duration) {
+        self.accumulator += delta;
+        let max_accumulator = self
+            .step
+            .checked_mul(self.max_steps_per_frame)
+            .unwrap_or(Duration::MAX);
+        if self.accumulator >= max_accumulator {
+            let fraction = self.accumulator.as_nanos() % self.step.as_nanos();
+            // the fraction is less than a step, which fits in a `Duration`
+            self.accumulator =
+                max_accumulator.saturating_add(Duration::from_nanos(fraction as u64));
+        }
+    }
+
+    /// Consumes one step from the accumulator and advances the fixed-step clock by it.
+    ///
+    /// Returns `false` and leaves everything unchanged if less than a step has accumulated.
+    pub fn expend(&mut self) -> bool {
+        if self.accumulator < self.step {
+            return false;
+        }
+        self.accumulator -= self.step;
+        let last_update = self
+            .time
+            .last_update()
+            .unwrap_or_else(|| self.time.startup());
+        self.time.update_with_instant(last_update + self.step);
+        true
+    }
+}
+
+impl Deref for FixedTime {
+    type Target = Time;
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        &self.time
+    }
+}
+
+/// The run criteria of the [`FixedUpdateStage`].
+///
+/// Accumulates the frame's [`Time::delta`] on the first check of a frame, then keeps running the
+/// stage as long as a whole step can be expended.
+pub fn run_fixed_update(time: Res<Time>, mut fixed_time: ResMut<FixedTime>) -> ShouldRun {
+    if !fixed_time.looping {
+        fixed_time.tick(time.delta());
+    }
+
+    if fixed_time.expend() {
+        fixed_time.looping = true;
+        ShouldRun::YesAndCheckAgain
+    } else {
+        fixed_time.looping = false;
+        ShouldRun::No
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{FixedTime, FixedUpdateStage};
+    use crate::{Time, TimePlugin, TimeUpdateStrategy};
+    use bevy_app::prelude::*;
+    use bevy_ecs::prelude::*;
+    use bevy_utils::Duration;
+
+    #[derive(Resource, Default)]
+    struct Steps(Vec<Duration>);
+
+    fn record_step(fixed_time: Res<FixedTime>, mut steps: ResMut<Steps>) {
+        steps.0.push(fixed_time.delta());
+    }
+
+    fn advance(app: &mut App, millis: u64) {
+        let last_update = app.world.resource::<Time>().last_update().unwrap();
+        app.world.insert_resource(TimeUpdateStrategy::ManualInstant(
+            last_update + Duration::from_millis(millis),
+        ));
+        app.update();
+    }
+
+    #[test]
+    fn runs_once_per_accumulated_step() {
+        let mut app = App::new();
+        app.insert_resource(FixedTime::new(Duration::from_millis(100)))
+            .add_plugin(TimePlugin)
+            .init_resource::<Steps>()
+            .add_system_to_stage(FixedUpdateStage, record_step);
+        app.update();
+
+        advance(&mut app, 50);
+        assert_eq!(app.world.resource::<Steps>().0.len(), 0);
+        assert_eq!(
+            app.world.resource::<FixedTime>().accumulated(),
+            Duration::from_millis(50)
+        );
+
+        advance(&mut app, 60);
+        assert_eq!(app.world.resource::<Steps>().0.len(), 1);
+
+        advance(&mut app, 250);
+        let steps = &app.world.resource::<Steps>().0;
+        assert_eq!(steps.len(), 3);
+        assert!(steps.iter().all(|step| *step == Duration::from_millis(100)));
+
+        let fixed_time = app.world.resource::<FixedTime>();
+        assert_eq!(fixed_time.elapsed(), Duration::from_millis(300));
+        assert_eq!(fixed_time.accumulated(), Duration::from_millis(60));
+        assert!((fixed_time.overstep_percentage() - 0.6).abs() < 1e-9);
+    }
+
+    #[test]
+    fn limits_steps_per_frame() {
+        let mut fixed_time = FixedTime::new(Duration::from_millis(100));
+        fixed_time.set_max_steps_per_frame(3);
+        let mut app = App::new();
+        app.insert_resource(fixed_time)
+            .add_plugin(TimePlugin)
+            .init_resource::<Steps>()
+            .add_system_to_stage(FixedUpdateStage, record_step);
+        app.update();
+
+        advance(&mut app, 2050);
+        assert_eq!(app.world.resource::<Steps>().0.len(), 3);
+        let fixed_time = app.world.resource::<FixedTime>();
+        assert_eq!(fixed_time.elapsed(), Duration::from_millis(300));
+        assert_eq!(fixed_time.accumulated(), Duration::from_millis(50));
+
+        // the dropped steps are not caught up on
+        advance(&mut app, 60);
+        assert_eq!(app.world.resource::<Steps>().0.len(), 4);
+        assert_eq!(
+            app.world.resource::<FixedTime>().accumulated(),
+            Duration::from_millis(10)
+        );
+    }
+}
@@ -1,10 +1,12 @@
 mod fixed_timestep;
+mod fixed_update;
 mod stopwatch;
 #[allow(clippy::module_inception)]
 mod time;
 mod timer;
 
 pub use fixed_timestep::*;
+pub use fixed_update::*;
 pub use stopwatch::*;
 pub use time::*;
 pub use timer::*;
@@ -16,10 +18,10 @@ use crossbeam_channel::{Receiver, Sender};
 pub mod prelude {
     //! The Bevy Time Prelude.
     #[doc(hidden)]
-    pub use crate::{Time, Timer, TimerMode};
+    pub use crate::{FixedTime, FixedUpdateStage, Time, Timer, TimerMode};
 }
 
-use bevy_app::prelude::*;
+use bevy_app::{prelude::*, FrameDeltaDriver};
 use bevy_ecs::prelude::*;
 
 /// Adds time functionality to Apps.
@@ -36,12 +38,19 @@ impl Plugin for TimePlugin {
         app.init_resource::<Time>()
             .init_resource::<TimeUpdateStrategy>()
             .init_resource::<FixedTimesteps>()
+            .init_resource::<FixedTime>()
             .register_type::<Timer>()
             .register_type::<Time>()
             .register_type::<Stopwatch>()
+            .insert_resource(FrameDeltaDriver::new(drive_frame_delta))
             // time system is added as an "exclusive system" to ensure it runs before other systems
             // in CoreStage::First
-            .add_system_to_stage(CoreStage::First, time_system.at_start().label(TimeSystem));
+            .add_system_to_stage(CoreStage::First, time_system.at_start().label(TimeSystem))
+            .add_stage_before(
+                CoreStage::Update,
+                FixedUpdateStage,
+                SystemStage::parallel().with_run_criteria(run_fixed_update),
+            );
     }
 }
 
@@ -59,6 +68,16 @@ pub enum TimeUpdateStrategy {
     ManualDuration(Duration),
 }
 
+/// Makes the next [`time_system`] run advance [`Time`] by exactly `delta` from its last update.
+///
+/// Used by [`AppTester`](bevy_app::AppTester) to step an app with a controlled frame delta.
+fn drive_frame_delta(world: &mut World, delta: Duration) {
+    let time = world.resource::<Time>();
+    let last_update = time.last_update().unwrap_or_else(|| time.startup());
+    *world.resource_mut::<TimeUpdateStrategy>() =
+        TimeUpdateStrategy::ManualInstant(last_update + delta);
+}
+
 /// Channel resource used to receive time from render world
 #[derive(Resource)]
 pub struct TimeReceiver(pub Receiver<Instant>);
@@ -106,3 +125,23 @@ fn time_system(
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::{Time, TimePlugin};
+    use bevy_app::{App, AppTester};
+    use bevy_utils::Duration;
+
+    #[test]
+    fn app_tester_drives_time() {
+        let mut app = App::new();
+        app.add_plugin(TimePlugin);
+
+        let mut tester = AppTester::new(app);
+        tester.set_frame_delta(Duration::from_millis(100)).step(3);
+
+        let time = tester.world().resource::<Time>();
+        assert_eq!(time.delta(), Duration::from_millis(100));
+        assert_eq!(time.elapsed(), Duration::from_millis(300));
+    }
+}
@@ -0,0 +1,299 @@
+use crate::{components::Transform, TransformSystem};
+use bevy_app::prelude::*;
+use bevy_ecs::prelude::*;
+use bevy_reflect::{FromReflect, Reflect};
+use bevy_time::{FixedTime, FixedUpdateStage};
+
+/// Smooths the [`Transform`] of entities that are moved in the [`FixedUpdateStage`].
+///
+/// Systems in the fixed update stage see and write the simulated [`Transform`], one step at a
+/// time. Every frame, the rendered [`Transform`] is then blended between the last two simulated
+/// states according to [`FixedTime::overstep_percentage`], so that movement stays smooth when
+/// the frame rate and the fixed timestep differ.
+///
+/// Moving an entity outside of the fixed update stage, for example to teleport it, is detected
+/// and resets its history instead of being blended.
+///
+/// Only entities with a [`TransformInterpolation`] component are affected.
+#[derive(Default)]
+pub struct TransformInterpolationPlugin;
+
+impl Plugin for TransformInterpolationPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<TransformInterpolation>()
+            .register_type::<TransformInterpolationMode>()
+            .add_system_to_stage(FixedUpdateStage, restore_fixed_transforms.at_start())
+            .add_system_to_stage(FixedUpdateStage, record_fixed_transforms.at_end())
+            .add_system_to_stage(
+                CoreStage::PostUpdate,
+                interpolate_fixed_transforms
+                    .label(TransformSystem::TransformInterpolate)
+                    .before(TransformSystem::TransformPropagate),
+            );
+    }
+}
+
+/// How a [`TransformInterpolation`] blends the last two fixed update states.
+#[derive(Debug, Default, PartialEq, Eq, Clone, Copy, Reflect, FromReflect)]
+pub enum TransformInterpolationMode {
+    /// Blends from the previous state to the current one.
+    ///
+    /// The rendered transform is always one step behind the simulation, but never wrong.
+    #[default]
+    Interpolate,
+    /// Continues the movement from the previous state to the current one past the current state.
+    ///
+    /// The rendered transform is not delayed, but overshoots when the movement changes.
+    Extrapolate,
+}
+
+/// Opts an entity into [`Transform`] interpolation between fixed update steps.
+///
+/// See [`TransformInterpolationPlugin`].
+#[derive(Component, Debug, Default, PartialEq, Clone, Copy, Reflect, FromReflect)]
+#[reflect(Component)]
+pub struct TransformInterpolation {
+    /// How the last two fixed update states are blended.
+    pub mode: TransformInterpolationMode,
+    previous: Option<Transform>,
+    current: Option<Transform>,
+    rendered: Option<Transform>,
+}
+
+impl TransformInterpolation {
+    /// Creates a [`TransformInterpolation`] using [`TransformInterpolationMode::Interpolate`].
+    pub fn interpolate() -> Self {
+        Self::default()
+    }
+
+    /// Creates a [`TransformInterpolation`] using [`TransformInterpolationMode::Extrapolate`].
+    pub fn extrapolate() -> Self {
+        Self {
+            mode: TransformInterpolationMode::Extrapolate,
+            ..Default::default()
+        }
+    }
+
+    /// The simulated transform before the last fixed update step, if there was one.
+    pub fn previous(&self) -> Option<Transform> {
+        self.previous
+    }
+
+    /// The simulated transform after the last fixed update step, if there was one.
+    pub fn current(&self) -> Option<Transform> {
+        self.current
+    }
+
+    /// Forgets the recorded fixed update states, so that the entity is not blended until the
+    /// next fixed update step.
+    pub fn reset(&mut self) {
+        self.previous = None;
+        self.current = None;
+        self.rendered = None;
+    }
+
+    /// Returns whether `transform` was changed since it was last rendered.
+    fn was_moved(&self, transform: &Transform) -> bool {
+        matches!(self.rendered, Some(rendered) if rendered != *transform)
+    }
+
+    /// Restarts the history from `transform`.
+    fn restart(&mut self, transform: Transform) {
+        self.previous = Some(transform);
+        self.current = Some(transform);
+        self.rendered = None;
+    }
+
+    /// Blends the previous and current states, `overstep` being the fraction of a step since the
+    /// current state was recorded.
+    fn blend(&self, overstep: f32) -> Option<Transform> {
+        let (previous, current) = (self.previous?, self.current?);
+        Some(match self.mode {
+            TransformInterpolationMode::Interpolate => Transform {
+                translation: previous.translation.lerp(current.translation, overstep),
+                rotation: previous.rotation.slerp(current.rotation, overstep),
+                scale: previous.scale.lerp(current.scale, overstep),
+            },
+            TransformInterpolationMode::Extrapolate => {
+                let rotation_step = current.rotation * previous.rotation.inverse();
+                Transform {
+                    translation: current.translation
+                        + (current.translation - previous.translation) * overstep,
+                    rotation: current
+                        .rotation
+                        .slerp(rotation_step * current.rotation, overstep),
+                    scale: current.scale + (current.scale - previous.scale) * overstep,
+                }
+            }
+        })
+    }
+}
+
+/// Puts back the simulated [`Transform`] of interpolated entities before each fixed update step.
+pub fn restore_fixed_transforms(
+    world: &mut World,
+    query: &mut QueryState<(&mut Transform, &mut TransformInterpolation)>,
+) {
+    for (mut transform, mut interpolation) in query.iter_mut(world) {
+        if interpolation.was_moved(&transform) {
+            interpolation.restart(*transform);
+        } else if interpolation.rendered.take().is_some() {
+            match interpolation.current {
+                Some(current) if *transform != current => *transform = current,
+                _ => {}
+            }
+        }
+        if interpolation.current.is_none() {
+            interpolation.current = Some(*transform);
+        }
+    }
+}
+
+/// Records the simulated [`Transform`] of interpolated entities after each fixed update step.
+pub fn record_fixed_transforms(
+    world: &mut World,
+    query: &mut QueryState<(&Transform, &mut TransformInterpolation)>,
+) {
+    for (transform, mut interpolation) in query.iter_mut(world) {
+        interpolation.previous = interpolation.current;
+        interpolation.current = Some(*transform);
+    }
+}
+
+/// Replaces the [`Transform`] of interpolated entities with the blend of their last two fixed
+/// update states.
+///
+/// Components are only written when their value changes, so that entities at rest don't
+/// trigger change detection every frame.
+pub fn interpolate_fixed_transforms(
+    fixed_time: Res<FixedTime>,
+    mut query: Query<(&mut Transform, &mut TransformInterpolation)>,
+) {
+    let overstep = fixed_time.overstep_percentage() as f32;
+    for (mut transform, mut interpolation) in &mut query {
+        if interpolation.was_moved(&transform) {
+            interpolation.restart(*transform);
+        }
+        if let Some(blended) = interpolation.blend(overstep) {
+            if *transform != blended {
+                *transform = blended;
+            }
+            if interpolation.rendered != Some(blended) {
+                interpolation.rendered = Some(blended);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{TransformInterpolation, TransformInterpolationPlugin};
+    use crate::{components::Transform, TransformPlugin};
+    use bevy_app::prelude::*;
+    use bevy_ecs::prelude::*;
+    use bevy_math::Vec3;
+    use bevy_time::{FixedTime, FixedUpdateStage, Time, TimePlugin, TimeUpdateStrategy};
+    use std::time::Duration;
+
+    fn move_right(mut query: Query<&mut Transform>) {
+        for mut transform in &mut query {
+            transform.translation.x += 1.0;
+        }
+    }
+
+    fn advance(app: &mut App, millis: u64) {
+        let last_update = app.world.resource::<Time>().last_update().unwrap();
+        app.world.insert_resource(TimeUpdateStrategy::ManualInstant(
+            last_update + Duration::from_millis(millis),
+        ));
+        app.update();
+    }
+
+    fn setup(interpolation: TransformInterpolation) -> (App, Entity) {
+        let mut app = App::new();
+        app.insert_resource(FixedTime::new(Duration::from_millis(100)))
+            .add_plugin(TimePlugin)
+            .add_plugin(TransformPlugin)
+            .add_plugin(TransformInterpolationPlugin)
+            .add_system_to_stage(FixedUpdateStage, move_right);
+        let entity = app.world.spawn((Transform::default(), interpolation)).id();
+        app.update();
+        (app, entity)
+    }
+
+    fn x(app: &App, entity: Entity) -> f32 {
+        app.world.get::<Transform>(entity).unwrap().translation.x
+    }
+
+    #[test]
+    fn interpolates_between_steps() {
+        let (mut app, entity) = setup(TransformInterpolation::interpolate());
+
+        // Two steps: the simulation is at 2, a quarter step past it.
+        advance(&mut app, 225);
+        assert!((x(&app, entity) - 1.25).abs() < 1e-4);
+
+        // No step this frame: the simulation stays at 2, three quarters past it.
+        advance(&mut app, 50);
+        assert!((x(&app, entity) - 1.75).abs() < 1e-4);
+
+        // The next step starts from the simulated state, not the rendered one.
+        advance(&mut app, 50);
+        assert!((x(&app, entity) - 2.25).abs() < 1e-4);
+        let interpolation = app.world.get::<TransformInterpolation>(entity).unwrap();
+        assert_eq!(interpolation.current().unwrap().translation.x, 3.0);
+    }
+
+    #[test]
+    fn extrapolates_past_current_step() {
+        let (mut app, entity) = setup(TransformInterpolation::extrapolate());
+
+        advance(&mut app, 225);
+        assert!((x(&app, entity) - 2.25).abs() < 1e-4);
+    }
+
+    #[test]
+    fn external_moves_reset_history() {
+        let (mut app, entity) = setup(TransformInterpolation::interpolate());
+        advance(&mut app, 225);
+
+        app.world.get_mut::<Transform>(entity).unwrap().translation = Vec3::new(10.0, 0.0, 0.0);
+        advance(&mut app, 50);
+        assert_eq!(x(&app, entity), 10.0);
+
+        advance(&mut app, 50);
+        assert!((x(&app, entity) - 10.25).abs() < 1e-4);
+    }
+
+    #[derive(Resource, Default)]
+    struct ChangedFrames(usize);
+
+    fn count_changed_frames(
+        query: Query<(), Changed<Transform>>,
+        mut changed_frames: ResMut<ChangedFrames>,
+    ) {
+        if !query.is_empty() {
+            changed_frames.0 += 1;
+        }
+    }
+
+    #[test]
+    fn entities_at_rest_are_not_changed() {
+        let mut app = App::new();
+        app.insert_resource(FixedTime::new(Duration::from_millis(100)))
+            .add_plugin(TimePlugin)
+            .add_plugin(TransformPlugin)
+            .add_plugin(TransformInterpolationPlugin)
+            .init_resource::<ChangedFrames>()
+            .add_system_to_stage(CoreStage::Last, count_changed_frames);
+        app.world
+            .spawn((Transform::default(), TransformInterpolation::interpolate()));
+        app.update();
+        assert_eq!(app.world.resource::<ChangedFrames>().0, 1);
+
+        for _ in 0..4 {
+            advance(&mut app, 75);
+        }
+        assert_eq!(app.world.resource::<ChangedFrames>().0, 1);
+    }
+}
@@ -3,13 +3,18 @@
 
 /// The basic components of the transform crate
 pub mod components;
+mod interpolation;
 mod systems;
+pub use crate::interpolation::*;
 pub use crate::systems::transform_propagate_system;
 
 #[doc(hidden)]
 pub mod prelude {
     #[doc(hidden)]
-    pub use crate::{components::*, TransformBundle, TransformPlugin};
+    pub use crate::{
+        components::*, TransformBundle, TransformInterpolation, TransformInterpolationPlugin,
+        TransformPlugin,
+    };
 }
 
 use bevy_app::prelude::*;
@@ -77,6 +82,8 @@ impl From<Transform> for TransformBundle {
 pub enum TransformSystem {
     /// Propagates changes in transform to children's [`GlobalTransform`](crate::components::GlobalTransform)
     TransformPropagate,
+    /// Blends the [`Transform`] of entities with a [`TransformInterpolation`] between fixed updates
+    TransformInterpolate,
 }
 
 /// The base plugin for handling [`Transform`] components
@@ -579,7 +579,7 @@ pub fn queue_uinodes(
     // If an image has changed, the GpuImage has (probably) changed
     for event in &events.images {
         match event {
-            AssetEvent::Created { .. } => None,
+            AssetEvent::Created { .. } | AssetEvent::LoadedWithDependencies { .. } => None,
             AssetEvent::Modified { handle } | AssetEvent::Removed { handle } => {
                 image_bind_groups.values.remove(handle)
             }